@@ -0,0 +1,138 @@
+//! Object ACLs: canned ACLs on upload plus `objects:get-acl` /
+//! `objects:set-acl`. Buckets with Object Ownership set to "bucket owner
+//! enforced" reject all of these; see `S3ErrorKind::AclsDisabled`.
+
+use super::*;
+use aws_sdk_s3::types::{
+    AccessControlPolicy, Grant, Grantee, ObjectCannedAcl, Owner, Permission, Type,
+};
+
+pub(crate) fn parse_canned_acl(acl: &str) -> Result<ObjectCannedAcl, String> {
+    let acl = acl.trim();
+    if OBJECT_CANNED_ACLS.contains(&acl) {
+        Ok(ObjectCannedAcl::from(acl))
+    } else {
+        Err(format!(
+            "Unknown ACL \"{acl}\" (use {})",
+            OBJECT_CANNED_ACLS.join(", ")
+        ))
+    }
+}
+
+pub(crate) async fn s3_get_object_acl(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+) -> Result<ObjectAcl, String> {
+    let output = client
+        .get_object_acl()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("read the ACL of {}", format_s3_uri(bucket, key)),
+            )
+        })?;
+    Ok(ObjectAcl {
+        owner_id: output
+            .owner()
+            .and_then(|owner| owner.id())
+            .map(str::to_string),
+        owner_display_name: output
+            .owner()
+            .and_then(|owner| owner.display_name())
+            .map(str::to_string),
+        grants: output
+            .grants()
+            .iter()
+            .filter_map(|grant| {
+                let grantee = grant.grantee()?;
+                Some(ObjectAclGrant {
+                    grantee_type: grantee.r#type().as_str().to_string(),
+                    id: grantee.id().map(str::to_string),
+                    display_name: grantee.display_name().map(str::to_string),
+                    uri: grantee.uri().map(str::to_string),
+                    email_address: grantee.email_address().map(str::to_string),
+                    permission: grant.permission()?.as_str().to_string(),
+                })
+            })
+            .collect(),
+    })
+}
+
+// Explicit grants replace the whole ACL and S3 requires the owner alongside
+// them, so it's read back first.
+pub(crate) async fn s3_set_object_acl(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    acl: Option<&str>,
+    grants: Option<&[ObjectAclGrant]>,
+) -> Result<(), String> {
+    let action = format!("update the ACL of {}", format_s3_uri(bucket, key));
+    let request = client.put_object_acl().bucket(bucket).key(key);
+    let request = match (acl, grants) {
+        (Some(acl), None) => request.acl(parse_canned_acl(acl)?),
+        (None, Some(grants)) => {
+            validate_acl_grants(grants)?;
+            let current = s3_get_object_acl(client, bucket, key).await?;
+            let owner = Owner::builder()
+                .set_id(current.owner_id)
+                .set_display_name(current.owner_display_name)
+                .build();
+            let mut sdk_grants = Vec::with_capacity(grants.len());
+            for grant in grants {
+                let grantee = Grantee::builder()
+                    .r#type(Type::from(grant.grantee_type.as_str()))
+                    .set_id(grant.id.clone())
+                    .set_display_name(grant.display_name.clone())
+                    .set_uri(grant.uri.clone())
+                    .set_email_address(grant.email_address.clone())
+                    .build()
+                    .map_err(|err| format!("Invalid grantee: {err}"))?;
+                sdk_grants.push(
+                    Grant::builder()
+                        .grantee(grantee)
+                        .permission(Permission::from(grant.permission.as_str()))
+                        .build(),
+                );
+            }
+            let policy = AccessControlPolicy::builder()
+                .set_grants(Some(sdk_grants))
+                .owner(owner)
+                .build();
+            request.access_control_policy(policy)
+        }
+        _ => return Err("Provide either a canned acl or grants".to_string()),
+    };
+    request.send().await.map_err(|err| s3_error(err, &action))?;
+    Ok(())
+}
+
+pub(crate) fn validate_acl_grants(grants: &[ObjectAclGrant]) -> Result<(), String> {
+    for grant in grants {
+        if !ACL_PERMISSIONS.contains(&grant.permission.as_str()) {
+            return Err(format!(
+                "Unknown permission {} (use {})",
+                grant.permission,
+                ACL_PERMISSIONS.join(", ")
+            ));
+        }
+        let identified = match grant.grantee_type.as_str() {
+            "CanonicalUser" => grant.id.is_some(),
+            "Group" => grant.uri.is_some(),
+            "AmazonCustomerByEmail" => grant.email_address.is_some(),
+            other => return Err(format!("Unknown grantee type {other}")),
+        };
+        if !identified {
+            return Err(format!(
+                "{} grantee is missing its id, uri or email address",
+                grant.grantee_type
+            ));
+        }
+    }
+    Ok(())
+}
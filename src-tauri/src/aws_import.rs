@@ -0,0 +1,169 @@
+//! `profile:import-aws`: reads the AWS CLI's shared `credentials` and `config`
+//! files and turns sections with static keys into object0 profiles. SSO,
+//! role and `credential_process` sections have no keys to copy and are left
+//! out.
+
+use super::*;
+
+// Sections in file order, keys lowercased. Indented lines under a key with
+// no value (`s3 =` then `  endpoint_url = ...`) become `s3.endpoint_url`.
+pub(crate) fn parse_aws_ini(text: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut sections: Vec<(String, HashMap<String, String>)> = Vec::new();
+    let mut parent: Option<String> = None;
+    for raw in text.lines() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            sections.push((name.trim().to_string(), HashMap::new()));
+            parent = None;
+            continue;
+        }
+        let Some((_, values)) = sections.last_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim().to_string();
+        if raw.starts_with(char::is_whitespace) {
+            if let Some(parent) = &parent {
+                values.insert(format!("{parent}.{key}"), value);
+                continue;
+            }
+        }
+        if value.is_empty() {
+            parent = Some(key);
+        } else {
+            parent = None;
+            values.insert(key, value);
+        }
+    }
+    sections
+}
+
+// `config` names sections `[profile name]` (bare `[default]`); the
+// credentials file uses bare names and wins where both set a key.
+pub(crate) fn merge_aws_shared_profiles(credentials: &str, config: &str) -> Vec<AwsSharedProfile> {
+    let mut merged: Vec<(String, HashMap<String, String>)> = Vec::new();
+    let config_sections = parse_aws_ini(config)
+        .into_iter()
+        .filter_map(|(name, values)| {
+            let name = match name.strip_prefix("profile ") {
+                Some(name) => name.trim().to_string(),
+                None if name == "default" => name,
+                // `sso-session`, `services` and the like aren't profiles.
+                None => return None,
+            };
+            Some((name, values))
+        });
+    for (name, values) in config_sections.chain(parse_aws_ini(credentials)) {
+        match merged.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) => existing.extend(values),
+            None => merged.push((name, values)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .filter_map(|(name, values)| {
+            let field = |key: &str| values.get(key).filter(|value| !value.is_empty()).cloned();
+            Some(AwsSharedProfile {
+                access_key_id: field("aws_access_key_id")?,
+                secret_access_key: field("aws_secret_access_key")?,
+                session_token: field("aws_session_token"),
+                region: field("region"),
+                endpoint: field("endpoint_url").or_else(|| field("s3.endpoint_url")),
+                name,
+            })
+        })
+        .collect()
+}
+
+// Missing files are fine as long as one of the two exists.
+pub(crate) fn read_aws_shared_profiles() -> Result<Vec<AwsSharedProfile>, String> {
+    let credentials_path = aws_shared_file_path("AWS_SHARED_CREDENTIALS_FILE", "credentials")?;
+    let config_path = aws_shared_file_path("AWS_CONFIG_FILE", "config")?;
+    if !credentials_path.exists() && !config_path.exists() {
+        return Err(format!(
+            "No AWS credentials found at {} or {}",
+            credentials_path.display(),
+            config_path.display()
+        ));
+    }
+    let read = |path: &Path| {
+        if !path.exists() {
+            return Ok(String::new());
+        }
+        fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {err}", path.display()))
+    };
+    Ok(merge_aws_shared_profiles(
+        &read(&credentials_path)?,
+        &read(&config_path)?,
+    ))
+}
+
+// Only well-known hosts get their own provider; anything else is Custom.
+pub(crate) fn provider_for_endpoint(endpoint: Option<&str>) -> S3Provider {
+    let Some(host) = endpoint
+        .and_then(|endpoint| Url::parse(endpoint).ok())
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+    else {
+        return if endpoint.is_some() {
+            S3Provider::Custom
+        } else {
+            S3Provider::Aws
+        };
+    };
+    if host.ends_with(".amazonaws.com") {
+        S3Provider::Aws
+    } else if host.ends_with(".r2.cloudflarestorage.com") {
+        S3Provider::R2
+    } else if host.ends_with(".digitaloceanspaces.com") {
+        S3Provider::Spaces
+    } else if host.ends_with(".backblazeb2.com") {
+        S3Provider::Backblaze
+    } else if host == "storage.googleapis.com" {
+        S3Provider::Gcs
+    } else {
+        S3Provider::Custom
+    }
+}
+
+pub(crate) fn aws_profile_candidate(profile: &AwsSharedProfile) -> AwsProfileCandidate {
+    AwsProfileCandidate {
+        name: profile.name.clone(),
+        provider: provider_for_endpoint(profile.endpoint.as_deref()),
+        access_key_id: profile.access_key_id.clone(),
+        region: profile.region.clone(),
+        endpoint: profile.endpoint.clone(),
+        has_session_token: profile.session_token.is_some(),
+    }
+}
+
+pub(crate) fn aws_profile_input(profile: AwsSharedProfile) -> ProfileInput {
+    ProfileInput {
+        provider: provider_for_endpoint(profile.endpoint.as_deref()),
+        name: profile.name,
+        access_key_id: profile.access_key_id,
+        secret_access_key: profile.secret_access_key,
+        session_token: profile.session_token,
+        endpoint: profile.endpoint,
+        region: profile.region,
+        default_bucket: None,
+        requester_pays: false,
+        ephemeral: false,
+        key_transform: None,
+        require_checksums: false,
+        disable_checksums: false,
+        use_accelerate: false,
+        use_dualstack: false,
+        sse: None,
+        sse_kms_key_id: None,
+    }
+}
@@ -0,0 +1,562 @@
+//! Bucket CORS, policy and lifecycle: `buckets:get-cors` / `set-cors`,
+//! `buckets:get-policy` / `set-policy` and `buckets:get-lifecycle` /
+//! `set-lifecycle`. Setting an empty value deletes the configuration.
+
+use super::*;
+use aws_sdk_s3::{
+    error::ProvideErrorMetadata,
+    types::{
+        AbortIncompleteMultipartUpload, BucketLifecycleConfiguration, CorsConfiguration, CorsRule,
+        ExpirationStatus, LifecycleExpiration, LifecycleRule, LifecycleRuleAndOperator,
+        LifecycleRuleFilter, NoncurrentVersionExpiration, NoncurrentVersionTransition, Tag,
+        Transition, TransitionStorageClass,
+    },
+};
+
+pub(crate) async fn s3_get_bucket_cors(
+    client: &S3Client,
+    bucket: &str,
+) -> Result<Vec<BucketCorsRule>, String> {
+    let output = match client.get_bucket_cors().bucket(bucket).send().await {
+        Ok(output) => output,
+        Err(err) if err.code() == Some("NoSuchCORSConfiguration") => return Ok(Vec::new()),
+        Err(err) => return Err(s3_error(err, &format!("read CORS for {bucket}"))),
+    };
+    Ok(output
+        .cors_rules()
+        .iter()
+        .map(|rule| BucketCorsRule {
+            id: rule.id().map(str::to_string),
+            allowed_methods: rule.allowed_methods().to_vec(),
+            allowed_origins: rule.allowed_origins().to_vec(),
+            allowed_headers: rule.allowed_headers().to_vec(),
+            expose_headers: rule.expose_headers().to_vec(),
+            max_age_seconds: rule.max_age_seconds(),
+        })
+        .collect())
+}
+
+pub(crate) async fn s3_set_bucket_cors(
+    client: &S3Client,
+    bucket: &str,
+    rules: &[BucketCorsRule],
+) -> Result<(), String> {
+    validate_cors_rules(rules)?;
+    let action = format!("update CORS for {bucket}");
+    if rules.is_empty() {
+        client
+            .delete_bucket_cors()
+            .bucket(bucket)
+            .send()
+            .await
+            .map_err(|err| s3_error(err, &action))?;
+        return Ok(());
+    }
+
+    let mut cors_rules = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let cors_rule = CorsRule::builder()
+            .set_id(rule.id.clone())
+            .set_allowed_methods(Some(rule.allowed_methods.clone()))
+            .set_allowed_origins(Some(rule.allowed_origins.clone()))
+            .set_allowed_headers(Some(rule.allowed_headers.clone()))
+            .set_expose_headers(Some(rule.expose_headers.clone()))
+            .set_max_age_seconds(rule.max_age_seconds)
+            .build()
+            .map_err(|err| format!("Invalid CORS rule: {err}"))?;
+        cors_rules.push(cors_rule);
+    }
+    let configuration = CorsConfiguration::builder()
+        .set_cors_rules(Some(cors_rules))
+        .build()
+        .map_err(|err| format!("Invalid CORS configuration: {err}"))?;
+
+    client
+        .put_bucket_cors()
+        .bucket(bucket)
+        .cors_configuration(configuration)
+        .send()
+        .await
+        .map_err(|err| s3_error(err, &action))?;
+    Ok(())
+}
+
+// `None` when the bucket has no policy.
+pub(crate) async fn s3_get_bucket_policy(
+    client: &S3Client,
+    bucket: &str,
+) -> Result<Option<String>, String> {
+    match client.get_bucket_policy().bucket(bucket).send().await {
+        Ok(output) => Ok(output.policy().map(str::to_string)),
+        Err(err) if err.code() == Some("NoSuchBucketPolicy") => Ok(None),
+        Err(err) => Err(s3_error(err, &format!("read the policy for {bucket}"))),
+    }
+}
+
+pub(crate) async fn s3_set_bucket_policy(
+    client: &S3Client,
+    bucket: &str,
+    policy: Option<&str>,
+) -> Result<(), String> {
+    let action = format!("update the policy for {bucket}");
+    match policy.map(str::trim).filter(|policy| !policy.is_empty()) {
+        Some(policy) => {
+            validate_bucket_policy(policy)?;
+            client
+                .put_bucket_policy()
+                .bucket(bucket)
+                .policy(policy)
+                .send()
+                .await
+                .map_err(|err| s3_error(err, &action))?;
+        }
+        None => {
+            client
+                .delete_bucket_policy()
+                .bucket(bucket)
+                .send()
+                .await
+                .map_err(|err| s3_error(err, &action))?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn s3_get_bucket_lifecycle(
+    client: &S3Client,
+    bucket: &str,
+) -> Result<Vec<BucketLifecycleRule>, String> {
+    let output = match client
+        .get_bucket_lifecycle_configuration()
+        .bucket(bucket)
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        Err(err) if err.code() == Some("NoSuchLifecycleConfiguration") => return Ok(Vec::new()),
+        Err(err) => return Err(s3_error(err, &format!("read lifecycle rules for {bucket}"))),
+    };
+    output.rules().iter().map(lifecycle_rule_from_sdk).collect()
+}
+
+pub(crate) async fn s3_set_bucket_lifecycle(
+    client: &S3Client,
+    bucket: &str,
+    rules: &[BucketLifecycleRule],
+) -> Result<(), String> {
+    validate_lifecycle_rules(rules)?;
+    let action = format!("update lifecycle rules for {bucket}");
+    if rules.is_empty() {
+        client
+            .delete_bucket_lifecycle()
+            .bucket(bucket)
+            .send()
+            .await
+            .map_err(|err| s3_error(err, &action))?;
+        return Ok(());
+    }
+
+    let mut lifecycle_rules = Vec::with_capacity(rules.len());
+    for rule in rules {
+        lifecycle_rules.push(lifecycle_rule_to_sdk(rule)?);
+    }
+    let configuration = BucketLifecycleConfiguration::builder()
+        .set_rules(Some(lifecycle_rules))
+        .build()
+        .map_err(|err| format!("Invalid lifecycle configuration: {err}"))?;
+
+    client
+        .put_bucket_lifecycle_configuration()
+        .bucket(bucket)
+        .lifecycle_configuration(configuration)
+        .send()
+        .await
+        .map_err(|err| s3_error(err, &action))?;
+    Ok(())
+}
+
+// Rules that can't be shown faithfully are an error rather than a partial
+// rule, which a save would write back wider than it was.
+pub(crate) fn lifecycle_rule_from_sdk(rule: &LifecycleRule) -> Result<BucketLifecycleRule, String> {
+    let label = rule.id().unwrap_or("without an id");
+    let tag = |tag: &Tag| BucketLifecycleTag {
+        key: tag.key().to_string(),
+        value: tag.value().to_string(),
+    };
+    let filter = rule.filter();
+    let (prefix, tags, size_greater_than, size_less_than) =
+        match filter.and_then(|filter| filter.and()) {
+            Some(and) => (
+                and.prefix(),
+                and.tags().iter().map(tag).collect(),
+                and.object_size_greater_than(),
+                and.object_size_less_than(),
+            ),
+            None => (
+                filter.and_then(|filter| filter.prefix()),
+                filter
+                    .and_then(|filter| filter.tag())
+                    .map(tag)
+                    .into_iter()
+                    .collect(),
+                filter.and_then(|filter| filter.object_size_greater_than()),
+                filter.and_then(|filter| filter.object_size_less_than()),
+            ),
+        };
+    // Rules written before filters existed keep their prefix on the rule.
+    #[allow(deprecated)]
+    let legacy_prefix = rule.prefix().filter(|prefix| !prefix.is_empty());
+    let prefix = match (legacy_prefix, prefix.filter(|prefix| !prefix.is_empty())) {
+        (Some(legacy), Some(prefix)) if legacy != prefix => {
+            return Err(format!(
+                "Lifecycle rule {label} has two prefixes ({legacy} and {prefix})"
+            ));
+        }
+        (legacy, prefix) => prefix.or(legacy).map(str::to_string),
+    };
+
+    let mut transitions = Vec::with_capacity(rule.transitions().len());
+    for transition in rule.transitions() {
+        let date = transition.date().map(s3_datetime_to_iso);
+        let timed = transition.days().is_some() || date.is_some();
+        let Some(storage_class) = transition.storage_class().filter(|_| timed) else {
+            return Err(format!(
+                "Lifecycle rule {label} has a transition without a storage class or a time"
+            ));
+        };
+        transitions.push(BucketLifecycleTransition {
+            days: transition.days().unwrap_or(0),
+            date,
+            storage_class: storage_class.as_str().to_string(),
+        });
+    }
+    let mut noncurrent_transitions =
+        Vec::with_capacity(rule.noncurrent_version_transitions().len());
+    for transition in rule.noncurrent_version_transitions() {
+        let (Some(noncurrent_days), Some(storage_class)) =
+            (transition.noncurrent_days(), transition.storage_class())
+        else {
+            return Err(format!(
+                "Lifecycle rule {label} has a noncurrent transition without days or a storage class"
+            ));
+        };
+        noncurrent_transitions.push(BucketLifecycleNoncurrentTransition {
+            noncurrent_days,
+            storage_class: storage_class.as_str().to_string(),
+            newer_noncurrent_versions: transition.newer_noncurrent_versions(),
+        });
+    }
+
+    let expiration = rule.expiration();
+    let noncurrent_expiration = rule.noncurrent_version_expiration();
+    Ok(BucketLifecycleRule {
+        id: rule.id().map(str::to_string),
+        enabled: *rule.status() == ExpirationStatus::Enabled,
+        prefix,
+        tags,
+        object_size_greater_than: size_greater_than,
+        object_size_less_than: size_less_than,
+        transitions,
+        expiration_days: expiration.and_then(|expiration| expiration.days()),
+        expiration_date: expiration
+            .and_then(|expiration| expiration.date())
+            .map(s3_datetime_to_iso),
+        expired_object_delete_marker: expiration
+            .and_then(|expiration| expiration.expired_object_delete_marker())
+            .unwrap_or(false),
+        noncurrent_transitions,
+        noncurrent_expiration_days: noncurrent_expiration
+            .and_then(|expiration| expiration.noncurrent_days()),
+        noncurrent_newer_versions: noncurrent_expiration
+            .and_then(|expiration| expiration.newer_noncurrent_versions()),
+        abort_incomplete_multipart_days: rule
+            .abort_incomplete_multipart_upload()
+            .and_then(|abort| abort.days_after_initiation()),
+    })
+}
+
+fn parse_lifecycle_date(value: &str) -> Result<aws_sdk_s3::primitives::DateTime, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|date| aws_sdk_s3::primitives::DateTime::from_secs(date.timestamp()))
+        .map_err(|err| format!("Invalid lifecycle date {value}: {err}"))
+}
+
+pub(crate) fn lifecycle_rule_to_sdk(rule: &BucketLifecycleRule) -> Result<LifecycleRule, String> {
+    let prefix = rule.prefix.clone().unwrap_or_default();
+    let mut tags = Vec::with_capacity(rule.tags.len());
+    for tag in &rule.tags {
+        let sdk_tag = Tag::builder()
+            .key(tag.key.clone())
+            .value(tag.value.clone())
+            .build()
+            .map_err(|err| format!("Invalid lifecycle tag: {err}"))?;
+        tags.push(sdk_tag);
+    }
+    let sizes = usize::from(rule.object_size_greater_than.is_some())
+        + usize::from(rule.object_size_less_than.is_some());
+    // S3 wants a bare condition when there's one and an And block only when
+    // there are several.
+    let filter = if usize::from(!prefix.is_empty()) + tags.len() + sizes > 1 {
+        let and = LifecycleRuleAndOperator::builder()
+            .set_prefix(Some(prefix).filter(|prefix| !prefix.is_empty()))
+            .set_tags(Some(tags).filter(|tags| !tags.is_empty()))
+            .set_object_size_greater_than(rule.object_size_greater_than)
+            .set_object_size_less_than(rule.object_size_less_than)
+            .build();
+        LifecycleRuleFilter::builder().and(and).build()
+    } else if let Some(tag) = tags.pop() {
+        LifecycleRuleFilter::builder().tag(tag).build()
+    } else if sizes > 0 {
+        LifecycleRuleFilter::builder()
+            .set_object_size_greater_than(rule.object_size_greater_than)
+            .set_object_size_less_than(rule.object_size_less_than)
+            .build()
+    } else {
+        LifecycleRuleFilter::builder().prefix(prefix).build()
+    };
+
+    let mut builder = LifecycleRule::builder()
+        .set_id(rule.id.clone())
+        .status(if rule.enabled {
+            ExpirationStatus::Enabled
+        } else {
+            ExpirationStatus::Disabled
+        })
+        .filter(filter);
+    for transition in &rule.transitions {
+        let sdk_transition = match transition.date.as_deref() {
+            Some(date) => Transition::builder().date(parse_lifecycle_date(date)?),
+            None => Transition::builder().days(transition.days),
+        };
+        builder = builder.transitions(
+            sdk_transition
+                .storage_class(TransitionStorageClass::from(
+                    transition.storage_class.as_str(),
+                ))
+                .build(),
+        );
+    }
+    if rule.expiration_days.is_some()
+        || rule.expiration_date.is_some()
+        || rule.expired_object_delete_marker
+    {
+        let date = rule
+            .expiration_date
+            .as_deref()
+            .map(parse_lifecycle_date)
+            .transpose()?;
+        let mut expiration = LifecycleExpiration::builder()
+            .set_days(rule.expiration_days)
+            .set_date(date);
+        if rule.expired_object_delete_marker {
+            expiration = expiration.expired_object_delete_marker(true);
+        }
+        builder = builder.expiration(expiration.build());
+    }
+    for transition in &rule.noncurrent_transitions {
+        builder = builder.noncurrent_version_transitions(
+            NoncurrentVersionTransition::builder()
+                .noncurrent_days(transition.noncurrent_days)
+                .storage_class(TransitionStorageClass::from(
+                    transition.storage_class.as_str(),
+                ))
+                .set_newer_noncurrent_versions(transition.newer_noncurrent_versions)
+                .build(),
+        );
+    }
+    if rule.noncurrent_expiration_days.is_some() || rule.noncurrent_newer_versions.is_some() {
+        builder = builder.noncurrent_version_expiration(
+            NoncurrentVersionExpiration::builder()
+                .set_noncurrent_days(rule.noncurrent_expiration_days)
+                .set_newer_noncurrent_versions(rule.noncurrent_newer_versions)
+                .build(),
+        );
+    }
+    if let Some(days) = rule.abort_incomplete_multipart_days {
+        builder = builder.abort_incomplete_multipart_upload(
+            AbortIncompleteMultipartUpload::builder()
+                .days_after_initiation(days)
+                .build(),
+        );
+    }
+    builder
+        .build()
+        .map_err(|err| format!("Invalid lifecycle rule: {err}"))
+}
+
+// Mirrors S3's own limits so mistakes surface before the round trip.
+pub(crate) fn validate_cors_rules(rules: &[BucketCorsRule]) -> Result<(), String> {
+    if rules.len() > BUCKET_CORS_MAX_RULES {
+        return Err(format!(
+            "A bucket can have at most {BUCKET_CORS_MAX_RULES} CORS rules"
+        ));
+    }
+    for (index, rule) in rules.iter().enumerate() {
+        let label = rule.id.clone().unwrap_or_else(|| format!("#{}", index + 1));
+        if rule.allowed_methods.is_empty() {
+            return Err(format!(
+                "CORS rule {label} needs at least one allowed method"
+            ));
+        }
+        if let Some(method) = rule
+            .allowed_methods
+            .iter()
+            .find(|method| !CORS_ALLOWED_METHODS.contains(&method.as_str()))
+        {
+            return Err(format!(
+                "CORS rule {label}: unsupported method {method} (use {})",
+                CORS_ALLOWED_METHODS.join(", ")
+            ));
+        }
+        if rule.allowed_origins.is_empty() {
+            return Err(format!(
+                "CORS rule {label} needs at least one allowed origin"
+            ));
+        }
+        if rule.max_age_seconds.is_some_and(|seconds| seconds < 0) {
+            return Err(format!("CORS rule {label}: max age can't be negative"));
+        }
+    }
+    Ok(())
+}
+
+// The policy is passed through as-is; this only checks it's a JSON object.
+pub(crate) fn validate_bucket_policy(policy: &str) -> Result<(), String> {
+    match serde_json::from_str::<Value>(policy) {
+        Ok(Value::Object(_)) => Ok(()),
+        Ok(_) => Err("Bucket policy must be a JSON object".to_string()),
+        Err(err) => Err(format!("Bucket policy is not valid JSON: {err}")),
+    }
+}
+
+// S3's lifecycle limits, plus the ordering it enforces between a rule's
+// transitions and its expiration.
+pub(crate) fn validate_lifecycle_rules(rules: &[BucketLifecycleRule]) -> Result<(), String> {
+    if rules.len() > BUCKET_LIFECYCLE_MAX_RULES {
+        return Err(format!(
+            "A bucket can have at most {BUCKET_LIFECYCLE_MAX_RULES} lifecycle rules"
+        ));
+    }
+    let mut ids = HashSet::new();
+    for (index, rule) in rules.iter().enumerate() {
+        let label = rule.id.clone().unwrap_or_else(|| format!("#{}", index + 1));
+        if let Some(id) = rule.id.as_deref() {
+            if id.len() > LIFECYCLE_RULE_ID_MAX_LEN {
+                return Err(format!(
+                    "Lifecycle rule ids can be at most {LIFECYCLE_RULE_ID_MAX_LEN} characters"
+                ));
+            }
+            if !ids.insert(id) {
+                return Err(format!("Duplicate lifecycle rule id {id}"));
+            }
+        }
+        let has_action = !rule.transitions.is_empty()
+            || rule.expiration_days.is_some()
+            || rule.expiration_date.is_some()
+            || rule.expired_object_delete_marker
+            || !rule.noncurrent_transitions.is_empty()
+            || rule.noncurrent_expiration_days.is_some()
+            || rule.abort_incomplete_multipart_days.is_some();
+        if !has_action {
+            return Err(format!(
+                "Lifecycle rule {label} needs a transition or an expiration"
+            ));
+        }
+        if rule.tags.iter().any(|tag| tag.key.trim().is_empty()) {
+            return Err(format!("Lifecycle rule {label}: tag keys can't be empty"));
+        }
+        let sizes = [rule.object_size_greater_than, rule.object_size_less_than];
+        if sizes.iter().flatten().any(|size| *size < 0) {
+            return Err(format!(
+                "Lifecycle rule {label}: object sizes can't be negative"
+            ));
+        }
+        if let [Some(greater_than), Some(less_than)] = sizes {
+            if greater_than >= less_than {
+                return Err(format!(
+                    "Lifecycle rule {label}: the minimum object size must be below the maximum"
+                ));
+            }
+        }
+        let mut storage_classes = HashSet::new();
+        for transition in &rule.transitions {
+            if !LIFECYCLE_STORAGE_CLASSES.contains(&transition.storage_class.as_str()) {
+                return Err(format!(
+                    "Lifecycle rule {label}: unsupported storage class {} (use {})",
+                    transition.storage_class,
+                    LIFECYCLE_STORAGE_CLASSES.join(", ")
+                ));
+            }
+            if !storage_classes.insert(transition.storage_class.as_str()) {
+                return Err(format!(
+                    "Lifecycle rule {label}: more than one transition to {}",
+                    transition.storage_class
+                ));
+            }
+            match transition.date.as_deref() {
+                Some(date) => {
+                    parse_lifecycle_date(date)?;
+                }
+                None if transition.days < 0 => {
+                    return Err(format!(
+                        "Lifecycle rule {label}: transition days can't be negative"
+                    ));
+                }
+                None => {}
+            }
+        }
+        if let Some(date) = rule.expiration_date.as_deref() {
+            if rule.expiration_days.is_some() {
+                return Err(format!(
+                    "Lifecycle rule {label}: expire after days or on a date, not both"
+                ));
+            }
+            parse_lifecycle_date(date)?;
+        }
+        for transition in &rule.noncurrent_transitions {
+            if !LIFECYCLE_STORAGE_CLASSES.contains(&transition.storage_class.as_str()) {
+                return Err(format!(
+                    "Lifecycle rule {label}: unsupported storage class {} (use {})",
+                    transition.storage_class,
+                    LIFECYCLE_STORAGE_CLASSES.join(", ")
+                ));
+            }
+            if transition.noncurrent_days < 0 {
+                return Err(format!(
+                    "Lifecycle rule {label}: noncurrent transition days can't be negative"
+                ));
+            }
+        }
+        let positive = [
+            (rule.noncurrent_expiration_days, "noncurrent expiration"),
+            (
+                rule.abort_incomplete_multipart_days,
+                "incomplete upload cleanup",
+            ),
+        ];
+        for (days, action) in positive {
+            if days.is_some_and(|days| days < 1) {
+                return Err(format!(
+                    "Lifecycle rule {label}: {action} must be at least 1 day"
+                ));
+            }
+        }
+        if let Some(days) = rule.expiration_days {
+            if days < 1 {
+                return Err(format!(
+                    "Lifecycle rule {label}: expiration must be at least 1 day"
+                ));
+            }
+            if rule
+                .transitions
+                .iter()
+                .any(|transition| transition.date.is_none() && transition.days >= days)
+            {
+                return Err(format!(
+                    "Lifecycle rule {label}: expiration must come after every transition"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
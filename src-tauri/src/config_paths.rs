@@ -21,14 +21,61 @@ pub fn object0_config_dir() -> Result<PathBuf, String> {
     Ok(path)
 }
 
+// Where older builds (or the same user on another OS layout) kept config:
+// `~/.object0`, `~/.config/object0` and macOS's Application Support folder.
+// The current directory is never included.
+pub fn legacy_config_dirs() -> Vec<PathBuf> {
+    let Ok(current) = object0_config_dir() else {
+        return Vec::new();
+    };
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+    let mut candidates = Vec::new();
+    if let Ok(home) = home {
+        let home = PathBuf::from(home);
+        candidates.push(home.join(".object0"));
+        candidates.push(home.join(".config").join("object0"));
+        candidates.push(
+            home.join("Library")
+                .join("Application Support")
+                .join("object0"),
+        );
+    }
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        candidates.push(PathBuf::from(xdg).join("object0"));
+    }
+    if let Ok(appdata) = std::env::var("APPDATA") {
+        candidates.push(PathBuf::from(appdata).join("object0"));
+    }
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if candidate != current && candidate.is_dir() && !dirs.contains(&candidate) {
+            dirs.push(candidate);
+        }
+    }
+    dirs
+}
+
 pub fn vault_path() -> Result<PathBuf, String> {
     Ok(object0_config_dir()?.join("vault.enc"))
 }
 
+pub fn settings_path() -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?.join("settings.json"))
+}
+
 pub fn favorites_path() -> Result<PathBuf, String> {
     Ok(object0_config_dir()?.join("favorites.json"))
 }
 
+pub fn bookmarks_path() -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?.join("bookmarks.json"))
+}
+
+pub fn nav_state_path() -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?.join("last-location.json"))
+}
+
 pub fn folder_sync_rules_path() -> Result<PathBuf, String> {
     Ok(object0_config_dir()?.join("folder-sync-rules.json"))
 }
@@ -39,6 +86,38 @@ pub fn folder_sync_records_path(rule_id: &str) -> Result<PathBuf, String> {
         .join(format!("{rule_id}.json")))
 }
 
+pub fn multipart_sidecar_dir() -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?.join("multipart-uploads"))
+}
+
+pub fn multipart_sidecar_path(id: &str) -> Result<PathBuf, String> {
+    Ok(multipart_sidecar_dir()?.join(format!("{id}.json")))
+}
+
+pub fn partial_download_sidecar_path(id: &str) -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?
+        .join("partial-downloads")
+        .join(format!("{id}.json")))
+}
+
 pub fn job_history_path() -> Result<PathBuf, String> {
     Ok(object0_config_dir()?.join("job-history.json"))
 }
+
+pub fn job_queue_path() -> Result<PathBuf, String> {
+    Ok(object0_config_dir()?.join("job-queue.json"))
+}
+
+// The AWS CLI's shared files: `~/.aws/<file_name>` unless `env_var` (e.g.
+// `AWS_CONFIG_FILE`) points elsewhere.
+pub fn aws_shared_file_path(env_var: &str, file_name: &str) -> Result<PathBuf, String> {
+    if let Ok(path) = std::env::var(env_var) {
+        if !path.trim().is_empty() {
+            return Ok(PathBuf::from(path));
+        }
+    }
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Unable to resolve HOME/USERPROFILE".to_string())?;
+    Ok(PathBuf::from(home).join(".aws").join(file_name))
+}
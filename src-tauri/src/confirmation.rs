@@ -0,0 +1,37 @@
+//! One-shot confirmation tokens for destructive RPCs. The first call returns a
+//! token bound to the exact operation; it only runs when a second call echoes
+//! that token back before it expires.
+
+use super::*;
+
+pub(crate) fn issue_confirmation_token(state: &AppState, action: &str) -> Result<String, String> {
+    let token = Uuid::new_v4().to_string();
+    let now = Instant::now();
+    let mut pending = lock_state(&state.confirmations)?;
+    pending.retain(|_, confirmation| confirmation.expires_at > now);
+    pending.insert(
+        token.clone(),
+        PendingConfirmation {
+            action: action.to_string(),
+            expires_at: now + StdDuration::from_secs(CONFIRMATION_TOKEN_TTL_SECS),
+        },
+    );
+    Ok(token)
+}
+
+// Tokens are single-use: a mismatched or expired token is spent too.
+pub(crate) fn consume_confirmation_token(
+    state: &AppState,
+    action: &str,
+    token: &str,
+) -> Result<(), String> {
+    let confirmation = lock_state(&state.confirmations)?.remove(token);
+    match confirmation {
+        Some(confirmation) if confirmation.expires_at <= Instant::now() => {
+            Err("Confirmation expired; request a new one".to_string())
+        }
+        Some(confirmation) if confirmation.action == action => Ok(()),
+        Some(_) => Err("Confirmation token was issued for a different operation".to_string()),
+        None => Err("Unknown or already used confirmation token".to_string()),
+    }
+}
@@ -1,17 +1,20 @@
 //! Vault cryptography: key derivation, AES-256-GCM payload encryption, recovery
 //! keys, and base64/random helpers. Constants (KEY_BYTES, IV_BYTES, …) live in
-//! the crate root and are visible here as a descendant module.
+//! the crate root and are visible here as a descendant module. Derived keys and
+//! decrypted payloads are `Zeroizing`, so they're overwritten when dropped.
 
 use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce,
 };
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use pbkdf2::pbkdf2_hmac;
 use rand::RngCore;
 use sha2::Sha512;
+use zeroize::Zeroizing;
 
-use super::{IV_BYTES, KEY_BYTES, PBKDF2_ITERATIONS, RECOVERY_KEY_LENGTH};
+use super::{VaultKdfConfig, IV_BYTES, KEY_BYTES, PBKDF2_ITERATIONS, RECOVERY_KEY_LENGTH};
 
 pub(crate) fn random_bytes<const N: usize>() -> [u8; N] {
     let mut bytes = [0u8; N];
@@ -29,12 +32,40 @@ pub(crate) fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
         .map_err(|err| format!("Invalid base64 payload: {err}"))
 }
 
-pub(crate) fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_BYTES] {
-    let mut key = [0u8; KEY_BYTES];
-    pbkdf2_hmac::<Sha512>(passphrase.as_bytes(), salt, PBKDF2_ITERATIONS, &mut key);
+pub(crate) fn derive_key(passphrase: &str, salt: &[u8]) -> Zeroizing<[u8; KEY_BYTES]> {
+    let mut key = Zeroizing::new([0u8; KEY_BYTES]);
+    pbkdf2_hmac::<Sha512>(passphrase.as_bytes(), salt, PBKDF2_ITERATIONS, &mut *key);
     key
 }
 
+pub(crate) fn derive_vault_key(
+    passphrase: &str,
+    salt: &[u8],
+    kdf: VaultKdfConfig,
+) -> Result<Zeroizing<[u8; KEY_BYTES]>, String> {
+    let mut key = Zeroizing::new([0u8; KEY_BYTES]);
+    match kdf {
+        VaultKdfConfig::Pbkdf2 { iterations } => {
+            if iterations == 0 {
+                return Err("Invalid PBKDF2 iteration count".to_string());
+            }
+            pbkdf2_hmac::<Sha512>(passphrase.as_bytes(), salt, iterations, &mut *key);
+        }
+        VaultKdfConfig::Argon2id {
+            memory_kib,
+            time_cost,
+            parallelism,
+        } => {
+            let params = Params::new(memory_kib, time_cost, parallelism, Some(KEY_BYTES))
+                .map_err(|err| format!("Invalid Argon2 parameters: {err}"))?;
+            Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                .hash_password_into(passphrase.as_bytes(), salt, &mut *key)
+                .map_err(|err| format!("Argon2 key derivation failed: {err}"))?;
+        }
+    }
+    Ok(key)
+}
+
 pub(crate) fn encrypt_payload(
     key: &[u8; KEY_BYTES],
     plaintext: &[u8],
@@ -54,7 +85,7 @@ pub(crate) fn decrypt_payload(
     key: &[u8; KEY_BYTES],
     iv: &[u8],
     ciphertext: &[u8],
-) -> Result<Vec<u8>, String> {
+) -> Result<Zeroizing<Vec<u8>>, String> {
     if iv.len() != IV_BYTES {
         return Err("Invalid vault IV length".to_string());
     }
@@ -64,6 +95,7 @@ pub(crate) fn decrypt_payload(
     let nonce = Nonce::from_slice(iv);
     cipher
         .decrypt(nonce, ciphertext)
+        .map(Zeroizing::new)
         .map_err(|_| "Invalid passphrase".to_string())
 }
 
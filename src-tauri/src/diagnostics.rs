@@ -0,0 +1,142 @@
+//! `app:self-test`: one-call availability checks for bug reports. Reports
+//! only presence/health — never passphrases, keys, or profile credentials.
+
+use super::*;
+
+pub(crate) async fn run_self_test(app: &AppHandle) -> Value {
+    let config_dir = match object0_config_dir() {
+        Ok(dir) => {
+            let writable = probe_dir_writable(&dir);
+            json!({
+                "path": dir.to_string_lossy(),
+                "writable": writable.is_ok(),
+                "error": writable.err(),
+            })
+        }
+        Err(err) => json!({ "path": null, "writable": false, "error": err }),
+    };
+
+    let keychain = match read_stored_passphrase() {
+        KeychainReadResult::Available(stored) => json!({
+            "available": true,
+            "hasStoredPassphrase": stored.is_some(),
+            "error": null,
+        }),
+        KeychainReadResult::Unavailable(err) => json!({
+            "available": false,
+            "hasStoredPassphrase": false,
+            "error": err,
+        }),
+    };
+
+    let unlocked = {
+        let state = app.state::<AppState>();
+        lock_state(&state.vault)
+            .map(|vault| vault.unlocked)
+            .unwrap_or(false)
+    };
+    let vault = match vault_path() {
+        Ok(path) if path.exists() => match read_vault_file(&path) {
+            Ok(file) => json!({
+                "exists": true,
+                "version": vault_file_version(&file),
+                "currentVersion": CURRENT_VAULT_VERSION,
+                "unlocked": unlocked,
+                "error": null,
+            }),
+            Err(err) => json!({
+                "exists": true,
+                "version": null,
+                "currentVersion": CURRENT_VAULT_VERSION,
+                "unlocked": unlocked,
+                "error": err,
+            }),
+        },
+        Ok(_) => json!({
+            "exists": false,
+            "version": null,
+            "currentVersion": CURRENT_VAULT_VERSION,
+            "unlocked": false,
+            "error": null,
+        }),
+        Err(err) => json!({
+            "exists": false,
+            "version": null,
+            "currentVersion": CURRENT_VAULT_VERSION,
+            "unlocked": false,
+            "error": err,
+        }),
+    };
+
+    let endpoint = updater_local_info_endpoint();
+    let started = Instant::now();
+    let reachable = tauri::async_runtime::spawn_blocking(move || probe_endpoint(&endpoint))
+        .await
+        .map_err(|err| format!("Updater probe failed: {err}"))
+        .and_then(|result| result);
+    let updater = json!({
+        "baseUrl": updater_local_info_base_url(),
+        "channel": updater_channel(),
+        "reachable": reachable.is_ok(),
+        "latencyMs": reachable.is_ok().then(|| started.elapsed().as_millis() as u64),
+        "error": reachable.err(),
+    });
+
+    let ok = config_dir["writable"] == json!(true)
+        && keychain["available"] == json!(true)
+        && vault["error"].is_null()
+        && updater["reachable"] == json!(true);
+
+    json!({
+        "ok": ok,
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "configDir": config_dir,
+        "keychain": keychain,
+        "vault": vault,
+        "updater": updater,
+    })
+}
+
+fn vault_file_version(file: &VaultFileDisk) -> u8 {
+    match file {
+        VaultFileDisk::V1(v1) => v1.version,
+        VaultFileDisk::V2(v2) => v2.version,
+        VaultFileDisk::V3(v3) => v3.version,
+        VaultFileDisk::V4(v4) => v4.version,
+    }
+}
+
+// Creates the directory if needed, then writes and removes a probe file.
+pub(crate) fn probe_dir_writable(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {err}", dir.display()))?;
+    let probe = dir.join(format!(".object0-self-test-{}.tmp", Uuid::new_v4()));
+    fs::write(&probe, b"ok").map_err(|err| format!("Failed to write {}: {err}", dir.display()))?;
+    fs::remove_file(&probe)
+        .map_err(|err| format!("Failed to remove probe in {}: {err}", dir.display()))
+}
+
+// A TCP connect is enough to tell "offline / blocked" from "reachable"
+// without fetching the manifest or touching the cached update.
+fn probe_endpoint(endpoint: &str) -> Result<(), String> {
+    let url = Url::parse(endpoint).map_err(|err| format!("Invalid updater endpoint: {err}"))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| "Updater endpoint has no host".to_string())?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| "Updater endpoint has no port".to_string())?;
+    let timeout = StdDuration::from_secs(SELF_TEST_NETWORK_TIMEOUT_SECS);
+
+    let addrs = std::net::ToSocketAddrs::to_socket_addrs(&(host, port))
+        .map_err(|err| format!("Failed to resolve {host}: {err}"))?;
+    let mut last_err = format!("No addresses found for {host}");
+    for addr in addrs {
+        match std::net::TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return Ok(()),
+            Err(err) => last_err = format!("Failed to connect to {host}:{port}: {err}"),
+        }
+    }
+    Err(last_err)
+}
@@ -31,6 +31,8 @@ pub(crate) fn mark_folder_sync_last_change(app: &AppHandle, rule_id: &str, files
                         last_change: None,
                         current_file: None,
                         progress: None,
+                        effective_poll_interval_ms: None,
+                        dry_run: false,
                     });
             record.last_change = Some(now_iso());
             record.files_watching = files_watching.max(0);
@@ -58,34 +60,147 @@ pub(crate) async fn wait_for_folder_sync_wake(control: &FolderSyncTaskControl, p
     }
 }
 
+// Sleeps until `next_run`; `false` when sync-now, pause/resume or stop woke
+// the rule first.
+pub(crate) async fn wait_for_folder_sync_schedule(
+    control: &FolderSyncTaskControl,
+    next_run: chrono::DateTime<chrono::Local>,
+) -> bool {
+    let (tx, mut rx) = oneshot::channel::<()>();
+    if let Ok(mut slot) = control.wake_tx.lock() {
+        *slot = Some(tx);
+    }
+
+    let mut fired = !control.sync_requested.load(Ordering::SeqCst);
+    while fired {
+        let remaining = (next_run - chrono::Local::now())
+            .to_std()
+            .unwrap_or_default();
+        if remaining.is_zero() {
+            break;
+        }
+        let step = remaining.min(StdDuration::from_secs(FOLDER_SYNC_SCHEDULE_CHECK_SECS));
+        fired = tokio::time::timeout(step, &mut rx).await.is_err();
+    }
+
+    if let Ok(mut slot) = control.wake_tx.lock() {
+        *slot = None;
+    }
+    fired
+}
+
+// Manual-only rules park without a timeout until sync-now, pause/resume or
+// stop wakes them.
+pub(crate) async fn wait_for_folder_sync_trigger(control: &FolderSyncTaskControl) {
+    let (tx, rx) = oneshot::channel::<()>();
+    if let Ok(mut slot) = control.wake_tx.lock() {
+        *slot = Some(tx);
+    }
+
+    // A request that landed before the slot was set would otherwise be lost.
+    if !control.sync_requested.load(Ordering::SeqCst) {
+        let _ = rx.await;
+    }
+
+    if let Ok(mut slot) = control.wake_tx.lock() {
+        *slot = None;
+    }
+}
+
+pub(crate) fn folder_sync_rule_watches_local(rule: &FolderSyncRuleRecord) -> bool {
+    rule.direction != SyncDirection::RemoteToLocal
+        && rule.schedule.is_none()
+        && (!rule.manual_only || rule.manual_watch)
+}
+
+pub(crate) fn parse_folder_sync_schedule(schedule: &str) -> Result<croner::Cron, String> {
+    croner::Cron::new(schedule.trim())
+        .parse()
+        .map_err(|err| format!("Invalid schedule \"{schedule}\": {err}"))
+}
+
+pub(crate) fn next_folder_sync_run(
+    schedule: &str,
+    after: &chrono::DateTime<chrono::Local>,
+) -> Result<chrono::DateTime<chrono::Local>, String> {
+    parse_folder_sync_schedule(schedule)?
+        .find_next_occurrence(after, false)
+        .map_err(|err| format!("Schedule \"{schedule}\" never runs: {err}"))
+}
+
 pub(crate) async fn run_folder_sync_once(
     app: &AppHandle,
     rule: &FolderSyncRuleRecord,
     control: &FolderSyncTaskControl,
-) -> Result<(), String> {
+) -> Result<FolderSyncPassOutcome, String> {
     let state = app.state::<AppState>();
     let profile = profile_for_id(&state, &rule.profile_id)?;
     let client = to_s3_client(&profile)?;
 
-    let known_records = load_folder_sync_file_records(&rule.id);
-    let diff = generate_folder_sync_diff_for_rule(rule, &client, &known_records).await?;
+    let mut records = FolderSyncRecordStore::load(&rule.id);
+    let mut diff = generate_folder_sync_diff_for_rule(rule, &client, &records.to_vec()).await?;
+    for paths in &diff.case_collisions {
+        emit_folder_sync_case_collision_warning(app, rule, paths);
+    }
+    let new_cycles: Vec<&String> = {
+        let mut reported = lock_state(&control.reported_symlink_cycles)?;
+        reported.retain(|path| diff.symlink_cycles.contains(path));
+        diff.symlink_cycles
+            .iter()
+            .filter(|path| reported.insert((*path).clone()))
+            .collect()
+    };
+    for path in new_cycles {
+        let message = format!("Symlink cycle at {path}: not followed");
+        emit_folder_sync_error_event(app, &rule.id, &message);
+    }
+    if rule.dry_run {
+        return Ok(report_folder_sync_dry_run(app, rule, &diff));
+    }
+    for record in std::mem::take(&mut diff.baseline) {
+        records.upsert(record)?;
+    }
+    records.flush()?;
+    let local_root = expand_user_path(&rule.local_path);
+    if rule.conflict_resolution == ConflictResolution::KeepBoth
+        && rule.direction != SyncDirection::LocalToRemote
+    {
+        // The local file is renamed before the remote version arrives. Its
+        // record goes now, so a download that fails or is cancelled leaves a
+        // remote-only file for the next pass to fetch, not a local delete to
+        // mirror onto the bucket.
+        for relative_path in apply_keep_both_conflicts(rule, &local_root, &mut diff, false) {
+            records.remove(&relative_path)?;
+        }
+        records.flush()?;
+    }
     for conflict in &diff.conflicts {
         emit_folder_sync_conflict_event(app, &rule.id, conflict);
     }
+    let mut counts = FolderSyncPassCounts {
+        conflicts: diff.conflicts.len(),
+        ..FolderSyncPassCounts::default()
+    };
 
     let total_actions = diff.uploads.len()
         + diff.downloads.len()
         + diff.delete_local.len()
         + diff.delete_remote.len();
-    let files_watching = if rule.direction == SyncDirection::RemoteToLocal {
-        0
-    } else {
+    let files_watching = if folder_sync_rule_watches_local(rule) {
         1
+    } else {
+        0
+    };
+
+    let pass_outcome = FolderSyncPassOutcome {
+        remote_objects: diff.remote_objects,
+        changed: total_actions > 0 || !diff.conflicts.is_empty(),
     };
 
     if total_actions == 0 {
         update_folder_sync_rule_result(&rule.id, Some("success"), None)?;
-        return Ok(());
+        notify_folder_sync_pass(app, rule, &counts);
+        return Ok(pass_outcome);
     }
 
     let bytes_total: i64 = diff
@@ -99,239 +214,204 @@ pub(crate) async fn run_folder_sync_once(
             .map(|entry| entry.remote_size.unwrap_or(0))
             .sum::<i64>();
 
-    let local_root = expand_user_path(&rule.local_path);
-    let mut completed: i64 = 0;
-    let total = total_actions as i64;
-    let mut bytes_transferred: i64 = 0;
     let mut errors: Vec<String> = Vec::new();
     let bucket_prefix = normalize_prefix(&rule.bucket_prefix);
-
-    let emit_progress = |current_file: Option<String>,
-                         completed: i64,
-                         bytes_transferred: i64|
-     -> Result<(), String> {
-        set_and_emit_folder_sync_status(
-            app,
-            &rule.id,
-            FolderSyncStatus::Syncing,
-            files_watching,
-            Some(now_iso()),
-            current_file,
-            Some(FolderSyncProgress {
-                completed,
-                total,
-                bytes_transferred: bytes_transferred.max(0),
-                bytes_total: bytes_total.max(0),
-            }),
-        )
+    let transform = folder_sync_key_transform(rule);
+    let concurrency = folder_sync_concurrency(rule);
+
+    let progress = FolderSyncPassProgress {
+        app: app.clone(),
+        rule_id: rule.id.clone(),
+        files_watching,
+        total: total_actions as i64,
+        bytes_total: bytes_total.max(0),
+        completed: Arc::new(AtomicI64::new(0)),
+        bytes_transferred: Arc::new(AtomicI64::new(0)),
     };
 
-    emit_progress(None, completed, bytes_transferred)?;
-
-    for entry in &diff.uploads {
-        if control.cancel_flag.load(Ordering::SeqCst) {
-            return Err(JOB_CANCELLED.to_string());
-        }
-        if control.pause_flag.load(Ordering::SeqCst) {
-            return Ok(());
-        }
+    progress.emit(None)?;
+    // Sync transfers count against the same bandwidth cap as jobs.
+    let bandwidth = lock_state(&state.jobs)?.bandwidth.clone();
+    let upload_attributes = UploadAttributes {
+        bandwidth: Some(bandwidth.clone()),
+        ..upload_attributes_from_profile(&profile)?
+    };
+    let download_attributes = DownloadAttributes {
+        bandwidth: Some(bandwidth),
+        ..download_attributes_from_settings(&state)
+    };
 
-        let Some(relative_path) = sanitize_relative_path(&entry.relative_path) else {
-            errors.push(format!(
-                "Upload {}: invalid relative path",
-                entry.relative_path
-            ));
-            completed += 1;
-            continue;
-        };
+    // Uploads and downloads share one bounded pool. Only this coordinator
+    // touches the record map, so updates stay serialized even though the
+    // transfers themselves overlap.
+    let mut pending = diff
+        .uploads
+        .iter()
+        .map(|entry| (FolderSyncTransferDirection::Upload, entry))
+        .chain(
+            diff.downloads
+                .iter()
+                .map(|entry| (FolderSyncTransferDirection::Download, entry)),
+        );
+    let mut in_flight: JoinSet<FolderSyncTransferOutcome> = JoinSet::new();
+    let mut interrupted: Option<Result<(), String>> = None;
 
-        let local_path = local_root.join(&relative_path);
-        let remote_key = format!("{}{}", bucket_prefix, entry.relative_path);
-        let current_file = entry.relative_path.clone();
-        let base_completed = completed;
-        let base_transferred = bytes_transferred;
-
-        emit_progress(Some(current_file.clone()), completed, bytes_transferred)?;
-
-        let upload_result = s3_upload_file(
-            &client,
-            &rule.bucket,
-            &remote_key,
-            &local_path,
-            &control.cancel_flag,
-            |transferred, _total| {
-                let _ = emit_progress(
-                    Some(current_file.clone()),
-                    base_completed,
-                    base_transferred + transferred,
-                );
-            },
-        )
-        .await;
-
-        match upload_result {
-            Ok(transferred) => {
-                let remote_meta = client
-                    .head_object()
-                    .bucket(rule.bucket.clone())
-                    .key(remote_key.clone())
-                    .send()
-                    .await
-                    .map_err(|err| err.to_string())?;
-                let record = FolderSyncFileRecord {
-                    relative_path: entry.relative_path.clone(),
-                    // Just-transferred file; epoch on stat failure is a harmless
-                    // "treat as changed" fallback, not a silent error.
-                    local_mtime: file_mtime_millis(&local_path).unwrap_or(0),
-                    local_size: fs::metadata(&local_path)
-                        .map(|meta| meta.len() as i64)
-                        .unwrap_or(0)
-                        .max(0),
-                    remote_etag: remote_meta
-                        .e_tag()
-                        .unwrap_or_default()
-                        .trim_matches('"')
-                        .to_string(),
-                    remote_last_modified: remote_meta
-                        .last_modified()
-                        .map(s3_datetime_to_iso)
-                        .unwrap_or_else(now_iso),
-                    remote_size: remote_meta.content_length().unwrap_or(0).max(0),
-                    synced_at: now_iso(),
-                };
-                update_folder_sync_file_record(&rule.id, record)?;
-                bytes_transferred += transferred.max(0);
+    loop {
+        while interrupted.is_none() && in_flight.len() < concurrency {
+            if control.cancel_flag.load(Ordering::SeqCst) {
+                interrupted = Some(Err(JOB_CANCELLED.to_string()));
+                break;
             }
-            Err(err) => {
-                errors.push(format!("Upload {}: {}", entry.relative_path, err));
+            if control.pause_flag.load(Ordering::SeqCst) {
+                interrupted = Some(Ok(()));
+                break;
             }
-        }
+            let Some((direction, entry)) = pending.next() else {
+                break;
+            };
 
-        completed += 1;
-        emit_progress(
-            Some(entry.relative_path.clone()),
-            completed,
-            bytes_transferred,
-        )?;
-    }
+            let Some(relative_path) = sanitize_relative_path(entry.local_path()) else {
+                errors.push(format!(
+                    "{} {}: invalid relative path",
+                    direction.label(),
+                    entry.relative_path
+                ));
+                progress.complete_one();
+                continue;
+            };
 
-    for entry in &diff.downloads {
-        if control.cancel_flag.load(Ordering::SeqCst) {
-            return Err(JOB_CANCELLED.to_string());
-        }
-        if control.pause_flag.load(Ordering::SeqCst) {
-            return Ok(());
+            // Uploads may read through a followed link; downloads must not
+            // write through one.
+            let local_path = match direction {
+                FolderSyncTransferDirection::Upload => Ok(local_root.join(&relative_path)),
+                FolderSyncTransferDirection::Download => {
+                    contained_local_path(&local_root, &relative_path)
+                }
+            };
+            let local_path = match local_path {
+                Ok(local_path) => local_path,
+                Err(err) => {
+                    counts.failed += 1;
+                    errors.push(format!(
+                        "{} {}: {err}",
+                        direction.label(),
+                        entry.relative_path
+                    ));
+                    progress.complete_one();
+                    continue;
+                }
+            };
+
+            progress.emit(Some(entry.relative_path.clone()))?;
+            let task = FolderSyncTransferTask {
+                direction,
+                client: client.clone(),
+                bucket: rule.bucket.clone(),
+                remote_key: transform.apply(&format!("{}{}", bucket_prefix, entry.relative_path)),
+                local_path,
+                entry: entry.clone(),
+                cancel_flag: control.cancel_flag.clone(),
+                progress: progress.clone(),
+                upload_attributes: upload_attributes.clone(),
+                download_attributes: download_attributes.clone(),
+            };
+            match direction {
+                FolderSyncTransferDirection::Upload => {
+                    in_flight.spawn(folder_sync_upload_entry(task));
+                }
+                FolderSyncTransferDirection::Download => {
+                    in_flight.spawn(folder_sync_download_entry(task));
+                }
+            }
         }
 
-        let Some(relative_path) = sanitize_relative_path(&entry.relative_path) else {
-            errors.push(format!(
-                "Download {}: invalid relative path",
-                entry.relative_path
-            ));
-            completed += 1;
-            continue;
+        let Some(joined) = in_flight.join_next().await else {
+            break;
         };
-
-        let local_path = local_root.join(&relative_path);
-        let tmp_path = PathBuf::from(format!("{}.object0-tmp", local_path.display()));
-        let remote_key = format!("{}{}", bucket_prefix, entry.relative_path);
-        let current_file = entry.relative_path.clone();
-        let base_completed = completed;
-        let base_transferred = bytes_transferred;
-
-        emit_progress(Some(current_file.clone()), completed, bytes_transferred)?;
-
-        let download_result = s3_download_file(
-            &client,
-            &rule.bucket,
-            &remote_key,
-            &tmp_path,
-            &control.cancel_flag,
-            |transferred, _total| {
-                let _ = emit_progress(
-                    Some(current_file.clone()),
-                    base_completed,
-                    base_transferred + transferred,
-                );
-            },
-        )
-        .await;
-
-        match download_result {
-            Ok(transferred) => {
-                if let Some(parent) = local_path.parent() {
-                    fs::create_dir_all(parent)
-                        .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        let outcome = joined.map_err(|err| format!("Folder sync transfer task failed: {err}"))?;
+
+        match outcome.result {
+            Ok((transferred, record)) => {
+                progress.add_bytes(transferred.max(0) - outcome.reported_bytes);
+                records.upsert(record)?;
+                match outcome.direction {
+                    FolderSyncTransferDirection::Upload => counts.uploaded += 1,
+                    FolderSyncTransferDirection::Download => counts.downloaded += 1,
                 }
-                fs::rename(&tmp_path, &local_path).map_err(|err| {
-                    format!(
-                        "Failed to move {} -> {}: {err}",
-                        tmp_path.display(),
-                        local_path.display()
-                    )
-                })?;
-
-                let record = FolderSyncFileRecord {
-                    relative_path: entry.relative_path.clone(),
-                    // Just-transferred file; epoch on stat failure is a harmless
-                    // "treat as changed" fallback, not a silent error.
-                    local_mtime: file_mtime_millis(&local_path).unwrap_or(0),
-                    local_size: fs::metadata(&local_path)
-                        .map(|meta| meta.len() as i64)
-                        .unwrap_or(0)
-                        .max(0),
-                    remote_etag: entry.remote_etag.clone().unwrap_or_default(),
-                    remote_last_modified: entry
-                        .remote_last_modified
-                        .clone()
-                        .unwrap_or_else(now_iso),
-                    remote_size: entry.remote_size.unwrap_or(transferred.max(0)),
-                    synced_at: now_iso(),
-                };
-                update_folder_sync_file_record(&rule.id, record)?;
-                bytes_transferred += transferred.max(0);
             }
             Err(err) => {
-                let _ = fs::remove_file(&tmp_path);
-                errors.push(format!("Download {}: {}", entry.relative_path, err));
+                counts.failed += 1;
+                progress.add_bytes(-outcome.reported_bytes);
+                errors.push(format!(
+                    "{} {}: {}",
+                    outcome.direction.label(),
+                    outcome.relative_path,
+                    err
+                ));
             }
         }
 
-        completed += 1;
-        emit_progress(
-            Some(entry.relative_path.clone()),
-            completed,
-            bytes_transferred,
-        )?;
+        progress.complete_one();
+        progress.emit(Some(outcome.relative_path))?;
+    }
+
+    if let Some(result) = interrupted {
+        return result.map(|()| pass_outcome);
+    }
+    if control.cancel_flag.load(Ordering::SeqCst) {
+        return Err(JOB_CANCELLED.to_string());
     }
 
+    // One trash batch per pass, shared by both sides.
+    let trash_stamp = Utc::now()
+        .format(FOLDER_SYNC_TRASH_STAMP_FORMAT)
+        .to_string();
+
     for entry in &diff.delete_local {
         if control.cancel_flag.load(Ordering::SeqCst) {
             return Err(JOB_CANCELLED.to_string());
         }
         if control.pause_flag.load(Ordering::SeqCst) {
-            return Ok(());
+            return Ok(pass_outcome);
         }
 
-        let Some(relative_path) = sanitize_relative_path(&entry.relative_path) else {
+        let Some(relative_path) = sanitize_relative_path(entry.local_path()) else {
             errors.push(format!(
                 "Delete local {}: invalid relative path",
                 entry.relative_path
             ));
-            completed += 1;
+            progress.complete_one();
             continue;
         };
 
-        let local_path = local_root.join(relative_path);
-        let _ = fs::remove_file(&local_path);
-        let _ = remove_folder_sync_file_record(&rule.id, &entry.relative_path);
+        if rule.safe_delete {
+            // Left tracked on failure so the next pass tries again.
+            if let Err(err) = move_to_local_trash(&local_root, &relative_path, &trash_stamp) {
+                counts.failed += 1;
+                errors.push(format!("Delete local {}: {err}", entry.relative_path));
+                progress.complete_one();
+                progress.emit(Some(entry.relative_path.clone()))?;
+                continue;
+            }
+        } else {
+            match contained_local_path(&local_root, &relative_path) {
+                Ok(local_path) => {
+                    let _ = fs::remove_file(local_path);
+                }
+                Err(err) => {
+                    counts.failed += 1;
+                    errors.push(format!("Delete local {}: {err}", entry.relative_path));
+                    progress.complete_one();
+                    progress.emit(Some(entry.relative_path.clone()))?;
+                    continue;
+                }
+            }
+        }
+        records.remove(&entry.relative_path)?;
+        counts.deleted_local += 1;
 
-        completed += 1;
-        emit_progress(
-            Some(entry.relative_path.clone()),
-            completed,
-            bytes_transferred,
-        )?;
+        progress.complete_one();
+        progress.emit(Some(entry.relative_path.clone()))?;
     }
 
     if !diff.delete_remote.is_empty() {
@@ -339,30 +419,60 @@ pub(crate) async fn run_folder_sync_once(
             return Err(JOB_CANCELLED.to_string());
         }
         if control.pause_flag.load(Ordering::SeqCst) {
-            return Ok(());
+            return Ok(pass_outcome);
         }
 
-        let delete_keys: Vec<String> = diff
-            .delete_remote
-            .iter()
-            .map(|entry| format!("{}{}", bucket_prefix, entry.relative_path))
-            .collect();
+        let mut delete_entries: Vec<&FolderSyncDiffEntryRecord> = Vec::new();
+        let mut delete_keys: Vec<String> = Vec::new();
+        for entry in &diff.delete_remote {
+            let key = transform.apply(&format!("{}{}", bucket_prefix, entry.relative_path));
+            if rule.safe_delete {
+                let trash_key = transform.apply(&format!(
+                    "{}{}/{}/{}",
+                    bucket_prefix, FOLDER_SYNC_TRASH_DIR, trash_stamp, entry.relative_path
+                ));
+                let copied = s3_copy_object(
+                    &client,
+                    &rule.bucket,
+                    &key,
+                    &client,
+                    &rule.bucket,
+                    &trash_key,
+                    &control.cancel_flag,
+                    |_, _| {},
+                )
+                .await;
+                // Without a trash copy the object stays, still tracked, and
+                // the next pass tries again.
+                if let Err(err) = copied {
+                    counts.failed += 1;
+                    errors.push(format!("Delete remote {}: {err}", entry.relative_path));
+                    progress.complete_one();
+                    progress.emit(Some(entry.relative_path.clone()))?;
+                    continue;
+                }
+            }
+            delete_entries.push(entry);
+            delete_keys.push(key);
+        }
 
-        if let Err(err) = s3_delete_keys(&client, &rule.bucket, &delete_keys).await {
-            errors.push(format!("Delete remote: {err}"));
+        match s3_delete_keys(&client, &rule.bucket, &delete_keys).await {
+            Ok(()) => counts.deleted_remote += delete_keys.len(),
+            Err(err) => {
+                counts.failed += delete_keys.len();
+                errors.push(format!("Delete remote: {err}"));
+            }
         }
 
-        for entry in &diff.delete_remote {
-            let _ = remove_folder_sync_file_record(&rule.id, &entry.relative_path);
-            completed += 1;
-            emit_progress(
-                Some(entry.relative_path.clone()),
-                completed,
-                bytes_transferred,
-            )?;
+        for entry in delete_entries {
+            records.remove(&entry.relative_path)?;
+            progress.complete_one();
+            progress.emit(Some(entry.relative_path.clone()))?;
         }
     }
 
+    records.flush()?;
+
     if errors.is_empty() {
         update_folder_sync_rule_result(&rule.id, Some("success"), None)?;
     } else {
@@ -378,8 +488,739 @@ pub(crate) async fn run_folder_sync_once(
         )?;
         emit_folder_sync_error_event(app, &rule.id, &errors.join("; "));
     }
+    notify_folder_sync_pass(app, rule, &counts);
+
+    Ok(pass_outcome)
+}
+
+pub(crate) fn validate_folder_sync_rule(rule: &FolderSyncRuleRecord) -> Result<(), String> {
+    if let Some(schedule) = &rule.schedule {
+        if rule.manual_only {
+            return Err("A manual-only rule can't also have a schedule".to_string());
+        }
+        next_folder_sync_run(schedule, &chrono::Local::now())?;
+    }
+    if let Some(max_poll_ms) = rule.max_poll_interval_ms {
+        if max_poll_ms < rule.poll_interval_ms {
+            return Err("Max poll interval can't be below the poll interval".to_string());
+        }
+    }
+    if let Some(transform) = &rule.key_transform {
+        validate_key_transform(transform)?;
+        if transform.prefix.contains("{date}") || transform.suffix.contains("{date}") {
+            return Err(
+                "Sync rules can't use {date} in keys: remote files would appear to vanish every day"
+                    .to_string(),
+            );
+        }
+        if transform.prefix.contains("{host}") || transform.suffix.contains("{host}") {
+            return Err(
+                "Sync rules need {host} resolved when saved: a hostname change would move every key"
+                    .to_string(),
+            );
+        }
+    }
+    validate_name_template(&rule.conflict_rename_template)
+        .map_err(|err| format!("Invalid conflict rename template: {err}"))
+}
+
+// Overlapping rules would sync each other's writes (and temp files) back and
+// forth; a root covering the config dir would wake on every record write.
+pub(crate) fn validate_folder_sync_local_root(
+    rule: &FolderSyncRuleRecord,
+    rules: &[FolderSyncRuleRecord],
+) -> Result<(), String> {
+    let root = resolve_local_root(&rule.local_path);
+    if let Ok(config_dir) = object0_config_dir() {
+        let config_dir = fs::canonicalize(&config_dir).unwrap_or(config_dir);
+        if local_roots_overlap(&root, &config_dir) {
+            return Err(format!(
+                "Local folder can't overlap the app's config folder ({})",
+                config_dir.display()
+            ));
+        }
+    }
+    for other in rules.iter().filter(|other| other.id != rule.id) {
+        if local_roots_overlap(&root, &resolve_local_root(&other.local_path)) {
+            return Err(format!(
+                "Local folder overlaps the sync rule for {} ({})",
+                other.bucket, other.local_path
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Writes this machine's hostname into the rule when it's saved. Resolved on
+// every pass instead, a hostname change would move every key and the old
+// ones would read as deleted.
+pub(crate) fn pin_folder_sync_key_host(rule: &mut FolderSyncRuleRecord) {
+    if let Some(transform) = rule.key_transform.as_mut() {
+        let host = local_hostname();
+        transform.prefix = transform.prefix.replace("{host}", &host);
+        transform.suffix = transform.suffix.replace("{host}", &host);
+    }
+}
+
+pub(crate) fn folder_sync_key_transform(rule: &FolderSyncRuleRecord) -> ResolvedKeyTransform {
+    rule.key_transform
+        .as_ref()
+        .map(resolve_key_transform)
+        .unwrap_or_default()
+}
+
+// Exported rules carry no per-machine state; import assigns fresh values.
+pub(crate) fn export_folder_sync_rules(
+    rules: &[FolderSyncRuleRecord],
+    profiles: &[Profile],
+) -> Result<Value, String> {
+    let mut exported = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let mut value = serde_json::to_value(rule)
+            .map_err(|err| format!("Failed to serialize folder sync rule: {err}"))?;
+        if let Some(map) = value.as_object_mut() {
+            for key in FOLDER_SYNC_EXPORT_OMITTED_FIELDS {
+                map.remove(*key);
+            }
+            // Lets another machine match the rule to its own profile by name.
+            if let Some(profile) = profiles
+                .iter()
+                .find(|profile| profile.id == rule.profile_id)
+            {
+                map.insert("profileName".to_string(), json!(profile.name));
+            }
+        }
+        exported.push(value);
+    }
+
+    Ok(json!({
+        "version": FOLDER_SYNC_EXPORT_VERSION,
+        "exportedAt": now_iso(),
+        "rules": exported,
+    }))
+}
+
+// Profiles are resolved via `profile_map` (exported id -> local id), then a
+// profile with the same id, then one with the exported `profileName`. Rules
+// come in disabled with fresh ids so local paths can be reviewed first.
+pub(crate) fn import_folder_sync_rules(
+    document: &Value,
+    existing: &[FolderSyncRuleRecord],
+    profiles: &[Profile],
+    profile_map: &HashMap<String, String>,
+) -> Result<(Vec<FolderSyncRuleRecord>, Vec<Value>), String> {
+    let rules = document
+        .get("rules")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "Not a folder sync rules export".to_string())?;
+
+    let mut imported: Vec<FolderSyncRuleRecord> = Vec::new();
+    let mut skipped: Vec<Value> = Vec::new();
+    for raw in rules {
+        let Some(mut map) = raw.as_object().cloned() else {
+            skipped.push(json!({ "reason": "Not a rule object" }));
+            continue;
+        };
+
+        let exported_profile_id = map_str(&map, "profileId").unwrap_or_default().to_string();
+        let profile_name = map
+            .remove("profileName")
+            .and_then(|name| name.as_str().map(str::to_string));
+        let profile_id = profile_map
+            .get(&exported_profile_id)
+            .filter(|id| profiles.iter().any(|profile| &profile.id == *id))
+            .cloned()
+            .or_else(|| {
+                profiles
+                    .iter()
+                    .find(|profile| profile.id == exported_profile_id)
+                    .map(|profile| profile.id.clone())
+            })
+            .or_else(|| {
+                let name = profile_name?;
+                profiles
+                    .iter()
+                    .find(|profile| profile.name == name)
+                    .map(|profile| profile.id.clone())
+            });
+        let Some(profile_id) = profile_id else {
+            skipped.push(skipped_folder_sync_rule(&map, "No matching profile"));
+            continue;
+        };
+
+        for key in FOLDER_SYNC_EXPORT_OMITTED_FIELDS {
+            map.remove(*key);
+        }
+        map.insert("profileId".to_string(), json!(profile_id));
+        map.insert("id".to_string(), json!(Uuid::new_v4().to_string()));
+        map.insert("enabled".to_string(), json!(false));
+        map.insert("createdAt".to_string(), json!(now_iso()));
+
+        let mut rule =
+            match serde_json::from_value::<FolderSyncRuleRecord>(Value::Object(map.clone())) {
+                Ok(rule) => rule,
+                Err(err) => {
+                    skipped.push(skipped_folder_sync_rule(
+                        &map,
+                        &format!("Invalid rule: {err}"),
+                    ));
+                    continue;
+                }
+            };
+        pin_folder_sync_key_host(&mut rule);
+        if let Err(err) = validate_folder_sync_rule(&rule) {
+            skipped.push(skipped_folder_sync_rule(&map, &err));
+            continue;
+        }
+        let duplicate = existing.iter().chain(imported.iter()).any(|other| {
+            other.profile_id == rule.profile_id
+                && other.bucket == rule.bucket
+                && other.bucket_prefix == rule.bucket_prefix
+                && other.local_path == rule.local_path
+        });
+        if duplicate {
+            skipped.push(skipped_folder_sync_rule(
+                &map,
+                "A rule already exists for this folder and bucket",
+            ));
+            continue;
+        }
+        let others: Vec<FolderSyncRuleRecord> =
+            existing.iter().chain(imported.iter()).cloned().collect();
+        if let Err(err) = validate_folder_sync_local_root(&rule, &others) {
+            skipped.push(skipped_folder_sync_rule(&map, &err));
+            continue;
+        }
+
+        imported.push(rule);
+    }
+
+    Ok((imported, skipped))
+}
+
+fn skipped_folder_sync_rule(map: &Map<String, Value>, reason: &str) -> Value {
+    json!({
+        "bucket": map_str(map, "bucket"),
+        "bucketPrefix": map_str(map, "bucketPrefix"),
+        "localPath": map_str(map, "localPath"),
+        "reason": reason,
+    })
+}
+
+// Interval to wait after a successful pass. Small remotes always poll at the
+// rule's interval. Large ones never poll faster than a per-object floor, and
+// each quiet pass doubles the wait up to the rule's ceiling; any change snaps
+// back to the floor. Local edits still wake the loop through the watcher.
+pub(crate) fn next_folder_sync_poll_interval(
+    rule: &FolderSyncRuleRecord,
+    current_ms: i64,
+    outcome: FolderSyncPassOutcome,
+) -> i64 {
+    let base = rule
+        .poll_interval_ms
+        .clamp(FOLDER_SYNC_MIN_POLL_MS, FOLDER_SYNC_MAX_POLL_MS);
+    if outcome.remote_objects < FOLDER_SYNC_ADAPTIVE_MIN_OBJECTS {
+        return base;
+    }
+
+    let ceiling = rule
+        .max_poll_interval_ms
+        .unwrap_or(FOLDER_SYNC_DEFAULT_MAX_POLL_MS)
+        .clamp(base, FOLDER_SYNC_MAX_POLL_MS);
+    let floor = (outcome.remote_objects as i64)
+        .saturating_mul(FOLDER_SYNC_POLL_MS_PER_OBJECT)
+        .clamp(base, ceiling);
+    if outcome.changed {
+        return floor;
+    }
+    current_ms.saturating_mul(2).clamp(floor, ceiling)
+}
+
+// Dry-run pass: surface the diff as a status + event and touch nothing.
+// Keep-both conflicts stay listed as conflicts since resolving them would
+// rename local files.
+fn report_folder_sync_dry_run(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    diff: &FolderSyncDiffRecord,
+) -> FolderSyncPassOutcome {
+    let (progress, files_watching) = folder_sync_dry_run_progress(rule, diff);
+    let total_actions = progress.total;
+    let _ = set_and_emit_folder_sync_status(
+        app,
+        &rule.id,
+        FolderSyncStatus::DryRun,
+        files_watching,
+        Some(now_iso()),
+        None,
+        Some(progress),
+    );
+    let _ = app.emit(
+        "folder-sync:dry-run",
+        FolderSyncDryRunEventPayload {
+            rule_id: &rule.id,
+            at: now_iso(),
+            diff,
+        },
+    );
+    FolderSyncPassOutcome {
+        remote_objects: diff.remote_objects,
+        changed: total_actions > 0 || !diff.conflicts.is_empty(),
+    }
+}
+
+// What a dry run reports as pending: the transfers and deletes it found and
+// the bytes they would move, plus the rule's watcher count.
+fn folder_sync_dry_run_progress(
+    rule: &FolderSyncRuleRecord,
+    diff: &FolderSyncDiffRecord,
+) -> (FolderSyncProgress, i64) {
+    let total_actions = diff.uploads.len()
+        + diff.downloads.len()
+        + diff.delete_local.len()
+        + diff.delete_remote.len();
+    let bytes_total = diff
+        .uploads
+        .iter()
+        .map(|entry| entry.local_size.unwrap_or(0))
+        .chain(
+            diff.downloads
+                .iter()
+                .map(|entry| entry.remote_size.unwrap_or(0)),
+        )
+        .sum::<i64>();
+    let files_watching = if folder_sync_rule_watches_local(rule) {
+        1
+    } else {
+        0
+    };
+    let progress = FolderSyncProgress {
+        completed: 0,
+        total: total_actions as i64,
+        bytes_transferred: 0,
+        bytes_total: bytes_total.max(0),
+    };
+    (progress, files_watching)
+}
+
+// On-demand dry run (folder-sync:dry-run): everything a live pass decides,
+// keep-both renames included, without transferring, renaming or deleting.
+// It sends one status event carrying `dryRun` at the start, which isn't
+// stored, and the rule's own status again at the end.
+pub(crate) async fn dry_run_folder_sync_rule(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+) -> Result<FolderSyncDryRunReport, String> {
+    let state = app.state::<AppState>();
+    let profile = profile_for_id(&state, &rule.profile_id)?;
+    let client = to_s3_client(&profile)?;
+    let known_records = load_folder_sync_file_records(&rule.id);
+    let mut diff = generate_folder_sync_diff_for_rule(rule, &client, &known_records).await?;
+    let local_root = expand_user_path(&rule.local_path);
+    if rule.conflict_resolution == ConflictResolution::KeepBoth
+        && rule.direction != SyncDirection::LocalToRemote
+    {
+        apply_keep_both_conflicts(rule, &local_root, &mut diff, true);
+    }
+
+    let bucket_prefix = normalize_prefix(&rule.bucket_prefix);
+    let transform = folder_sync_key_transform(rule);
+    let remote_target = |entry: &FolderSyncDiffEntryRecord| {
+        let key = transform.apply(&format!("{}{}", bucket_prefix, entry.relative_path));
+        Some(format_s3_uri(&rule.bucket, &key))
+    };
+    let local_target = |entry: &FolderSyncDiffEntryRecord| {
+        sanitize_relative_path(entry.local_path())
+            .map(|relative_path| local_root.join(relative_path).to_string_lossy().to_string())
+    };
+    // Same order as the live pass: transfers, local deletes, remote deletes.
+    let actions: Vec<FolderSyncResolvedAction> = diff
+        .uploads
+        .iter()
+        .map(|entry| (entry, remote_target(entry)))
+        .chain(
+            diff.downloads
+                .iter()
+                .map(|entry| (entry, local_target(entry))),
+        )
+        .chain(
+            diff.delete_local
+                .iter()
+                .map(|entry| (entry, local_target(entry))),
+        )
+        .chain(
+            diff.delete_remote
+                .iter()
+                .map(|entry| (entry, remote_target(entry))),
+        )
+        .chain(diff.conflicts.iter().map(|entry| (entry, None)))
+        .chain(diff.skipped.iter().map(|entry| (entry, None)))
+        .map(|(entry, target)| FolderSyncResolvedAction {
+            relative_path: entry.relative_path.clone(),
+            action: entry.action.clone(),
+            reason: entry.reason.clone(),
+            target,
+        })
+        .collect();
+
+    let (progress, files_watching) = folder_sync_dry_run_progress(rule, &diff);
+    emit_folder_sync_status_event(
+        app,
+        &FolderSyncStateRecord {
+            rule_id: rule.id.clone(),
+            status: FolderSyncStatus::Syncing,
+            files_watching,
+            last_change: Some(now_iso()),
+            current_file: None,
+            progress: Some(progress),
+            effective_poll_interval_ms: None,
+            dry_run: true,
+        },
+    );
+
+    let current = folder_sync_statuses_snapshot(app)
+        .into_iter()
+        .find(|status| status.rule_id == rule.id)
+        .unwrap_or(FolderSyncStateRecord {
+            rule_id: rule.id.clone(),
+            status: FolderSyncStatus::Idle,
+            files_watching: 0,
+            last_change: None,
+            current_file: None,
+            progress: None,
+            effective_poll_interval_ms: None,
+            dry_run: false,
+        });
+    emit_folder_sync_status_event(app, &current);
+
+    Ok(FolderSyncDryRunReport { diff, actions })
+}
+
+// Keep-both: set the local copy aside under the rule's rename template and
+// let the remote version download into the original path. The renamed copy
+// is picked up as a new local file (and uploaded) on the next pass. Conflicts
+// that can't be set aside stay in the conflict list.
+// With `dry_run`, only the name the local copy would get is worked out.
+// Returns the relative paths whose local file was set aside.
+fn apply_keep_both_conflicts(
+    rule: &FolderSyncRuleRecord,
+    local_root: &Path,
+    diff: &mut FolderSyncDiffRecord,
+    dry_run: bool,
+) -> Vec<String> {
+    let mut set_aside = Vec::new();
+    for mut entry in std::mem::take(&mut diff.conflicts) {
+        let renamed = sanitize_relative_path(entry.local_path()).and_then(|relative_path| {
+            let local_path = contained_local_path(local_root, &relative_path).ok()?;
+            if dry_run {
+                conflict_copy_name(&local_path, &rule.conflict_rename_template).ok()
+            } else {
+                set_aside_conflict_copy(&local_path, &rule.conflict_rename_template).ok()
+            }
+        });
+
+        match renamed {
+            Some(renamed) => {
+                entry.action = "download".to_string();
+                entry.reason = if dry_run {
+                    format!("Conflict resolved: kept both (local copy would be saved as {renamed})")
+                } else {
+                    format!("Conflict resolved: kept both (local copy saved as {renamed})")
+                };
+                if !dry_run {
+                    set_aside.push(entry.relative_path.clone());
+                }
+                diff.downloads.push(entry);
+            }
+            None => diff.conflicts.push(entry),
+        }
+    }
+    set_aside
+}
+
+fn set_aside_conflict_copy(local_path: &Path, template: &str) -> Result<String, String> {
+    let candidate = conflict_copy_name(local_path, template)?;
+    let parent = local_path.parent().unwrap_or_else(|| Path::new(""));
+    fs::rename(local_path, parent.join(&candidate))
+        .map_err(|err| format!("Failed to rename {}: {err}", local_path.display()))?;
+    Ok(candidate)
+}
+
+// First free name for the set-aside copy of a conflicting local file.
+pub(crate) fn conflict_copy_name(local_path: &Path, template: &str) -> Result<String, String> {
+    let file_name = local_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid conflict path: {}", local_path.display()))?;
+    let parent = local_path.parent().unwrap_or_else(|| Path::new(""));
+
+    let rendered = render_name_template(template, &file_name);
+    let mut candidate = rendered.clone();
+    let mut attempt = 2;
+    while parent.join(&candidate).exists() {
+        if attempt > 1000 {
+            return Err(format!("No free name for conflict copy of {file_name}"));
+        }
+        candidate =
+            expand_name_template(&format!("{{name}} ({attempt}){{ext}}"), &rendered, "", "");
+        attempt += 1;
+    }
+    Ok(candidate)
+}
+
+// Safe-delete for the local side: the file keeps its relative path inside
+// this pass's trash batch.
+fn move_to_local_trash(local_root: &Path, relative_path: &Path, stamp: &str) -> Result<(), String> {
+    let source = contained_local_path(local_root, relative_path)?;
+    let target = local_root
+        .join(FOLDER_SYNC_TRASH_DIR)
+        .join(stamp)
+        .join(relative_path);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    fs::rename(&source, &target)
+        .map_err(|err| format!("Failed to move {} to trash: {err}", source.display()))
+}
+
+pub(crate) fn is_folder_sync_trash_path(relative_path: &str) -> bool {
+    relative_path
+        .strip_prefix(FOLDER_SYNC_TRASH_DIR)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+// Whether a trash batch named `stamp` is at least `older_than_days` old.
+// Names that don't parse are never purged.
+pub(crate) fn folder_sync_trash_batch_expired(stamp: &str, older_than_days: u32) -> bool {
+    chrono::NaiveDateTime::parse_from_str(stamp, FOLDER_SYNC_TRASH_STAMP_FORMAT).is_ok_and(
+        |created| Utc::now().naive_utc() - created >= Duration::days(i64::from(older_than_days)),
+    )
+}
+
+// folder-sync:empty-trash: purges expired batches from both trashes and
+// returns how many local files and remote objects went.
+pub(crate) async fn empty_folder_sync_trash(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    older_than_days: u32,
+) -> Result<(usize, usize), String> {
+    let trash_root = expand_user_path(&rule.local_path).join(FOLDER_SYNC_TRASH_DIR);
+    let mut local_removed = 0;
+    if let Ok(batches) = fs::read_dir(&trash_root) {
+        for batch in batches.filter_map(Result::ok) {
+            let stamp = batch.file_name().to_string_lossy().to_string();
+            if !folder_sync_trash_batch_expired(&stamp, older_than_days) {
+                continue;
+            }
+            let files = WalkDir::new(batch.path())
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .count();
+            fs::remove_dir_all(batch.path())
+                .map_err(|err| format!("Failed to remove {}: {err}", batch.path().display()))?;
+            local_removed += files;
+        }
+    }
+
+    let state = app.state::<AppState>();
+    let profile = profile_for_id(&state, &rule.profile_id)?;
+    let client = to_s3_client(&profile)?;
+    let transform = folder_sync_key_transform(rule);
+    let trash_prefix = format!(
+        "{}{}{}/",
+        transform.prefix,
+        normalize_prefix(&rule.bucket_prefix),
+        FOLDER_SYNC_TRASH_DIR
+    );
+    let mut expired_keys: Vec<String> = Vec::new();
+    s3_for_each_object(&client, &rule.bucket, &trash_prefix, |object| {
+        let stamp = object
+            .key
+            .strip_prefix(trash_prefix.as_str())
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_default();
+        if folder_sync_trash_batch_expired(stamp, older_than_days) {
+            expired_keys.push(object.key);
+        }
+    })
+    .await?;
+    s3_delete_keys(&client, &rule.bucket, &expired_keys).await?;
+
+    Ok((local_removed, expired_keys.len()))
+}
+
+pub(crate) fn folder_sync_concurrency(rule: &FolderSyncRuleRecord) -> usize {
+    rule.sync_concurrency.clamp(1, FOLDER_SYNC_MAX_CONCURRENCY) as usize
+}
+
+impl FolderSyncTransferDirection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Upload => "Upload",
+            Self::Download => "Download",
+        }
+    }
+}
+
+impl FolderSyncPassProgress {
+    fn add_bytes(&self, delta: i64) {
+        self.bytes_transferred.fetch_add(delta, Ordering::SeqCst);
+    }
+
+    fn complete_one(&self) {
+        self.completed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn emit(&self, current_file: Option<String>) -> Result<(), String> {
+        set_and_emit_folder_sync_status(
+            &self.app,
+            &self.rule_id,
+            FolderSyncStatus::Syncing,
+            self.files_watching,
+            Some(now_iso()),
+            current_file,
+            Some(FolderSyncProgress {
+                completed: self.completed.load(Ordering::SeqCst),
+                total: self.total,
+                bytes_transferred: self.bytes_transferred.load(Ordering::SeqCst).max(0),
+                bytes_total: self.bytes_total,
+            }),
+        )
+    }
+}
+
+pub(crate) async fn folder_sync_upload_entry(
+    task: FolderSyncTransferTask,
+) -> FolderSyncTransferOutcome {
+    let relative_path = task.entry.relative_path.clone();
+    let mut reported_bytes: i64 = 0;
+
+    let upload_result = s3_upload_file(
+        &task.client,
+        &task.bucket,
+        &task.remote_key,
+        &task.local_path,
+        &task.upload_attributes,
+        &task.cancel_flag,
+        |transferred, _total| {
+            task.progress.add_bytes(transferred - reported_bytes);
+            reported_bytes = transferred;
+            let _ = task.progress.emit(Some(relative_path.clone()));
+        },
+    )
+    .await;
+
+    let result = match upload_result {
+        Ok(transferred) => task
+            .client
+            .head_object()
+            .bucket(task.bucket.clone())
+            .key(task.remote_key.clone())
+            .send()
+            .await
+            .map_err(|err| err.to_string())
+            .map(|remote_meta| {
+                let record = FolderSyncFileRecord {
+                    relative_path: relative_path.clone(),
+                    // Just-transferred file; epoch on stat failure is a harmless
+                    // "treat as changed" fallback, not a silent error.
+                    local_mtime: file_mtime_millis(&task.local_path).unwrap_or(0),
+                    local_size: fs::metadata(&task.local_path)
+                        .map(|meta| meta.len() as i64)
+                        .unwrap_or(0)
+                        .max(0),
+                    remote_etag: normalize_etag(remote_meta.e_tag()),
+                    remote_last_modified: remote_meta
+                        .last_modified()
+                        .map(s3_datetime_to_iso)
+                        .unwrap_or_else(now_iso),
+                    remote_size: remote_meta.content_length().unwrap_or(0).max(0),
+                    synced_at: now_iso(),
+                    local_relative_path: task.entry.local_relative_path.clone(),
+                };
+                (transferred, record)
+            }),
+        Err(err) => Err(err),
+    };
+
+    FolderSyncTransferOutcome {
+        direction: task.direction,
+        relative_path,
+        reported_bytes,
+        result,
+    }
+}
+
+pub(crate) async fn folder_sync_download_entry(
+    task: FolderSyncTransferTask,
+) -> FolderSyncTransferOutcome {
+    let relative_path = task.entry.relative_path.clone();
+    let local_path = task.local_path.clone();
+    let tmp_path = PathBuf::from(format!("{}.object0-tmp", local_path.display()));
+    let mut reported_bytes: i64 = 0;
+
+    let download_result = s3_download_file(
+        &task.client,
+        &task.bucket,
+        &task.remote_key,
+        &tmp_path,
+        task.download_attributes,
+        &task.cancel_flag,
+        |transferred, _total| {
+            task.progress.add_bytes(transferred - reported_bytes);
+            reported_bytes = transferred;
+            let _ = task.progress.emit(Some(relative_path.clone()));
+        },
+    )
+    .await;
+
+    let result = download_result.and_then(|transferred| {
+        if let Some(parent) = local_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        }
+        fs::rename(&tmp_path, &local_path).map_err(|err| {
+            format!(
+                "Failed to move {} -> {}: {err}",
+                tmp_path.display(),
+                local_path.display()
+            )
+        })?;
+
+        let record = FolderSyncFileRecord {
+            relative_path: relative_path.clone(),
+            // Just-transferred file; epoch on stat failure is a harmless
+            // "treat as changed" fallback, not a silent error.
+            local_mtime: file_mtime_millis(&local_path).unwrap_or(0),
+            local_size: fs::metadata(&local_path)
+                .map(|meta| meta.len() as i64)
+                .unwrap_or(0)
+                .max(0),
+            remote_etag: normalize_etag(task.entry.remote_etag.as_deref()),
+            remote_last_modified: task
+                .entry
+                .remote_last_modified
+                .clone()
+                .unwrap_or_else(now_iso),
+            remote_size: task.entry.remote_size.unwrap_or(transferred.max(0)),
+            synced_at: now_iso(),
+            local_relative_path: task.entry.local_relative_path.clone(),
+        };
+        Ok((transferred, record))
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
 
-    Ok(())
+    FolderSyncTransferOutcome {
+        direction: task.direction,
+        relative_path,
+        reported_bytes,
+        result,
+    }
 }
 
 pub(crate) fn stop_folder_sync_rule(app: &AppHandle, rule_id: &str) {
@@ -410,11 +1251,15 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
 
     stop_folder_sync_rule(app, rule_id);
 
+    let paused_for_metered = folder_sync_paused_for_metered(app, rule_id);
     let control = FolderSyncTaskControl {
         cancel_flag: Arc::new(AtomicBool::new(false)),
-        pause_flag: Arc::new(AtomicBool::new(false)),
+        pause_flag: Arc::new(AtomicBool::new(paused_for_metered)),
         wake_tx: Arc::new(Mutex::new(None)),
         watcher: Arc::new(Mutex::new(None)),
+        watcher_failed: Arc::new(AtomicBool::new(false)),
+        sync_requested: Arc::new(AtomicBool::new(false)),
+        reported_symlink_cycles: Arc::new(Mutex::new(HashSet::new())),
     };
 
     {
@@ -427,17 +1272,17 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
         app,
         &rule.id,
         FolderSyncStatus::Idle,
-        if rule.direction == SyncDirection::RemoteToLocal {
-            0
-        } else {
+        if folder_sync_rule_watches_local(&rule) {
             1
+        } else {
+            0
         },
         None,
         None,
         None,
     );
 
-    if rule.direction != SyncDirection::RemoteToLocal {
+    if folder_sync_rule_watches_local(&rule) {
         let local_watch_path = expand_user_path(&rule.local_path);
         if let Err(err) = fs::create_dir_all(&local_watch_path) {
             emit_folder_sync_error_event(
@@ -448,66 +1293,19 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
                     local_watch_path.display()
                 ),
             );
-        } else {
-            let app_for_watch = app.clone();
-            let rule_id_for_watch = rule.id.clone();
-            let cancel_flag = control.cancel_flag.clone();
-            let pause_flag = control.pause_flag.clone();
-            let wake_tx = control.wake_tx.clone();
-            match recommended_watcher(move |event_result: Result<notify::Event, notify::Error>| {
-                match event_result {
-                    Ok(_event) => {
-                        if cancel_flag.load(Ordering::SeqCst) {
-                            return;
-                        }
-                        mark_folder_sync_last_change(&app_for_watch, &rule_id_for_watch, 1);
-                        if !pause_flag.load(Ordering::SeqCst) {
-                            wake_folder_sync_slot(&wake_tx);
-                        }
-                    }
-                    Err(err) => {
-                        emit_folder_sync_error_event(
-                            &app_for_watch,
-                            &rule_id_for_watch,
-                            &format!("Folder watcher error: {err}"),
-                        );
-                    }
-                }
-            }) {
-                Ok(mut watcher) => {
-                    if let Err(err) = watcher.watch(&local_watch_path, RecursiveMode::Recursive) {
-                        emit_folder_sync_error_event(
-                            app,
-                            &rule.id,
-                            &format!(
-                                "Failed to watch folder {}: {err}",
-                                local_watch_path.display()
-                            ),
-                        );
-                    } else if let Ok(mut watcher_slot) = control.watcher.lock() {
-                        *watcher_slot = Some(watcher);
-                    } else {
-                        emit_folder_sync_error_event(
-                            app,
-                            &rule.id,
-                            "Failed to store folder watcher handle",
-                        );
-                    }
-                }
-                Err(err) => {
-                    emit_folder_sync_error_event(
-                        app,
-                        &rule.id,
-                        &format!("Failed to start folder watcher: {err}"),
-                    );
-                }
-            }
+        } else if let Err(err) = start_folder_sync_watcher(app, &rule, &local_watch_path, &control)
+        {
+            emit_folder_sync_error_event(app, &rule.id, &err);
+            // The poll loop retries before giving up on the rule.
+            control.watcher_failed.store(true, Ordering::SeqCst);
         }
     }
 
     let app_handle = app.clone();
     let rule_id = rule.id.clone();
     tauri::async_runtime::spawn(async move {
+        let mut poll_interval_ms = rule.poll_interval_ms;
+        let mut watcher_lost = false;
         loop {
             if control.cancel_flag.load(Ordering::SeqCst) {
                 break;
@@ -524,10 +1322,29 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
                 break;
             }
 
-            let files_watching = if rule.direction == SyncDirection::RemoteToLocal {
-                0
-            } else {
+            // Without a watcher the rule would look active while ignoring
+            // local edits until the next poll, so it stops instead.
+            if control.watcher_failed.load(Ordering::SeqCst) {
+                if let Err(err) = restart_folder_sync_watcher(&app_handle, &rule, &control).await {
+                    let message = format!("Folder watcher stopped: {err}");
+                    let _ = update_folder_sync_rule_result(
+                        &rule_id,
+                        Some("error"),
+                        Some(message.as_str()),
+                    );
+                    emit_folder_sync_error_event(&app_handle, &rule_id, &message);
+                    watcher_lost = true;
+                    break;
+                }
+                if control.cancel_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            let files_watching = if folder_sync_rule_watches_local(&rule) {
                 1
+            } else {
+                0
             };
             if control.pause_flag.load(Ordering::SeqCst) {
                 let _ = set_and_emit_folder_sync_status(
@@ -539,26 +1356,85 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
                     None,
                     None,
                 );
-                wait_for_folder_sync_wake(&control, rule.poll_interval_ms).await;
+                wait_for_folder_sync_wake(&control, poll_interval_ms).await;
                 continue;
             }
 
-            match run_folder_sync_once(&app_handle, &rule, &control).await {
-                Ok(()) => {
-                    let status = if control.pause_flag.load(Ordering::SeqCst) {
-                        FolderSyncStatus::Paused
-                    } else {
-                        FolderSyncStatus::Watching
+            if rule.manual_only && !control.sync_requested.swap(false, Ordering::SeqCst) {
+                let _ = set_and_emit_folder_sync_status(
+                    &app_handle,
+                    &rule_id,
+                    FolderSyncStatus::Manual,
+                    files_watching,
+                    None,
+                    None,
+                    None,
+                );
+                wait_for_folder_sync_trigger(&control).await;
+                continue;
+            }
+
+            if let Some(schedule) = rule.schedule.as_deref() {
+                if !control.sync_requested.swap(false, Ordering::SeqCst) {
+                    let next_run = match next_folder_sync_run(schedule, &chrono::Local::now()) {
+                        Ok(next_run) => next_run,
+                        Err(err) => {
+                            let _ = update_folder_sync_rule_result(
+                                &rule_id,
+                                Some("error"),
+                                Some(err.as_str()),
+                            );
+                            emit_folder_sync_error_event(&app_handle, &rule_id, &err);
+                            break;
+                        }
                     };
                     let _ = set_and_emit_folder_sync_status(
                         &app_handle,
                         &rule_id,
-                        status,
+                        FolderSyncStatus::Scheduled,
                         files_watching,
-                        Some(now_iso()),
                         None,
                         None,
+                        None,
+                    );
+                    if !wait_for_folder_sync_schedule(&control, next_run).await {
+                        continue;
+                    }
+                }
+            }
+
+            match run_folder_sync_once(&app_handle, &rule, &control).await {
+                Ok(outcome) => {
+                    poll_interval_ms =
+                        next_folder_sync_poll_interval(&rule, poll_interval_ms, outcome);
+                    let _ = set_folder_sync_effective_poll_interval(
+                        &app_handle,
+                        &rule_id,
+                        poll_interval_ms,
                     );
+                    let status = if control.pause_flag.load(Ordering::SeqCst) {
+                        FolderSyncStatus::Paused
+                    } else if rule.dry_run {
+                        FolderSyncStatus::DryRun
+                    } else if rule.manual_only {
+                        FolderSyncStatus::Manual
+                    } else if rule.schedule.is_some() {
+                        FolderSyncStatus::Scheduled
+                    } else {
+                        FolderSyncStatus::Watching
+                    };
+                    // A dry-run status keeps the pass's would-do counts.
+                    if status != FolderSyncStatus::DryRun {
+                        let _ = set_and_emit_folder_sync_status(
+                            &app_handle,
+                            &rule_id,
+                            status,
+                            files_watching,
+                            Some(now_iso()),
+                            None,
+                            None,
+                        );
+                    }
                 }
                 Err(err) if err == JOB_CANCELLED => break,
                 Err(err) => {
@@ -577,7 +1453,12 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
                 }
             }
 
-            wait_for_folder_sync_wake(&control, rule.poll_interval_ms).await;
+            // Manual-only and scheduled rules park at the top of the loop
+            // instead of polling.
+            if rule.manual_only || rule.schedule.is_some() {
+                continue;
+            }
+            wait_for_folder_sync_wake(&control, poll_interval_ms).await;
         }
 
         if let Ok(mut watcher) = control.watcher.lock() {
@@ -593,7 +1474,11 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
             let _ = set_and_emit_folder_sync_status(
                 &app_handle,
                 &rule_id,
-                FolderSyncStatus::Idle,
+                if watcher_lost {
+                    FolderSyncStatus::Error
+                } else {
+                    FolderSyncStatus::Idle
+                },
                 0,
                 Some(now_iso()),
                 None,
@@ -613,6 +1498,108 @@ pub(crate) fn start_folder_sync_rule(app: &AppHandle, rule_id: &str) -> Result<(
     Ok(())
 }
 
+fn start_folder_sync_watcher(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    path: &Path,
+    control: &FolderSyncTaskControl,
+) -> Result<(), String> {
+    let app_for_watch = app.clone();
+    let rule_id_for_watch = rule.id.clone();
+    // Manual-only rules keep the watcher for last-change tracking only.
+    let wake_on_change = !rule.manual_only;
+    let cancel_flag = control.cancel_flag.clone();
+    let pause_flag = control.pause_flag.clone();
+    let watcher_failed = control.watcher_failed.clone();
+    let wake_tx = control.wake_tx.clone();
+    let mut watcher =
+        recommended_watcher(move |event_result: Result<notify::Event, notify::Error>| {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return;
+            }
+            match event_result {
+                Ok(_event) => {
+                    mark_folder_sync_last_change(&app_for_watch, &rule_id_for_watch, 1);
+                    if wake_on_change && !pause_flag.load(Ordering::SeqCst) {
+                        wake_folder_sync_slot(&wake_tx);
+                    }
+                }
+                // Reported once; the poll loop rebuilds the watcher.
+                Err(err) => {
+                    if !watcher_failed.swap(true, Ordering::SeqCst) {
+                        emit_folder_sync_error_event(
+                            &app_for_watch,
+                            &rule_id_for_watch,
+                            &format!("Folder watcher error: {err}"),
+                        );
+                        wake_folder_sync_slot(&wake_tx);
+                    }
+                }
+            }
+        })
+        .map_err(|err| format!("Failed to start folder watcher: {err}"))?;
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .map_err(|err| format!("Failed to watch folder {}: {err}", path.display()))?;
+    let mut watcher_slot = control
+        .watcher
+        .lock()
+        .map_err(|_| "Failed to store folder watcher handle".to_string())?;
+    *watcher_slot = Some(watcher);
+    Ok(())
+}
+
+// A missing folder isn't recreated here: an empty replacement would read as
+// every file having been deleted locally.
+async fn restart_folder_sync_watcher(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    control: &FolderSyncTaskControl,
+) -> Result<(), String> {
+    let path = expand_user_path(&rule.local_path);
+    // Only a successful start stores a watcher, so one clear covers every
+    // attempt.
+    if let Ok(mut watcher) = control.watcher.lock() {
+        *watcher = None;
+    }
+    let result = retry_with_backoff(
+        FOLDER_SYNC_WATCHER_MAX_RETRIES,
+        FOLDER_SYNC_WATCHER_RETRY_BASE_MS,
+        &control.cancel_flag,
+        || {
+            control.watcher_failed.store(false, Ordering::SeqCst);
+            start_folder_sync_watcher(app, rule, &path, control)
+        },
+    )
+    .await;
+    if result.is_err() {
+        control.watcher_failed.store(true, Ordering::SeqCst);
+    }
+    result
+}
+
+// Runs `attempt` after `base_ms`, doubling the delay after each failure, until
+// it succeeds or `attempts` are used up. Cancellation ends it early as `Ok`.
+pub(crate) async fn retry_with_backoff(
+    attempts: u32,
+    base_ms: u64,
+    cancel_flag: &AtomicBool,
+    mut attempt: impl FnMut() -> Result<(), String>,
+) -> Result<(), String> {
+    let mut last_error = String::new();
+    for retry in 0..attempts {
+        tokio::time::sleep(StdDuration::from_millis(base_ms << retry)).await;
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        match attempt() {
+            Ok(()) => return Ok(()),
+            Err(err) => last_error = err,
+        }
+    }
+    Err(format!("{last_error} (gave up after {attempts} attempts)"))
+}
+
 pub(crate) fn start_all_folder_sync_rules(app: &AppHandle) -> Result<(), String> {
     for rule in load_folder_sync_rules_records() {
         if rule.enabled {
@@ -624,6 +1611,33 @@ pub(crate) fn start_all_folder_sync_rules(app: &AppHandle) -> Result<(), String>
     Ok(())
 }
 
+// Background mode at launch: start enabled rules without waiting for the
+// window. With no usable keychain passphrase the vault stays locked and the
+// tray says so until the user unlocks it.
+pub(crate) fn start_background_sync(app: &AppHandle) {
+    let unlocked = unlock_vault_from_keychain(&app.state::<AppState>());
+    if unlocked {
+        let _ = start_all_folder_sync_rules(app);
+        try_start_queued_jobs(app.clone());
+    }
+    refresh_tray_menu(app);
+}
+
+// Enabled rules that can't run because the vault is locked.
+pub(crate) fn folder_sync_waiting_for_vault(app: &AppHandle) -> bool {
+    let locked = {
+        let state = app.state::<AppState>();
+        let value = lock_state(&state.vault)
+            .map(|vault| !vault.unlocked)
+            .unwrap_or(false);
+        value
+    };
+    locked
+        && load_folder_sync_rules_records()
+            .iter()
+            .any(|rule| rule.enabled)
+}
+
 pub(crate) fn stop_all_folder_sync_rules(app: &AppHandle) {
     let task_ids = {
         let state = app.state::<AppState>();
@@ -641,37 +1655,54 @@ pub(crate) fn stop_all_folder_sync_rules(app: &AppHandle) {
     }
 }
 
-pub(crate) fn pause_all_folder_sync_rules(app: &AppHandle) {
-    let controls = {
-        let state = app.state::<AppState>();
-        let value = if let Ok(runtime) = lock_state(&state.folder_sync) {
-            runtime.tasks.values().cloned().collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
-        value
+fn folder_sync_task_controls(app: &AppHandle) -> Vec<(String, FolderSyncTaskControl)> {
+    let state = app.state::<AppState>();
+    let value = if let Ok(runtime) = lock_state(&state.folder_sync) {
+        runtime
+            .tasks
+            .iter()
+            .map(|(rule_id, control)| (rule_id.clone(), control.clone()))
+            .collect()
+    } else {
+        Vec::new()
     };
+    value
+}
 
-    for control in controls {
+// A manual Pause/Resume All takes over from the metered pause, so lifting
+// that pause later leaves these rules as the user set them.
+pub(crate) fn pause_all_folder_sync_rules(app: &AppHandle) {
+    forget_metered_paused_rules(app);
+    for (_, control) in folder_sync_task_controls(app) {
         control.pause_flag.store(true, Ordering::SeqCst);
         wake_folder_sync_control(&control);
     }
 }
 
 pub(crate) fn resume_all_folder_sync_rules(app: &AppHandle) {
-    let controls = {
-        let state = app.state::<AppState>();
-        let value = if let Ok(runtime) = lock_state(&state.folder_sync) {
-            runtime.tasks.values().cloned().collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
-        value
-    };
+    forget_metered_paused_rules(app);
+    resume_folder_sync_rules(app, |_| true);
+}
 
-    for control in controls {
-        control.pause_flag.store(false, Ordering::SeqCst);
-        wake_folder_sync_control(&control);
+// Pauses every running rule and returns the ids of those that weren't
+// already paused.
+pub(crate) fn pause_running_folder_sync_rules(app: &AppHandle) -> Vec<String> {
+    folder_sync_task_controls(app)
+        .into_iter()
+        .filter(|(_, control)| !control.pause_flag.swap(true, Ordering::SeqCst))
+        .map(|(rule_id, control)| {
+            wake_folder_sync_control(&control);
+            rule_id
+        })
+        .collect()
+}
+
+pub(crate) fn resume_folder_sync_rules(app: &AppHandle, should_resume: impl Fn(&str) -> bool) {
+    for (rule_id, control) in folder_sync_task_controls(app) {
+        if should_resume(&rule_id) {
+            control.pause_flag.store(false, Ordering::SeqCst);
+            wake_folder_sync_control(&control);
+        }
     }
 }
 
@@ -687,11 +1718,21 @@ pub(crate) fn trigger_folder_sync_now(app: &AppHandle, rule_id: &str) -> Result<
     };
 
     if let Some(control) = control {
+        control.sync_requested.store(true, Ordering::SeqCst);
         wake_folder_sync_control(&control);
         return Ok(());
     }
 
-    start_folder_sync_rule(app, rule_id)
+    start_folder_sync_rule(app, rule_id)?;
+    // A freshly started manual-only rule would otherwise just park.
+    let state = app.state::<AppState>();
+    if let Ok(runtime) = lock_state(&state.folder_sync) {
+        if let Some(control) = runtime.tasks.get(rule_id) {
+            control.sync_requested.store(true, Ordering::SeqCst);
+            wake_folder_sync_control(control);
+        }
+    };
+    Ok(())
 }
 
 pub(crate) fn folder_sync_has_active_tasks(app: &AppHandle) -> bool {
@@ -704,13 +1745,29 @@ pub(crate) fn folder_sync_has_active_tasks(app: &AppHandle) -> bool {
     value
 }
 
-pub(crate) fn folder_sync_status_counts(app: &AppHandle) -> (usize, usize, usize, usize) {
+pub(crate) fn folder_sync_status_counts(app: &AppHandle) -> (usize, usize, usize, usize, usize) {
     let statuses = folder_sync_statuses_snapshot(app);
-    let syncing = statuses.iter().filter(|s| s.status == FolderSyncStatus::Syncing).count();
-    let watching = statuses.iter().filter(|s| s.status == FolderSyncStatus::Watching).count();
-    let paused = statuses.iter().filter(|s| s.status == FolderSyncStatus::Paused).count();
-    let errors = statuses.iter().filter(|s| s.status == FolderSyncStatus::Error).count();
-    (syncing, watching, paused, errors)
+    let syncing = statuses
+        .iter()
+        .filter(|s| s.status == FolderSyncStatus::Syncing)
+        .count();
+    let watching = statuses
+        .iter()
+        .filter(|s| s.status == FolderSyncStatus::Watching)
+        .count();
+    let paused = statuses
+        .iter()
+        .filter(|s| s.status == FolderSyncStatus::Paused)
+        .count();
+    let errors = statuses
+        .iter()
+        .filter(|s| s.status == FolderSyncStatus::Error)
+        .count();
+    let dry_run = statuses
+        .iter()
+        .filter(|s| s.status == FolderSyncStatus::DryRun)
+        .count();
+    (syncing, watching, paused, errors, dry_run)
 }
 
 pub(crate) fn resolve_folder_sync_conflict(
@@ -746,13 +1803,18 @@ pub(crate) fn resolve_folder_sync_conflict(
     }
 }
 
+// Deletes the rule doesn't allow are downgraded to "keep": no action, and
+// the file stays on the side that still has it. `local_matches_etag` is the
+// "etag" compare mode's hash check, done by the caller only when it counts.
 pub(crate) fn resolve_folder_sync_action(
     local: Option<&LocalFileInfo>,
     remote: Option<&RemoteFileInfo>,
     known: Option<&FolderSyncFileRecord>,
-    direction: SyncDirection,
-    conflict_resolution: ConflictResolution,
+    rule: &FolderSyncRuleRecord,
+    local_matches_etag: Option<bool>,
 ) -> Option<(String, String)> {
+    let direction = rule.direction;
+    let conflict_resolution = rule.conflict_resolution;
     match (local, remote) {
         (Some(local), Some(remote)) => {
             if let Some(known) = known {
@@ -784,7 +1846,7 @@ pub(crate) fn resolve_folder_sync_action(
                     remote,
                     conflict_resolution,
                 ))
-            } else if local.size == remote.size {
+            } else if untracked_files_match(local, remote, rule.compare_mode, local_matches_etag) {
                 None
             } else {
                 Some(resolve_folder_sync_conflict(
@@ -801,8 +1863,10 @@ pub(crate) fn resolve_folder_sync_action(
                         "upload".to_string(),
                         "Re-upload (remote deleted)".to_string(),
                     ))
-                } else {
+                } else if rule.allow_delete_local {
                     Some(("delete-local".to_string(), "Remote deleted".to_string()))
+                } else {
+                    None
                 }
             } else if direction == SyncDirection::RemoteToLocal {
                 None
@@ -817,8 +1881,10 @@ pub(crate) fn resolve_folder_sync_action(
                         "download".to_string(),
                         "Re-download (local deleted)".to_string(),
                     ))
-                } else {
+                } else if rule.allow_delete_remote {
                     Some(("delete-remote".to_string(), "Local deleted".to_string()))
+                } else {
+                    None
                 }
             } else if direction == SyncDirection::LocalToRemote {
                 None
@@ -830,43 +1896,67 @@ pub(crate) fn resolve_folder_sync_action(
     }
 }
 
+// A path both sides have but no sync record covers; see FolderSyncCompareMode.
+// An ETag that was never checked, or can't be matched, counts as different.
+pub(crate) fn untracked_files_match(
+    local: &LocalFileInfo,
+    remote: &RemoteFileInfo,
+    compare_mode: FolderSyncCompareMode,
+    local_matches_etag: Option<bool>,
+) -> bool {
+    if local.size != remote.size {
+        return false;
+    }
+    match compare_mode {
+        FolderSyncCompareMode::Size => true,
+        FolderSyncCompareMode::MtimeSize => parse_iso_millis(&remote.last_modified)
+            .is_some_and(|remote_ms| local.mtime_ms <= remote_ms),
+        FolderSyncCompareMode::Etag => local_matches_etag.unwrap_or(false),
+    }
+}
+
 pub(crate) async fn generate_folder_sync_diff_for_rule(
     rule: &FolderSyncRuleRecord,
     client: &S3Client,
     known_records: &[FolderSyncFileRecord],
 ) -> Result<FolderSyncDiffRecord, String> {
     let local_root = expand_user_path(&rule.local_path);
-    let local_files = scan_local_directory(&local_root, &rule.exclude_patterns);
+    let (local_files, symlink_cycles) = scan_local_directory_with_symlinks(
+        &local_root,
+        &rule.exclude_patterns,
+        rule.symlink_policy,
+    );
 
     let bucket_prefix = normalize_prefix(&rule.bucket_prefix);
-    let remote_objects = s3_list_all_objects(client, &rule.bucket, &bucket_prefix).await?;
-
-    let mut local_map: HashMap<String, LocalFileInfo> = HashMap::new();
-    for local in local_files {
-        local_map.insert(local.relative_path.clone(), local);
-    }
+    let transform = folder_sync_key_transform(rule);
+    let list_prefix = format!("{}{}", transform.prefix, bucket_prefix);
 
+    // Streamed straight into the map so the raw listing is never held whole.
     let mut remote_map: HashMap<String, RemoteFileInfo> = HashMap::new();
-    for RemoteObject {
-        key,
-        size,
-        etag,
-        last_modified,
-    } in remote_objects
-    {
-        let relative = if bucket_prefix.is_empty() {
-            key.clone()
-        } else if key.starts_with(&bucket_prefix) {
-            key[bucket_prefix.len()..].to_string()
-        } else {
-            continue;
+    s3_for_each_object(client, &rule.bucket, &list_prefix, |object| {
+        let RemoteObject {
+            key,
+            size,
+            etag,
+            last_modified,
+            storage_class,
+        } = object;
+        // Keys without the rule's suffix aren't part of the sync.
+        let Some(key) = transform.strip(&key) else {
+            return;
         };
+        let Some(relative) = key.strip_prefix(bucket_prefix.as_str()) else {
+            return;
+        };
+        let relative = relative.to_string();
 
         if relative.is_empty() || relative.ends_with('/') {
-            continue;
+            return;
         }
-        if is_excluded_path(&relative, &rule.exclude_patterns) {
-            continue;
+        if is_excluded_path(&relative, &rule.exclude_patterns)
+            || is_folder_sync_trash_path(&relative)
+        {
+            return;
         }
 
         remote_map.insert(
@@ -875,10 +1965,27 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
                 size: size.max(0),
                 etag,
                 last_modified,
+                storage_class,
             },
         );
+    })
+    .await?;
+
+    let mut local_map: HashMap<String, LocalFileInfo> = HashMap::new();
+    for local in local_files {
+        if !is_folder_sync_trash_path(&local.relative_path) {
+            local_map.insert(local.relative_path.clone(), local);
+        }
     }
 
+    let case_collisions = if local_fs_is_case_insensitive(&local_root) {
+        find_case_collisions(&local_map, &remote_map)
+    } else {
+        Vec::new()
+    };
+    let (collision_losers, local_aliases) =
+        apply_case_collision_aliases(rule, &case_collisions, known_records, &mut local_map);
+
     let mut known_map: HashMap<String, FolderSyncFileRecord> = HashMap::new();
     for known in known_records {
         known_map.insert(known.relative_path.clone(), known.clone());
@@ -898,7 +2005,12 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
         delete_local: Vec::new(),
         delete_remote: Vec::new(),
         conflicts: Vec::new(),
+        skipped: Vec::new(),
+        case_collisions,
+        symlink_cycles,
         unchanged: 0,
+        remote_objects: remote_map.len(),
+        baseline: Vec::new(),
     };
 
     for path in paths {
@@ -909,17 +2021,57 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
         let local = local_map.get(&path);
         let remote = remote_map.get(&path);
         let known = known_map.get(&path);
+        let local_matches_etag = match (local, remote, known) {
+            (Some(local), Some(remote), None)
+                if rule.compare_mode == FolderSyncCompareMode::Etag
+                    && local.size == remote.size =>
+            {
+                // Hashing a large file would stall the async runtime.
+                let local_file = local_root.join(&local.relative_path);
+                let etag = remote.etag.clone();
+                let matches = tauri::async_runtime::spawn_blocking(move || {
+                    file_matches_etag(&local_file, &etag).unwrap_or(false)
+                })
+                .await
+                .unwrap_or(false);
+                Some(matches)
+            }
+            _ => None,
+        };
 
-        let Some((action, reason)) = resolve_folder_sync_action(
-            local,
-            remote,
-            known,
-            rule.direction,
-            rule.conflict_resolution,
-        ) else {
+        let Some((mut action, mut reason)) =
+            resolve_folder_sync_action(local, remote, known, rule, local_matches_etag)
+        else {
+            if let (Some(true), Some(local), Some(remote)) = (local_matches_etag, local, remote) {
+                diff.baseline.push(FolderSyncFileRecord {
+                    relative_path: path.clone(),
+                    local_mtime: local.mtime_ms,
+                    local_size: local.size,
+                    remote_etag: normalize_etag(Some(remote.etag.as_str())),
+                    remote_last_modified: remote.last_modified.clone(),
+                    remote_size: remote.size,
+                    synced_at: now_iso(),
+                    local_relative_path: local_aliases.get(&path).cloned(),
+                });
+            }
             diff.unchanged += 1;
             continue;
         };
+        if action == "download"
+            && rule.archived_objects == ArchivedObjectPolicy::Skip
+            && is_archived_storage_class(remote.and_then(|v| v.storage_class.as_deref()))
+        {
+            action = "skip".to_string();
+            reason = "skipped: archived".to_string();
+        }
+        // Writing a loser locally would clobber its case twin; skipping all
+        // its actions also keeps a missing local copy from reading as a delete.
+        if let Some(winner) = collision_losers.get(&path) {
+            if rule.case_collisions == CaseCollisionPolicy::Skip {
+                action = "skip".to_string();
+                reason = format!("skipped: case collision with {winner}");
+            }
+        }
 
         let entry = FolderSyncDiffEntryRecord {
             relative_path: path.clone(),
@@ -930,6 +2082,7 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
             remote_size: remote.map(|v| v.size),
             remote_last_modified: remote.map(|v| v.last_modified.clone()),
             remote_etag: remote.map(|v| v.etag.clone()),
+            local_relative_path: local_aliases.get(&path).cloned(),
         };
 
         match action.as_str() {
@@ -937,6 +2090,7 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
             "download" => diff.downloads.push(entry),
             "delete-local" => diff.delete_local.push(entry),
             "delete-remote" => diff.delete_remote.push(entry),
+            "skip" => diff.skipped.push(entry),
             _ => diff.conflicts.push(entry),
         }
     }
@@ -944,6 +2098,146 @@ pub(crate) async fn generate_folder_sync_diff_for_rule(
     Ok(diff)
 }
 
+// Every path but the first in a collision set is a loser; returns each loser
+// with the path that keeps its name. Under rename, a loser's local side is
+// the suffixed copy, which stands in for it in `local_map`. A pair synced
+// that way keeps resolving through the alias in its record once the twin is
+// gone; otherwise the key would read as deleted locally and the copy as new.
+pub(crate) fn apply_case_collision_aliases(
+    rule: &FolderSyncRuleRecord,
+    case_collisions: &[Vec<String>],
+    known_records: &[FolderSyncFileRecord],
+    local_map: &mut HashMap<String, LocalFileInfo>,
+) -> (HashMap<String, String>, HashMap<String, String>) {
+    let mut collision_losers: HashMap<String, String> = HashMap::new();
+    let mut local_aliases: HashMap<String, String> = HashMap::new();
+    for paths in case_collisions {
+        for loser in &paths[1..] {
+            collision_losers.insert(loser.clone(), paths[0].clone());
+            if rule.case_collisions == CaseCollisionPolicy::Rename {
+                let alias = case_collision_copy_name(loser);
+                if let Some(local) = local_map.remove(&alias) {
+                    local_map.insert(loser.clone(), local);
+                }
+                local_aliases.insert(loser.clone(), alias);
+            }
+        }
+    }
+    for known in known_records {
+        let Some(alias) = &known.local_relative_path else {
+            continue;
+        };
+        if local_aliases.contains_key(&known.relative_path)
+            || local_map.contains_key(&known.relative_path)
+        {
+            continue;
+        }
+        if let Some(local) = local_map.remove(alias) {
+            local_map.insert(known.relative_path.clone(), local);
+        }
+        local_aliases.insert(known.relative_path.clone(), alias.clone());
+    }
+    (collision_losers, local_aliases)
+}
+
+impl FolderSyncDiffEntryRecord {
+    pub(crate) fn local_path(&self) -> &str {
+        self.local_relative_path
+            .as_deref()
+            .unwrap_or(&self.relative_path)
+    }
+}
+
+// Groups remote keys with any other local or remote path that matches them
+// case-insensitively. The path already on disk goes first (it's the one that
+// keeps its name), otherwise the sets are in sorted order.
+pub(crate) fn find_case_collisions(
+    local_map: &HashMap<String, LocalFileInfo>,
+    remote_map: &HashMap<String, RemoteFileInfo>,
+) -> Vec<Vec<String>> {
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+    for path in remote_map.keys().chain(local_map.keys()) {
+        let paths = groups.entry(path.to_lowercase()).or_default();
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+
+    let mut collisions: Vec<Vec<String>> = groups
+        .into_values()
+        .filter(|paths| paths.len() > 1)
+        .map(|mut paths| {
+            paths.sort_by(|a, b| {
+                let a_local = local_map.contains_key(a);
+                let b_local = local_map.contains_key(b);
+                b_local.cmp(&a_local).then_with(|| a.cmp(b))
+            });
+            paths
+        })
+        .collect();
+    collisions.sort();
+    collisions
+}
+
+// Compares a pending change against the file's sync record, the same way
+// `resolve_folder_sync_action` decides what changed.
+pub(crate) fn folder_sync_change_origin(
+    entry: &FolderSyncDiffEntryRecord,
+    known: Option<&FolderSyncFileRecord>,
+) -> &'static str {
+    let Some(known) = known else {
+        return "untracked";
+    };
+    let local_changed = match entry.local_size {
+        Some(size) => size != known.local_size || entry.local_mtime != Some(known.local_mtime),
+        None => true,
+    };
+    let remote_changed = match entry.remote_size {
+        Some(size) => {
+            size != known.remote_size || entry.remote_etag.as_deref() != Some(&known.remote_etag)
+        }
+        None => true,
+    };
+    match (local_changed, remote_changed) {
+        (true, true) => "both",
+        (false, true) => "remote",
+        _ => "local",
+    }
+}
+
+pub(crate) fn folder_sync_status_detail(
+    diff: &FolderSyncDiffRecord,
+    known_records: &[FolderSyncFileRecord],
+) -> Vec<FolderSyncStatusDetailEntry> {
+    let known_map: HashMap<&str, &FolderSyncFileRecord> = known_records
+        .iter()
+        .map(|known| (known.relative_path.as_str(), known))
+        .collect();
+    let mut files: Vec<FolderSyncStatusDetailEntry> = diff
+        .uploads
+        .iter()
+        .chain(&diff.downloads)
+        .chain(&diff.delete_local)
+        .chain(&diff.delete_remote)
+        .chain(&diff.conflicts)
+        .chain(&diff.skipped)
+        .map(|entry| {
+            let known = known_map.get(entry.relative_path.as_str()).copied();
+            FolderSyncStatusDetailEntry {
+                entry: entry.clone(),
+                origin: folder_sync_change_origin(entry, known),
+                last_synced_at: known.map(|known| known.synced_at.clone()),
+                synced_local_size: known.map(|known| known.local_size),
+                synced_local_mtime: known.map(|known| known.local_mtime),
+                synced_remote_size: known.map(|known| known.remote_size),
+                synced_remote_etag: known.map(|known| known.remote_etag.clone()),
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.entry.relative_path.cmp(&b.entry.relative_path));
+    files
+}
+
 pub(crate) fn emit_folder_sync_status_event(app: &AppHandle, status: &FolderSyncStateRecord) {
     // FolderSyncStateRecord is camelCase Serialize; emit it directly as the event payload.
     let _ = app.emit("folder-sync:status", status);
@@ -957,6 +2251,28 @@ pub(crate) fn emit_folder_sync_error_event(app: &AppHandle, rule_id: &str, error
     let _ = app.emit("folder-sync:error", payload);
 }
 
+pub(crate) fn emit_folder_sync_case_collision_warning(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    paths: &[String],
+) {
+    let handling = match rule.case_collisions {
+        CaseCollisionPolicy::Skip => "skipping the others".to_string(),
+        CaseCollisionPolicy::Rename => "downloading the others under a suffixed name".to_string(),
+    };
+    let payload = FolderSyncWarningEventPayload {
+        rule_id: &rule.id,
+        kind: "case-collision",
+        paths,
+        message: format!(
+            "{} differ only by case on this filesystem; keeping {} and {handling}",
+            paths.join(", "),
+            paths[0]
+        ),
+    };
+    let _ = app.emit("folder-sync:warning", payload);
+}
+
 pub(crate) fn emit_folder_sync_conflict_event(
     app: &AppHandle,
     rule_id: &str,
@@ -985,21 +2301,24 @@ pub(crate) fn set_and_emit_folder_sync_status(
     current_file: Option<String>,
     progress: Option<FolderSyncProgress>,
 ) -> Result<(), String> {
-    let record = FolderSyncStateRecord {
-        rule_id: rule_id.to_string(),
-        status,
-        files_watching: files_watching.max(0),
-        last_change,
-        current_file,
-        progress,
-    };
-
-    let status_changed = {
+    let (record, status_changed) = {
         let state = app.state::<AppState>();
         let mut runtime = lock_state(&state.folder_sync)?;
-        let prev = runtime.statuses.get(rule_id).map(|r| r.status);
+        let prev = runtime.statuses.get(rule_id);
+        let record = FolderSyncStateRecord {
+            rule_id: rule_id.to_string(),
+            status,
+            files_watching: files_watching.max(0),
+            last_change,
+            current_file,
+            progress,
+            // Set by the poll loop between passes; carried across status updates.
+            effective_poll_interval_ms: prev.and_then(|r| r.effective_poll_interval_ms),
+            dry_run: false,
+        };
+        let status_changed = prev.map(|r| r.status) != Some(status);
         runtime.statuses.insert(rule_id.to_string(), record.clone());
-        prev != Some(status)
+        (record, status_changed)
     };
 
     emit_folder_sync_status_event(app, &record);
@@ -1015,6 +2334,19 @@ pub(crate) fn set_and_emit_folder_sync_status(
     Ok(())
 }
 
+pub(crate) fn set_folder_sync_effective_poll_interval(
+    app: &AppHandle,
+    rule_id: &str,
+    interval_ms: i64,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut runtime = lock_state(&state.folder_sync)?;
+    if let Some(record) = runtime.statuses.get_mut(rule_id) {
+        record.effective_poll_interval_ms = Some(interval_ms);
+    }
+    Ok(())
+}
+
 pub(crate) fn folder_sync_statuses_snapshot(app: &AppHandle) -> Vec<FolderSyncStateRecord> {
     let state = app.state::<AppState>();
     let Ok(runtime) = lock_state(&state.folder_sync) else {
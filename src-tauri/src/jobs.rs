@@ -5,13 +5,22 @@ use super::*;
 
 pub(crate) fn try_start_queued_jobs(app: AppHandle) {
     let state = app.state::<AppState>();
+    // Jobs restored from job-queue.json wait here until the vault is unlocked.
+    let unlocked = lock_state(&state.vault)
+        .map(|vault| vault.unlocked)
+        .unwrap_or(false);
+    if !unlocked {
+        return;
+    }
 
-    let mut start_now: Vec<(JobTask, Arc<AtomicBool>)> = Vec::new();
+    let mut start_now: Vec<(JobTask, Arc<AtomicBool>, Option<Arc<AtomicBool>>)> = Vec::new();
     let mut running_snapshots: Vec<JobInfo> = Vec::new();
 
     if let Ok(mut jobs) = lock_state(&state.jobs) {
-        while jobs.running.len() < jobs.concurrency as usize {
-            let Some(task) = jobs.queue.pop_front() else {
+        while jobs.running.len() < effective_job_concurrency(&jobs) as usize {
+            let Some(task) =
+                next_queued_job_index(&jobs.queue).and_then(|index| jobs.queue.remove(index))
+            else {
                 break;
             };
 
@@ -20,7 +29,13 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                 .entry(task.id.clone())
                 .or_insert_with(|| Arc::new(AtomicBool::new(false)))
                 .clone();
+            let pause_flag = job_task_is_pausable(&task.kind).then(|| {
+                let flag = Arc::new(AtomicBool::new(false));
+                jobs.pause_flags.insert(task.id.clone(), flag.clone());
+                flag
+            });
             jobs.running.insert(task.id.clone());
+            jobs.running_tasks.insert(task.id.clone(), task.clone());
 
             if let Some(job) = jobs.jobs.get_mut(&task.id) {
                 job.status = JobStatus::Running;
@@ -30,7 +45,7 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                 running_snapshots.push(job.clone());
             }
 
-            start_now.push((task, cancel_flag));
+            start_now.push((task, cancel_flag, pause_flag));
         }
     }
 
@@ -38,7 +53,7 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
         emit_job_progress_event(&app, &snapshot);
     }
 
-    for (task, cancel_flag) in start_now {
+    for (task, cancel_flag, pause_flag) in start_now {
         let app_handle = app.clone();
         tauri::async_runtime::spawn(async move {
             let result: Result<i64, String> = async {
@@ -71,9 +86,50 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                         bucket,
                         key,
                         local_path,
+                        overwrite,
+                        acl,
+                        content_type,
+                        cache_control,
+                        metadata,
+                        storage_class,
+                        sse,
+                        sse_kms_key_id,
                     } => {
                         let profile = profile_for_id(&state, profile_id)?;
                         let client = to_s3_client(&profile)?;
+                        let (server_side_encryption, ssekms_key_id) = resolve_upload_encryption(
+                            &profile,
+                            sse.as_deref(),
+                            sse_kms_key_id.as_deref(),
+                        )?;
+                        let bandwidth = lock_state(&state.jobs)?.bandwidth.clone();
+                        let create_only =
+                            !*overwrite && supports_conditional_writes(&profile.provider);
+                        let attributes = UploadAttributes {
+                            create_only,
+                            acl: acl.as_deref().map(parse_canned_acl).transpose()?,
+                            content_type: content_type
+                                .clone()
+                                .or_else(|| guess_content_type(key).map(str::to_string)),
+                            cache_control: cache_control.clone(),
+                            metadata: metadata.clone(),
+                            storage_class: storage_class
+                                .as_deref()
+                                .map(parse_storage_class)
+                                .transpose()?,
+                            server_side_encryption,
+                            ssekms_key_id,
+                            content_md5: false,
+                            owner: Some(MultipartUploadOwner {
+                                job_id: task.id.clone(),
+                                profile_id: profile_id.clone(),
+                            }),
+                            bandwidth: Some(bandwidth),
+                            pause_flag: pause_flag.clone(),
+                        };
+                        if !*overwrite && s3_head_if_exists(&client, bucket, key).await?.is_some() {
+                            return Err(already_exists_error(bucket, key));
+                        }
                         if local_path.trim().is_empty() {
                             update(0, 0, &mut speed_calc);
                             client
@@ -81,9 +137,19 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                 .bucket(bucket.to_string())
                                 .key(key.to_string())
                                 .body(ByteStream::from(Vec::<u8>::new()))
+                                .set_acl(attributes.acl.clone())
+                                .set_content_type(attributes.content_type.clone())
+                                .set_cache_control(attributes.cache_control.clone())
+                                .set_metadata(upload_metadata(&attributes))
+                                .set_storage_class(attributes.storage_class.clone())
+                                .set_server_side_encryption(
+                                    attributes.server_side_encryption.clone(),
+                                )
+                                .set_ssekms_key_id(attributes.ssekms_key_id.clone())
+                                .set_if_none_match(create_only.then(|| "*".to_string()))
                                 .send()
                                 .await
-                                .map_err(|err| err.to_string())?;
+                                .map_err(|err| s3_upload_error(err, bucket, key))?;
                             update(0, 0, &mut speed_calc);
                             Ok(0)
                         } else {
@@ -93,9 +159,17 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                 .unwrap_or(0)
                                 .max(0);
                             update(0, total, &mut speed_calc);
-                            s3_upload_file(&client, bucket, key, &local, &cancel_flag, |t, tot| {
-                                update(t, tot, &mut speed_calc);
-                            })
+                            s3_upload_file(
+                                &client,
+                                bucket,
+                                key,
+                                &local,
+                                &attributes,
+                                &cancel_flag,
+                                |t, tot| {
+                                    update(t, tot, &mut speed_calc);
+                                },
+                            )
                             .await
                         }
                     }
@@ -104,14 +178,88 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                         bucket,
                         key,
                         local_path,
+                        on_existing,
+                        verify_checksum,
+                        version_id,
                     } => {
                         let profile = profile_for_id(&state, profile_id)?;
                         let client = to_s3_client(&profile)?;
-                        let local = expand_user_path(local_path);
+                        let requested = expand_user_path(local_path);
+                        // Resolved at start: earlier jobs may have created it.
+                        let Some(local) = resolve_download_destination(&requested, *on_existing)
+                        else {
+                            return Err(format!(
+                                "{JOB_SKIPPED_PREFIX}{} already exists",
+                                requested.display()
+                            ));
+                        };
+                        let bandwidth = lock_state(&state.jobs)?.bandwidth.clone();
                         update(0, 0, &mut speed_calc);
-                        s3_download_file(&client, bucket, key, &local, &cancel_flag, |t, tot| {
-                            update(t, tot, &mut speed_calc);
-                        })
+                        s3_download_file(
+                            &client,
+                            bucket,
+                            key,
+                            &local,
+                            DownloadAttributes {
+                                version_id: version_id.clone(),
+                                verify_checksum: *verify_checksum,
+                                bandwidth: Some(bandwidth),
+                                pause_flag: pause_flag.clone(),
+                                ..download_attributes_from_settings(&state)
+                            },
+                            &cancel_flag,
+                            |t, tot| update(t, tot, &mut speed_calc),
+                        )
+                        .await
+                    }
+                    JobTaskKind::OpenExternal {
+                        profile_id,
+                        bucket,
+                        key,
+                        local_path,
+                    } => {
+                        let profile = profile_for_id(&state, profile_id)?;
+                        let client = to_s3_client(&profile)?;
+                        let local = PathBuf::from(local_path);
+                        update(0, 0, &mut speed_calc);
+                        let bytes = s3_download_file(
+                            &client,
+                            bucket,
+                            key,
+                            &local,
+                            DownloadAttributes::default(),
+                            &cancel_flag,
+                            |t, tot| update(t, tot, &mut speed_calc),
+                        )
+                        .await?;
+                        schedule_open_external_cleanup(&local);
+                        app_handle
+                            .opener()
+                            .open_path(local_path.as_str(), None::<&str>)
+                            .map_err(|err| format!("Failed to open {}: {err}", local.display()))?;
+                        Ok(bytes)
+                    }
+                    JobTaskKind::UploadFolderArchive {
+                        profile_id,
+                        bucket,
+                        key,
+                        local_dir,
+                        exclude_patterns,
+                    } => {
+                        let profile = profile_for_id(&state, profile_id)?;
+                        let client = to_s3_client(&profile)?;
+                        let local_dir = expand_user_path(local_dir);
+                        let files = scan_local_directory(&local_dir, exclude_patterns);
+                        update(0, 0, &mut speed_calc);
+                        s3_upload_directory_tar_gz(
+                            &client,
+                            bucket,
+                            key,
+                            &local_dir,
+                            &files,
+                            &cancel_flag,
+                            |t, tot| update(t, tot, &mut speed_calc),
+                        )
                         .await
                     }
                     JobTaskKind::Copy {
@@ -126,6 +274,7 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                         let dst_profile = profile_for_id(&state, dest_profile_id)?;
                         let src_client = to_s3_client(&src_profile)?;
                         let dst_client = to_s3_client(&dst_profile)?;
+                        let dest_attributes = upload_attributes_from_profile(&dst_profile)?;
                         let same_profile = source_profile_id == dest_profile_id;
                         update(0, 0, &mut speed_calc);
                         if same_profile {
@@ -147,9 +296,12 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                     &src_client,
                                     source_bucket,
                                     source_key,
-                                    &dst_client,
-                                    dest_bucket,
-                                    dest_key,
+                                    CopyDestination {
+                                        client: &dst_client,
+                                        bucket: dest_bucket,
+                                        key: dest_key,
+                                        attributes: &dest_attributes,
+                                    },
                                     &cancel_flag,
                                     |t, tot| update(t, tot, &mut speed_calc),
                                 )
@@ -163,9 +315,12 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                 &src_client,
                                 source_bucket,
                                 source_key,
-                                &dst_client,
-                                dest_bucket,
-                                dest_key,
+                                CopyDestination {
+                                    client: &dst_client,
+                                    bucket: dest_bucket,
+                                    key: dest_key,
+                                    attributes: &dest_attributes,
+                                },
                                 &cancel_flag,
                                 |t, tot| update(t, tot, &mut speed_calc),
                             )
@@ -184,8 +339,20 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                         let dst_profile = profile_for_id(&state, dest_profile_id)?;
                         let src_client = to_s3_client(&src_profile)?;
                         let dst_client = to_s3_client(&dst_profile)?;
+                        let dest_attributes = upload_attributes_from_profile(&dst_profile)?;
                         let same_profile = source_profile_id == dest_profile_id;
                         update(0, 0, &mut speed_calc);
+                        if same_profile && source_bucket == dest_bucket {
+                            return s3_move_object_within_bucket(
+                                &src_client,
+                                source_bucket,
+                                source_key,
+                                dest_key,
+                                &cancel_flag,
+                                |t, tot| update(t, tot, &mut speed_calc),
+                            )
+                            .await;
+                        }
                         let transferred = if same_profile {
                             match s3_copy_object(
                                 &src_client,
@@ -205,9 +372,12 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                     &src_client,
                                     source_bucket,
                                     source_key,
-                                    &dst_client,
-                                    dest_bucket,
-                                    dest_key,
+                                    CopyDestination {
+                                        client: &dst_client,
+                                        bucket: dest_bucket,
+                                        key: dest_key,
+                                        attributes: &dest_attributes,
+                                    },
                                     &cancel_flag,
                                     |t, tot| update(t, tot, &mut speed_calc),
                                 )
@@ -221,15 +391,19 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                                 &src_client,
                                 source_bucket,
                                 source_key,
-                                &dst_client,
-                                dest_bucket,
-                                dest_key,
+                                CopyDestination {
+                                    client: &dst_client,
+                                    bucket: dest_bucket,
+                                    key: dest_key,
+                                    attributes: &dest_attributes,
+                                },
                                 &cancel_flag,
                                 |t, tot| update(t, tot, &mut speed_calc),
                             )
                             .await?
                         };
 
+                        verify_copied_size(&dst_client, dest_bucket, dest_key, transferred).await?;
                         if cancel_flag.load(Ordering::SeqCst) {
                             return Err(JOB_CANCELLED.to_string());
                         }
@@ -244,10 +418,23 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                     } => {
                         let profile = profile_for_id(&state, profile_id)?;
                         let client = to_s3_client(&profile)?;
-                        update(0, keys.len() as i64, &mut speed_calc);
-                        s3_delete_keys(&client, bucket, keys).await?;
-                        update(keys.len() as i64, keys.len() as i64, &mut speed_calc);
-                        Ok(keys.len() as i64)
+                        s3_delete_keys_batched(&client, bucket, keys, &cancel_flag, |t, tot| {
+                            update(t, tot, &mut speed_calc)
+                        })
+                        .await
+                    }
+                    JobTaskKind::DeletePrefix {
+                        profile_id,
+                        bucket,
+                        prefix,
+                    } => {
+                        let profile = profile_for_id(&state, profile_id)?;
+                        let client = to_s3_client(&profile)?;
+                        update(0, 0, &mut speed_calc);
+                        s3_delete_prefix(&client, bucket, prefix, &cancel_flag, |t, tot| {
+                            update(t, tot, &mut speed_calc)
+                        })
+                        .await
                     }
                     JobTaskKind::Archive {
                         profile_id,
@@ -255,19 +442,32 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                         keys,
                         common_prefix,
                         destination_path,
+                        format,
                     } => {
                         let profile = profile_for_id(&state, profile_id)?;
                         let client = to_s3_client(&profile)?;
                         let destination = expand_user_path(destination_path);
                         update(0, 0, &mut speed_calc);
-                        s3_download_archive_tar_gz(
+                        s3_download_archive(
                             &client,
                             bucket,
-                            keys,
-                            common_prefix,
-                            &destination,
+                            ArchiveRequest {
+                                keys,
+                                common_prefix,
+                                destination_path: &destination,
+                                format: *format,
+                            },
                             &cancel_flag,
                             |t, tot| update(t, tot, &mut speed_calc),
+                            |current, completed, files| {
+                                emit_archive_progress_event(
+                                    &app_handle,
+                                    &task.id,
+                                    current,
+                                    completed,
+                                    files,
+                                )
+                            },
                         )
                         .await
                     }
@@ -286,6 +486,14 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
                 Err(err) if err == JOB_CANCELLED => {
                     finish_job(&app_handle, &task.id, JobStatus::Cancelled, Some(err), None)
                 }
+                Err(err) if err == JOB_PAUSED => park_paused_job(&app_handle, task),
+                Err(err) if err.starts_with(JOB_SKIPPED_PREFIX) => finish_job(
+                    &app_handle,
+                    &task.id,
+                    JobStatus::Skipped,
+                    Some(err),
+                    Some(0),
+                ),
                 Err(err) => finish_job(&app_handle, &task.id, JobStatus::Failed, Some(err), None),
             }
 
@@ -294,6 +502,16 @@ pub(crate) fn try_start_queued_jobs(app: AppHandle) {
     }
 }
 
+// The queue is kept in insertion order; the next job is the first one with
+// the highest priority, so equal priorities run FIFO.
+pub(crate) fn next_queued_job_index(queue: &VecDeque<JobTask>) -> Option<usize> {
+    queue
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, task)| (task.priority, std::cmp::Reverse(*index)))
+        .map(|(index, _)| index)
+}
+
 pub(crate) fn enqueue_job(
     app: &AppHandle,
     job_type: JobType,
@@ -315,6 +533,8 @@ pub(crate) fn enqueue_job(
         speed: 0,
         eta: 0,
         error: None,
+        cancel_reason: None,
+        priority: 0,
         created_at: now_iso(),
         started_at: None,
         completed_at: None,
@@ -323,6 +543,7 @@ pub(crate) fn enqueue_job(
     let task = JobTask {
         id: job_id.clone(),
         kind,
+        priority: 0,
     };
 
     let state = app.state::<AppState>();
@@ -333,8 +554,9 @@ pub(crate) fn enqueue_job(
         jobs.order.insert(0, job_id.clone());
         if jobs.order.len() > JOB_ORDER_MAX {
             for removed in jobs.order.split_off(JOB_ORDER_MAX) {
-                if !jobs.running.contains(&removed) {
+                if !jobs.running.contains(&removed) && !jobs.paused.contains_key(&removed) {
                     jobs.jobs.remove(&removed);
+                    jobs.retry_tasks.remove(&removed);
                 }
             }
         }
@@ -343,27 +565,54 @@ pub(crate) fn enqueue_job(
             .insert(job_id.clone(), Arc::new(AtomicBool::new(false)));
     }
 
+    persist_job_queue_snapshot(app);
     emit_job_progress_event(app, &info);
     try_start_queued_jobs(app.clone());
     Ok(job_id)
 }
 
-pub(crate) fn cancel_job(app: &AppHandle, job_id: &str) {
+impl CancelReason {
+    pub(crate) fn message(self) -> String {
+        match self {
+            Self::User => format!("{JOB_CANCELLED} by user"),
+            Self::VaultLocked => format!("{JOB_CANCELLED}: vault locked"),
+            Self::AbortAll => format!("{JOB_CANCELLED}: all activity aborted"),
+            Self::Shutdown => format!("{JOB_CANCELLED}: app shutting down"),
+        }
+    }
+}
+
+pub(crate) fn mark_job_cancelled(job: &mut JobInfo, reason: CancelReason) {
+    job.status = JobStatus::Cancelled;
+    job.error = Some(reason.message());
+    job.cancel_reason = Some(reason);
+    job.completed_at = Some(now_iso());
+}
+
+pub(crate) fn cancel_job(app: &AppHandle, job_id: &str, reason: CancelReason) {
     let mut queued_cancel_snapshot: Option<JobInfo> = None;
     {
         let state = app.state::<AppState>();
         if let Ok(mut jobs) = lock_state(&state.jobs) {
             if let Some(index) = jobs.queue.iter().position(|task| task.id == job_id) {
-                jobs.queue.remove(index);
+                if let Some(task) = jobs.queue.remove(index) {
+                    jobs.retry_tasks.insert(task.id.clone(), task);
+                }
                 if let Some(job) = jobs.jobs.get_mut(job_id) {
-                    job.status = JobStatus::Cancelled;
-                    job.error = Some(JOB_CANCELLED.to_string());
-                    job.completed_at = Some(now_iso());
+                    mark_job_cancelled(job, reason);
                     queued_cancel_snapshot = Some(job.clone());
                 }
                 jobs.cancel_flags.remove(job_id);
-            } else if let Some(cancel_flag) = jobs.cancel_flags.get(job_id) {
+            } else if let Some(task) = jobs.paused.remove(job_id) {
+                discard_paused_job_progress(&task);
+                jobs.retry_tasks.insert(task.id.clone(), task);
+                if let Some(job) = jobs.jobs.get_mut(job_id) {
+                    mark_job_cancelled(job, reason);
+                    queued_cancel_snapshot = Some(job.clone());
+                }
+            } else if let Some(cancel_flag) = jobs.cancel_flags.get(job_id).cloned() {
                 cancel_flag.store(true, Ordering::SeqCst);
+                jobs.cancel_reasons.insert(job_id.to_string(), reason);
             }
         };
     }
@@ -372,7 +621,450 @@ pub(crate) fn cancel_job(app: &AppHandle, job_id: &str) {
         emit_job_progress_event(app, &job);
         emit_job_complete_event(app, &job);
         persist_job_history_snapshot(app);
+        persist_job_queue_snapshot(app);
+    }
+}
+
+// Multipart uploads started by a job that no longer exists: the app quit or
+// crashed mid-upload. A job still in the list keeps its sidecar: active ones
+// will use it, and failed ones resume through `jobs:retry`.
+pub(crate) fn list_resumable_uploads(app: &AppHandle) -> Result<Vec<ResumableUpload>, String> {
+    let state = app.state::<AppState>();
+    let known: HashSet<String> = {
+        let jobs = lock_state(&state.jobs)?;
+        let ids = jobs.jobs.keys().cloned().collect();
+        ids
+    };
+    let mut uploads: Vec<ResumableUpload> = list_multipart_sidecars()
+        .into_iter()
+        .filter_map(|(id, sidecar)| {
+            let owner = sidecar.owner.clone()?;
+            if known.contains(&owner.job_id) {
+                return None;
+            }
+            let local_path = Path::new(&sidecar.local_path);
+            let local_unchanged = fs::metadata(local_path)
+                .is_ok_and(|meta| meta.len() as i64 == sidecar.file_size)
+                && file_mtime_millis(local_path) == sidecar.file_mtime_ms;
+            Some(ResumableUpload {
+                id,
+                job_id: owner.job_id,
+                profile_id: owner.profile_id,
+                bytes_uploaded: sidecar.parts.iter().map(|part| part.size).sum(),
+                file_size: sidecar.file_size,
+                created_at: sidecar.created_at,
+                bucket: sidecar.bucket,
+                key: sidecar.key,
+                local_path: sidecar.local_path,
+                local_unchanged,
+            })
+        })
+        .collect();
+    uploads.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(uploads)
+}
+
+fn resumable_upload_sidecar(
+    app: &AppHandle,
+    id: &str,
+) -> Result<(ResumableUpload, MultipartUploadSidecar), String> {
+    let upload = list_resumable_uploads(app)?
+        .into_iter()
+        .find(|upload| upload.id == id)
+        .ok_or_else(|| "No interrupted upload with that id".to_string())?;
+    let sidecar = load_multipart_sidecar_by_id(id)
+        .ok_or_else(|| "No interrupted upload with that id".to_string())?;
+    Ok((upload, sidecar))
+}
+
+// Queues a fresh upload job for the sidecar's file; `s3_upload_file` finds the
+// sidecar, checks the parts with ListParts and uploads only the rest.
+pub(crate) fn resume_interrupted_upload(app: &AppHandle, id: &str) -> Result<String, String> {
+    let (upload, sidecar) = resumable_upload_sidecar(app, id)?;
+    if !upload.local_unchanged {
+        return Err(format!(
+            "{} changed since the upload was interrupted; discard it and upload again",
+            upload.local_path
+        ));
+    }
+    let file_name = upload
+        .key
+        .rsplit('/')
+        .find(|part| !part.is_empty())
+        .unwrap_or(upload.key.as_str())
+        .to_string();
+    enqueue_job(
+        app,
+        JobType::Upload,
+        file_name,
+        format!("Upload to {}/{}", upload.bucket, upload.key),
+        upload.file_size,
+        JobTaskKind::Upload {
+            profile_id: upload.profile_id,
+            bucket: upload.bucket,
+            key: upload.key,
+            local_path: upload.local_path,
+            overwrite: !sidecar.create_only,
+            // The multipart upload already carries its headers.
+            acl: None,
+            content_type: None,
+            cache_control: None,
+            metadata: HashMap::new(),
+            storage_class: None,
+            sse: None,
+            sse_kms_key_id: None,
+        },
+    )
+}
+
+// Aborts the multipart upload so the provider frees its parts, then forgets it.
+pub(crate) async fn discard_interrupted_upload(app: &AppHandle, id: &str) -> Result<(), String> {
+    let (upload, sidecar) = resumable_upload_sidecar(app, id)?;
+    let profile = profile_for_id(&app.state::<AppState>(), &upload.profile_id)?;
+    let client = to_s3_client(&profile)?;
+    discard_multipart_upload(&client, &sidecar).await;
+    Ok(())
+}
+
+pub(crate) fn job_task_is_pausable(kind: &JobTaskKind) -> bool {
+    matches!(
+        kind,
+        JobTaskKind::Upload { .. } | JobTaskKind::Download { .. }
+    )
+}
+
+// Queued jobs pause on the spot; running ones stop at the next part or chunk
+// and are parked by `park_paused_job`.
+pub(crate) fn pause_job(app: &AppHandle, job_id: &str) -> Result<(), String> {
+    let mut snapshot: Option<JobInfo> = None;
+    {
+        let state = app.state::<AppState>();
+        let mut jobs = lock_state(&state.jobs)?;
+        if jobs.paused.contains_key(job_id) {
+            return Ok(());
+        }
+        if let Some(flag) = jobs.pause_flags.get(job_id) {
+            flag.store(true, Ordering::SeqCst);
+            return Ok(());
+        }
+        if jobs.running.contains(job_id) {
+            return Err("Only uploads and downloads can be paused".to_string());
+        }
+        let Some(index) = jobs.queue.iter().position(|task| task.id == job_id) else {
+            return Err(format!("Job {job_id} is not queued or running"));
+        };
+        if !job_task_is_pausable(&jobs.queue[index].kind) {
+            return Err("Only uploads and downloads can be paused".to_string());
+        }
+        let Some(task) = jobs.queue.remove(index) else {
+            return Err(format!("Job {job_id} is not queued or running"));
+        };
+        jobs.cancel_flags.remove(job_id);
+        jobs.paused.insert(job_id.to_string(), task);
+        if let Some(job) = jobs.jobs.get_mut(job_id) {
+            job.status = JobStatus::Paused;
+            snapshot = Some(job.clone());
+        }
+    }
+    if let Some(job) = snapshot {
+        emit_job_progress_event(app, &job);
+    }
+    Ok(())
+}
+
+pub(crate) fn is_retryable_job_status(status: JobStatus) -> bool {
+    matches!(status, JobStatus::Failed | JobStatus::Cancelled)
+}
+
+// Puts a failed or cancelled job back in the queue under the same id, so the
+// UI keeps tracking the same row and a multipart upload owned by that id
+// resumes. Jobs loaded from history have no task and can't be retried.
+pub(crate) fn requeue_finished_job(jobs: &mut JobRuntime, job_id: &str) -> Result<JobInfo, String> {
+    let Some(job) = jobs.jobs.get_mut(job_id) else {
+        return Err(format!("Job {job_id} not found"));
+    };
+    if !is_retryable_job_status(job.status) {
+        return Err("Only failed or cancelled jobs can be retried".to_string());
+    }
+    let Some(task) = jobs.retry_tasks.remove(job_id) else {
+        return Err(format!("Job {job_id} can no longer be retried"));
+    };
+    reset_requeued_job(job);
+    let snapshot = job.clone();
+    jobs.queue.push_back(task);
+    jobs.cancel_flags
+        .insert(job_id.to_string(), Arc::new(AtomicBool::new(false)));
+    Ok(snapshot)
+}
+
+// Retried jobs leave the history file and join the persisted queue.
+fn after_jobs_requeued(app: &AppHandle, requeued: &[JobInfo]) {
+    for job in requeued {
+        emit_job_progress_event(app, job);
+    }
+    if !requeued.is_empty() {
+        persist_job_history_snapshot(app);
+        persist_job_queue_snapshot(app);
+        try_start_queued_jobs(app.clone());
+    }
+}
+
+pub(crate) fn retry_job(app: &AppHandle, job_id: &str) -> Result<(), String> {
+    let snapshot = {
+        let state = app.state::<AppState>();
+        let mut jobs = lock_state(&state.jobs)?;
+        requeue_finished_job(&mut jobs, job_id)?
+    };
+    after_jobs_requeued(app, &[snapshot]);
+    Ok(())
+}
+
+// Every failed job that still has its task, oldest first; returns their ids.
+pub(crate) fn retry_all_failed_jobs(app: &AppHandle) -> Result<Vec<String>, String> {
+    let requeued: Vec<JobInfo> = {
+        let state = app.state::<AppState>();
+        let mut jobs = lock_state(&state.jobs)?;
+        let failed: Vec<String> = jobs
+            .order
+            .iter()
+            .rev()
+            .filter(|id| {
+                jobs.retry_tasks.contains_key(*id)
+                    && jobs
+                        .jobs
+                        .get(*id)
+                        .is_some_and(|job| job.status == JobStatus::Failed)
+            })
+            .cloned()
+            .collect();
+        let requeued = failed
+            .iter()
+            .filter_map(|id| requeue_finished_job(&mut jobs, id).ok())
+            .collect();
+        requeued
+    };
+    after_jobs_requeued(app, &requeued);
+    Ok(requeued.into_iter().map(|job| job.id).collect())
+}
+
+// Only a queued job can move; one that has started keeps its slot.
+pub(crate) fn set_job_priority(app: &AppHandle, job_id: &str, priority: i64) -> Result<(), String> {
+    let mut snapshot: Option<JobInfo> = None;
+    {
+        let state = app.state::<AppState>();
+        let mut jobs = lock_state(&state.jobs)?;
+        let Some(task) = jobs.queue.iter_mut().find(|task| task.id == job_id) else {
+            return Err(format!("Job {job_id} is not queued"));
+        };
+        task.priority = priority;
+        if let Some(job) = jobs.jobs.get_mut(job_id) {
+            job.priority = priority;
+            snapshot = Some(job.clone());
+        }
+    }
+    if let Some(job) = snapshot {
+        emit_job_progress_event(app, &job);
+    }
+    persist_job_queue_snapshot(app);
+    Ok(())
+}
+
+pub(crate) fn resume_job(app: &AppHandle, job_id: &str) -> Result<(), String> {
+    let mut snapshot: Option<JobInfo> = None;
+    {
+        let state = app.state::<AppState>();
+        let mut jobs = lock_state(&state.jobs)?;
+        let Some(task) = jobs.paused.remove(job_id) else {
+            // Paused but not stopped yet: just take the request back.
+            return match jobs.pause_flags.get(job_id) {
+                Some(flag) => {
+                    flag.store(false, Ordering::SeqCst);
+                    Ok(())
+                }
+                None => Err(format!("Job {job_id} is not paused")),
+            };
+        };
+        jobs.queue.push_back(task);
+        jobs.cancel_flags
+            .insert(job_id.to_string(), Arc::new(AtomicBool::new(false)));
+        if let Some(job) = jobs.jobs.get_mut(job_id) {
+            job.status = JobStatus::Queued;
+            snapshot = Some(job.clone());
+        }
+    }
+    if let Some(job) = snapshot {
+        emit_job_progress_event(app, &job);
+    }
+    persist_job_queue_snapshot(app);
+    try_start_queued_jobs(app.clone());
+    Ok(())
+}
+
+// A running job that stopped with `JOB_PAUSED`: frees its slot and keeps the
+// task for `resume_job`. Progress stays as reported.
+fn park_paused_job(app: &AppHandle, task: JobTask) {
+    let state = app.state::<AppState>();
+    let cancel_reason = lock_state(&state.jobs).ok().and_then(|jobs| {
+        let cancelled = jobs
+            .cancel_flags
+            .get(&task.id)
+            .is_some_and(|flag| flag.load(Ordering::SeqCst));
+        cancelled.then(|| {
+            jobs.cancel_reasons
+                .get(&task.id)
+                .copied()
+                .unwrap_or(CancelReason::User)
+        })
+    });
+    if let Some(reason) = cancel_reason {
+        // Cancelled while it was stopping for the pause.
+        discard_paused_job_progress(&task);
+        let error = Some(reason.message());
+        finish_job(app, &task.id, JobStatus::Cancelled, error, None);
+        return;
+    }
+
+    let mut snapshot: Option<JobInfo> = None;
+    if let Ok(mut jobs) = lock_state(&state.jobs) {
+        jobs.running.remove(&task.id);
+        jobs.running_tasks.remove(&task.id);
+        jobs.cancel_flags.remove(&task.id);
+        jobs.pause_flags.remove(&task.id);
+        jobs.progress_emitted_at.remove(&task.id);
+        jobs.cancel_reasons.remove(&task.id);
+        if let Some(job) = jobs.jobs.get_mut(&task.id) {
+            job.status = JobStatus::Paused;
+            job.speed = 0;
+            job.eta = 0;
+            snapshot = Some(job.clone());
+        }
+        jobs.paused.insert(task.id.clone(), task);
+    }
+    if let Some(job) = snapshot {
+        emit_job_progress_event(app, &job);
+    }
+    persist_job_queue_snapshot(app);
+}
+
+// Cancelling a paused download drops its partial file. A paused multipart
+// upload keeps its sidecar, like a failed one, so uploading the same file
+// again picks it up.
+fn discard_paused_job_progress(task: &JobTask) {
+    if let JobTaskKind::Download {
+        bucket,
+        key,
+        local_path,
+        on_existing,
+        ..
+    } = &task.kind
+    {
+        if let Some(local) =
+            resolve_download_destination(&expand_user_path(local_path), *on_existing)
+        {
+            discard_partial_download(bucket, key, &local);
+        }
+    }
+}
+
+// Cancels every queued and running job for `reason`; returns how many of
+// each. Running jobs finish as cancelled once they notice the flag.
+pub(crate) fn cancel_all_jobs(app: &AppHandle, reason: CancelReason) -> (usize, usize) {
+    let mut cancelled_queued = Vec::new();
+    let mut cancelled_running = 0;
+    {
+        let state = app.state::<AppState>();
+        if let Ok(mut jobs) = lock_state(&state.jobs) {
+            let mut queued: Vec<JobTask> = jobs.queue.drain(..).collect();
+            let paused: Vec<JobTask> = jobs.paused.drain().map(|(_, task)| task).collect();
+            for task in paused {
+                discard_paused_job_progress(&task);
+                queued.push(task);
+            }
+            for task in queued {
+                let job_id = task.id.clone();
+                jobs.cancel_flags.remove(&job_id);
+                jobs.retry_tasks.insert(job_id.clone(), task);
+                if let Some(job) = jobs.jobs.get_mut(&job_id) {
+                    mark_job_cancelled(job, reason);
+                    cancelled_queued.push(job.clone());
+                }
+            }
+            let running: Vec<String> = jobs.cancel_flags.keys().cloned().collect();
+            for job_id in running {
+                if let Some(cancel_flag) = jobs.cancel_flags.get(&job_id) {
+                    cancel_flag.store(true, Ordering::SeqCst);
+                }
+                jobs.cancel_reasons.insert(job_id, reason);
+                cancelled_running += 1;
+            }
+        };
+    }
+    for job in &cancelled_queued {
+        emit_job_progress_event(app, job);
+        emit_job_complete_event(app, job);
+    }
+    if !cancelled_queued.is_empty() {
+        persist_job_history_snapshot(app);
+        persist_job_queue_snapshot(app);
     }
+    (cancelled_queued.len(), cancelled_running)
+}
+
+// Quitting kills running transfers before they can report back, so they are
+// recorded as cancelled here and history is written before exit. The queue
+// file is written out and frozen first, so those jobs run again at the next
+// launch.
+pub(crate) fn cancel_jobs_for_shutdown(app: &AppHandle) {
+    write_job_queue_snapshot(app);
+    {
+        let state = app.state::<AppState>();
+        if let Ok(mut jobs) = lock_state(&state.jobs) {
+            jobs.queue_frozen = true;
+        };
+    }
+    cancel_all_jobs(app, CancelReason::Shutdown);
+    {
+        let state = app.state::<AppState>();
+        let Ok(mut jobs) = lock_state(&state.jobs) else {
+            return;
+        };
+        let running: Vec<String> = jobs.running.drain().collect();
+        for job_id in running {
+            jobs.cancel_flags.remove(&job_id);
+            jobs.cancel_reasons.remove(&job_id);
+            if let Some(job) = jobs.jobs.get_mut(&job_id) {
+                mark_job_cancelled(job, CancelReason::Shutdown);
+            }
+        }
+    }
+    persist_job_history_snapshot(app);
+}
+
+// Kill switch: cancels queued and running jobs, stops every folder-sync rule
+// and tells the UI. Unlike pause, in-flight work is abandoned (multipart
+// uploads are aborted); the app stays usable.
+pub(crate) fn abort_all_activity(app: &AppHandle) -> Value {
+    let (cancelled_queued, cancelled_running) = cancel_all_jobs(app, CancelReason::AbortAll);
+
+    let stopped_rules = {
+        let state = app.state::<AppState>();
+        let value = lock_state(&state.folder_sync)
+            .map(|runtime| runtime.tasks.len())
+            .unwrap_or(0);
+        value
+    };
+    stop_all_folder_sync_rules(app);
+    refresh_tray_menu(app);
+    let cancelled_operations = cancel_all_operations(&app.state::<AppState>());
+
+    let summary = json!({
+        "cancelledQueued": cancelled_queued,
+        "cancelledRunning": cancelled_running,
+        "stoppedRules": stopped_rules,
+        "cancelledOperations": cancelled_operations,
+    });
+    let _ = app.emit("app:aborted", summary.clone());
+    summary
 }
 
 pub(crate) fn calculate_percentage(transferred: i64, total: i64) -> i64 {
@@ -395,6 +1087,7 @@ pub(crate) fn job_to_progress_event(job: &JobInfo) -> JobProgressEvent {
         speed: job.speed,
         eta: job.eta,
         error: job.error.clone(),
+        priority: job.priority,
     }
 }
 
@@ -406,12 +1099,29 @@ pub(crate) fn emit_job_complete_event(app: &AppHandle, job: &JobInfo) {
     let complete = JobCompleteEvent {
         job_id: job.id.clone(),
         file_name: Some(job.file_name.clone()),
-        success: job.status == JobStatus::Completed,
+        success: matches!(job.status, JobStatus::Completed | JobStatus::Skipped),
         error: job.error.clone(),
+        cancel_reason: job.cancel_reason,
     };
     let _ = app.emit("job:complete", complete);
 }
 
+pub(crate) fn emit_archive_progress_event(
+    app: &AppHandle,
+    job_id: &str,
+    current_file: &str,
+    files_completed: usize,
+    files_total: usize,
+) {
+    let payload = ArchiveProgressEvent {
+        job_id: job_id.to_string(),
+        current_file: current_file.to_string(),
+        files_completed,
+        files_total,
+    };
+    let _ = app.emit("job:archive-progress", payload);
+}
+
 pub(crate) fn emit_update_available_event(
     app: &AppHandle,
     version: &str,
@@ -426,6 +1136,112 @@ pub(crate) fn emit_update_available_event(
     let _ = app.emit("update:available", payload);
 }
 
+pub(crate) fn effective_job_concurrency(jobs: &JobRuntime) -> u8 {
+    if jobs.adaptive.enabled {
+        jobs.adaptive
+            .limit
+            .clamp(MIN_JOB_CONCURRENCY, jobs.concurrency)
+    } else {
+        jobs.concurrency
+    }
+}
+
+// Below MIN_BANDWIDTH_LIMIT_BYTES_PER_SEC is raised to it; None lifts the cap.
+// Running transfers pick up the new rate on their next chunk.
+pub(crate) fn set_bandwidth_limit(jobs: &mut JobRuntime, limit: Option<u64>) {
+    let limit = limit.map(|limit| limit.max(MIN_BANDWIDTH_LIMIT_BYTES_PER_SEC));
+    jobs.max_bytes_per_sec = limit;
+    jobs.bandwidth.set_limit(limit);
+}
+
+pub(crate) fn set_adaptive_job_concurrency(jobs: &mut JobRuntime, enabled: bool) {
+    if enabled && !jobs.adaptive.enabled {
+        // Start halfway to the ceiling and let the samples move it from there.
+        jobs.adaptive = AdaptiveConcurrency {
+            enabled: true,
+            limit: jobs.concurrency.div_ceil(2).max(MIN_JOB_CONCURRENCY),
+            ..AdaptiveConcurrency::default()
+        };
+    }
+    jobs.adaptive.enabled = enabled;
+}
+
+// Adaptive heuristic (AIMD-style), evaluated once per sample window:
+// - any failed job in the window halves the limit (errors and throttling
+//   usually mean the endpoint or link is overloaded);
+// - aggregate throughput up by >10% while every slot was busy adds one slot;
+// - throughput down by >25% removes one slot (extra streams are contending);
+// - otherwise the limit holds. The manual setting is always the ceiling.
+pub(crate) fn next_adaptive_limit(
+    current: u8,
+    ceiling: u8,
+    prev_throughput: i64,
+    throughput: i64,
+    failures: u32,
+    saturated: bool,
+) -> u8 {
+    let ceiling = ceiling.max(MIN_JOB_CONCURRENCY);
+    let current = current.clamp(MIN_JOB_CONCURRENCY, ceiling);
+    let next = if failures > 0 {
+        current / 2
+    } else if saturated && throughput * 10 > prev_throughput * 11 {
+        current.saturating_add(1)
+    } else if throughput * 4 < prev_throughput * 3 {
+        current.saturating_sub(1)
+    } else {
+        current
+    };
+    next.clamp(MIN_JOB_CONCURRENCY, ceiling)
+}
+
+// Returns true when the limit grew, so the caller can start queued jobs.
+fn sample_adaptive_concurrency(jobs: &mut JobRuntime) -> bool {
+    if !jobs.adaptive.enabled
+        || jobs.adaptive.last_sample_at.elapsed()
+            < StdDuration::from_millis(ADAPTIVE_SAMPLE_INTERVAL_MS)
+    {
+        return false;
+    }
+
+    let throughput: i64 = jobs
+        .running
+        .iter()
+        .filter_map(|id| jobs.jobs.get(id))
+        .map(|job| job.speed)
+        .sum();
+    let current = effective_job_concurrency(jobs);
+    let saturated = jobs.running.len() >= current as usize && !jobs.queue.is_empty();
+    let next = next_adaptive_limit(
+        current,
+        jobs.concurrency,
+        jobs.adaptive.last_throughput,
+        throughput,
+        jobs.adaptive.failures,
+        saturated,
+    );
+
+    jobs.adaptive.limit = next;
+    jobs.adaptive.last_throughput = throughput;
+    jobs.adaptive.failures = 0;
+    jobs.adaptive.last_sample_at = Instant::now();
+    next > current
+}
+
+// Throttles job:progress per job; the last chunk of a transfer always goes
+// out so the UI never sits at 99%.
+pub(crate) fn should_emit_job_progress(
+    last: Option<Instant>,
+    now: Instant,
+    transferred: i64,
+    total: i64,
+) -> bool {
+    let Some(last) = last else {
+        return true;
+    };
+    (total > 0 && transferred >= total)
+        || now.duration_since(last) >= StdDuration::from_millis(JOB_PROGRESS_EMIT_INTERVAL_MS)
+}
+
 pub(crate) fn update_job_progress(
     app: &AppHandle,
     job_id: &str,
@@ -435,8 +1251,11 @@ pub(crate) fn update_job_progress(
     eta: i64,
 ) {
     let mut snapshot: Option<JobInfo> = None;
+    let mut grew = false;
     let state = app.state::<AppState>();
     if let Ok(mut jobs) = lock_state(&state.jobs) {
+        let now = Instant::now();
+        let last_emitted = jobs.progress_emitted_at.get(job_id).copied();
         if let Some(job) = jobs.jobs.get_mut(job_id) {
             job.bytes_transferred = transferred.max(0);
             if total >= 0 {
@@ -445,12 +1264,21 @@ pub(crate) fn update_job_progress(
             job.percentage = calculate_percentage(job.bytes_transferred, job.bytes_total);
             job.speed = speed.max(0);
             job.eta = eta.max(0);
-            snapshot = Some(job.clone());
+            if should_emit_job_progress(last_emitted, now, job.bytes_transferred, job.bytes_total) {
+                snapshot = Some(job.clone());
+            }
+        }
+        if snapshot.is_some() {
+            jobs.progress_emitted_at.insert(job_id.to_string(), now);
         }
+        grew = sample_adaptive_concurrency(&mut jobs);
     }
     if let Some(job) = snapshot {
         emit_job_progress_event(app, &job);
     }
+    if grew {
+        try_start_queued_jobs(app.clone());
+    }
 }
 
 pub(crate) fn finish_job(
@@ -464,7 +1292,17 @@ pub(crate) fn finish_job(
     let state = app.state::<AppState>();
     if let Ok(mut jobs) = lock_state(&state.jobs) {
         jobs.running.remove(job_id);
+        let task = jobs.running_tasks.remove(job_id);
+        if let Some(task) = task.filter(|_| is_retryable_job_status(status)) {
+            jobs.retry_tasks.insert(task.id.clone(), task);
+        }
         jobs.cancel_flags.remove(job_id);
+        jobs.pause_flags.remove(job_id);
+        jobs.progress_emitted_at.remove(job_id);
+        let cancel_reason = jobs.cancel_reasons.remove(job_id);
+        if status == JobStatus::Failed {
+            jobs.adaptive.failures += 1;
+        }
         if let Some(job) = jobs.jobs.get_mut(job_id) {
             job.status = status;
             if let Some(transferred) = bytes_transferred {
@@ -482,6 +1320,9 @@ pub(crate) fn finish_job(
             }
             job.error = error;
             job.completed_at = Some(now_iso());
+            if status == JobStatus::Cancelled {
+                mark_job_cancelled(job, cancel_reason.unwrap_or(CancelReason::User));
+            }
             snapshot = Some(job.clone());
         }
     }
@@ -490,6 +1331,7 @@ pub(crate) fn finish_job(
         emit_job_complete_event(app, &job);
     }
     persist_job_history_snapshot(app);
+    persist_job_queue_snapshot(app);
 }
 
 pub(crate) fn persist_job_history_snapshot(app: &AppHandle) {
@@ -498,26 +1340,29 @@ pub(crate) fn persist_job_history_snapshot(app: &AppHandle) {
         let Ok(jobs) = lock_state(&state.jobs) else {
             return;
         };
-
-        let mut collected = Vec::new();
-        for id in &jobs.order {
-            let Some(job) = jobs.jobs.get(id) else {
-                continue;
-            };
-            if !is_terminal_job_status(job.status) || jobs.running.contains(id) {
-                continue;
-            }
-            collected.push(job.clone());
-            if collected.len() >= JOB_HISTORY_MAX {
-                break;
-            }
-        }
-        collected
+        job_history_snapshot(&jobs)
     };
 
     let _ = save_job_history_to_disk(&history);
 }
 
+pub(crate) fn job_history_snapshot(jobs: &JobRuntime) -> Vec<JobInfo> {
+    let mut collected = Vec::new();
+    for id in &jobs.order {
+        let Some(job) = jobs.jobs.get(id) else {
+            continue;
+        };
+        if !is_terminal_job_status(job.status) || jobs.running.contains(id) {
+            continue;
+        }
+        collected.push(job.clone());
+        if collected.len() >= JOB_HISTORY_MAX {
+            break;
+        }
+    }
+    collected
+}
+
 pub(crate) fn hydrate_job_history_runtime(app: &AppHandle) {
     let history = load_job_history_from_disk();
     if history.is_empty() {
@@ -528,7 +1373,10 @@ pub(crate) fn hydrate_job_history_runtime(app: &AppHandle) {
     let Ok(mut jobs) = lock_state(&state.jobs) else {
         return;
     };
+    restore_job_history(&mut jobs, history);
+}
 
+pub(crate) fn restore_job_history(jobs: &mut JobRuntime, history: Vec<JobInfo>) {
     for job in history {
         if !is_terminal_job_status(job.status) {
             continue;
@@ -542,6 +1390,126 @@ pub(crate) fn hydrate_job_history_runtime(app: &AppHandle) {
     }
 
     if jobs.order.len() > JOB_HISTORY_MAX {
-        jobs.order.truncate(JOB_HISTORY_MAX);
+        for removed in jobs.order.split_off(JOB_HISTORY_MAX) {
+            jobs.jobs.remove(&removed);
+        }
+    }
+}
+
+// Schedules a job-queue.json write; changes made before it runs share it.
+pub(crate) fn persist_job_queue_snapshot(app: &AppHandle) {
+    {
+        let state = app.state::<AppState>();
+        let Ok(mut jobs) = lock_state(&state.jobs) else {
+            return;
+        };
+        if jobs.queue_frozen || jobs.queue_persist_pending {
+            return;
+        }
+        jobs.queue_persist_pending = true;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(StdDuration::from_millis(JOB_QUEUE_PERSIST_DEBOUNCE_MS)).await;
+        write_job_queue_snapshot(&app);
+    });
+}
+
+// Running and paused jobs (oldest first), then the queue. Open-with jobs are
+// left out: their temp folder is cleared at launch, and opening a file
+// unprompted after a restart would be a surprise.
+fn write_job_queue_snapshot(app: &AppHandle) {
+    let queue: Vec<PersistedJob> = {
+        let state = app.state::<AppState>();
+        let Ok(mut jobs) = lock_state(&state.jobs) else {
+            return;
+        };
+        jobs.queue_persist_pending = false;
+        if jobs.queue_frozen {
+            return;
+        }
+
+        job_queue_snapshot(&jobs)
+    };
+
+    let _ = save_job_queue_to_disk(&queue);
+}
+
+pub(crate) fn job_queue_snapshot(jobs: &JobRuntime) -> Vec<PersistedJob> {
+    let mut tasks: Vec<&JobTask> = jobs
+        .order
+        .iter()
+        .rev()
+        .filter_map(|id| jobs.running_tasks.get(id).or_else(|| jobs.paused.get(id)))
+        .collect();
+    tasks.extend(jobs.queue.iter());
+    tasks
+        .into_iter()
+        .filter(|task| !matches!(task.kind, JobTaskKind::OpenExternal { .. }))
+        .filter_map(|task| {
+            Some(PersistedJob {
+                job: jobs.jobs.get(&task.id)?.clone(),
+                task: task.clone(),
+            })
+        })
+        .collect()
+}
+
+// A job going back in the queue: restored from job-queue.json or retried.
+// Paused jobs keep their progress for `jobs:resume`; anything else, including
+// a job that was running at a crash, starts over.
+pub(crate) fn reset_requeued_job(job: &mut JobInfo) {
+    if job.status != JobStatus::Paused {
+        job.status = JobStatus::Queued;
+        job.bytes_transferred = 0;
+        job.percentage = 0;
+        job.started_at = None;
+    }
+    job.speed = 0;
+    job.eta = 0;
+    job.error = None;
+    job.cancel_reason = None;
+    job.completed_at = None;
+}
+
+// Puts jobs that were pending at the last quit or crash back in the list.
+// They don't start until the vault is unlocked (see `try_start_queued_jobs`).
+pub(crate) fn hydrate_job_queue_runtime(app: &AppHandle) {
+    let restored = load_job_queue_from_disk();
+    if restored.is_empty() {
+        return;
+    }
+
+    let state = app.state::<AppState>();
+    let Ok(mut jobs) = lock_state(&state.jobs) else {
+        return;
+    };
+    restore_job_queue(&mut jobs, restored);
+}
+
+// A clean quit records pending jobs in history as cancelled too; the queue
+// entry wins, so they come back queued rather than as history.
+pub(crate) fn restore_job_queue(jobs: &mut JobRuntime, restored: Vec<PersistedJob>) {
+    for PersistedJob { mut job, task } in restored {
+        if job.id != task.id {
+            continue;
+        }
+        match jobs.jobs.get(&task.id) {
+            Some(existing) if !is_terminal_job_status(existing.status) => continue,
+            Some(_) => jobs.order.retain(|id| id != &task.id),
+            None => {}
+        }
+        reset_requeued_job(&mut job);
+        let id = task.id.clone();
+        jobs.order.insert(0, id.clone());
+        if job.status == JobStatus::Paused {
+            jobs.paused.insert(id.clone(), task);
+        } else {
+            jobs.queue.push_back(task);
+            jobs.cancel_flags
+                .insert(id.clone(), Arc::new(AtomicBool::new(false)));
+        }
+        jobs.jobs.insert(id, job);
     }
 }
@@ -1,12 +1,17 @@
 use aws_sdk_s3::{
     config::{Credentials, Region},
+    operation::head_object::HeadObjectOutput,
     presigning::PresigningConfig,
     primitives::ByteStream,
-    types::{CompletedMultipartUpload, CompletedPart, Delete, ObjectIdentifier},
+    types::{
+        CompletedMultipartUpload, CompletedPart, Delete, ObjectCannedAcl, ObjectIdentifier,
+        ServerSideEncryption, StorageClass,
+    },
     Client as S3Client,
 };
 use chrono::{Duration, Utc};
 use flate2::{write::GzEncoder, Compression};
+use futures_util::future::join_all;
 use keyring::Entry;
 use notify::{recommended_watcher, RecursiveMode, Watcher};
 use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
@@ -20,7 +25,7 @@ use std::{
     path::Component,
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering},
         Arc, Mutex,
     },
     time::{Duration as StdDuration, Instant},
@@ -28,84 +33,444 @@ use std::{
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, State, WindowEvent,
+    AppHandle, Emitter, Manager, WindowEvent,
 };
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
 use tauri_plugin_updater::UpdaterExt;
 use tokio::{
     fs as tokio_fs,
     io::{AsyncReadExt, AsyncWriteExt, BufWriter},
     sync::oneshot,
+    task::JoinSet,
 };
 use url::Url;
 use uuid::Uuid;
 use walkdir::WalkDir;
+use zeroize::{Zeroize, Zeroizing};
 
+mod acl;
+mod aws_import;
+mod bucket_config;
 mod config_paths;
+mod confirmation;
 mod crypto;
+mod diagnostics;
 mod folder_sync;
 mod jobs;
 mod keychain;
+mod network;
+mod notifications;
+mod operations;
 mod persistence;
+mod probe;
+mod restore;
 mod rpc;
 mod rpc_method;
 mod s3;
 mod sync;
+mod tagging;
 mod tray;
 mod updater;
 mod util;
 mod vault;
 
+use acl::*;
+use aws_import::*;
+use bucket_config::*;
+use confirmation::*;
+use diagnostics::*;
 use folder_sync::*;
 use jobs::*;
 use keychain::*;
+use network::*;
+use notifications::*;
+use operations::*;
 use persistence::*;
+use probe::*;
+use restore::*;
 use s3::*;
 use sync::*;
+use tagging::*;
 use tray::{build_tray_menu, handle_tray_menu_action, refresh_tray_menu, show_main_window};
 use updater::*;
 use util::*;
 use vault::*;
 
 use crypto::{
-    decode_base64, decrypt_payload, derive_key, encode_base64, encrypt_payload,
+    decode_base64, decrypt_payload, derive_key, derive_vault_key, encode_base64, encrypt_payload,
     generate_recovery_key, random_bytes,
 };
 
 use config_paths::{
-    favorites_path, folder_sync_records_path, folder_sync_rules_path, job_history_path, vault_path,
+    aws_shared_file_path, bookmarks_path, favorites_path, folder_sync_records_path,
+    folder_sync_rules_path, job_history_path, job_queue_path, legacy_config_dirs,
+    multipart_sidecar_dir, multipart_sidecar_path, nav_state_path, object0_config_dir,
+    partial_download_sidecar_path, settings_path, vault_path,
 };
 use rpc_method::RpcMethod;
 
-const CURRENT_VAULT_VERSION: u8 = 3;
+const CURRENT_VAULT_VERSION: u8 = 4;
 const PBKDF2_ITERATIONS: u32 = 600_000;
+// RFC 9106's second recommended Argon2id setting (64 MiB, 3 passes, 4 lanes).
+const ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+const ARGON2_TIME_COST: u32 = 3;
+const ARGON2_PARALLELISM: u32 = 4;
+// Ceilings for KDF parameters read from a vault file, so a tampered or
+// damaged file can't make unlock allocate gigabytes or spin for hours.
+const PBKDF2_MAX_ITERATIONS: u32 = 10_000_000;
+const ARGON2_MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const ARGON2_MAX_TIME_COST: u32 = 64;
+const ARGON2_MAX_PARALLELISM: u32 = 64;
+const VAULT_KDF_PBKDF2: VaultKdfConfig = VaultKdfConfig::Pbkdf2 {
+    iterations: PBKDF2_ITERATIONS,
+};
+const VAULT_KDF_ARGON2ID: VaultKdfConfig = VaultKdfConfig::Argon2id {
+    memory_kib: ARGON2_MEMORY_KIB,
+    time_cost: ARGON2_TIME_COST,
+    parallelism: ARGON2_PARALLELISM,
+};
 const KEY_BYTES: usize = 32;
 const SALT_BYTES: usize = 32;
 const IV_BYTES: usize = 12;
+const GCM_TAG_BYTES: usize = 16;
+// Prefix of read errors for a damaged file, so unlock can tell it apart from
+// a wrong passphrase.
+const VAULT_CORRUPT: &str = "Vault file is corrupt";
 const RECOVERY_KEY_LENGTH: usize = 24;
 const KEYCHAIN_SERVICE: &str = "com.object0.vault";
 const KEYCHAIN_ACCOUNT: &str = "passphrase";
-const COPY_SOURCE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'/');
+// Schema version written to bookmarks.json.
+const BOOKMARKS_VERSION: u32 = 1;
+// Config files and folders carried over from a legacy config directory.
+const LEGACY_CONFIG_ENTRIES: [&str; 11] = [
+    "vault.enc",
+    "settings.json",
+    "favorites.json",
+    "bookmarks.json",
+    "last-location.json",
+    "folder-sync-rules.json",
+    "folder-sync",
+    "job-history.json",
+    "job-queue.json",
+    "multipart-uploads",
+    "partial-downloads",
+];
+
+// `x-amz-copy-source` is URI-encoded like a SigV4 path: RFC 3986 unreserved
+// characters and `/` stay literal, everything else (space, `+`, `%`, UTF-8
+// bytes) is escaped. Encoding `-._~` too trips some S3-compatible providers.
+const COPY_SOURCE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
 const TRAY_MENU_OPEN: &str = "tray-open";
 const TRAY_MENU_PAUSE_ALL: &str = "tray-pause-all";
 const TRAY_MENU_RESUME_ALL: &str = "tray-resume-all";
+const TRAY_MENU_ABORT_ALL: &str = "tray-abort-all";
 const TRAY_MENU_QUIT: &str = "tray-quit";
 const MULTIPART_THRESHOLD_BYTES: i64 = 5 * 1024 * 1024;
 const MULTIPART_PART_SIZE_BYTES: usize = 8 * 1024 * 1024;
+const MULTIPART_MAX_PARTS: i64 = 10_000;
+// Largest object a single CopyObject call accepts.
+const COPY_OBJECT_MAX_BYTES: i64 = 5 * 1024 * 1024 * 1024;
 const JOB_HISTORY_MAX: usize = 100;
 const JOB_ORDER_MAX: usize = 200;
 const JOB_CANCELLED: &str = "Job cancelled";
+// Job errors starting with this finish the job as skipped, not failed.
+const JOB_SKIPPED_PREFIX: &str = "Skipped: ";
+// Returned by a transfer that stopped for `jobs:pause`; its progress is kept.
+const JOB_PAUSED: &str = "Job paused";
+const CHECKSUM_MISMATCH: &str = "Checksum mismatch";
+// Paused job downloads collect bytes in `<destination>.object0-partial`.
+const PARTIAL_DOWNLOAD_SUFFIX: &str = ".object0-partial";
+const OPERATION_CANCELLED: &str = "Operation cancelled";
+// Error prefix for uploads refused because the key exists and overwrite is off.
+const JOB_ALREADY_EXISTS: &str = "AlreadyExists";
+// Error prefix for ephemeral profiles whose secret hasn't been entered yet.
+const PROFILE_NEEDS_CREDENTIALS: &str = "NeedsCredentials";
+// Error prefix for uploads the provider refused without a checksum.
+const CHECKSUM_REQUIRED: &str = "ChecksumRequired";
+// Error prefix for accelerated requests to a bucket without acceleration.
+const ACCELERATE_NOT_CONFIGURED: &str = "AccelerateNotConfigured";
 const S3_LIST_MAX_KEYS: i32 = 1000;
+// Storage classes whose objects must be restored before they can be read.
+const ARCHIVED_STORAGE_CLASSES: [&str; 2] = ["GLACIER", "DEEP_ARCHIVE"];
+// Retrieval tiers for objects:restore, fastest first. DEEP_ARCHIVE has no
+// Expedited tier; S3 rejects it there.
+const RESTORE_TIERS: [&str; 3] = ["Expedited", "Standard", "Bulk"];
+// Restored copies are billed per day, so a typo shouldn't keep one for years.
+const MAX_RESTORE_DAYS: i32 = 365;
+// objects:list with `fetchAll` stops after this many objects + prefixes.
+const OBJECT_LIST_FETCH_ALL_MAX_ENTRIES: usize = 10_000;
+// DeleteObjects accepts at most this many keys per request.
+const S3_DELETE_MAX_KEYS: usize = 1000;
+// Per-key delete failures spelled out in a job error before "+N more".
+const DELETE_FAILURES_REPORTED: usize = 5;
+const CONFIRMATION_TOKEN_TTL_SECS: u64 = 120;
+const BUCKET_CORS_MAX_RULES: usize = 100;
+const CORS_ALLOWED_METHODS: &[&str] = &["GET", "PUT", "POST", "DELETE", "HEAD"];
+const BUCKET_LIFECYCLE_MAX_RULES: usize = 1000;
+const LIFECYCLE_RULE_ID_MAX_LEN: usize = 255;
+const LIFECYCLE_STORAGE_CLASSES: &[&str] = &[
+    "STANDARD_IA",
+    "ONEZONE_IA",
+    "INTELLIGENT_TIERING",
+    "GLACIER_IR",
+    "GLACIER",
+    "DEEP_ARCHIVE",
+];
+// Storage classes an upload may ask for; absent means the bucket default.
+const OBJECT_STORAGE_CLASSES: &[&str] = &[
+    "STANDARD",
+    "REDUCED_REDUNDANCY",
+    "STANDARD_IA",
+    "ONEZONE_IA",
+    "INTELLIGENT_TIERING",
+    "GLACIER_IR",
+    "GLACIER",
+    "DEEP_ARCHIVE",
+    "EXPRESS_ONEZONE",
+];
+// Server-side encryption an upload can ask for.
+const UPLOAD_SSE_ALGORITHMS: &[&str] = &["AES256", "aws:kms"];
+const OBJECT_CANNED_ACLS: &[&str] = &[
+    "private",
+    "public-read",
+    "public-read-write",
+    "authenticated-read",
+    "aws-exec-read",
+    "bucket-owner-read",
+    "bucket-owner-full-control",
+];
+// S3's per-object tagging limits.
+const MAX_OBJECT_TAGS: usize = 10;
+const MAX_OBJECT_TAG_KEY_CHARS: usize = 128;
+const MAX_OBJECT_TAG_VALUE_CHARS: usize = 256;
+// Content types guessed from the key's extension when an upload names none.
+const CONTENT_TYPES_BY_EXTENSION: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("md", "text/markdown"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("wasm", "application/wasm"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+];
+const ACL_PERMISSIONS: &[&str] = &["FULL_CONTROL", "READ", "READ_ACP", "WRITE", "WRITE_ACP"];
+const RPC_BATCH_MAX_CALLS: usize = 100;
 const FOLDER_SYNC_MIN_POLL_MS: i64 = 250;
 const FOLDER_SYNC_MAX_POLL_MS: i64 = 86_400_000;
+// Adaptive polling: remotes with at least this many objects back off on quiet
+// passes, never polling faster than `FOLDER_SYNC_POLL_MS_PER_OBJECT` per listed
+// object, up to the rule's ceiling (default below).
+const FOLDER_SYNC_ADAPTIVE_MIN_OBJECTS: usize = 1_000;
+const FOLDER_SYNC_POLL_MS_PER_OBJECT: i64 = 1;
+const FOLDER_SYNC_DEFAULT_MAX_POLL_MS: i64 = 600_000;
+const FOLDER_SYNC_DEFAULT_CONCURRENCY: u8 = 4;
+const FOLDER_SYNC_MAX_CONCURRENCY: u8 = 16;
+// A failed watcher is rebuilt with exponential backoff from the base delay
+// (1s, 2s, 4s, ...) before the rule is stopped with an error.
+const FOLDER_SYNC_WATCHER_MAX_RETRIES: u32 = 5;
+const FOLDER_SYNC_WATCHER_RETRY_BASE_MS: u64 = 1_000;
+// A scheduled rule re-checks the clock this often while it waits, so a
+// suspend or clock change doesn't push its run back by hours.
+const FOLDER_SYNC_SCHEDULE_CHECK_SECS: u64 = 60;
+const DEFAULT_CONFLICT_RENAME_TEMPLATE: &str = "{name}.conflict-{timestamp}{ext}";
+// Safe-delete rules move deleted files to `<root>/.object0-trash/<stamp>/` and
+// copy deleted objects under `<bucket prefix>.object0-trash/<stamp>/` first.
+// Neither trash is ever synced.
+const FOLDER_SYNC_TRASH_DIR: &str = ".object0-trash";
+const FOLDER_SYNC_TRASH_STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+const FOLDER_SYNC_TRASH_DEFAULT_RETENTION_DAYS: u32 = 30;
+const NAME_TEMPLATE_TOKENS: [&str; 4] = ["name", "ext", "timestamp", "host"];
+const KEY_TRANSFORM_TOKENS: [&str; 2] = ["host", "date"];
+const FOLDER_SYNC_RECORD_FLUSH_EVERY: usize = 500;
+const FOLDER_SYNC_RECORD_FLUSH_INTERVAL_SECS: u64 = 10;
+// Rule export file format (folder-sync:export-rules / import-rules).
+const FOLDER_SYNC_EXPORT_VERSION: u32 = 1;
+const FOLDER_SYNC_EXPORT_OMITTED_FIELDS: &[&str] = &[
+    "id",
+    "enabled",
+    "createdAt",
+    "lastSyncAt",
+    "lastSyncStatus",
+    "lastSyncError",
+];
+// Folder listings (and HEADs) in flight while cross-bucket and archive
+// requests expand their selection; `expandConcurrency` overrides the default.
+const KEY_EXPAND_DEFAULT_CONCURRENCY: usize = 4;
+const KEY_EXPAND_MAX_CONCURRENCY: usize = 16;
 const MIN_JOB_CONCURRENCY: u8 = 1;
 const MAX_JOB_CONCURRENCY: u8 = 10;
+const DEFAULT_JOB_CONCURRENCY: u8 = 3;
+// Lowest cap `jobs:set-bandwidth-limit` accepts; anything smaller stalls
+// multipart parts for minutes between cancel checks.
+const MIN_BANDWIDTH_LIMIT_BYTES_PER_SEC: u64 = 64 * 1024;
+// Longest single sleep while throttled, so cancel and pause stay responsive.
+const BANDWIDTH_THROTTLE_SLICE_MS: u64 = 250;
+// Schema version written to settings.json; files without one are version 0.
+const SETTINGS_VERSION: u32 = 1;
+// Auto-lock: the idle check runs this often, and shorter timeouts are refused.
+const VAULT_AUTO_LOCK_CHECK_SECS: u64 = 30;
+const VAULT_AUTO_LOCK_MIN_SECS: u64 = 60;
+const ADAPTIVE_SAMPLE_INTERVAL_MS: u64 = 3_000;
+// How often a single-PUT upload reports bytes sent so far.
+const UPLOAD_PROGRESS_TICK_MS: u64 = 200;
+// Minimum gap between job:progress events for one job (~7/sec). JobInfo
+// itself is updated on every chunk; final and terminal updates always emit.
+const JOB_PROGRESS_EMIT_INTERVAL_MS: u64 = 150;
+// job-queue.json is rewritten at most this often, so enqueuing a large batch
+// writes it once instead of once per job.
+const JOB_QUEUE_PERSIST_DEBOUNCE_MS: u64 = 500;
 const MIN_SHARE_TTL_SECS: i64 = 1;
 const MAX_SHARE_TTL_SECS: i64 = 604_800;
+// Presigning is normally local, but credential/endpoint resolution can hang.
+const PRESIGN_TIMEOUT_SECS: u64 = 15;
+// share:generate with `validate` fetches one byte of the presigned URL.
+const SHARE_VALIDATE_TIMEOUT_SECS: u64 = 10;
+// objects:preview reads at most this much of the object, and decompresses a
+// gzip head to at most `PREVIEW_MAX_DECOMPRESSED_BYTES` (zip-bomb guard).
+const PREVIEW_DEFAULT_BYTES: i64 = 64 * 1024;
+const PREVIEW_MAX_BYTES: i64 = 1024 * 1024;
+const PREVIEW_MAX_DECOMPRESSED_BYTES: usize = 4 * 1024 * 1024;
+// objects:get-text reads whole small objects (e.g. for the clipboard);
+// anything larger comes back truncated.
+const GET_TEXT_MAX_BYTES: i64 = 1024 * 1024;
+// objects:open-external downloads into a per-call folder under the temp dir,
+// removed after the delay (the default app may still be reading it) or on
+// the next launch. Larger objects need a confirmation token first.
+const OPEN_EXTERNAL_DIR_NAME: &str = "object0-open";
+const OPEN_EXTERNAL_CLEANUP_DELAY_SECS: u64 = 60 * 60;
+const OPEN_EXTERNAL_CONFIRM_BYTES: i64 = 100 * 1024 * 1024;
+// Launching these would run whatever was in the bucket; objects:open-external
+// refuses them (they can still be downloaded).
+const OPEN_EXTERNAL_BLOCKED_EXTENSIONS: &[&str] = &[
+    "app", "apk", "appimage", "bat", "bash", "cmd", "com", "command", "cpl", "csh", "deb",
+    "desktop", "dll", "dmg", "exe", "hta", "inf", "ins", "jar", "js", "jse", "ksh", "lnk", "msc",
+    "msi", "msp", "pif", "pkg", "ps1", "psm1", "reg", "rpm", "run", "scf", "scr", "sh", "so",
+    "url", "vb", "vbe", "vbs", "ws", "wsc", "wsf", "wsh", "zsh",
+];
+// objects:stream-range pages through large objects (e.g. logs) as events;
+// one call sends at most the total cap, in chunks of at most the chunk cap.
+const STREAM_RANGE_DEFAULT_CHUNK_BYTES: usize = 64 * 1024;
+const STREAM_RANGE_MAX_CHUNK_BYTES: usize = 1024 * 1024;
+const STREAM_RANGE_MAX_TOTAL_BYTES: i64 = 64 * 1024 * 1024;
+const STREAM_RANGE_TIMEOUT_SECS: u64 = 10 * 60;
+// objects:diff-prefixes lists both sides in full, which can take a while.
+const PREFIX_DIFF_TIMEOUT_SECS: u64 = 30 * 60;
+// objects:search walks the whole subtree under its prefix.
+const OBJECT_SEARCH_DEFAULT_MAX_RESULTS: usize = 1000;
+const OBJECT_SEARCH_TIMEOUT_SECS: u64 = 30 * 60;
+// Zip entries smaller than this are stored: there's too little repetition
+// for deflate to save much more than its own block framing, and a stored
+// entry skips the compressor entirely.
+const ZIP_STORED_MAX_BYTES: i64 = 256;
+// Already-compressed formats are stored at any size; deflating them again
+// costs time and usually grows them slightly.
+const ZIP_STORED_CONTENT_TYPES: &[&str] = &[
+    "application/zip",
+    "application/gzip",
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "audio/mpeg",
+    "audio/ogg",
+    "video/mp4",
+    "video/webm",
+    "video/quicktime",
+    "font/woff",
+    "font/woff2",
+];
 const UPDATE_CHECK_INITIAL_DELAY_SECS: u64 = 5;
 const UPDATE_CHECK_INTERVAL_SECS: u64 = 30 * 60;
+const METERED_NETWORK_POLL_SECS: u64 = 30;
 const DEFAULT_UPDATER_ENDPOINT: &str =
     "https://github.com/sayedhfatimi/object0/releases/latest/download/latest.json";
 const DEFAULT_UPDATER_CHANNEL: &str = "stable";
+// Connect timeout for the updater reachability probe in app:self-test.
+const SELF_TEST_NETWORK_TIMEOUT_SECS: u64 = 5;
+const PROVIDER_PROBE_FEATURES: &[&str] = &[
+    "multipart",
+    "versioning",
+    "tagging",
+    "presign",
+    "object-lock",
+    "checksums",
+];
+// Temporary objects written by provider:probe; always deleted afterwards.
+const PROVIDER_PROBE_KEY_PREFIX: &str = ".object0-probe-";
+// Used when a profile has no region; also the SigV4 region most
+// S3-compatible providers expect.
+const DEFAULT_AWS_REGION: &str = "us-east-1";
+// Public AWS regions with S3, for the region picker and profile validation.
+const AWS_REGIONS: &[&str] = &[
+    "af-south-1",
+    "ap-east-1",
+    "ap-east-2",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-south-1",
+    "ap-south-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-southeast-3",
+    "ap-southeast-4",
+    "ap-southeast-5",
+    "ap-southeast-7",
+    "ca-central-1",
+    "ca-west-1",
+    "cn-north-1",
+    "cn-northwest-1",
+    "eu-central-1",
+    "eu-central-2",
+    "eu-north-1",
+    "eu-south-1",
+    "eu-south-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "il-central-1",
+    "me-central-1",
+    "me-south-1",
+    "mx-central-1",
+    "sa-east-1",
+    "us-east-1",
+    "us-east-2",
+    "us-gov-east-1",
+    "us-gov-west-1",
+    "us-west-1",
+    "us-west-2",
+];
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -144,10 +509,60 @@ struct VaultFileV3 {
     recovery: Option<VaultRecoveryBlob>,
 }
 
+// V4 records how the passphrase key was derived. The recovery blob still uses
+// PBKDF2: recovery keys are random, so a slow KDF adds little there.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultFileV4 {
+    version: u8,
+    kdf: VaultKdf,
+    kdf_params: VaultKdfParams,
+    salt: String,
+    iv: String,
+    data: String,
+    recovery: Option<VaultRecoveryBlob>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum VaultKdf {
+    Pbkdf2,
+    Argon2id,
+}
+
+// `iterations` for PBKDF2; memory (KiB), time and parallelism for Argon2id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultKdfParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iterations: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memory_kib: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_cost: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parallelism: Option<u32>,
+}
+
+// The resolved KDF behind a passphrase key, kept with the key so saves
+// describe it correctly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VaultKdfConfig {
+    Pbkdf2 {
+        iterations: u32,
+    },
+    Argon2id {
+        memory_kib: u32,
+        time_cost: u32,
+        parallelism: u32,
+    },
+}
+
 enum VaultFileDisk {
     V1(VaultFileV1),
     V2(VaultFileV2),
     V3(VaultFileV3),
+    V4(VaultFileV4),
 }
 
 // ── Closed-set domain enums (serde-renamed to preserve the existing wire format
@@ -181,6 +596,66 @@ enum ConflictResolution {
     KeepBoth,
 }
 
+// What sync and folder sync do with objects in an archived storage class:
+// skip them (reported as "skipped: archived") or attempt the copy anyway,
+// which fails or triggers a restore unless the object was already restored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ArchivedObjectPolicy {
+    #[default]
+    Skip,
+    Attempt,
+}
+
+// Folder-sync downloads whose keys differ only by case from another file on a
+// case-insensitive local filesystem: skip them, or download them under a
+// suffixed name (see `case_collision_copy_name`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CaseCollisionPolicy {
+    #[default]
+    Skip,
+    Rename,
+}
+
+// How folder sync decides that a file both sides have, but which has no sync
+// record yet, is already in sync: equal size; equal size with the local copy
+// no newer than the object; or the local file hashed against the ETag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FolderSyncCompareMode {
+    #[default]
+    Size,
+    Etag,
+    #[serde(rename = "mtime-size")]
+    MtimeSize,
+}
+
+// What a download does when its local file already exists: replace it, leave
+// it and finish the job as skipped, or write "<name> (n)<ext>" beside it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DownloadExistingPolicy {
+    #[default]
+    Overwrite,
+    Skip,
+    Rename,
+}
+
+// How folder-sync scans treat symlinks under the local root.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SymlinkPolicy {
+    // Left out, as before the setting existed.
+    #[default]
+    Skip,
+    // Symlinked files and folders are walked like real ones.
+    Follow,
+    // Symlinked files sync as copies of their targets; symlinked folders
+    // aren't entered.
+    CopyTarget,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "lowercase")]
 enum FolderSyncStatus {
@@ -189,6 +664,13 @@ enum FolderSyncStatus {
     Watching,
     Error,
     Paused,
+    // Rule in dry-run mode: passes compute and report, never transfer.
+    #[serde(rename = "dry-run")]
+    DryRun,
+    // Manual-only rule waiting for sync-now.
+    Manual,
+    // Scheduled rule waiting for its next run.
+    Scheduled,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
@@ -218,10 +700,58 @@ struct Profile {
     endpoint: Option<String>,
     region: Option<String>,
     default_bucket: Option<String>,
+    // Send `x-amz-request-payer: requester` on every request.
+    #[serde(default)]
+    requester_pays: bool,
+    // The secret is never written to the vault; it's re-entered each session
+    // with `profile:provide-credentials`.
+    #[serde(default)]
+    ephemeral: bool,
+    // Wrapped around every key uploaded with this profile. Folder-sync rules
+    // have their own, so this doesn't move their remote files.
+    #[serde(default)]
+    key_transform: Option<KeyTransform>,
+    // Attach a CRC32 checksum to every upload, whatever the SDK default is.
+    #[serde(default)]
+    require_checksums: bool,
+    // Only send checksums where the operation requires one, for S3-compatible
+    // providers that reject the SDK's default ones.
+    #[serde(default)]
+    disable_checksums: bool,
+    // AWS only: S3 Transfer Acceleration (the bucket must have it enabled)
+    // and dual-stack (IPv6) endpoints.
+    #[serde(default)]
+    use_accelerate: bool,
+    #[serde(default)]
+    use_dualstack: bool,
+    // Default server-side encryption for uploads ("AES256" or "aws:kms"); an
+    // upload that names its own replaces both.
+    #[serde(default)]
+    sse: Option<String>,
+    #[serde(default)]
+    sse_kms_key_id: Option<String>,
     created_at: String,
     updated_at: String,
 }
 
+// Text added around uploaded keys, e.g. a `{host}/{date}/` prefix to file
+// backups by machine and day. `{host}` is this machine's hostname and
+// `{date}` today's UTC `YYYY-MM-DD`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct KeyTransform {
+    prefix: String,
+    suffix: String,
+}
+
+// A `KeyTransform` with its tokens filled in, resolved once per call or sync
+// pass so every key in it agrees on the date.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ResolvedKeyTransform {
+    prefix: String,
+    suffix: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 struct VaultData {
     profiles: Vec<Profile>,
@@ -236,6 +766,16 @@ struct ProfileInfo {
     endpoint: Option<String>,
     region: Option<String>,
     default_bucket: Option<String>,
+    requester_pays: bool,
+    ephemeral: bool,
+    key_transform: Option<KeyTransform>,
+    require_checksums: bool,
+    disable_checksums: bool,
+    use_accelerate: bool,
+    use_dualstack: bool,
+    sse: Option<String>,
+    sse_kms_key_id: Option<String>,
+    needs_credentials: bool,
     created_at: String,
     updated_at: String,
 }
@@ -262,6 +802,20 @@ enum JobStatus {
     Completed,
     Failed,
     Cancelled,
+    // Finished without doing anything, e.g. a download onto an existing file.
+    Skipped,
+    // Stopped by `jobs:pause`; `jobs:resume` queues it again.
+    Paused,
+}
+
+// Why a job was cancelled; spelled out in `JobInfo.error`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum CancelReason {
+    User,
+    VaultLocked,
+    AbortAll,
+    Shutdown,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -279,6 +833,11 @@ struct JobInfo {
     speed: i64,
     eta: i64,
     error: Option<String>,
+    #[serde(default)]
+    cancel_reason: Option<CancelReason>,
+    // Mirrors `JobTask.priority`.
+    #[serde(default)]
+    priority: i64,
     created_at: String,
     started_at: Option<String>,
     completed_at: Option<String>,
@@ -298,6 +857,17 @@ struct JobProgressEvent {
     speed: i64,
     eta: i64,
     error: Option<String>,
+    priority: i64,
+}
+
+// `job:archive-progress`, alongside the byte counts in `job:progress`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveProgressEvent {
+    job_id: String,
+    current_file: String,
+    files_completed: usize,
+    files_total: usize,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -307,21 +877,52 @@ struct JobCompleteEvent {
     file_name: Option<String>,
     success: bool,
     error: Option<String>,
+    cancel_reason: Option<CancelReason>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 enum JobTaskKind {
     Upload {
         profile_id: String,
         bucket: String,
         key: String,
         local_path: String,
+        overwrite: bool,
+        // Canned ACL, already validated.
+        acl: Option<String>,
+        // None guesses from the key's extension when the job runs.
+        content_type: Option<String>,
+        cache_control: Option<String>,
+        metadata: HashMap<String, String>,
+        // Already validated; None uses the bucket default.
+        storage_class: Option<String>,
+        // Already validated; None uses the profile's default encryption.
+        sse: Option<String>,
+        sse_kms_key_id: Option<String>,
     },
     Download {
         profile_id: String,
         bucket: String,
         key: String,
         local_path: String,
+        on_existing: DownloadExistingPolicy,
+        verify_checksum: bool,
+        version_id: Option<String>,
+    },
+    // Download to a temp file, then hand it to the OS default app.
+    OpenExternal {
+        profile_id: String,
+        bucket: String,
+        key: String,
+        local_path: String,
+    },
+    UploadFolderArchive {
+        profile_id: String,
+        bucket: String,
+        key: String,
+        local_dir: String,
+        exclude_patterns: Vec<String>,
     },
     Copy {
         source_profile_id: String,
@@ -344,39 +945,134 @@ enum JobTaskKind {
         bucket: String,
         keys: Vec<String>,
     },
+    // Everything under `prefix`, listed when the job starts.
+    DeletePrefix {
+        profile_id: String,
+        bucket: String,
+        prefix: String,
+    },
     Archive {
         profile_id: String,
         bucket: String,
         keys: Vec<String>,
         common_prefix: String,
         destination_path: String,
+        #[serde(default)]
+        format: ArchiveFormat,
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct JobTask {
     id: String,
     kind: JobTaskKind,
+    // Higher starts first; equal priorities keep queue order. 0 by default.
+    #[serde(default)]
+    priority: i64,
+}
+
+// An unfinished job in job-queue.json: its row in the jobs list and the task
+// to run again after a restart.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedJob {
+    job: JobInfo,
+    task: JobTask,
 }
 
 struct JobRuntime {
+    // Manual limit; with adaptive mode on it is the ceiling for `adaptive.limit`.
     concurrency: u8,
+    adaptive: AdaptiveConcurrency,
     queue: VecDeque<JobTask>,
     running: HashSet<String>,
     jobs: HashMap<String, JobInfo>,
     order: Vec<String>,
     cancel_flags: HashMap<String, Arc<AtomicBool>>,
+    // Running uploads and downloads only; other job types can't pause.
+    pause_flags: HashMap<String, Arc<AtomicBool>>,
+    // Paused jobs, kept so `jobs:resume` can queue them again.
+    paused: HashMap<String, JobTask>,
+    // Copies of running tasks, so job-queue.json can requeue them after a crash.
+    running_tasks: HashMap<String, JobTask>,
+    // Tasks of failed and cancelled jobs still in the list, for `jobs:retry`.
+    retry_tasks: HashMap<String, JobTask>,
+    // Set on shutdown: job-queue.json keeps what was pending at quit.
+    queue_frozen: bool,
+    // A job-queue.json write is scheduled; further changes ride along with it.
+    queue_persist_pending: bool,
+    // When each running job last emitted a progress event.
+    progress_emitted_at: HashMap<String, Instant>,
+    // Set when a running job is told to stop; applied by `finish_job`.
+    cancel_reasons: HashMap<String, CancelReason>,
+    // Cap on the combined rate of all running uploads and downloads.
+    max_bytes_per_sec: Option<u64>,
+    // Shared with the transfer loops; mirrors `max_bytes_per_sec`.
+    bandwidth: Arc<BandwidthLimiter>,
+}
+
+// Global transfer rate cap. Each transfer holds a `BandwidthLease` while it
+// runs and gets an even share of the cap, so one large job can't starve the
+// others. A limit of 0 means unlimited.
+#[derive(Debug, Default)]
+struct BandwidthLimiter {
+    max_bytes_per_sec: AtomicU64,
+    active_transfers: AtomicUsize,
+}
+
+// One transfer's token bucket, refilled at its share of the global cap.
+#[derive(Debug)]
+struct BandwidthLease {
+    limiter: Arc<BandwidthLimiter>,
+    // Bytes the transfer may still move before it has to wait; negative
+    // while it is ahead of its share.
+    allowance: f64,
+    refilled_at: Instant,
+}
+
+// Opt-in throughput-driven running-job limit. Sampled from job progress
+// updates every ADAPTIVE_SAMPLE_INTERVAL_MS; see `next_adaptive_limit`.
+struct AdaptiveConcurrency {
+    enabled: bool,
+    limit: u8,
+    last_sample_at: Instant,
+    last_throughput: i64,
+    failures: u32,
+}
+
+impl Default for AdaptiveConcurrency {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            limit: MIN_JOB_CONCURRENCY,
+            last_sample_at: Instant::now(),
+            last_throughput: 0,
+            failures: 0,
+        }
+    }
 }
 
 impl Default for JobRuntime {
     fn default() -> Self {
         Self {
-            concurrency: 3,
+            concurrency: DEFAULT_JOB_CONCURRENCY,
+            adaptive: AdaptiveConcurrency::default(),
             queue: VecDeque::new(),
             running: HashSet::new(),
             jobs: HashMap::new(),
             order: Vec::new(),
             cancel_flags: HashMap::new(),
+            pause_flags: HashMap::new(),
+            paused: HashMap::new(),
+            running_tasks: HashMap::new(),
+            retry_tasks: HashMap::new(),
+            queue_frozen: false,
+            queue_persist_pending: false,
+            progress_emitted_at: HashMap::new(),
+            cancel_reasons: HashMap::new(),
+            max_bytes_per_sec: None,
+            bandwidth: Arc::new(BandwidthLimiter::default()),
         }
     }
 }
@@ -393,13 +1089,97 @@ struct FolderSyncRuleRecord {
     enabled: bool,
     conflict_resolution: ConflictResolution,
     poll_interval_ms: i64,
+    // Ceiling for adaptive polling on large, quiet remotes. Defaults to
+    // `FOLDER_SYNC_DEFAULT_MAX_POLL_MS`; set equal to `poll_interval_ms` to
+    // always poll at the fixed interval.
+    #[serde(default)]
+    max_poll_interval_ms: Option<i64>,
     exclude_patterns: Vec<String>,
+    // Max uploads/downloads in flight during a single pass. Rules saved before
+    // this field existed deserialize to the default.
+    #[serde(default = "default_folder_sync_concurrency")]
+    sync_concurrency: u8,
+    // Name given to the local copy set aside by keep-both conflict resolution.
+    #[serde(default = "default_conflict_rename_template")]
+    conflict_rename_template: String,
+    // Propagate deletions to each side. Either can be turned off to sync
+    // additions and edits without ever deleting there.
+    #[serde(default = "default_true")]
+    allow_delete_local: bool,
+    #[serde(default = "default_true")]
+    allow_delete_remote: bool,
+    // Deletions go to the trash (FOLDER_SYNC_TRASH_DIR) instead of being
+    // permanent; folder-sync:empty-trash purges it.
+    #[serde(default)]
+    safe_delete: bool,
+    // Desktop notification after a pass that changed something or hit a
+    // conflict (subject to the global `notificationsEnabled` setting).
+    #[serde(default)]
+    notify_on_sync: bool,
+    // Passes only report what they would do: no transfers, deletes or
+    // record writes. For observing a new rule before trusting it.
+    #[serde(default)]
+    dry_run: bool,
+    // Remote keys are `<prefix><bucket prefix><path><suffix>`. `{host}` is
+    // resolved when the rule is saved; `{date}` isn't allowed: the remote side
+    // would appear to vanish every day.
+    #[serde(default)]
+    key_transform: Option<KeyTransform>,
+    // Remote objects in GLACIER/DEEP_ARCHIVE aren't downloaded by default.
+    #[serde(default)]
+    archived_objects: ArchivedObjectPolicy,
+    #[serde(default)]
+    case_collisions: CaseCollisionPolicy,
+    #[serde(default)]
+    compare_mode: FolderSyncCompareMode,
+    // Passes only run on `folder-sync:sync-now`; the loop never polls. The
+    // watcher is off unless `manual_watch` keeps it for last-change tracking.
+    #[serde(default)]
+    manual_only: bool,
+    #[serde(default)]
+    manual_watch: bool,
+    // Cron expression in local time (five fields, or `@daily` etc.). Passes
+    // run only then instead of polling, and the watcher is off.
+    #[serde(default)]
+    schedule: Option<String>,
+    #[serde(default)]
+    symlink_policy: SymlinkPolicy,
     last_sync_at: Option<String>,
     last_sync_status: Option<String>,
     last_sync_error: Option<String>,
     created_at: String,
 }
 
+fn default_folder_sync_concurrency() -> u8 {
+    FOLDER_SYNC_DEFAULT_CONCURRENCY
+}
+
+fn default_conflict_rename_template() -> String {
+    DEFAULT_CONFLICT_RENAME_TEMPLATE.to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// What the poll loop needs from a successful pass to pick the next interval.
+#[derive(Clone, Copy, Debug, Default)]
+struct FolderSyncPassOutcome {
+    remote_objects: usize,
+    changed: bool,
+}
+
+// What a folder-sync pass actually did, for the completion notification.
+#[derive(Clone, Copy, Debug, Default)]
+struct FolderSyncPassCounts {
+    uploaded: usize,
+    downloaded: usize,
+    deleted_local: usize,
+    deleted_remote: usize,
+    conflicts: usize,
+    failed: usize,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FolderSyncFileRecord {
@@ -410,6 +1190,10 @@ struct FolderSyncFileRecord {
     remote_last_modified: String,
     remote_size: i64,
     synced_at: String,
+    // The renamed local copy a case-colliding key was synced through, so the
+    // pair still resolves after its case twin is gone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    local_relative_path: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -421,6 +1205,11 @@ struct FolderSyncStateRecord {
     last_change: Option<String>,
     current_file: Option<String>,
     progress: Option<FolderSyncProgress>,
+    // Interval the poll loop is actually waiting between passes; above the
+    // rule's `pollIntervalMs` when adaptive polling has backed off.
+    effective_poll_interval_ms: Option<i64>,
+    // Progress of a folder-sync:dry-run; these records are emitted, never stored.
+    dry_run: bool,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -443,6 +1232,45 @@ struct FolderSyncConflictEventPayload {
     remote_last_modified: String,
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncWarningEventPayload<'a> {
+    rule_id: &'a str,
+    kind: &'static str,
+    paths: &'a [String],
+    message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncDryRunEventPayload<'a> {
+    rule_id: &'a str,
+    at: String,
+    diff: &'a FolderSyncDiffRecord,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectChunkEventPayload<'a> {
+    operation_id: &'a str,
+    // Absolute byte offset of `data` within the object.
+    offset: i64,
+    data: String, // base64
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectChunkDoneEventPayload {
+    operation_id: String,
+    start: i64,
+    bytes_sent: i64,
+    // Full object size; `None` if the stream failed before it was known.
+    size: Option<i64>,
+    eof: bool,
+    cancelled: bool,
+    error: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct FolderSyncErrorEventPayload {
@@ -469,6 +1297,31 @@ struct FolderSyncDiffEntryRecord {
     remote_size: Option<i64>,
     remote_last_modified: Option<String>,
     remote_etag: Option<String>,
+    // Local file when it isn't `relative_path`: the renamed copy of a key
+    // that collides by case with another file.
+    local_relative_path: Option<String>,
+}
+
+// A folder-sync:status-detail entry: the pending change plus what the last
+// successful sync recorded for the file.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncStatusDetailEntry {
+    #[serde(flatten)]
+    entry: FolderSyncDiffEntryRecord,
+    // Which side moved since the last sync: "local", "remote", "both", or
+    // "untracked" when there is no sync record for the file.
+    origin: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_synced_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    synced_local_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    synced_local_mtime: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    synced_remote_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    synced_remote_etag: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -479,7 +1332,264 @@ struct FolderSyncDiffRecord {
     delete_local: Vec<FolderSyncDiffEntryRecord>,
     delete_remote: Vec<FolderSyncDiffEntryRecord>,
     conflicts: Vec<FolderSyncDiffEntryRecord>,
+    // Downloads left out because the remote object is archived or its key
+    // collides by case with another file.
+    skipped: Vec<FolderSyncDiffEntryRecord>,
+    // Paths that differ only by case on a case-insensitive filesystem; the
+    // first of each set keeps its name.
+    case_collisions: Vec<Vec<String>>,
+    // Symlinks under the local root that loop back into it (follow policy).
+    symlink_cycles: Vec<String>,
     unchanged: i64,
+    // Remote objects in scope for the rule (after prefix and excludes).
+    #[serde(skip)]
+    remote_objects: usize,
+    // Untracked pairs whose contents match by ETag; recorded as synced so
+    // later passes don't hash them again.
+    #[serde(skip)]
+    baseline: Vec<FolderSyncFileRecord>,
+}
+
+// folder-sync:dry-run result: the diff after conflict resolution, plus each
+// entry's outcome in the order a live pass would apply it.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncDryRunReport {
+    diff: FolderSyncDiffRecord,
+    actions: Vec<FolderSyncResolvedAction>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncResolvedAction {
+    relative_path: String,
+    action: String,
+    reason: String,
+    // Local file or s3:// URI the action would write or remove; None for
+    // conflicts and skips.
+    target: Option<String>,
+}
+
+// Upload body that counts bytes as the SDK reads them from the wrapped body,
+// pausing between frames while the transfer is over its bandwidth share.
+struct UploadProgressBody {
+    inner: aws_sdk_s3::primitives::SdkBody,
+    sent: Arc<AtomicI64>,
+    bandwidth: Option<BandwidthLease>,
+    // Pending throttle delay; the next frame isn't pulled until it elapses.
+    throttle: Option<std::pin::Pin<Box<tokio::time::Sleep>>>,
+}
+
+// A ranged read of an object's first bytes (objects:preview, objects:get-text).
+struct ObjectHead {
+    bytes: Vec<u8>,
+    // Full object size, not just what was read.
+    size: i64,
+    content_type: String,
+    gzip_encoded: bool,
+}
+
+// S3 failures with a distinct, actionable message (see `classify_s3_error`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum S3ErrorKind {
+    AccessDenied,
+    NoSuchKey,
+    NoSuchBucket,
+    SlowDown,
+    InvalidObjectState,
+    // NotImplemented / MethodNotAllowed from S3-compatible providers.
+    Unsupported,
+    // The upload was rejected for lacking a Content-MD5 / x-amz-checksum-*
+    // header, e.g. an Object Lock bucket.
+    ChecksumRequired,
+    // The profile uses Transfer Acceleration but the bucket doesn't have it.
+    AccelerateNotConfigured,
+    // Object Ownership is "bucket owner enforced", so ACLs are rejected.
+    AclsDisabled,
+    Other,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectAcl {
+    owner_id: Option<String>,
+    owner_display_name: Option<String>,
+    grants: Vec<ObjectAclGrant>,
+}
+
+// `grantee_type` is CanonicalUser (by `id`), Group (by `uri`) or
+// AmazonCustomerByEmail (by `email_address`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectAclGrant {
+    grantee_type: String,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    uri: Option<String>,
+    #[serde(default)]
+    email_address: Option<String>,
+    permission: String,
+}
+
+// One CORS rule as the UI edits it; converted to the SDK's `CorsRule`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketCorsRule {
+    #[serde(default)]
+    id: Option<String>,
+    allowed_methods: Vec<String>,
+    allowed_origins: Vec<String>,
+    #[serde(default)]
+    allowed_headers: Vec<String>,
+    #[serde(default)]
+    expose_headers: Vec<String>,
+    #[serde(default)]
+    max_age_seconds: Option<i32>,
+}
+
+// One lifecycle rule as the UI edits it; converted to the SDK's
+// `LifecycleRule`. Every action S3 returns is modelled, so a read-then-set
+// keeps the rule as it was.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketLifecycleRule {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    // No prefix and no tags applies the rule to the whole bucket.
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(default)]
+    tags: Vec<BucketLifecycleTag>,
+    // Object size bounds in bytes, both exclusive.
+    #[serde(default)]
+    object_size_greater_than: Option<i64>,
+    #[serde(default)]
+    object_size_less_than: Option<i64>,
+    #[serde(default)]
+    transitions: Vec<BucketLifecycleTransition>,
+    #[serde(default)]
+    expiration_days: Option<i32>,
+    // RFC 3339; S3 only takes midnight UTC. Used instead of `expiration_days`.
+    #[serde(default)]
+    expiration_date: Option<String>,
+    // Removes delete markers left with no noncurrent versions behind them.
+    #[serde(default)]
+    expired_object_delete_marker: bool,
+    #[serde(default)]
+    noncurrent_transitions: Vec<BucketLifecycleNoncurrentTransition>,
+    #[serde(default)]
+    noncurrent_expiration_days: Option<i32>,
+    // Noncurrent versions kept regardless of age by the expiration above.
+    #[serde(default)]
+    noncurrent_newer_versions: Option<i32>,
+    #[serde(default)]
+    abort_incomplete_multipart_days: Option<i32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketLifecycleTag {
+    key: String,
+    value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketLifecycleTransition {
+    #[serde(default)]
+    days: i32,
+    // RFC 3339 midnight UTC; when set, `days` is ignored.
+    #[serde(default)]
+    date: Option<String>,
+    storage_class: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketLifecycleNoncurrentTransition {
+    noncurrent_days: i32,
+    storage_class: String,
+    #[serde(default)]
+    newer_noncurrent_versions: Option<i32>,
+}
+
+// An in-progress multipart upload, persisted after every part so a retry
+// of the same file continues where it stopped instead of at part 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MultipartUploadSidecar {
+    bucket: String,
+    key: String,
+    local_path: String,
+    upload_id: String,
+    file_size: i64,
+    file_mtime_ms: Option<i64>,
+    part_size: usize,
+    parts: Vec<MultipartSidecarPart>,
+    created_at: String,
+    #[serde(default)]
+    create_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<MultipartUploadOwner>,
+}
+
+// The upload job a multipart sidecar belongs to. An owned sidecar whose job
+// is gone was interrupted by a crash or restart and can be offered again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MultipartUploadOwner {
+    job_id: String,
+    profile_id: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResumableUpload {
+    id: String,
+    job_id: String,
+    profile_id: String,
+    bucket: String,
+    key: String,
+    local_path: String,
+    file_size: i64,
+    bytes_uploaded: i64,
+    created_at: String,
+    // False once the local file's size or mtime moved; resuming would restart.
+    local_unchanged: bool,
+}
+
+// Written when a pausable download starts streaming, so a resume can ask for
+// the rest of the same object version with a ranged, `If-Match` GET.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownloadSidecar {
+    bucket: String,
+    key: String,
+    local_path: String,
+    e_tag: String,
+    total: i64,
+    created_at: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MultipartSidecarPart {
+    part_number: i32,
+    e_tag: Option<String>,
+    offset: i64,
+    size: i64,
+}
+
+// A multipart upload fed from a stream of unknown length; created lazily on
+// the first full part.
+struct StreamingMultipart {
+    upload_id: Option<String>,
+    parts: Vec<CompletedPart>,
 }
 
 #[derive(Clone, Debug)]
@@ -494,9 +1604,10 @@ struct RemoteFileInfo {
     size: i64,
     etag: String,
     last_modified: String,
+    storage_class: Option<String>,
 }
 
-// A single object returned by s3_list_all_objects — replaces a positional
+// A single object returned by s3_for_each_object — replaces a positional
 // (key, size, etag, last_modified) tuple to make call sites self-documenting.
 #[derive(Clone, Debug)]
 struct RemoteObject {
@@ -504,6 +1615,18 @@ struct RemoteObject {
     size: i64,
     etag: String,
     last_modified: String,
+    // None for STANDARD on providers that omit it.
+    storage_class: Option<String>,
+}
+
+// Walks a ListObjectsV2 listing one page at a time, so callers that only
+// fold over the objects never hold a whole multi-million-key prefix.
+struct S3ObjectPager<'a> {
+    client: &'a S3Client,
+    bucket: &'a str,
+    prefix: &'a str,
+    continuation_token: Option<String>,
+    done: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -511,11 +1634,26 @@ struct SyncObjectInfo {
     size: i64,
     etag: String,
     last_modified: String,
+    storage_class: Option<String>,
+}
+
+// A sync diff built one source object at a time against the destination map;
+// matched destination entries are dropped as they're seen, so what's left at
+// the end is destination-only.
+struct SyncDiffFold {
+    dest_map: HashMap<String, SyncObjectInfo>,
+    mode: SyncMode,
+    archived_objects: ArchivedObjectPolicy,
+    to_add: Vec<SyncDiffEntryRecord>,
+    to_update: Vec<SyncDiffEntryRecord>,
+    skipped_archived: Vec<SyncDiffEntryRecord>,
+    unchanged: i64,
 }
 
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SyncDiffEntryRecord {
+    id: String,
     key: String,
     source_size: Option<i64>,
     dest_size: Option<i64>,
@@ -532,7 +1670,12 @@ struct SyncDiffRecord {
     to_add: Vec<SyncDiffEntryRecord>,
     to_update: Vec<SyncDiffEntryRecord>,
     to_delete: Vec<SyncDiffEntryRecord>,
+    // Adds/updates whose source is archived, left out under the skip policy.
+    skipped_archived: Vec<SyncDiffEntryRecord>,
     unchanged: i64,
+    to_add_bytes: i64,
+    to_update_bytes: i64,
+    to_delete_count: usize,
 }
 
 #[derive(Clone)]
@@ -541,6 +1684,65 @@ struct FolderSyncTaskControl {
     pause_flag: Arc<AtomicBool>,
     wake_tx: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     watcher: Arc<Mutex<Option<notify::RecommendedWatcher>>>,
+    // Set when the watcher reports an error or fails to start; the poll loop
+    // then rebuilds it.
+    watcher_failed: Arc<AtomicBool>,
+    // Set by sync-now; a manual-only rule runs a pass only when it's set.
+    sync_requested: Arc<AtomicBool>,
+    // Symlink cycles already reported, so each is reported once while it lasts.
+    reported_symlink_cycles: Arc<Mutex<HashSet<String>>>,
+}
+
+// Shared progress counters for one folder-sync pass. Cloned into every
+// in-flight transfer so concurrent uploads/downloads report into one total.
+#[derive(Clone)]
+struct FolderSyncPassProgress {
+    app: AppHandle,
+    rule_id: String,
+    files_watching: i64,
+    total: i64,
+    bytes_total: i64,
+    completed: Arc<AtomicI64>,
+    bytes_transferred: Arc<AtomicI64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FolderSyncTransferDirection {
+    Upload,
+    Download,
+}
+
+// Everything a spawned upload/download needs; owned so it can cross into a
+// JoinSet task.
+struct FolderSyncTransferTask {
+    direction: FolderSyncTransferDirection,
+    client: S3Client,
+    bucket: String,
+    remote_key: String,
+    local_path: PathBuf,
+    entry: FolderSyncDiffEntryRecord,
+    cancel_flag: Arc<AtomicBool>,
+    progress: FolderSyncPassProgress,
+    upload_attributes: UploadAttributes,
+    download_attributes: DownloadAttributes,
+}
+
+struct FolderSyncTransferOutcome {
+    direction: FolderSyncTransferDirection,
+    relative_path: String,
+    // Bytes already added to the shared counter by progress callbacks, so the
+    // coordinator can reconcile (or roll back on failure) the aggregate.
+    reported_bytes: i64,
+    result: Result<(i64, FolderSyncFileRecord), String>,
+}
+
+// Sync records for one rule, held in memory for the duration of a pass and
+// flushed to `<id>.json` periodically and when the pass ends.
+struct FolderSyncRecordStore {
+    rule_id: String,
+    records: HashMap<String, FolderSyncFileRecord>,
+    dirty: usize,
+    last_flush: Instant,
 }
 
 struct FolderSyncRuntime {
@@ -557,47 +1759,263 @@ impl Default for FolderSyncRuntime {
     }
 }
 
+// What `save_vault` does with the recovery blob. Only a session holding the
+// recovery key can re-encrypt it; a passphrase session keeps the blob already
+// on disk untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RecoveryBlobPlan {
+    Reencrypt,
+    PreserveExisting,
+    Omit,
+}
+
 #[derive(Default)]
 struct VaultRuntime {
     unlocked: bool,
     data: Option<VaultData>,
-    key: Option<[u8; KEY_BYTES]>,
+    key: Option<Zeroizing<[u8; KEY_BYTES]>>,
+    // How `key` was (or, in a recovery session, will be) derived.
+    kdf: VaultKdfConfig,
     salt: Option<Vec<u8>>,
-    recovery_key: Option<[u8; KEY_BYTES]>,
+    recovery_key: Option<Zeroizing<[u8; KEY_BYTES]>>,
     recovery_salt: Option<Vec<u8>>,
+    // Last RPC other than vault:status; drives `autoLockSecs`.
+    last_activity: Option<Instant>,
 }
 
-#[derive(Default)]
-struct UpdaterRuntime {
-    downloaded_version: Option<String>,
-    downloaded_bytes: Option<Vec<u8>>,
-}
-
-struct AppState {
-    vault: Mutex<VaultRuntime>,
-    jobs: Mutex<JobRuntime>,
-    folder_sync: Mutex<FolderSyncRuntime>,
-    updater: Mutex<UpdaterRuntime>,
-    is_quitting: AtomicBool,
+// App-wide preferences persisted to settings.json. Every field has a default
+// so older or partial files still load. Loaded once into `AppState.settings`
+// at startup; read it through `current_settings` rather than the file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AppSettings {
+    #[serde(default)]
+    version: u32,
+    notifications_enabled: bool,
+    // Pause folder sync while the OS reports a metered connection.
+    pause_sync_on_metered: bool,
+    // Set downloaded files' mtime to the object's LastModified.
+    download_preserve_mtime: bool,
+    // Unix only: apply a `mode` metadata value (s3fs-style) to downloads.
+    download_restore_mode: bool,
+    // Stay resident in the tray when the window closes, and start enabled
+    // folder-sync rules at launch (keychain unlock permitting).
+    run_in_background: bool,
+    // Lock the vault after this long without RPC calls; None never locks.
+    auto_lock_secs: Option<u64>,
+    // Fields written by a newer build, kept so saving here doesn't drop them.
+    #[serde(flatten)]
+    unknown: Map<String, Value>,
 }
 
-impl Default for AppState {
+impl Default for AppSettings {
     fn default() -> Self {
         Self {
-            vault: Mutex::new(VaultRuntime::default()),
-            jobs: Mutex::new(JobRuntime::default()),
-            folder_sync: Mutex::new(FolderSyncRuntime::default()),
-            updater: Mutex::new(UpdaterRuntime::default()),
-            is_quitting: AtomicBool::new(false),
+            version: SETTINGS_VERSION,
+            notifications_enabled: true,
+            pause_sync_on_metered: false,
+            download_preserve_mtime: false,
+            download_restore_mode: false,
+            run_in_background: false,
+            auto_lock_secs: None,
+            unknown: Map::new(),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+// Headers `s3_upload_file` sets on the object it creates, and how the
+// transfer itself runs.
+#[derive(Clone, Debug, Default)]
+struct UploadAttributes {
+    // Sends `If-None-Match: *` so an existing key is never overwritten.
+    create_only: bool,
+    acl: Option<ObjectCannedAcl>,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    metadata: HashMap<String, String>,
+    storage_class: Option<StorageClass>,
+    server_side_encryption: Option<ServerSideEncryption>,
+    ssekms_key_id: Option<String>,
+    // Sends `Content-MD5` with the object or each part, for providers that
+    // ask for it rather than a CRC; set on the `ChecksumRequired` retry.
+    content_md5: bool,
+    // Job uploads only: recorded in the multipart sidecar.
+    owner: Option<MultipartUploadOwner>,
+    // Job uploads only: throttles the transfer to its share of the cap.
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+    // Job uploads only: stops a multipart upload between parts.
+    pause_flag: Option<Arc<AtomicBool>>,
+}
+
+// Which version `s3_download_file` fetches and what it restores on the local
+// file besides its contents.
+#[derive(Clone, Debug, Default)]
+struct DownloadAttributes {
+    // None fetches the latest version.
+    version_id: Option<String>,
+    preserve_mtime: bool,
+    restore_mode: bool,
+    // Compare the file with the object's ETag (MD5) or size once written.
+    verify_checksum: bool,
+    // Job downloads only: throttles the transfer to its share of the cap.
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+    // Job downloads only: keeps the partial file on pause for a later resume.
+    pause_flag: Option<Arc<AtomicBool>>,
+}
+
+// What `s3_download_archive` packs and where the archive goes.
+struct ArchiveRequest<'a> {
+    keys: &'a [String],
+    // Stripped from each key to form its entry path.
+    common_prefix: &'a str,
+    destination_path: &'a Path,
+    format: ArchiveFormat,
+}
+
+// Byte range `s3_stream_object_range` sends, in pieces of `chunk_bytes`.
+#[derive(Clone, Copy, Debug)]
+struct StreamRange {
+    start: i64,
+    length: i64,
+    chunk_bytes: usize,
+}
+
+// Source object of `s3_multipart_copy`; its HEAD supplies the size and the
+// headers UploadPartCopy doesn't carry over.
+struct MultipartCopySource<'a> {
+    bucket: &'a str,
+    key: &'a str,
+    head: &'a HeadObjectOutput,
+}
+
+// Target of `s3_copy_object_via_temp_file`. The upload uses the destination
+// profile's default encryption, as a plain upload to it would.
+struct CopyDestination<'a> {
+    client: &'a S3Client,
+    bucket: &'a str,
+    key: &'a str,
+    attributes: &'a UploadAttributes,
+}
+
+struct PendingConfirmation {
+    // Fingerprint of the operation the token was issued for.
+    action: String,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+struct NetworkRuntime {
+    // `None` until detected, or when the platform can't tell.
+    metered: Option<bool>,
+    sync_paused_for_metered: bool,
+    // Rules the metered pause switched off; only these resume when it lifts.
+    metered_paused_rule_ids: HashSet<String>,
+}
+
+#[derive(Default)]
+struct UpdaterRuntime {
+    downloaded_version: Option<String>,
+    downloaded_bytes: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderProbeResult {
+    bucket: String,
+    // `None` when the probe couldn't tell, e.g. access denied.
+    supported: Option<bool>,
+    detail: String,
+    probed_at: String,
+}
+
+// Outcome of requesting a presigned URL outside the SDK.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PresignedUrlCheck {
+    ok: bool,
+    // `None` when no response came back at all.
+    status: Option<u16>,
+    error: Option<String>,
+}
+
+struct AppState {
+    vault: Mutex<VaultRuntime>,
+    jobs: Mutex<JobRuntime>,
+    folder_sync: Mutex<FolderSyncRuntime>,
+    updater: Mutex<UpdaterRuntime>,
+    settings: Mutex<AppSettings>,
+    // provider:probe results: profile id -> feature -> result.
+    provider_probes: Mutex<HashMap<String, HashMap<String, ProviderProbeResult>>>,
+    network: Mutex<NetworkRuntime>,
+    // Outstanding confirmation tokens for destructive RPCs.
+    confirmations: Mutex<HashMap<String, PendingConfirmation>>,
+    // Cancel handles for in-flight operations, keyed by caller-chosen id.
+    operations: Mutex<HashMap<String, oneshot::Sender<()>>>,
+    is_quitting: AtomicBool,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            vault: Mutex::new(VaultRuntime::default()),
+            jobs: Mutex::new(JobRuntime::default()),
+            folder_sync: Mutex::new(FolderSyncRuntime::default()),
+            updater: Mutex::new(UpdaterRuntime::default()),
+            // Replaced from settings.json in setup and after a legacy migration.
+            settings: Mutex::new(AppSettings::default()),
+            provider_probes: Mutex::new(HashMap::new()),
+            network: Mutex::new(NetworkRuntime::default()),
+            confirmations: Mutex::new(HashMap::new()),
+            operations: Mutex::new(HashMap::new()),
+            is_quitting: AtomicBool::new(false),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncExportRulesInput {
+    // Prompts for a destination when omitted.
+    path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncImportRulesInput {
+    // Prompts for a file when omitted.
+    path: Option<String>,
+    // Exported profile id -> local profile id.
+    #[serde(default)]
+    profile_map: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderProbeInput {
+    profile_id: String,
+    // Defaults to the profile's default bucket.
+    bucket: Option<String>,
+    // One of `PROVIDER_PROBE_FEATURES`; all of them when omitted.
+    feature: Option<String>,
+    // Ignore cached results and probe again.
+    #[serde(default)]
+    refresh: bool,
+}
+
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct VaultSetupInput {
     passphrase: String,
     remember: Option<bool>,
+    // Defaults to Argon2id.
+    kdf: Option<VaultKdf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultAutoLockInput {
+    // None turns auto-lock off.
+    secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -631,6 +2049,54 @@ struct ProfileInput {
     endpoint: Option<String>,
     region: Option<String>,
     default_bucket: Option<String>,
+    #[serde(default)]
+    requester_pays: bool,
+    #[serde(default)]
+    ephemeral: bool,
+    #[serde(default)]
+    key_transform: Option<KeyTransform>,
+    #[serde(default)]
+    require_checksums: bool,
+    #[serde(default)]
+    disable_checksums: bool,
+    #[serde(default)]
+    use_accelerate: bool,
+    #[serde(default)]
+    use_dualstack: bool,
+    sse: Option<String>,
+    sse_kms_key_id: Option<String>,
+}
+
+// A profile from the AWS CLI's shared files that has static keys.
+#[derive(Clone, Debug)]
+struct AwsSharedProfile {
+    name: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+}
+
+// What profile:import-aws offers; the secret stays in the file until the
+// import is confirmed.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AwsProfileCandidate {
+    name: String,
+    // Guessed from the endpoint's host; Aws without one.
+    provider: S3Provider,
+    access_key_id: String,
+    region: Option<String>,
+    endpoint: Option<String>,
+    has_session_token: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileImportAwsConfirmInput {
+    // Section names from profile:import-aws.
+    names: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -645,6 +2111,26 @@ struct ProfileUpdateInput {
     endpoint: Option<String>,
     region: Option<String>,
     default_bucket: Option<String>,
+    requester_pays: Option<bool>,
+    ephemeral: Option<bool>,
+    // An empty prefix and suffix clears the transform.
+    key_transform: Option<KeyTransform>,
+    require_checksums: Option<bool>,
+    disable_checksums: Option<bool>,
+    use_accelerate: Option<bool>,
+    use_dualstack: Option<bool>,
+    // Omitted keeps the default; an empty `sse` clears it.
+    sse: Option<String>,
+    sse_kms_key_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileProvideCredentialsInput {
+    id: String,
+    access_key_id: Option<String>,
+    secret_access_key: String,
+    session_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -656,6 +2142,10 @@ struct ProfileTestInput {
     access_key_id: String,
     secret_access_key: String,
     default_bucket: Option<String>,
+    #[serde(default)]
+    use_accelerate: bool,
+    #[serde(default)]
+    use_dualstack: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -664,18 +2154,130 @@ struct ProfileIdInput {
     profile_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketInput {
+    profile_id: String,
+    bucket: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketCorsSetInput {
+    profile_id: String,
+    bucket: String,
+    // Empty deletes the bucket's CORS configuration.
+    rules: Vec<BucketCorsRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketLifecycleSetInput {
+    profile_id: String,
+    bucket: String,
+    // Empty deletes the bucket's lifecycle configuration.
+    rules: Vec<BucketLifecycleRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketPolicySetInput {
+    profile_id: String,
+    bucket: String,
+    // Raw policy JSON; null or empty deletes the policy.
+    policy: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FavoritesSaveInput {
     favorites: Vec<String>,
 }
 
+// A saved location (bucket plus optional prefix) on any profile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bookmark {
+    profile_id: String,
+    bucket: String,
+    #[serde(default)]
+    prefix: String,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+// bookmarks.json; seeded from the flat favorites.json list on first load.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct BookmarksFile {
+    version: u32,
+    bookmarks: Vec<Bookmark>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BookmarksSaveInput {
+    bookmarks: Vec<Bookmark>,
+}
+
+// Last browsed spot per profile (last-location.json), restored on launch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct NavState {
+    last_profile_id: Option<String>,
+    locations: HashMap<String, NavLocation>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NavLocation {
+    bucket: String,
+    #[serde(default)]
+    prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NavGetLastInput {
+    profile_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NavSetLastInput {
+    profile_id: String,
+    bucket: String,
+    #[serde(default)]
+    prefix: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JobConcurrencyInput {
     concurrency: u8,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSyncEmptyTrashInput {
+    id: String,
+    // Batches at least this old are purged; 0 empties the trash.
+    older_than_days: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobBandwidthLimitInput {
+    // None removes the cap.
+    max_bytes_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobAdaptiveConcurrencyInput {
+    enabled: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ShareGenerateInput {
@@ -683,6 +2285,16 @@ struct ShareGenerateInput {
     bucket: String,
     key: String,
     expires_in: i64,
+    // Lets operation:cancel abandon a presign that's taking too long.
+    operation_id: Option<String>,
+    // Opt-in: fetch the URL once to catch clock skew, region or path-style
+    // problems before the link is handed out. GET links only.
+    #[serde(default)]
+    validate: bool,
+    // "get" (default) or "put" for a browser-direct upload URL.
+    method: Option<String>,
+    // PUT only: signed into the URL, so the upload must send the same header.
+    content_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -693,6 +2305,103 @@ struct ObjectsListInput {
     prefix: Option<String>,
     max_keys: Option<u16>,
     start_after: Option<String>,
+    // Sorting applies to what this call returns; without `fetch_all` that's
+    // one page, not the whole folder.
+    sort_field: Option<ObjectSortField>,
+    #[serde(default)]
+    sort_dir: SortDirection,
+    #[serde(default)]
+    fetch_all: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ObjectSortField {
+    Key,
+    Size,
+    LastModified,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+// One object row in an objects:list response.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectListEntry {
+    key: String,
+    size: i64,
+    last_modified: String,
+    etag: String,
+    // "STANDARD" when the provider leaves it out, as S3 does for STANDARD.
+    storage_class: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectSearchResult {
+    objects: Vec<ObjectListEntry>,
+    // More keys matched than `max_results`.
+    is_truncated: bool,
+    // Keys looked at before the search finished or hit the cap.
+    scanned: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ObjectRestoreState {
+    // Not in an archive class; readable as is.
+    NotArchived,
+    // Archived with no restore requested (or the restored copy expired).
+    Archived,
+    InProgress,
+    // A temporary copy is readable until `expiry`.
+    Restored,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectRestoreStatus {
+    storage_class: Option<String>,
+    state: ObjectRestoreState,
+    expiry: Option<String>,
+}
+
+// One version or delete marker in an objects:list-versions response.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectVersionEntry {
+    key: String,
+    // "null" for objects written before versioning was enabled.
+    version_id: String,
+    size: i64,
+    last_modified: String,
+    // Empty for delete markers.
+    etag: String,
+    is_latest: bool,
+    is_delete_marker: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectVersionListing {
+    versions: Vec<ObjectVersionEntry>,
+    // Set when the listing stopped at `OBJECT_LIST_FETCH_ALL_MAX_ENTRIES`.
+    is_truncated: bool,
+}
+
+// `next_cursor` is the last object key in S3's order, whatever the sort.
+#[derive(Clone, Debug, Default)]
+struct ObjectListing {
+    objects: Vec<ObjectListEntry>,
+    prefixes: Vec<String>,
+    is_truncated: bool,
+    next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -701,6 +2410,36 @@ struct ObjectsDeleteInput {
     profile_id: String,
     bucket: String,
     keys: Vec<String>,
+    // Removes that exact version instead of adding a delete marker. Only
+    // valid with a single key.
+    version_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsRestoreInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+    days: i32,
+    // "Expedited", "Standard" or "Bulk".
+    tier: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsCreateFolderInput {
+    profile_id: String,
+    bucket: String,
+    prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsListVersionsInput {
+    profile_id: String,
+    bucket: String,
+    prefix: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -714,61 +2453,235 @@ struct ObjectsRenameInput {
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ObjectsStatInput {
+struct ObjectsPreviewInput {
     profile_id: String,
     bucket: String,
     key: String,
+    max_bytes: Option<i64>,
+    // Gunzip `.gz` keys and `Content-Encoding: gzip` objects before returning.
+    #[serde(default = "default_true")]
+    decompress: bool,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct UploadInput {
+struct ObjectsGetTextInput {
     profile_id: String,
     bucket: String,
     key: String,
-    local_path: String,
+    max_bytes: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct DownloadInput {
+struct MultipartStateInput {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsOpenExternalInput {
     profile_id: String,
     bucket: String,
     key: String,
-    local_path: String,
+    // Required above `OPEN_EXTERNAL_CONFIRM_BYTES`; see objects:delete-prefix.
+    confirm_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct PickUploadInput {
+struct ObjectsStreamRangeInput {
     profile_id: String,
     bucket: String,
-    prefix: String,
+    key: String,
+    // Required so the stream can be stopped with operation:cancel.
+    operation_id: String,
+    start: i64,
+    // Bytes to send from `start`; defaults to (and is capped at) the total cap.
+    length: Option<i64>,
+    chunk_bytes: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct DownloadFolderInput {
+struct ObjectsSearchInput {
     profile_id: String,
     bucket: String,
+    #[serde(default)]
     prefix: String,
+    pattern: String,
+    max_results: Option<usize>,
+    // Lets operation:cancel stop a search of a large bucket.
+    operation_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CopyInput {
-    source_profile_id: String,
-    source_bucket: String,
-    source_key: String,
-    dest_profile_id: String,
-    dest_bucket: String,
-    dest_key: String,
+struct ObjectsDiffPrefixesInput {
+    profile_id: String,
+    bucket: String,
+    base_prefix: String,
+    compare_prefix: String,
+    // Lets operation:cancel stop the listing of a large prefix.
+    operation_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CrossBucketInput {
-    source_profile_id: String,
+struct ParseS3UriInput {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsStatInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+    local_path: String,
+    overwrite: Option<bool>,
+    // Canned ACL such as "public-read"; omitted means the bucket default.
+    acl: Option<String>,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    // User metadata, sent as `x-amz-meta-<name>` headers.
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    // e.g. "STANDARD_IA" or "GLACIER"; omitted means the bucket default.
+    storage_class: Option<String>,
+    // "AES256" or "aws:kms"; omitted uses the profile's default.
+    sse: Option<String>,
+    // Only with "aws:kms"; omitted uses the bucket's default KMS key.
+    sse_kms_key_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationCancelInput {
+    operation_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectsDeletePrefixInput {
+    profile_id: String,
+    bucket: String,
+    prefix: String,
+    // Required to accept an empty prefix, i.e. emptying the whole bucket.
+    #[serde(default)]
+    allow_bucket_wide: bool,
+    // Echoed from the first call; without it only a token is issued.
+    confirm_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectAclSetInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+    // Exactly one of a canned ACL or a full grant list.
+    acl: Option<String>,
+    grants: Option<Vec<ObjectAclGrant>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectTagsSetInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+    // The full tag set; an empty map removes every tag.
+    tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadInput {
+    profile_id: String,
+    bucket: String,
+    key: String,
+    local_path: String,
+    #[serde(default)]
+    on_existing: DownloadExistingPolicy,
+    #[serde(default)]
+    verify_checksum: bool,
+    // None downloads the latest version.
+    version_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadFolderArchiveInput {
+    profile_id: String,
+    bucket: String,
+    // Destination key; defaults to `<prefix><folder name>.tar.gz`.
+    key: Option<String>,
+    #[serde(default)]
+    prefix: String,
+    // Prompts for a folder when omitted.
+    local_path: Option<String>,
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PickUploadInput {
+    profile_id: String,
+    bucket: String,
+    prefix: String,
+    overwrite: Option<bool>,
+    // Folder uploads only; off by default, matching folder sync.
+    #[serde(default)]
+    follow_symlinks: bool,
+    // Folder uploads only: create a zero-byte `dir/` marker for each empty
+    // directory so the structure survives. Off by default.
+    #[serde(default)]
+    include_empty_dirs: bool,
+}
+
+// A local entry a folder upload left out, relative to the picked folder.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SkippedLocalFile {
+    path: String,
+    reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadFolderInput {
+    profile_id: String,
+    bucket: String,
+    prefix: String,
+    #[serde(default)]
+    on_existing: DownloadExistingPolicy,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CopyInput {
+    source_profile_id: String,
+    source_bucket: String,
+    source_key: String,
+    dest_profile_id: String,
+    dest_bucket: String,
+    dest_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrossBucketInput {
+    source_profile_id: String,
     source_bucket: String,
     keys: Vec<String>,
     source_prefix: String,
@@ -776,6 +2689,25 @@ struct CrossBucketInput {
     dest_bucket: String,
     dest_prefix: String,
     mode: TransferMode,
+    expand_concurrency: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadSelectionInput {
+    profile_id: String,
+    bucket: String,
+    keys: Vec<String>,
+    common_prefix: Option<String>,
+    destination: Option<String>,
+    #[serde(default)]
+    on_existing: DownloadExistingPolicy,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskSpaceInput {
+    path: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -786,6 +2718,23 @@ struct DownloadArchiveInput {
     keys: Vec<String>,
     prefix: Option<String>,
     archive_name: Option<String>,
+    expand_concurrency: Option<usize>,
+    // "tar.gz" (default) or "zip".
+    format: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ArchiveFormat {
+    #[default]
+    TarGz,
+    Zip,
+}
+
+// An archive download being written; entries are streamed in as they arrive.
+enum ArchiveWriter {
+    TarGz(GzEncoder<io::BufWriter<fs::File>>),
+    Zip(zip::ZipWriter<io::BufWriter<fs::File>>),
 }
 
 #[derive(Debug, Deserialize)]
@@ -798,6 +2747,8 @@ struct SyncInput {
     dest_bucket: String,
     dest_prefix: String,
     mode: SyncMode,
+    #[serde(default)]
+    archived_objects: ArchivedObjectPolicy,
 }
 
 #[derive(Debug, Deserialize)]
@@ -807,6 +2758,34 @@ struct FolderSyncToggleInput {
     enabled: bool,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBatchCall {
+    method: String,
+    #[serde(default)]
+    payload: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrateLegacyConfigInput {
+    // Defaults to the first legacy directory that has config in it.
+    source_path: Option<String>,
+    // Confirms the move: originals whose copy matches are deleted.
+    #[serde(default)]
+    remove_legacy: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyConfigMigration {
+    source: Option<String>,
+    copied: Vec<String>,
+    // Already present in the current directory; never overwritten.
+    skipped: Vec<String>,
+    removed: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct IdInput {
@@ -819,9 +2798,17 @@ struct JobIdInput {
     job_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobSetPriorityInput {
+    job_id: String,
+    priority: i64,
+}
+
 struct UnlockPayload {
     data: VaultData,
-    key: [u8; KEY_BYTES],
+    key: Zeroizing<[u8; KEY_BYTES]>,
+    kdf: VaultKdfConfig,
     salt: Vec<u8>,
     has_recovery_key: bool,
     recovery_salt: Option<Vec<u8>>,
@@ -830,9 +2817,10 @@ struct UnlockPayload {
 
 struct RecoveryUnlockPayload {
     data: VaultData,
+    kdf: VaultKdfConfig,
     salt: Vec<u8>,
     recovery_salt: Vec<u8>,
-    recovery_key: [u8; KEY_BYTES],
+    recovery_key: Zeroizing<[u8; KEY_BYTES]>,
 }
 
 enum KeychainReadResult {
@@ -844,7 +2832,12 @@ pub fn run() {
     tauri::Builder::default()
         .manage(AppState::default())
         .setup(|app| {
+            if let Ok(mut settings) = app.state::<AppState>().settings.lock() {
+                *settings = load_settings_from_disk();
+            }
             hydrate_job_history_runtime(app.app_handle());
+            hydrate_job_queue_runtime(app.app_handle());
+            clear_open_external_dir();
 
             // Custom window decorations: macOS keeps the native frame (traffic
             // lights float over an overlay title bar via tauri.conf.json), while
@@ -859,6 +2852,23 @@ pub fn run() {
                 run_periodic_updater_checks(updater_handle).await;
             });
 
+            let network_handle = app.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                run_metered_network_monitor(network_handle).await;
+            });
+
+            let auto_lock_handle = app.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                run_vault_auto_lock_monitor(auto_lock_handle).await;
+            });
+            if current_settings(&app.state::<AppState>()).run_in_background {
+                // The keychain read can block; keep it off the main thread.
+                let sync_handle = app.app_handle().clone();
+                tauri::async_runtime::spawn_blocking(move || {
+                    start_background_sync(&sync_handle);
+                });
+            }
+
             let menu = build_tray_menu(app.app_handle()).map_err(std::io::Error::other)?;
             let mut tray_builder = TrayIconBuilder::with_id("object0-tray")
                 .menu(&menu)
@@ -895,19 +2905,23 @@ pub fn run() {
                     return;
                 }
 
-                if folder_sync_has_active_tasks(&app) {
+                if current_settings(&state).run_in_background
+                    || folder_sync_has_active_tasks(&app)
+                {
                     api.prevent_close();
                     let _ = window.hide();
                     refresh_tray_menu(&app);
                 } else {
                     state.is_quitting.store(true, Ordering::SeqCst);
+                    cancel_jobs_for_shutdown(app);
                     app.exit(0);
                 }
             }
         })
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![rpc::rpc_request])
+        .plugin(tauri_plugin_notification::init())
+        .invoke_handler(tauri::generate_handler![rpc::rpc_request, rpc::rpc_batch])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -922,7 +2936,7 @@ mod tests {
         let key = derive_key("correct horse battery staple", &salt);
         let (iv, ct) = encrypt_payload(&key, b"top secret profile blob").unwrap();
         let pt = decrypt_payload(&key, &iv, &ct).unwrap();
-        assert_eq!(pt, b"top secret profile blob");
+        assert_eq!(pt.as_slice(), b"top secret profile blob");
     }
 
     #[test]
@@ -933,6 +2947,61 @@ mod tests {
         assert!(decrypt_payload(&wrong, &iv, &ct).is_err());
     }
 
+    #[test]
+    fn recovery_session_must_rekey_before_saving() {
+        let path = std::env::temp_dir().join(format!("object0-vault-{}.json", Uuid::new_v4()));
+        let recovery_plain = generate_recovery_key();
+        let recovery_salt = [3u8; SALT_BYTES];
+        let original = VaultRuntime {
+            unlocked: true,
+            data: Some(VaultData::default()),
+            key: Some(derive_key("old", &[1u8; SALT_BYTES])),
+            kdf: VAULT_KDF_PBKDF2,
+            salt: Some(vec![1u8; SALT_BYTES]),
+            recovery_key: Some(derive_key(&recovery_plain, &recovery_salt)),
+            recovery_salt: Some(recovery_salt.to_vec()),
+            last_activity: None,
+        };
+        save_vault(&path, &original).unwrap();
+
+        let unlock = unlock_with_recovery_key(&path, &recovery_plain).unwrap();
+        let mut session = VaultRuntime {
+            unlocked: true,
+            data: Some(unlock.data),
+            key: None,
+            kdf: unlock.kdf,
+            salt: Some(unlock.salt),
+            recovery_key: Some(unlock.recovery_key),
+            recovery_salt: Some(unlock.recovery_salt),
+            last_activity: None,
+        };
+        assert!(is_recovery_session(&session));
+        assert!(ensure_writable(&session).is_err());
+        assert!(save_vault(&path, &session).is_err());
+
+        // Setting a new passphrase makes the session writable and re-encrypts
+        // the recovery blob alongside the main payload.
+        session.key = Some(derive_key("new", &[2u8; SALT_BYTES]));
+        session.salt = Some(vec![2u8; SALT_BYTES]);
+        assert!(!is_recovery_session(&session));
+        assert!(ensure_writable(&session).is_ok());
+        assert_eq!(recovery_blob_plan(&session), RecoveryBlobPlan::Reencrypt);
+        save_vault(&path, &session).unwrap();
+        assert!(unlock_with_passphrase(&path, "new").is_ok());
+        assert!(unlock_with_passphrase(&path, "old").is_err());
+
+        // A passphrase session doesn't hold the recovery key, so a save must
+        // carry the existing blob over rather than drop it.
+        session.recovery_key = None;
+        assert_eq!(recovery_blob_plan(&session), RecoveryBlobPlan::PreserveExisting);
+        save_vault(&path, &session).unwrap();
+        assert!(unlock_with_recovery_key(&path, &recovery_plain).is_ok());
+
+        session.recovery_salt = None;
+        assert_eq!(recovery_blob_plan(&session), RecoveryBlobPlan::Omit);
+        let _ = fs::remove_file(&path);
+    }
+
     #[test]
     fn derive_key_is_deterministic_and_salt_sensitive() {
         let a = derive_key("pw", &[0u8; SALT_BYTES]);
@@ -994,6 +3063,67 @@ mod tests {
         assert_eq!(parse_iso_millis(""), None);
     }
 
+    #[test]
+    fn self_test_probes_dir_writability() {
+        let dir = std::env::temp_dir().join(format!("object0-self-test-{}", Uuid::new_v4()));
+        probe_dir_writable(&dir).unwrap();
+        // The probe cleans up after itself.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+
+        let file = std::env::temp_dir().join(format!("object0-self-test-{}", Uuid::new_v4()));
+        fs::write(&file, b"not a dir").unwrap();
+        assert!(probe_dir_writable(&file).is_err());
+        let _ = fs::remove_file(&file);
+    }
+
+    // Hands out at most three bytes per read, like a slow pipe or network fs.
+    struct ShortReader {
+        data: Vec<u8>,
+        pos: usize,
+    }
+
+    impl tokio::io::AsyncRead for ShortReader {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            let end = (self.pos + 3).min(self.data.len()).min(self.pos + buf.remaining());
+            buf.put_slice(&self.data[self.pos..end]);
+            self.pos = end;
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn read_full_chunk_survives_short_reads() {
+        let data: Vec<u8> = (0..20u8).collect();
+        let mut reader = ShortReader {
+            data: data.clone(),
+            pos: 0,
+        };
+        let mut chunks = Vec::new();
+        tauri::async_runtime::block_on(async {
+            loop {
+                let mut buffer = vec![0u8; 8];
+                let read = read_full_chunk(&mut reader, &mut buffer).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                buffer.truncate(read);
+                chunks.push(buffer);
+            }
+        });
+
+        // Full parts despite 3-byte reads, only the last one short.
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![8, 8, 4]
+        );
+        assert_eq!(chunks.concat(), data);
+    }
+
     #[test]
     fn write_atomic_roundtrips_via_tempdir() {
         let dir = std::env::temp_dir().join(format!("object0-test-{}", std::process::id()));
@@ -1003,92 +3133,2540 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
-    // Lock the exact wire strings for the domain enums. These must stay byte-identical
-    // to the frontend unions in src/shared/*.types.ts and to any persisted vault/sync
-    // JSON; a rename here would silently break deserialization of existing data.
-    fn assert_wire<T>(value: T, expected: &str)
-    where
-        T: Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
-    {
-        let json = serde_json::to_string(&value).unwrap();
-        assert_eq!(json, format!("\"{expected}\""));
-        let back: T = serde_json::from_str(&json).unwrap();
-        assert_eq!(back, value);
+    // A rule as persisted before any of the optional per-rule settings existed.
+    fn legacy_rule_json() -> Value {
+        json!({
+            "id": "rule-1",
+            "profileId": "profile-1",
+            "bucket": "bucket",
+            "bucketPrefix": "photos/",
+            "localPath": "/tmp/object0-rule",
+            "direction": "bidirectional",
+            "enabled": true,
+            "conflictResolution": "newer-wins",
+            "pollIntervalMs": 30000,
+            "excludePatterns": [".DS_Store"],
+            "createdAt": "2024-01-01T00:00:00Z"
+        })
     }
 
     #[test]
-    fn s3_provider_wire_format_is_stable() {
-        assert_wire(S3Provider::Aws, "aws");
-        assert_wire(S3Provider::R2, "r2");
-        assert_wire(S3Provider::Spaces, "spaces");
-        assert_wire(S3Provider::Minio, "minio");
-        assert_wire(S3Provider::Gcs, "gcs");
-        assert_wire(S3Provider::Backblaze, "backblaze");
-        assert_wire(S3Provider::Custom, "custom");
+    fn legacy_folder_sync_rule_gets_default_concurrency() {
+        let rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert_eq!(rule.sync_concurrency, FOLDER_SYNC_DEFAULT_CONCURRENCY);
+        assert_eq!(folder_sync_concurrency(&rule), 4);
+
+        let mut tuned = rule.clone();
+        tuned.sync_concurrency = 0;
+        assert_eq!(folder_sync_concurrency(&tuned), 1);
+        tuned.sync_concurrency = 200;
+        assert_eq!(folder_sync_concurrency(&tuned), FOLDER_SYNC_MAX_CONCURRENCY as usize);
     }
 
     #[test]
-    fn sync_direction_wire_format_is_stable() {
-        assert_wire(SyncDirection::Bidirectional, "bidirectional");
-        assert_wire(SyncDirection::LocalToRemote, "local-to-remote");
-        assert_wire(SyncDirection::RemoteToLocal, "remote-to-local");
+    fn ephemeral_profile_secrets_stay_out_of_the_vault() {
+        let profile = |id: &str, ephemeral: bool| {
+            serde_json::from_value::<Profile>(json!({
+                "id": id,
+                "name": id,
+                "provider": "aws",
+                "accessKeyId": "AKIA",
+                "secretAccessKey": "secret",
+                "sessionToken": "token",
+                "ephemeral": ephemeral,
+                "createdAt": "2024-01-01T00:00:00Z",
+                "updatedAt": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap()
+        };
+        let data = VaultData {
+            profiles: vec![profile("stored", false), profile("session", true)],
+        };
+
+        let persisted = persisted_vault_data(&data);
+        assert_eq!(persisted.profiles[0].secret_access_key, "secret");
+        assert_eq!(persisted.profiles[1].access_key_id, "AKIA");
+        assert!(persisted.profiles[1].secret_access_key.is_empty());
+        assert!(persisted.profiles[1].session_token.is_none());
+        // The running session keeps the secret.
+        assert!(!profile_needs_credentials(&data.profiles[1]));
+
+        let unlocked = &persisted.profiles[1];
+        assert!(profile_needs_credentials(unlocked));
+        assert!(to_profile_info(unlocked).needs_credentials);
+        assert!(to_s3_client(unlocked)
+            .err()
+            .is_some_and(|err| err.starts_with(PROFILE_NEEDS_CREDENTIALS)));
     }
 
     #[test]
-    fn conflict_resolution_wire_format_is_stable() {
-        assert_wire(ConflictResolution::NewerWins, "newer-wins");
-        assert_wire(ConflictResolution::LocalWins, "local-wins");
-        assert_wire(ConflictResolution::RemoteWins, "remote-wins");
-        assert_wire(ConflictResolution::KeepBoth, "keep-both");
+    fn exported_rules_import_disabled_with_remapped_profiles() {
+        let profile = |id: &str, name: &str| {
+            serde_json::from_value::<Profile>(json!({
+                "id": id,
+                "name": name,
+                "provider": "aws",
+                "region": "us-east-1",
+                "accessKeyId": "AKIA",
+                "secretAccessKey": "secret",
+                "createdAt": "2024-01-01T00:00:00Z",
+                "updatedAt": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap()
+        };
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        rule.last_sync_status = Some("success".to_string());
+        let mut second = rule.clone();
+        second.local_path = "/tmp/object0-other".to_string();
+
+        let exported =
+            export_folder_sync_rules(&[rule.clone(), second], &[profile("profile-1", "Work")])
+                .unwrap();
+        let first = &exported["rules"][0];
+        assert!(first.get("id").is_none() && first.get("lastSyncStatus").is_none());
+        assert_eq!(first["profileName"], "Work");
+
+        // Matched by name on the new machine; the first rule already exists there.
+        let mut existing = rule.clone();
+        existing.profile_id = "local-work".to_string();
+        let profiles = [profile("local-work", "Work")];
+        let (imported, skipped) =
+            import_folder_sync_rules(&exported, &[existing], &profiles, &HashMap::new()).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(imported[0].profile_id, "local-work");
+        assert_eq!(imported[0].local_path, "/tmp/object0-other");
+        assert!(!imported[0].enabled);
+        assert_ne!(imported[0].id, rule.id);
+        assert!(imported[0].last_sync_status.is_none());
+
+        // An explicit mapping wins; unknown profiles are skipped.
+        let mapping = HashMap::from([("profile-1".to_string(), "local-work".to_string())]);
+        let (imported, _) = import_folder_sync_rules(&exported, &[], &profiles, &mapping).unwrap();
+        assert_eq!(imported.len(), 2);
+        let (imported, skipped) =
+            import_folder_sync_rules(&exported, &[], &[profile("x", "Home")], &HashMap::new())
+                .unwrap();
+        assert!(imported.is_empty());
+        assert_eq!(skipped[0]["reason"], "No matching profile");
+
+        // A folder inside an existing rule's folder is skipped, as in rules:add.
+        let mut nested = rule.clone();
+        nested.bucket = "other-bucket".to_string();
+        nested.local_path = "/tmp/object0-rule/nested".to_string();
+        let exported =
+            export_folder_sync_rules(&[nested], &[profile("profile-1", "Work")]).unwrap();
+        let (imported, skipped) =
+            import_folder_sync_rules(&exported, &[rule], &profiles, &mapping).unwrap();
+        assert!(imported.is_empty());
+        assert!(skipped[0]["reason"].as_str().unwrap().contains("overlaps"));
     }
 
     #[test]
-    fn folder_sync_status_serializes_to_stable_strings() {
-        // Serialize-only enum (emitted to the frontend, never deserialized).
-        assert_eq!(
-            serde_json::to_string(&FolderSyncStatus::Idle).unwrap(),
-            "\"idle\""
-        );
-        assert_eq!(
-            serde_json::to_string(&FolderSyncStatus::Syncing).unwrap(),
-            "\"syncing\""
-        );
-        assert_eq!(
-            serde_json::to_string(&FolderSyncStatus::Watching).unwrap(),
-            "\"watching\""
-        );
-        assert_eq!(
-            serde_json::to_string(&FolderSyncStatus::Error).unwrap(),
-            "\"error\""
-        );
-        assert_eq!(
-            serde_json::to_string(&FolderSyncStatus::Paused).unwrap(),
-            "\"paused\""
-        );
+    fn disallowed_deletes_are_downgraded_to_keep() {
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert!(rule.allow_delete_local && rule.allow_delete_remote);
+
+        let local = LocalFileInfo {
+            relative_path: "a.txt".to_string(),
+            size: 3,
+            mtime_ms: 1_000,
+        };
+        let remote = RemoteFileInfo {
+            size: 3,
+            etag: "etag".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        let known = FolderSyncFileRecord {
+            relative_path: "a.txt".to_string(),
+            local_mtime: 1_000,
+            local_size: 3,
+            remote_etag: "etag".to_string(),
+            remote_last_modified: "2024-01-01T00:00:00Z".to_string(),
+            remote_size: 3,
+            synced_at: "2024-01-01T00:00:00Z".to_string(),
+            local_relative_path: None,
+        };
+        let action = |rule: &FolderSyncRuleRecord, local, remote| {
+            resolve_folder_sync_action(local, remote, Some(&known), rule, None)
+                .map(|(action, _)| action)
+        };
+
+        assert_eq!(action(&rule, Some(&local), None).as_deref(), Some("delete-local"));
+        assert_eq!(action(&rule, None, Some(&remote)).as_deref(), Some("delete-remote"));
+
+        rule.allow_delete_local = false;
+        rule.allow_delete_remote = false;
+        assert_eq!(action(&rule, Some(&local), None), None);
+        assert_eq!(action(&rule, None, Some(&remote)), None);
     }
 
     #[test]
-    fn sync_and_transfer_mode_deserialize_from_stable_strings() {
-        // Deserialize-only enums (transient RPC inputs from the frontend).
-        assert_eq!(
-            serde_json::from_str::<SyncMode>("\"mirror\"").unwrap(),
-            SyncMode::Mirror
-        );
-        assert_eq!(
-            serde_json::from_str::<SyncMode>("\"additive\"").unwrap(),
-            SyncMode::Additive
-        );
-        assert_eq!(
-            serde_json::from_str::<SyncMode>("\"overwrite\"").unwrap(),
-            SyncMode::Overwrite
-        );
+    fn poll_interval_backs_off_only_for_large_quiet_remotes() {
+        let rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert_eq!(rule.max_poll_interval_ms, None);
+        let quiet = |remote_objects| FolderSyncPassOutcome {
+            remote_objects,
+            changed: false,
+        };
+
+        // Small folders stay at the configured interval.
+        assert_eq!(next_folder_sync_poll_interval(&rule, 30_000, quiet(10)), 30_000);
+
+        // 50k objects: floor of 50s, doubling on quiet passes up to the default cap.
+        let mut interval = rule.poll_interval_ms;
+        interval = next_folder_sync_poll_interval(&rule, interval, quiet(50_000));
+        assert_eq!(interval, 60_000);
+        for _ in 0..10 {
+            interval = next_folder_sync_poll_interval(&rule, interval, quiet(50_000));
+        }
+        assert_eq!(interval, FOLDER_SYNC_DEFAULT_MAX_POLL_MS);
+
+        // A change snaps back to the per-object floor.
+        let changed = FolderSyncPassOutcome {
+            remote_objects: 50_000,
+            changed: true,
+        };
+        assert_eq!(next_folder_sync_poll_interval(&rule, interval, changed), 50_000);
+
+        // The user's ceiling wins, and a ceiling equal to the interval pins it.
+        let mut pinned = rule.clone();
+        pinned.max_poll_interval_ms = Some(30_000);
+        assert!(validate_folder_sync_rule(&pinned).is_ok());
+        assert_eq!(next_folder_sync_poll_interval(&pinned, 30_000, quiet(50_000)), 30_000);
+
+        pinned.max_poll_interval_ms = Some(1_000);
+        assert!(validate_folder_sync_rule(&pinned).is_err());
+    }
+
+    #[test]
+    fn conflict_rename_template_defaults_and_expands() {
+        let rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert_eq!(rule.conflict_rename_template, DEFAULT_CONFLICT_RENAME_TEMPLATE);
+        assert!(validate_folder_sync_rule(&rule).is_ok());
+
         assert_eq!(
-            serde_json::from_str::<TransferMode>("\"copy\"").unwrap(),
-            TransferMode::Copy
+            expand_name_template(&rule.conflict_rename_template, "report.pdf", "20240101-120000", "box"),
+            "report.conflict-20240101-120000.pdf"
         );
         assert_eq!(
-            serde_json::from_str::<TransferMode>("\"move\"").unwrap(),
-            TransferMode::Move
+            expand_name_template("{name} ({host}){ext}", "notes", "ts", "laptop"),
+            "notes (laptop)"
         );
     }
+
+    #[test]
+    fn name_template_validation_rejects_bad_templates() {
+        assert!(validate_name_template("{name} ({host}){ext}").is_ok());
+        assert!(validate_name_template("").is_err());
+        assert!(validate_name_template("copy-{timestamp}{ext}").is_err());
+        assert!(validate_name_template("{name}/{ext}").is_err());
+        assert!(validate_name_template("{name}-{user}{ext}").is_err());
+        assert!(validate_name_template("{name}-{ext").is_err());
+    }
+
+    #[test]
+    fn gunzip_prefix_handles_truncated_input_and_caps_output() {
+        let gzip = |data: &[u8]| {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        };
+
+        let log: Vec<u8> = (0..5_000)
+            .flat_map(|line| format!("{line} GET /index.html 200 {}\n", line * 7).into_bytes())
+            .collect();
+        let compressed = gzip(&log);
+        let (whole, truncated) = gunzip_prefix(&compressed, usize::MAX).unwrap();
+        assert_eq!(whole, log);
+        assert!(!truncated);
+
+        // A ranged read only has the head of the stream.
+        let half = &compressed[..compressed.len() / 2];
+        let (head, truncated) = gunzip_prefix(half, usize::MAX).unwrap();
+        assert!(truncated);
+        assert!(!head.is_empty() && log.starts_with(&head));
+
+        // A tiny bomb never expands past the cap.
+        let bomb = gzip(&vec![0u8; 8 * 1024 * 1024]);
+        let (capped, truncated) = gunzip_prefix(&bomb, 1024).unwrap();
+        assert_eq!(capped.len(), 1024);
+        assert!(truncated);
+
+        assert!(gunzip_prefix(b"plain text", 1024).is_err());
+    }
+
+    #[test]
+    fn multipart_resume_keeps_only_confirmed_leading_parts() {
+        let part = |part_number: i32, e_tag: &str| MultipartSidecarPart {
+            part_number,
+            e_tag: Some(e_tag.to_string()),
+            offset: (part_number as i64 - 1) * 8,
+            size: 8,
+        };
+        let recorded = vec![part(1, "a"), part(2, "b"), part(3, "c"), part(4, "d")];
+        let uploaded = |parts: &[(i32, &str)]| {
+            parts
+                .iter()
+                .map(|(number, e_tag)| (*number, Some(e_tag.to_string())))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let all = uploaded(&[(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+        assert_eq!(surviving_multipart_parts(&recorded, &all), recorded);
+
+        // Part 3 is missing, so 4 can't be kept either: parts must stay contiguous.
+        let gap = uploaded(&[(1, "a"), (2, "b"), (4, "d")]);
+        assert_eq!(surviving_multipart_parts(&recorded, &gap), recorded[..2].to_vec());
+
+        let replaced = uploaded(&[(1, "a"), (2, "other"), (3, "c")]);
+        assert_eq!(surviving_multipart_parts(&recorded, &replaced), recorded[..1].to_vec());
+
+        assert!(surviving_multipart_parts(&recorded, &HashMap::new()).is_empty());
+
+        let mut misaligned = recorded.clone();
+        misaligned[1].offset = 4;
+        assert_eq!(surviving_multipart_parts(&misaligned, &all), recorded[..1].to_vec());
+    }
+
+    #[test]
+    fn network_manager_metered_values_are_parsed() {
+        assert_eq!(parse_nm_metered("u 1\n"), Some(true));
+        assert_eq!(parse_nm_metered("u 3"), Some(true));
+        assert_eq!(parse_nm_metered("u 2"), Some(false));
+        assert_eq!(parse_nm_metered("u 4\n"), Some(false));
+        assert_eq!(parse_nm_metered("u 0"), None);
+        assert_eq!(parse_nm_metered(""), None);
+        assert_eq!(parse_nm_metered("s \"yes\""), None);
+    }
+
+    #[test]
+    fn only_aws_profiles_warn_about_unknown_regions() {
+        assert_eq!(region_warning(&S3Provider::Aws, Some("eu-west-2")), None);
+        assert_eq!(region_warning(&S3Provider::Aws, Some(" us-east-1 ")), None);
+        assert!(region_warning(&S3Provider::Aws, Some("eu-west-9"))
+            .is_some_and(|warning| warning.contains("eu-west-9")));
+        assert!(region_warning(&S3Provider::Aws, Some("EU-WEST-2")).is_some());
+        assert!(region_warning(&S3Provider::Aws, None)
+            .is_some_and(|warning| warning.contains(DEFAULT_AWS_REGION)));
+
+        // Region is mostly cosmetic for S3-compatible providers.
+        assert_eq!(region_warning(&S3Provider::Custom, Some("anything")), None);
+        assert_eq!(region_warning(&S3Provider::Minio, None), None);
+        assert_eq!(region_warning(&S3Provider::R2, Some("auto")), None);
+    }
+
+    #[test]
+    fn bucket_cors_and_policy_are_validated() {
+        let rule = |methods: &[&str], origins: &[&str]| BucketCorsRule {
+            id: None,
+            allowed_methods: methods.iter().map(|value| value.to_string()).collect(),
+            allowed_origins: origins.iter().map(|value| value.to_string()).collect(),
+            allowed_headers: vec!["*".to_string()],
+            expose_headers: Vec::new(),
+            max_age_seconds: Some(3000),
+        };
+        assert!(validate_cors_rules(&[]).is_ok());
+        assert!(validate_cors_rules(&[rule(&["GET", "HEAD"], &["https://example.com"])]).is_ok());
+        assert!(validate_cors_rules(&[rule(&[], &["*"])]).is_err());
+        assert!(validate_cors_rules(&[rule(&["get"], &["*"])])
+            .is_err_and(|err| err.contains("unsupported method get")));
+        assert!(validate_cors_rules(&[rule(&["GET"], &[])]).is_err());
+        let too_many = vec![rule(&["GET"], &["*"]); BUCKET_CORS_MAX_RULES + 1];
+        assert!(validate_cors_rules(&too_many).is_err());
+
+        let parsed: BucketCorsRule = serde_json::from_value(json!({
+            "allowedMethods": ["PUT"],
+            "allowedOrigins": ["*"]
+        }))
+        .unwrap();
+        assert!(parsed.allowed_headers.is_empty() && parsed.max_age_seconds.is_none());
+
+        assert!(validate_bucket_policy(r#"{"Version":"2012-10-17","Statement":[]}"#).is_ok());
+        assert!(validate_bucket_policy("[]").is_err());
+        assert!(validate_bucket_policy("{not json").is_err());
+    }
+
+    #[test]
+    fn text_reads_tolerate_a_character_cut_by_truncation() {
+        assert_eq!(decode_utf8_text("héllo".as_bytes(), false).as_deref(), Some("héllo"));
+        // "é" is two bytes; a read ending after the first keeps the text before it.
+        let cut = &"hé".as_bytes()[..2];
+        assert_eq!(decode_utf8_text(cut, true).as_deref(), Some("h"));
+        assert_eq!(decode_utf8_text(cut, false), None);
+        assert_eq!(decode_utf8_text(&[0x66, 0xff, 0x6f], true), None);
+        assert_eq!(decode_utf8_text(&[], false).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn object_acls_are_validated() {
+        assert!(parse_canned_acl("public-read").is_ok());
+        assert!(parse_canned_acl(" bucket-owner-full-control ").is_ok());
+        assert!(parse_canned_acl("public").is_err());
+
+        let grant = |grantee_type: &str, permission: &str| ObjectAclGrant {
+            grantee_type: grantee_type.to_string(),
+            id: Some("owner-id".to_string()),
+            display_name: None,
+            uri: None,
+            email_address: None,
+            permission: permission.to_string(),
+        };
+        assert!(validate_acl_grants(&[grant("CanonicalUser", "FULL_CONTROL")]).is_ok());
+        assert!(validate_acl_grants(&[grant("CanonicalUser", "DELETE")]).is_err());
+        assert!(validate_acl_grants(&[grant("Robot", "READ")]).is_err());
+        // Groups are identified by URI, not id.
+        assert!(validate_acl_grants(&[grant("Group", "READ")]).is_err());
+        let mut all_users = grant("Group", "READ");
+        all_users.uri = Some("http://acs.amazonaws.com/groups/global/AllUsers".to_string());
+        assert!(validate_acl_grants(&[all_users]).is_ok());
+    }
+
+    #[test]
+    fn s3_errors_are_classified_by_code_then_status() {
+        assert_eq!(classify_s3_error(Some("AccessDenied"), Some(403)), S3ErrorKind::AccessDenied);
+        assert_eq!(classify_s3_error(Some("NoSuchKey"), Some(404)), S3ErrorKind::NoSuchKey);
+        assert_eq!(classify_s3_error(Some("NoSuchBucket"), Some(404)), S3ErrorKind::NoSuchBucket);
+        assert_eq!(classify_s3_error(Some("SlowDown"), Some(503)), S3ErrorKind::SlowDown);
+        // Glacier objects answer 403 but aren't a permission problem.
+        assert_eq!(
+            classify_s3_error(Some("InvalidObjectState"), Some(403)),
+            S3ErrorKind::InvalidObjectState
+        );
+
+        // HEAD responses have no body, so only the status is available.
+        assert_eq!(classify_s3_error(None, Some(403)), S3ErrorKind::AccessDenied);
+        assert_eq!(classify_s3_error(None, Some(404)), S3ErrorKind::NoSuchKey);
+        assert_eq!(classify_s3_error(None, Some(503)), S3ErrorKind::SlowDown);
+        assert_eq!(classify_s3_error(Some("InternalError"), Some(500)), S3ErrorKind::Other);
+        assert_eq!(classify_s3_error(None, None), S3ErrorKind::Other);
+        assert_eq!(classify_s3_error(Some("NotImplemented"), Some(501)), S3ErrorKind::Unsupported);
+        assert_eq!(classify_s3_error(None, Some(405)), S3ErrorKind::Unsupported);
+        assert_eq!(
+            classify_s3_error(Some("AccessControlListNotSupported"), Some(400)),
+            S3ErrorKind::AclsDisabled
+        );
+
+        let message = s3_error_message(
+            S3ErrorKind::AccessDenied,
+            "list s3://bucket/photos/",
+            "raw".to_string(),
+        );
+        assert!(message.starts_with("AccessDenied: "));
+        assert!(message.contains("list s3://bucket/photos/"));
+        assert_eq!(
+            s3_error_message(S3ErrorKind::Other, "list", "raw sdk error".to_string()),
+            "raw sdk error"
+        );
+    }
+
+    #[test]
+    fn s3_uri_round_trips_and_rejects_malformed_input() {
+        let parse = parse_s3_uri;
+        assert_eq!(
+            parse("s3://my-bucket/photos/2024/a.jpg").unwrap(),
+            ("my-bucket".to_string(), "photos/2024/a.jpg".to_string())
+        );
+        assert_eq!(parse("  S3://my-bucket  ").unwrap(), ("my-bucket".to_string(), String::new()));
+        assert_eq!(parse("s3://my-bucket/").unwrap(), ("my-bucket".to_string(), String::new()));
+        assert_eq!(
+            parse("s3://my-bucket/photos/").unwrap(),
+            ("my-bucket".to_string(), "photos/".to_string())
+        );
+
+        assert!(parse("https://my-bucket/key").is_err());
+        assert!(parse("s3://").is_err());
+        assert!(parse("s3:///key").is_err());
+        assert!(parse("s3://ab/key").is_err());
+        assert!(parse("s3://bad bucket/key").is_err());
+        assert!(parse("s3://my-bucket//key").is_err());
+
+        let (bucket, key) = parse_s3_uri("s3://my-bucket/a b/c.txt").unwrap();
+        assert_eq!(format_s3_uri(&bucket, &key), "s3://my-bucket/a b/c.txt");
+    }
+
+    #[test]
+    fn common_key_prefix_stops_at_directory_boundary() {
+        let keys = vec![
+            "photos/2024/a.jpg".to_string(),
+            "photos/2024/raw/b.cr2".to_string(),
+            "photos/2023/c.jpg".to_string(),
+        ];
+        assert_eq!(common_key_prefix(&keys), "photos/");
+        assert_eq!(common_key_prefix(&keys[..2]), "photos/2024/");
+        assert_eq!(common_key_prefix(&["a.txt".to_string(), "b/c.txt".to_string()]), "");
+        assert_eq!(
+            common_key_prefix(&["logs/app1/x".to_string(), "logs/app2/y".to_string()]),
+            "logs/"
+        );
+    }
+
+    #[test]
+    fn adaptive_limit_climbs_while_throughput_rises_and_backs_off_on_failures() {
+        // Rising throughput with every slot busy: probe one more.
+        assert_eq!(next_adaptive_limit(2, 6, 1_000, 1_500, 0, true), 3);
+        // Rising but not saturated: more slots wouldn't be used.
+        assert_eq!(next_adaptive_limit(2, 6, 1_000, 1_500, 0, false), 2);
+        // Never past the manual ceiling.
+        assert_eq!(next_adaptive_limit(6, 6, 1_000, 1_500, 0, true), 6);
+        // Throughput dropped noticeably: step down.
+        assert_eq!(next_adaptive_limit(4, 6, 1_000, 600, 0, true), 3);
+        // Flat: hold.
+        assert_eq!(next_adaptive_limit(4, 6, 1_000, 1_050, 0, true), 4);
+        // Failures halve the limit, floored at the minimum.
+        assert_eq!(next_adaptive_limit(5, 6, 1_000, 2_000, 1, true), 2);
+        assert_eq!(next_adaptive_limit(1, 6, 1_000, 2_000, 3, true), 1);
+        // A lowered ceiling applies immediately.
+        assert_eq!(next_adaptive_limit(8, 3, 1_000, 1_000, 0, true), 3);
+    }
+
+    #[test]
+    fn folder_sync_pass_summary_lists_only_nonzero_counts() {
+        assert_eq!(folder_sync_pass_summary(&FolderSyncPassCounts::default()), None);
+        let counts = FolderSyncPassCounts {
+            uploaded: 3,
+            conflicts: 1,
+            ..FolderSyncPassCounts::default()
+        };
+        assert_eq!(
+            folder_sync_pass_summary(&counts).as_deref(),
+            Some("3 uploaded, 1 conflict")
+        );
+        let counts = FolderSyncPassCounts {
+            downloaded: 1,
+            deleted_local: 2,
+            deleted_remote: 1,
+            conflicts: 2,
+            failed: 1,
+            ..FolderSyncPassCounts::default()
+        };
+        assert_eq!(
+            folder_sync_pass_summary(&counts).as_deref(),
+            Some("1 downloaded, 2 deleted locally, 1 deleted remotely, 2 conflicts, 1 failed")
+        );
+    }
+
+    #[test]
+    fn settings_fill_missing_fields_with_defaults() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.notifications_enabled);
+        assert!(!settings.pause_sync_on_metered);
+        assert!(!settings.download_preserve_mtime && !settings.download_restore_mode);
+        assert!(!settings.run_in_background);
+        let settings: AppSettings =
+            serde_json::from_str(r#"{"notificationsEnabled":false}"#).unwrap();
+        assert!(!settings.notifications_enabled);
+    }
+
+    #[test]
+    fn batch_method_classification() {
+        for name in ["objects:stat", "objects:list", "vault:status", "jobs:list"] {
+            let method = RpcMethod::parse(name).unwrap();
+            assert!(method.is_read_only() && method.is_batchable(), "{name}");
+        }
+        let delete = RpcMethod::parse("objects:delete").unwrap();
+        assert!(delete.is_batchable() && !delete.is_read_only());
+        for name in ["transfer:pick-and-upload", "folder-sync:pick-folder", "updater:apply"] {
+            assert!(!RpcMethod::parse(name).unwrap().is_batchable(), "{name}");
+        }
+    }
+
+    // Lock the exact wire strings for the domain enums. These must stay byte-identical
+    // to the frontend unions in src/shared/*.types.ts and to any persisted vault/sync
+    // JSON; a rename here would silently break deserialization of existing data.
+    fn assert_wire<T>(value: T, expected: &str)
+    where
+        T: Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, format!("\"{expected}\""));
+        let back: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, value);
+    }
+
+    #[test]
+    fn s3_provider_wire_format_is_stable() {
+        assert_wire(S3Provider::Aws, "aws");
+        assert_wire(S3Provider::R2, "r2");
+        assert_wire(S3Provider::Spaces, "spaces");
+        assert_wire(S3Provider::Minio, "minio");
+        assert_wire(S3Provider::Gcs, "gcs");
+        assert_wire(S3Provider::Backblaze, "backblaze");
+        assert_wire(S3Provider::Custom, "custom");
+    }
+
+    #[test]
+    fn sync_direction_wire_format_is_stable() {
+        assert_wire(SyncDirection::Bidirectional, "bidirectional");
+        assert_wire(SyncDirection::LocalToRemote, "local-to-remote");
+        assert_wire(SyncDirection::RemoteToLocal, "remote-to-local");
+    }
+
+    #[test]
+    fn conflict_resolution_wire_format_is_stable() {
+        assert_wire(ConflictResolution::NewerWins, "newer-wins");
+        assert_wire(ConflictResolution::LocalWins, "local-wins");
+        assert_wire(ConflictResolution::RemoteWins, "remote-wins");
+        assert_wire(ConflictResolution::KeepBoth, "keep-both");
+    }
+
+    #[test]
+    fn folder_sync_status_serializes_to_stable_strings() {
+        // Serialize-only enum (emitted to the frontend, never deserialized).
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::Idle).unwrap(),
+            "\"idle\""
+        );
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::Syncing).unwrap(),
+            "\"syncing\""
+        );
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::Watching).unwrap(),
+            "\"watching\""
+        );
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::Error).unwrap(),
+            "\"error\""
+        );
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::Paused).unwrap(),
+            "\"paused\""
+        );
+        assert_eq!(
+            serde_json::to_string(&FolderSyncStatus::DryRun).unwrap(),
+            "\"dry-run\""
+        );
+    }
+
+    #[test]
+    fn sync_and_transfer_mode_deserialize_from_stable_strings() {
+        // Deserialize-only enums (transient RPC inputs from the frontend).
+        assert_eq!(
+            serde_json::from_str::<SyncMode>("\"mirror\"").unwrap(),
+            SyncMode::Mirror
+        );
+        assert_eq!(
+            serde_json::from_str::<SyncMode>("\"additive\"").unwrap(),
+            SyncMode::Additive
+        );
+        assert_eq!(
+            serde_json::from_str::<SyncMode>("\"overwrite\"").unwrap(),
+            SyncMode::Overwrite
+        );
+        assert_eq!(
+            serde_json::from_str::<TransferMode>("\"copy\"").unwrap(),
+            TransferMode::Copy
+        );
+        assert_eq!(
+            serde_json::from_str::<TransferMode>("\"move\"").unwrap(),
+            TransferMode::Move
+        );
+    }
+
+    #[test]
+    fn sync_diff_record_sorts_lists_and_sums_totals() {
+        let entry = |key: &str, source_size: Option<i64>| SyncDiffEntryRecord {
+            id: sync_diff_entry_id(key),
+            key: key.to_string(),
+            source_size,
+            dest_size: None,
+            source_etag: None,
+            dest_etag: None,
+            source_last_modified: None,
+            dest_last_modified: None,
+            selected: true,
+        };
+        let diff = build_sync_diff_record(
+            vec![entry("b.txt", Some(20)), entry("a.txt", Some(10))],
+            vec![entry("c.txt", Some(5))],
+            vec![entry("z.txt", None), entry("y.txt", None)],
+            3,
+        );
+
+        let keys = |list: &[SyncDiffEntryRecord]| {
+            list.iter().map(|e| e.key.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&diff.to_add), vec!["a.txt", "b.txt"]);
+        assert_eq!(keys(&diff.to_delete), vec!["y.txt", "z.txt"]);
+        assert_eq!(diff.to_add_bytes, 30);
+        assert_eq!(diff.to_update_bytes, 5);
+        assert_eq!(diff.to_delete_count, 2);
+
+        assert_eq!(sync_diff_entry_id("a.txt"), sync_diff_entry_id("a.txt"));
+        assert_ne!(sync_diff_entry_id("a.txt"), sync_diff_entry_id("b.txt"));
+        assert_eq!(sync_diff_entry_id("a.txt").len(), 32);
+    }
+
+    #[test]
+    fn confirmation_tokens_are_single_use_and_bound_to_their_action() {
+        let state = AppState::default();
+        let token = issue_confirmation_token(&state, "delete a").unwrap();
+        assert!(consume_confirmation_token(&state, "delete b", &token).is_err());
+        // The mismatched attempt spent it.
+        assert!(consume_confirmation_token(&state, "delete a", &token).is_err());
+
+        let token = issue_confirmation_token(&state, "delete a").unwrap();
+        assert!(consume_confirmation_token(&state, "delete a", &token).is_ok());
+        assert!(consume_confirmation_token(&state, "delete a", &token).is_err());
+    }
+
+    #[test]
+    fn delete_failure_summary_caps_listed_messages() {
+        let failures: Vec<(String, String)> = (0..7)
+            .map(|i| (format!("k{i}"), format!("failed k{i}")))
+            .collect();
+        let summary = summarize_delete_failures(&failures, 2000);
+        assert!(summary.starts_with("7 of 2000 deletes failed: failed k0; "));
+        assert!(summary.contains("failed k4"));
+        assert!(!summary.contains("failed k5"));
+        assert!(summary.ends_with("(+2 more)"));
+    }
+
+    #[test]
+    fn cancellable_operations_time_out_and_cancel() {
+        let state = AppState::default();
+
+        let hung = run_cancellable_operation(
+            &state,
+            None,
+            StdDuration::from_millis(10),
+            "presign s3://b/k",
+            std::future::pending::<Result<(), String>>(),
+        );
+        let result = tauri::async_runtime::block_on(hung);
+        assert!(result.unwrap_err().starts_with("Timed out"));
+
+        let cancelled = run_cancellable_operation(
+            &state,
+            Some("op-1"),
+            StdDuration::from_secs(5),
+            "presign s3://b/k",
+            async {
+                assert!(cancel_operation(&state, "op-1").unwrap());
+                tokio::time::sleep(StdDuration::from_secs(5)).await;
+                Ok(())
+            },
+        );
+        let result = tauri::async_runtime::block_on(cancelled);
+        assert_eq!(result.unwrap_err(), OPERATION_CANCELLED);
+        // Finished operations leave nothing behind to cancel.
+        assert!(!cancel_operation(&state, "op-1").unwrap());
+    }
+
+    #[test]
+    fn mode_metadata_parses_decimal_and_octal() {
+        // s3fs stores st_mode in decimal; the file type bits are dropped.
+        assert_eq!(parse_mode_metadata("33188"), Some(0o644));
+        assert_eq!(parse_mode_metadata("0755"), Some(0o755));
+        assert_eq!(parse_mode_metadata(" 0o600 "), Some(0o600));
+        assert_eq!(parse_mode_metadata("0"), None);
+        assert_eq!(parse_mode_metadata("04755"), Some(0o755));
+        assert_eq!(parse_mode_metadata("rwxr-xr-x"), None);
+    }
+
+    #[test]
+    fn stream_chunks_are_capped_and_carry_over() {
+        let mut buffer = Vec::new();
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        push_stream_chunks(&mut buffer, b"abcdefg", 3, |chunk| chunks.push(chunk.to_vec()));
+        assert_eq!(chunks, vec![b"abc".to_vec(), b"def".to_vec()]);
+        assert_eq!(buffer, b"g");
+
+        push_stream_chunks(&mut buffer, b"hi", 3, |chunk| chunks.push(chunk.to_vec()));
+        assert_eq!(chunks.last().unwrap(), b"ghi");
+        assert!(buffer.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn folder_upload_skips_and_reports_special_files() {
+        let dir = std::env::temp_dir().join(format!("object0-upload-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/a.txt"), b"a").unwrap();
+        std::os::unix::fs::symlink(dir.join("sub/a.txt"), dir.join("link.txt")).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing"), dir.join("broken")).unwrap();
+        let _socket = std::os::unix::net::UnixListener::bind(dir.join("sock")).unwrap();
+
+        let (files, skipped) = collect_folder_upload_files(&dir, false);
+        assert_eq!(files, vec![dir.join("sub/a.txt")]);
+        let mut reasons: Vec<(String, String)> = skipped
+            .into_iter()
+            .map(|skip| (skip.path, skip.reason))
+            .collect();
+        reasons.sort();
+        assert_eq!(
+            reasons,
+            vec![
+                ("broken".to_string(), "symlink (not followed)".to_string()),
+                ("link.txt".to_string(), "symlink (not followed)".to_string()),
+                ("sock".to_string(), "socket".to_string()),
+            ]
+        );
+
+        let (mut files, skipped) = collect_folder_upload_files(&dir, true);
+        files.sort();
+        assert_eq!(files, vec![dir.join("link.txt"), dir.join("sub/a.txt")]);
+        assert!(skipped
+            .iter()
+            .any(|skip| skip.path == "broken" && skip.reason.starts_with("unreadable")));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn bucket_lifecycle_rules_are_validated() {
+        let transition = |days: i32, storage_class: &str| BucketLifecycleTransition {
+            days,
+            date: None,
+            storage_class: storage_class.to_string(),
+        };
+        let rule = |transitions: Vec<BucketLifecycleTransition>, expiration_days: Option<i32>| {
+            BucketLifecycleRule {
+                enabled: true,
+                prefix: Some("logs/".to_string()),
+                transitions,
+                expiration_days,
+                ..BucketLifecycleRule::default()
+            }
+        };
+        assert!(validate_lifecycle_rules(&[]).is_ok());
+        assert!(validate_lifecycle_rules(&[rule(
+            vec![transition(30, "STANDARD_IA"), transition(90, "GLACIER")],
+            Some(365)
+        )])
+        .is_ok());
+        assert!(validate_lifecycle_rules(&[rule(Vec::new(), None)])
+            .is_err_and(|err| err.contains("needs a transition or an expiration")));
+        assert!(validate_lifecycle_rules(&[rule(vec![transition(30, "glacier")], None)])
+            .is_err_and(|err| err.contains("unsupported storage class glacier")));
+        assert!(validate_lifecycle_rules(&[rule(vec![transition(90, "GLACIER")], Some(30))])
+            .is_err_and(|err| err.contains("after every transition")));
+        assert!(validate_lifecycle_rules(&[rule(Vec::new(), Some(0))]).is_err());
+
+        let mut named = rule(Vec::new(), Some(7));
+        named.id = Some("expire-logs".to_string());
+        assert!(validate_lifecycle_rules(&[named.clone(), named])
+            .is_err_and(|err| err.contains("Duplicate lifecycle rule id")));
+
+        let parsed: BucketLifecycleRule = serde_json::from_value(json!({
+            "expirationDays": 30
+        }))
+        .unwrap();
+        assert!(parsed.enabled && parsed.prefix.is_none() && parsed.tags.is_empty());
+
+        let versioned = BucketLifecycleRule {
+            noncurrent_transitions: vec![BucketLifecycleNoncurrentTransition {
+                noncurrent_days: 30,
+                storage_class: "GLACIER".to_string(),
+                newer_noncurrent_versions: None,
+            }],
+            noncurrent_expiration_days: Some(90),
+            abort_incomplete_multipart_days: Some(7),
+            ..rule(Vec::new(), None)
+        };
+        assert!(validate_lifecycle_rules(&[versioned.clone()]).is_ok());
+        let sdk = lifecycle_rule_to_sdk(&versioned).unwrap();
+        assert_eq!(lifecycle_rule_from_sdk(&sdk).unwrap(), versioned);
+
+        let dated = BucketLifecycleRule {
+            expiration_date: Some("2030-01-01T00:00:00+00:00".to_string()),
+            ..rule(Vec::new(), None)
+        };
+        assert!(validate_lifecycle_rules(&[dated.clone()]).is_ok());
+        let sdk = lifecycle_rule_to_sdk(&dated).unwrap();
+        assert_eq!(lifecycle_rule_from_sdk(&sdk).unwrap(), dated);
+
+        let sized = BucketLifecycleRule {
+            object_size_greater_than: Some(1024),
+            ..rule(Vec::new(), Some(30))
+        };
+        assert!(validate_lifecycle_rules(&[sized.clone()]).is_ok());
+        let sdk = lifecycle_rule_to_sdk(&sized).unwrap();
+        assert_eq!(lifecycle_rule_from_sdk(&sdk).unwrap(), sized);
+        assert!(validate_lifecycle_rules(&[BucketLifecycleRule {
+            object_size_less_than: Some(1024),
+            ..sized.clone()
+        }])
+        .is_err_and(|err| err.contains("below the maximum")));
+
+        // A rule scoped the pre-filter way keeps its prefix.
+        #[allow(deprecated)]
+        let legacy = aws_sdk_s3::types::LifecycleRule::builder()
+            .prefix("logs/")
+            .status(aws_sdk_s3::types::ExpirationStatus::Enabled)
+            .expiration(
+                aws_sdk_s3::types::LifecycleExpiration::builder()
+                    .days(30)
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            lifecycle_rule_from_sdk(&legacy).unwrap(),
+            rule(Vec::new(), Some(30))
+        );
+        assert!(validate_lifecycle_rules(&[BucketLifecycleRule {
+            expiration_date: Some("next year".to_string()),
+            ..rule(Vec::new(), None)
+        }])
+        .is_err());
+        assert!(validate_lifecycle_rules(&[BucketLifecycleRule {
+            abort_incomplete_multipart_days: Some(0),
+            ..rule(Vec::new(), None)
+        }])
+        .is_err());
+    }
+
+    #[test]
+    fn prefix_diffs_report_added_changed_and_removed_keys() {
+        let object = |key: &str, size: i64, etag: &str| RemoteObject {
+            key: key.to_string(),
+            size,
+            etag: etag.to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        let base: HashMap<String, SyncObjectInfo> = [
+            object("v1/same.txt", 3, "a"),
+            object("v1/changed.txt", 3, "b"),
+            object("v1/gone.txt", 3, "c"),
+        ]
+        .into_iter()
+        .filter_map(|item| sync_object_entry(item, "v1/"))
+        .collect();
+        let mut fold = SyncDiffFold::new(base, SyncMode::Mirror, ArchivedObjectPolicy::Attempt);
+        for item in [
+            object("v2/same.txt", 3, "a"),
+            object("v2/changed.txt", 4, "d"),
+            object("v2/new/file.txt", 5, "e"),
+        ] {
+            let (key, info) = sync_object_entry(item, "v2/").unwrap();
+            fold.push(key, &info);
+        }
+        let diff = fold.finish();
+        let keys = |entries: &[SyncDiffEntryRecord]| {
+            entries
+                .iter()
+                .map(|entry| entry.key.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&diff.to_add), ["new/file.txt"]);
+        assert_eq!(keys(&diff.to_update), ["changed.txt"]);
+        assert_eq!(keys(&diff.to_delete), ["gone.txt"]);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.to_update[0].dest_size, Some(3));
+        assert_eq!(diff.to_update[0].source_size, Some(4));
+    }
+
+    #[test]
+    fn key_transforms_round_trip_and_are_validated() {
+        let transform = KeyTransform {
+            prefix: "{host}/{date}/".to_string(),
+            suffix: ".bak".to_string(),
+        };
+        assert!(validate_key_transform(&transform).is_ok());
+        let resolved = expand_key_transform(&transform, "laptop", "2024-01-01");
+        let key = resolved.apply("photos/a.jpg");
+        assert_eq!(key, "laptop/2024-01-01/photos/a.jpg.bak");
+        assert_eq!(resolved.strip(&key), Some("photos/a.jpg"));
+        assert_eq!(resolved.strip("laptop/2024-01-01/photos/a.jpg"), None);
+        assert_eq!(resolved.strip("laptop/2024-01-01/.bak"), None);
+        assert_eq!(ResolvedKeyTransform::default().apply("a.txt"), "a.txt");
+
+        let unknown = KeyTransform {
+            prefix: "{user}/".to_string(),
+            suffix: String::new(),
+        };
+        assert!(validate_key_transform(&unknown)
+            .is_err_and(|err| err.contains("Unknown key prefix token")));
+        let nested_suffix = KeyTransform {
+            prefix: String::new(),
+            suffix: "/x".to_string(),
+        };
+        assert!(validate_key_transform(&nested_suffix).is_err());
+
+        // Sync rules pin {host} when saved and can't use {date}.
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert!(rule.key_transform.is_none());
+        rule.key_transform = Some(KeyTransform {
+            prefix: "{host}/".to_string(),
+            suffix: String::new(),
+        });
+        assert!(validate_folder_sync_rule(&rule).is_err_and(|err| err.contains("{host}")));
+        pin_folder_sync_key_host(&mut rule);
+        assert_eq!(
+            rule.key_transform.as_ref().map(|transform| transform.prefix.clone()),
+            Some(format!("{}/", local_hostname()))
+        );
+        assert!(validate_folder_sync_rule(&rule).is_ok());
+        rule.key_transform = Some(transform);
+        assert!(validate_folder_sync_rule(&rule).is_err_and(|err| err.contains("{date}")));
+    }
+
+    #[test]
+    fn object_listings_sort_by_field_and_direction() {
+        let entry = |key: &str, size: i64, last_modified: &str| ObjectListEntry {
+            key: key.to_string(),
+            size,
+            last_modified: last_modified.to_string(),
+            etag: String::new(),
+            storage_class: "STANDARD".to_string(),
+        };
+        let listing = ObjectListing {
+            objects: vec![
+                entry("b.txt", 10, "2024-01-02T00:00:00Z"),
+                entry("a.txt", 10, "2024-01-03T00:00:00Z"),
+                entry("c.txt", 5, "2024-01-01T00:00:00Z"),
+            ],
+            prefixes: vec!["y/".to_string(), "x/".to_string()],
+            is_truncated: false,
+            next_cursor: Some("c.txt".to_string()),
+        };
+        let keys = |listing: &ObjectListing| {
+            listing
+                .objects
+                .iter()
+                .map(|entry| entry.key.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let mut by_size = listing.clone();
+        sort_object_listing(&mut by_size, ObjectSortField::Size, SortDirection::Desc);
+        assert_eq!(keys(&by_size), "b.txt,a.txt,c.txt");
+        assert_eq!(by_size.prefixes, ["x/", "y/"]);
+        assert_eq!(by_size.next_cursor.as_deref(), Some("c.txt"));
+
+        let mut by_date = listing.clone();
+        sort_object_listing(&mut by_date, ObjectSortField::LastModified, SortDirection::Asc);
+        assert_eq!(keys(&by_date), "c.txt,b.txt,a.txt");
+
+        let mut by_key = listing;
+        sort_object_listing(&mut by_key, ObjectSortField::Key, SortDirection::Desc);
+        assert_eq!(keys(&by_key), "c.txt,b.txt,a.txt");
+        assert_eq!(by_key.prefixes, ["y/", "x/"]);
+
+        let input: ObjectsListInput = serde_json::from_value(json!({
+            "profileId": "p",
+            "bucket": "b",
+            "sortField": "lastModified"
+        }))
+        .unwrap();
+        assert_eq!(input.sort_field, Some(ObjectSortField::LastModified));
+        assert_eq!(input.sort_dir, SortDirection::Asc);
+        assert!(!input.fetch_all);
+    }
+
+    #[test]
+    fn vault_key_material_is_wiped() {
+        // Zeroizing's drop runs this same wipe.
+        let mut key = derive_key("pw", &[0u8; SALT_BYTES]);
+        assert_ne!(*key, [0u8; KEY_BYTES]);
+        key.zeroize();
+        assert_eq!(*key, [0u8; KEY_BYTES]);
+
+        let mut vault = VaultRuntime {
+            unlocked: true,
+            data: Some(VaultData::default()),
+            key: Some(derive_key("pw", &[1u8; SALT_BYTES])),
+            kdf: VAULT_KDF_PBKDF2,
+            salt: Some(vec![1u8; SALT_BYTES]),
+            recovery_key: Some(derive_key("recovery", &[2u8; SALT_BYTES])),
+            recovery_salt: Some(vec![2u8; SALT_BYTES]),
+            last_activity: None,
+        };
+        lock_vault_runtime(&mut vault);
+        assert!(!vault.unlocked && vault.data.is_none());
+        assert!(vault.key.is_none() && vault.recovery_key.is_none());
+        assert!(vault.salt.is_none() && vault.recovery_salt.is_none());
+    }
+
+    #[test]
+    fn checksum_required_errors_are_detected() {
+        assert!(validate_checksum_options(true, false).is_ok());
+        assert!(validate_checksum_options(true, true).is_err());
+        assert_eq!(content_md5(b"abc"), "kAFQmDzST7DWlj99KOF/cg==");
+        assert!(is_checksum_required_error(Some("MissingContentMD5"), None));
+        assert!(is_checksum_required_error(
+            Some("InvalidRequest"),
+            Some("Content-MD5 OR x-amz-checksum- HTTP header is required for Put Object requests with Object Lock parameters"),
+        ));
+        assert!(!is_checksum_required_error(
+            Some("InvalidRequest"),
+            Some("The storage class you specified is not valid"),
+        ));
+        assert!(!is_checksum_required_error(Some("AccessDenied"), Some("checksum required")));
+        assert!(s3_error_message(S3ErrorKind::ChecksumRequired, "upload a", String::new())
+            .starts_with(CHECKSUM_REQUIRED));
+    }
+
+    #[test]
+    fn empty_folder_dirs_are_collected() {
+        let dir = std::env::temp_dir().join(format!("object0-empty-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("a/empty")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        fs::write(dir.join("b/file.txt"), b"x").unwrap();
+
+        let mut empty = collect_empty_folder_dirs(&dir, false);
+        empty.sort();
+        assert_eq!(empty, vec!["a/empty".to_string()]);
+
+        let transform = ResolvedKeyTransform {
+            prefix: "host/".to_string(),
+            suffix: ".bak".to_string(),
+        };
+        assert_eq!(transform.apply("up/a/empty/"), "host/up/a/empty/");
+        assert_eq!(transform.apply("up/b/file.txt"), "host/up/b/file.txt.bak");
+
+        let lone = dir.join("a/empty");
+        assert_eq!(collect_empty_folder_dirs(&lone, false), vec![String::new()]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn job_progress_events_are_throttled() {
+        let start = Instant::now();
+        let soon = start + StdDuration::from_millis(JOB_PROGRESS_EMIT_INTERVAL_MS / 2);
+        let later = start + StdDuration::from_millis(JOB_PROGRESS_EMIT_INTERVAL_MS);
+        assert!(should_emit_job_progress(None, start, 0, 100));
+        assert!(!should_emit_job_progress(Some(start), soon, 50, 100));
+        assert!(should_emit_job_progress(Some(start), later, 60, 100));
+        // The final chunk is never held back.
+        assert!(should_emit_job_progress(Some(start), soon, 100, 100));
+        assert!(!should_emit_job_progress(Some(start), soon, 0, 0));
+    }
+
+    #[test]
+    fn job_history_is_salvaged_from_a_damaged_file() {
+        let job = |id: &str, status: &str| {
+            format!(
+                r#"{{"id":"{id}","type":"upload","status":"{status}","fileName":"a.txt","description":"Upload","bytesTransferred":1,"bytesTotal":1,"percentage":100,"speed":0,"eta":0,"error":null,"createdAt":"2026-01-01T00:00:00Z","startedAt":null,"completedAt":null}}"#
+            )
+        };
+        let raw = format!(
+            "[{},{{\"id\":\"broken\",\"type\":,{},{},{},{}",
+            job("a", "completed"),
+            job("b", "failed"),
+            job("b", "failed"),
+            job("c", "running"),
+            &job("d", "completed")[..40],
+        );
+        assert!(serde_json::from_str::<Vec<JobInfo>>(&raw).is_err());
+
+        let ids: Vec<String> = salvage_job_history(&raw)
+            .into_iter()
+            .map(|job| job.id)
+            .collect();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn endpoint_options_are_aws_only() {
+        assert!(validate_endpoint_options(&S3Provider::R2, None, false, false).is_ok());
+        assert!(validate_endpoint_options(&S3Provider::Aws, None, true, true).is_ok());
+        assert!(validate_endpoint_options(&S3Provider::Aws, Some(" "), true, false).is_ok());
+        assert!(validate_endpoint_options(&S3Provider::Minio, None, false, true).is_err());
+        assert!(
+            validate_endpoint_options(&S3Provider::Aws, Some("https://s3.example.com"), true, false)
+                .is_err()
+        );
+        assert!(is_accelerate_not_configured_error(
+            Some("InvalidRequest"),
+            Some("S3 Transfer Acceleration is not configured on this bucket"),
+        ));
+        assert!(!is_accelerate_not_configured_error(Some("InvalidRequest"), None));
+    }
+
+    #[test]
+    fn folder_sync_change_origin_compares_against_the_sync_record() {
+        let known = FolderSyncFileRecord {
+            relative_path: "a.txt".to_string(),
+            local_mtime: 10,
+            local_size: 5,
+            remote_etag: "etag-1".to_string(),
+            remote_last_modified: "2026-01-01T00:00:00Z".to_string(),
+            remote_size: 5,
+            synced_at: "2026-01-01T00:00:00Z".to_string(),
+            local_relative_path: None,
+        };
+        let entry = |local: Option<(i64, i64)>, remote: Option<(i64, &str)>| {
+            FolderSyncDiffEntryRecord {
+                relative_path: "a.txt".to_string(),
+                action: "upload".to_string(),
+                reason: String::new(),
+                local_size: local.map(|(size, _)| size),
+                local_mtime: local.map(|(_, mtime)| mtime),
+                remote_size: remote.map(|(size, _)| size),
+                remote_last_modified: None,
+                remote_etag: remote.map(|(_, etag)| etag.to_string()),
+                local_relative_path: None,
+            }
+        };
+
+        let local_edit = entry(Some((6, 20)), Some((5, "etag-1")));
+        assert_eq!(folder_sync_change_origin(&local_edit, Some(&known)), "local");
+        let remote_edit = entry(Some((5, 10)), Some((7, "etag-2")));
+        assert_eq!(folder_sync_change_origin(&remote_edit, Some(&known)), "remote");
+        let both = entry(Some((6, 20)), Some((7, "etag-2")));
+        assert_eq!(folder_sync_change_origin(&both, Some(&known)), "both");
+        let remote_deleted = entry(Some((5, 10)), None);
+        assert_eq!(folder_sync_change_origin(&remote_deleted, Some(&known)), "remote");
+        assert_eq!(folder_sync_change_origin(&local_edit, None), "untracked");
+    }
+
+    #[test]
+    fn disk_space_checks_use_the_nearest_existing_folder() {
+        let missing = std::env::temp_dir().join(format!("object0-space-{}/a/b", Uuid::new_v4()));
+        assert!(available_disk_space(&missing).unwrap() > 0);
+        assert!(ensure_disk_space(&missing, 0).is_ok());
+        let err = ensure_disk_space(&missing, i64::MAX).unwrap_err();
+        assert!(err.starts_with("Not enough disk space"), "{err}");
+
+        assert_eq!(format_byte_size(512), "512 B");
+        assert_eq!(format_byte_size(1536), "1.5 KB");
+        assert_eq!(format_byte_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn copy_source_keys_are_encoded_once() {
+        assert_eq!(copy_source_header("b", "dir/file.txt"), "b/dir/file.txt");
+        assert_eq!(copy_source_header("b", "a b+c.txt"), "b/a%20b%2Bc.txt");
+        assert_eq!(copy_source_header("b", "my-file_v1.~tmp"), "b/my-file_v1.~tmp");
+        assert_eq!(copy_source_header("b", "café/日.txt"), "b/caf%C3%A9/%E6%97%A5.txt");
+        // Already-encoded-looking keys are literal: `%` is escaped, not kept.
+        assert_eq!(copy_source_header("b", "a%20b%2F.txt"), "b/a%2520b%252F.txt");
+        assert_eq!(copy_source_header("b", "q?x=1&y#z"), "b/q%3Fx%3D1%26y%23z");
+    }
+
+    #[test]
+    fn legacy_config_is_copied_then_removed_on_confirmation() {
+        let root = std::env::temp_dir().join(format!("object0-legacy-{}", Uuid::new_v4()));
+        let (source, dest) = (root.join("old"), root.join("new"));
+        fs::create_dir_all(source.join("folder-sync")).unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(source.join("vault.enc"), b"vault").unwrap();
+        fs::write(source.join("folder-sync/rule.json"), b"[]").unwrap();
+        fs::write(source.join("favorites.json"), b"[\"old\"]").unwrap();
+        fs::write(dest.join("favorites.json"), b"[\"new\"]").unwrap();
+        assert!(config_dir_is_empty(&root));
+        assert!(!config_dir_is_empty(&source));
+
+        let copied = migrate_legacy_config(&source, &dest, false).unwrap();
+        assert_eq!(copied.copied, vec!["vault.enc", "folder-sync"]);
+        assert_eq!(copied.skipped, vec!["favorites.json"]);
+        assert!(copied.removed.is_empty());
+        assert_eq!(fs::read(dest.join("folder-sync/rule.json")).unwrap(), b"[]");
+        assert_eq!(fs::read(dest.join("favorites.json")).unwrap(), b"[\"new\"]");
+        assert!(source.join("vault.enc").exists());
+
+        let moved = migrate_legacy_config(&source, &dest, true).unwrap();
+        assert_eq!(moved.removed, vec!["vault.enc", "folder-sync"]);
+        assert_eq!(moved.skipped, vec!["favorites.json"]);
+        assert!(!source.join("vault.enc").exists());
+        assert!(source.join("favorites.json").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cancel_reasons_are_spelled_out() {
+        assert_eq!(CancelReason::User.message(), "Job cancelled by user");
+        assert_eq!(CancelReason::VaultLocked.message(), "Job cancelled: vault locked");
+        assert_eq!(CancelReason::Shutdown.message(), "Job cancelled: app shutting down");
+        assert_eq!(json!(CancelReason::AbortAll), json!("abort-all"));
+
+        // History written before the field existed still loads.
+        let job: JobInfo = serde_json::from_value(json!({
+            "id": "a",
+            "type": "upload",
+            "status": "cancelled",
+            "fileName": "a.txt",
+            "description": "Upload",
+            "bytesTransferred": 0,
+            "bytesTotal": 0,
+            "percentage": 0,
+            "speed": 0,
+            "eta": 0,
+            "error": "Job cancelled",
+            "createdAt": "2026-01-01T00:00:00Z",
+            "startedAt": null,
+            "completedAt": null,
+        }))
+        .unwrap();
+        assert_eq!(job.cancel_reason, None);
+    }
+
+    #[test]
+    fn legacy_favorites_migrate_to_bookmarks() {
+        let favorites = vec![
+            "p1:photos".to_string(),
+            "p1:photos".to_string(),
+            "p2:bucket:with:colons".to_string(),
+            "no-separator".to_string(),
+            ":orphan".to_string(),
+        ];
+        let bookmarks = bookmarks_from_legacy_favorites(&favorites);
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[0].profile_id, "p1");
+        assert_eq!(bookmarks[0].bucket, "photos");
+        assert_eq!(bookmarks[0].prefix, "");
+        assert_eq!(bookmarks[1].bucket, "bucket:with:colons");
+
+        let file: BookmarksFile = serde_json::from_value(json!({
+            "version": BOOKMARKS_VERSION,
+            "bookmarks": [{ "profileId": "p1", "bucket": "b" }],
+        }))
+        .unwrap();
+        assert_eq!(file.bookmarks[0].prefix, "");
+        assert_eq!(file.bookmarks[0].label, None);
+    }
+
+    #[test]
+    fn sync_diff_fold_streams_the_source_side() {
+        let object = |key: &str, size: i64| RemoteObject {
+            key: key.to_string(),
+            size,
+            etag: "e".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        assert!(sync_object_entry(object("src/", 0), "src/").is_none());
+        assert!(sync_object_entry(object("src/dir/", 0), "src/").is_none());
+        assert!(sync_object_entry(object("other/a", 1), "src/").is_none());
+
+        let dest: HashMap<String, SyncObjectInfo> = [object("dst/a", 1), object("dst/b", 1)]
+            .into_iter()
+            .filter_map(|item| sync_object_entry(item, "dst/"))
+            .collect();
+        let mut fold =
+            SyncDiffFold::new(dest.clone(), SyncMode::Overwrite, ArchivedObjectPolicy::Skip);
+        for item in [object("src/a", 2), object("src/c", 1)] {
+            let (key, info) = sync_object_entry(item, "src/").unwrap();
+            fold.push(key, &info);
+        }
+        let diff = fold.finish();
+        assert_eq!(diff.to_update.len(), 1);
+        assert!(diff.to_add.is_empty());
+        assert!(diff.to_delete.is_empty());
+
+        let mut fold = SyncDiffFold::new(dest, SyncMode::Mirror, ArchivedObjectPolicy::Skip);
+        let (key, info) = sync_object_entry(object("src/a", 1), "src/").unwrap();
+        fold.push(key, &info);
+        let diff = fold.finish();
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.to_delete.len(), 1);
+        assert_eq!(diff.to_delete[0].key, "b");
+    }
+
+    #[test]
+    fn archived_sources_are_skipped_unless_attempted() {
+        assert!(is_archived_storage_class(Some("GLACIER")));
+        assert!(is_archived_storage_class(Some("DEEP_ARCHIVE")));
+        assert!(!is_archived_storage_class(Some("GLACIER_IR")));
+        assert!(!is_archived_storage_class(None));
+
+        let info = |storage_class: Option<&str>| SyncObjectInfo {
+            size: 1,
+            etag: "e".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: storage_class.map(str::to_string),
+        };
+        let mut fold =
+            SyncDiffFold::new(HashMap::new(), SyncMode::Mirror, ArchivedObjectPolicy::Skip);
+        fold.push("cold".to_string(), &info(Some("GLACIER")));
+        fold.push("warm".to_string(), &info(None));
+        let diff = fold.finish();
+        assert_eq!(diff.to_add.len(), 1);
+        assert_eq!(diff.skipped_archived[0].key, "cold");
+
+        let mut fold =
+            SyncDiffFold::new(HashMap::new(), SyncMode::Mirror, ArchivedObjectPolicy::Attempt);
+        fold.push("cold".to_string(), &info(Some("DEEP_ARCHIVE")));
+        assert_eq!(fold.finish().to_add.len(), 1);
+
+        let input: SyncInput = serde_json::from_value(json!({
+            "sourceProfileId": "a",
+            "sourceBucket": "b",
+            "sourcePrefix": "",
+            "destProfileId": "c",
+            "destBucket": "d",
+            "destPrefix": "",
+            "mode": "mirror",
+        }))
+        .unwrap();
+        assert_eq!(input.archived_objects, ArchivedObjectPolicy::Skip);
+    }
+
+    #[test]
+    fn case_collisions_keep_the_local_file_and_rename_stably() {
+        let local = |path: &str| LocalFileInfo {
+            relative_path: path.to_string(),
+            size: 1,
+            mtime_ms: 1,
+        };
+        let remote = || RemoteFileInfo {
+            size: 1,
+            etag: "e".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        let local_map: HashMap<String, LocalFileInfo> =
+            [("docs/foo.txt".to_string(), local("docs/foo.txt"))].into();
+        let remote_map: HashMap<String, RemoteFileInfo> = [
+            ("docs/Foo.txt".to_string(), remote()),
+            ("docs/foo.txt".to_string(), remote()),
+            ("docs/FOO.txt".to_string(), remote()),
+            ("other.txt".to_string(), remote()),
+        ]
+        .into();
+        assert_eq!(
+            find_case_collisions(&local_map, &remote_map),
+            vec![vec!["docs/foo.txt", "docs/FOO.txt", "docs/Foo.txt"]]
+        );
+
+        let renamed = case_collision_copy_name("docs/Foo.txt");
+        assert!(renamed.starts_with("docs/Foo (case "));
+        assert!(renamed.ends_with(").txt"));
+        assert_eq!(renamed, case_collision_copy_name("docs/Foo.txt"));
+        assert_ne!(renamed, case_collision_copy_name("docs/FOO.txt"));
+    }
+
+    #[test]
+    fn effective_settings_tag_where_each_value_came_from() {
+        let stored = json!({ "runInBackground": true, "notificationsEnabled": true });
+        let entries = effective_settings_entries(Some(&stored));
+        assert_eq!(entries["runInBackground"]["value"], json!(true));
+        assert_eq!(entries["runInBackground"]["default"], json!(false));
+        assert_eq!(entries["runInBackground"]["source"], json!("user"));
+        // Set explicitly, even though it matches the default.
+        assert_eq!(entries["notificationsEnabled"]["source"], json!("user"));
+        assert_eq!(entries["pauseSyncOnMetered"]["source"], json!("default"));
+
+        // An unparseable file loads as defaults, so nothing is "user".
+        let broken = json!({ "runInBackground": "yes" });
+        let entries = effective_settings_entries(Some(&broken));
+        assert_eq!(entries["runInBackground"]["value"], json!(false));
+        assert_eq!(entries["runInBackground"]["source"], json!("default"));
+        assert_eq!(effective_settings_entries(None).len(), entries.len());
+    }
+
+    #[test]
+    fn settings_are_versioned_and_keep_unknown_fields() {
+        let legacy: AppSettings =
+            serde_json::from_value(json!({ "runInBackground": true })).unwrap();
+        assert_eq!(legacy.version, 0);
+        let upgraded = upgrade_settings(legacy);
+        assert_eq!(upgraded.version, SETTINGS_VERSION);
+        assert!(upgraded.run_in_background);
+
+        // A newer build's file keeps its version and fields through a save.
+        let newer: AppSettings = serde_json::from_value(json!({
+            "version": SETTINGS_VERSION + 1,
+            "bandwidthLimit": 1024,
+        }))
+        .unwrap();
+        let merged = merge_settings_update(
+            &upgrade_settings(newer),
+            json!({ "notificationsEnabled": false, "version": 0 })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(merged.version, SETTINGS_VERSION + 1);
+        assert!(!merged.notifications_enabled);
+        let saved = json!(merged);
+        assert_eq!(saved["bandwidthLimit"], json!(1024));
+        assert!(effective_settings_entries(Some(&saved)).get("bandwidthLimit").is_none());
+
+        let bad = json!({ "runInBackground": "yes" }).as_object().cloned().unwrap();
+        assert!(merge_settings_update(&AppSettings::default(), bad).is_err());
+        for secs in [0, VAULT_AUTO_LOCK_MIN_SECS - 1] {
+            let too_short = json!({ "autoLockSecs": secs }).as_object().cloned().unwrap();
+            assert!(merge_settings_update(&AppSettings::default(), too_short).is_err());
+        }
+    }
+
+    #[test]
+    fn quoted_and_unquoted_etags_compare_equal() {
+        assert_eq!(normalize_etag(Some("\"abc\"")), "abc");
+        assert_eq!(normalize_etag(Some("W/\"abc\"")), "abc");
+        assert_eq!(normalize_etag(Some(" abc ")), "abc");
+        assert_eq!(normalize_etag(None), "");
+
+        // An older build's sync record held the quoted HEAD value; the listing
+        // reports it bare. The file must read as unchanged, not remote-edited.
+        let rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        let records = parse_folder_sync_file_records(
+            &json!([{
+                "relativePath": "a.txt",
+                "localMtime": 1_000,
+                "localSize": 3,
+                "remoteEtag": "\"abc\"",
+                "remoteLastModified": "2024-01-01T00:00:00Z",
+                "remoteSize": 3,
+                "syncedAt": "2024-01-01T00:00:00Z",
+            }])
+            .to_string(),
+        );
+        let local = LocalFileInfo {
+            relative_path: "a.txt".to_string(),
+            size: 3,
+            mtime_ms: 1_000,
+        };
+        let remote = RemoteFileInfo {
+            size: 3,
+            etag: normalize_etag(Some("abc")),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        assert_eq!(
+            resolve_folder_sync_action(Some(&local), Some(&remote), records.first(), &rule, None),
+            None
+        );
+    }
+
+    #[test]
+    fn manual_only_rules_drop_the_watcher_unless_asked() {
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert!(!rule.manual_only);
+        assert!(folder_sync_rule_watches_local(&rule));
+
+        rule.manual_only = true;
+        assert!(!folder_sync_rule_watches_local(&rule));
+        rule.manual_watch = true;
+        assert!(folder_sync_rule_watches_local(&rule));
+        rule.direction = SyncDirection::RemoteToLocal;
+        assert!(!folder_sync_rule_watches_local(&rule));
+
+        assert_eq!(serde_json::to_value(FolderSyncStatus::Manual).unwrap(), json!("manual"));
+    }
+
+    #[test]
+    fn folder_sync_rules_cannot_overlap_local_roots() {
+        assert!(local_roots_overlap(Path::new("/data/sync"), Path::new("/data/sync/")));
+        assert!(local_roots_overlap(Path::new("/data"), Path::new("/data/sync/photos")));
+        assert!(!local_roots_overlap(Path::new("/data/sync"), Path::new("/data/sync-2")));
+
+        let existing: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        let mut rule = existing.clone();
+        rule.id = "rule-2".to_string();
+        rule.local_path = format!("{}/nested", existing.local_path);
+        assert!(validate_folder_sync_local_root(&rule, &[existing.clone()]).is_err());
+
+        // A rule never overlaps itself, so updates in place pass.
+        rule.id = existing.id.clone();
+        assert!(validate_folder_sync_local_root(&rule, &[existing]).is_ok());
+    }
+
+    #[test]
+    fn open_external_temp_path_keeps_the_file_name_in_its_own_folder() {
+        let path = open_external_temp_path("docs/2024/report.pdf");
+        assert_eq!(path.file_name().unwrap(), "report.pdf");
+        assert_eq!(path.parent().unwrap().parent().unwrap(), open_external_dir());
+        assert_ne!(path, open_external_temp_path("docs/2024/report.pdf"));
+
+        assert_eq!(open_external_temp_path("docs/..").file_name().unwrap(), "object");
+        assert_eq!(open_external_temp_path("folder/").file_name().unwrap(), "folder");
+    }
+
+    #[test]
+    fn download_destination_follows_the_existing_file_policy() {
+        let dir = std::env::temp_dir().join(format!("object0-existing-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("report.pdf");
+        let fresh = dir.join("fresh.pdf");
+        fs::write(&target, b"old").unwrap();
+        fs::write(dir.join("report (1).pdf"), b"old").unwrap();
+
+        for policy in [
+            DownloadExistingPolicy::Overwrite,
+            DownloadExistingPolicy::Skip,
+            DownloadExistingPolicy::Rename,
+        ] {
+            assert_eq!(resolve_download_destination(&fresh, policy), Some(fresh.clone()));
+        }
+        assert_eq!(
+            resolve_download_destination(&target, DownloadExistingPolicy::Overwrite),
+            Some(target.clone())
+        );
+        assert_eq!(resolve_download_destination(&target, DownloadExistingPolicy::Skip), None);
+        assert_eq!(
+            resolve_download_destination(&target, DownloadExistingPolicy::Rename),
+            Some(dir.join("report (2).pdf"))
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn presigned_url_check_accepts_ranged_and_empty_responses() {
+        for status in [200, 206, 416] {
+            assert!(presigned_status_ok(status));
+        }
+        // Skew/expiry and signature mismatches (403), wrong region or
+        // addressing style (301/400), and a missing object (404).
+        for status in [301, 400, 403, 404, 500] {
+            assert!(!presigned_status_ok(status));
+        }
+    }
+
+    #[test]
+    fn truncated_v1_vault_reads_as_corrupt() {
+        let path = std::env::temp_dir().join(format!("object0-vault-{}.json", Uuid::new_v4()));
+        let v1 = |data: &[u8], auth_tag: &[u8]| {
+            json!({
+                "version": 1,
+                "salt": encode_base64(&[1u8; SALT_BYTES]),
+                "iv": encode_base64(&[2u8; IV_BYTES]),
+                "authTag": encode_base64(auth_tag),
+                "data": encode_base64(data),
+            })
+            .to_string()
+        };
+
+        for raw in [
+            v1(b"payload", b""),
+            v1(b"", &[0u8; GCM_TAG_BYTES]),
+            v1(b"abc", b"tag"),
+            "{\"version\": 1, \"salt\": \"AA".to_string(),
+        ] {
+            fs::write(&path, raw).unwrap();
+            let err = unlock_with_passphrase(&path, "secret").err().unwrap();
+            assert!(err.starts_with(VAULT_CORRUPT), "{err}");
+        }
+
+        // An intact file with the wrong passphrase still says so.
+        fs::write(&path, v1(b"payload", &[0u8; GCM_TAG_BYTES])).unwrap();
+        assert_eq!(unlock_with_passphrase(&path, "secret").err().unwrap(), "Invalid passphrase");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn legacy_vault_is_rewritten_as_argon2id_v4() {
+        let path = std::env::temp_dir().join(format!("object0-vault-{}.json", Uuid::new_v4()));
+        let salt = [4u8; SALT_BYTES];
+        let plaintext = serde_json::to_vec(&VaultData::default()).unwrap();
+        let (iv, ciphertext) = encrypt_payload(&derive_key("pw", &salt), &plaintext).unwrap();
+        let v3 = json!({
+            "version": 3,
+            "salt": encode_base64(&salt),
+            "iv": encode_base64(&iv),
+            "data": encode_base64(&ciphertext),
+            "recovery": null,
+        });
+        fs::write(&path, v3.to_string()).unwrap();
+
+        let unlock = unlock_with_passphrase(&path, "pw").unwrap();
+        assert!(unlock.needs_rewrite);
+        assert_eq!(unlock.kdf, VAULT_KDF_ARGON2ID);
+        assert_ne!(unlock.salt, salt.to_vec());
+        let vault = VaultRuntime {
+            unlocked: true,
+            data: Some(unlock.data),
+            key: Some(unlock.key),
+            kdf: unlock.kdf,
+            salt: Some(unlock.salt),
+            recovery_key: None,
+            recovery_salt: None,
+            last_activity: None,
+        };
+        save_vault(&path, &vault).unwrap();
+
+        let VaultFileDisk::V4(v4) = read_vault_file(&path).unwrap() else {
+            panic!("expected a V4 vault");
+        };
+        assert_eq!(v4.kdf, VaultKdf::Argon2id);
+        assert_eq!(vault_kdf_config(v4.kdf, &v4.kdf_params), Ok(VAULT_KDF_ARGON2ID));
+        let again = unlock_with_passphrase(&path, "pw").unwrap();
+        assert!(!again.needs_rewrite);
+        assert!(unlock_with_passphrase(&path, "wrong").is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn vault_auto_lock_waits_for_the_idle_timeout() {
+        let start = Instant::now();
+        let mut vault = VaultRuntime {
+            unlocked: true,
+            last_activity: Some(start),
+            ..VaultRuntime::default()
+        };
+        let later = |secs| start + StdDuration::from_secs(secs);
+
+        assert!(!vault_auto_lock_due(&vault, None, later(3_600)));
+        assert!(!vault_auto_lock_due(&vault, Some(300), later(299)));
+        assert!(vault_auto_lock_due(&vault, Some(300), later(300)));
+
+        vault.unlocked = false;
+        assert!(!vault_auto_lock_due(&vault, Some(300), later(3_600)));
+        assert_eq!(
+            serde_json::from_value::<AppSettings>(json!({})).unwrap().auto_lock_secs,
+            None
+        );
+    }
+
+    #[test]
+    fn guesses_content_type_from_key_extension() {
+        assert_eq!(guess_content_type("site/index.HTML"), Some("text/html"));
+        assert_eq!(guess_content_type("photos/a.b.jpeg"), Some("image/jpeg"));
+        assert_eq!(guess_content_type("archive.unknown"), None);
+        assert_eq!(guess_content_type("docs/.env"), None);
+        assert_eq!(guess_content_type("README"), None);
+    }
+
+    #[test]
+    fn validates_upload_metadata() {
+        let metadata = HashMap::from([("X-Amz-Meta-Owner".to_string(), " ops ".to_string())]);
+        assert_eq!(
+            validate_upload_metadata(metadata).unwrap(),
+            HashMap::from([("owner".to_string(), "ops".to_string())])
+        );
+        let bad_name = HashMap::from([("has space".to_string(), "x".to_string())]);
+        assert!(validate_upload_metadata(bad_name).is_err());
+        let bad_value = HashMap::from([("note".to_string(), "caf\u{e9}".to_string())]);
+        assert!(validate_upload_metadata(bad_value).is_err());
+        let duplicate = HashMap::from([
+            ("Owner".to_string(), "a".to_string()),
+            ("owner".to_string(), "b".to_string()),
+        ]);
+        assert!(validate_upload_metadata(duplicate).is_err());
+    }
+
+    #[test]
+    fn partial_download_sits_next_to_destination() {
+        assert_eq!(
+            partial_download_path(Path::new("/tmp/out/report.pdf")),
+            PathBuf::from("/tmp/out/report.pdf.object0-partial")
+        );
+        assert_eq!(
+            partial_download_path(Path::new("notes")),
+            PathBuf::from("notes.object0-partial")
+        );
+    }
+
+    #[test]
+    fn multipart_sidecars_without_an_owner_still_load() {
+        let raw = r#"{"bucket":"b","key":"k","localPath":"/tmp/f","uploadId":"u",
+            "fileSize":10,"fileMtimeMs":null,"partSize":8,"parts":[],"createdAt":"t"}"#;
+        let sidecar: MultipartUploadSidecar = serde_json::from_str(raw).unwrap();
+        assert!(sidecar.owner.is_none());
+        assert!(!sidecar.create_only);
+        assert!(load_multipart_sidecar_by_id("../settings").is_none());
+    }
+
+    #[test]
+    fn only_single_part_etags_name_an_md5() {
+        assert_eq!(
+            etag_md5_hex(Some("\"D41D8CD98F00B204E9800998ECF8427E\"")).as_deref(),
+            Some("d41d8cd98f00b204e9800998ecf8427e")
+        );
+        assert_eq!(etag_md5_hex(Some("\"d41d8cd98f00b204e9800998ecf8427e-3\"")), None);
+        assert_eq!(etag_md5_hex(Some("not-an-md5")), None);
+        assert_eq!(etag_md5_hex(None), None);
+    }
+
+    #[test]
+    fn upload_storage_classes_are_validated() {
+        assert_eq!(parse_storage_class(" standard_ia ").unwrap(), StorageClass::StandardIa);
+        assert_eq!(parse_storage_class("GLACIER").unwrap(), StorageClass::Glacier);
+        assert!(parse_storage_class("COLD").is_err());
+    }
+
+    #[test]
+    fn bandwidth_limit_is_clamped_and_split_between_transfers() {
+        let mut jobs = JobRuntime::default();
+        set_bandwidth_limit(&mut jobs, Some(1_000));
+        assert_eq!(jobs.max_bytes_per_sec, Some(MIN_BANDWIDTH_LIMIT_BYTES_PER_SEC));
+        assert_eq!(jobs.bandwidth.limit(), Some(MIN_BANDWIDTH_LIMIT_BYTES_PER_SEC));
+
+        set_bandwidth_limit(&mut jobs, Some(1024 * 1024));
+        let mut first = jobs.bandwidth.lease();
+        let second = jobs.bandwidth.lease();
+        // Two transfers share 1 MiB/s, so 512 KiB is about a second's worth.
+        let delay = first.reserve(512 * 1024);
+        assert!(delay > StdDuration::from_millis(900) && delay <= StdDuration::from_secs(1));
+        drop(second);
+        assert_eq!(jobs.bandwidth.active_transfers.load(Ordering::SeqCst), 1);
+
+        set_bandwidth_limit(&mut jobs, None);
+        assert_eq!(jobs.bandwidth.limit(), None);
+        assert_eq!(first.reserve(usize::MAX / 2), StdDuration::ZERO);
+    }
+
+    #[test]
+    fn conflict_copy_names_are_free_and_leave_the_file_alone() {
+        let dir = std::env::temp_dir().join(format!("object0-conflict-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let local = dir.join("notes.txt");
+        fs::write(&local, b"local").unwrap();
+
+        let first = conflict_copy_name(&local, DEFAULT_CONFLICT_RENAME_TEMPLATE).unwrap();
+        assert!(local.exists());
+        assert!(!dir.join(&first).exists());
+        fs::write(dir.join(&first), b"earlier copy").unwrap();
+        let second = conflict_copy_name(&local, DEFAULT_CONFLICT_RENAME_TEMPLATE).unwrap();
+        assert_ne!(first, second);
+        assert!(!dir.join(&second).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn untracked_same_size_files_follow_the_compare_mode() {
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert_eq!(rule.compare_mode, FolderSyncCompareMode::Size);
+        let local = LocalFileInfo {
+            relative_path: "a.txt".to_string(),
+            size: 3,
+            mtime_ms: parse_iso_millis("2024-01-02T00:00:00Z").unwrap(),
+        };
+        let remote = RemoteFileInfo {
+            size: 3,
+            etag: "900150983cd24fb0d6963f7d28e17f72".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+        let action = |rule: &FolderSyncRuleRecord, matches| {
+            resolve_folder_sync_action(Some(&local), Some(&remote), None, rule, matches)
+        };
+        assert_eq!(action(&rule, None), None);
+
+        // The local copy is newer than the object, so it isn't assumed equal.
+        rule.compare_mode = FolderSyncCompareMode::MtimeSize;
+        assert!(action(&rule, None).is_some());
+
+        rule.compare_mode = FolderSyncCompareMode::Etag;
+        assert_eq!(action(&rule, Some(true)), None);
+        assert!(action(&rule, Some(false)).is_some());
+        assert!(action(&rule, None).is_some());
+    }
+
+    #[test]
+    fn local_files_match_single_and_multipart_etags() {
+        use md5::{Digest, Md5};
+
+        let dir = std::env::temp_dir().join(format!("object0-etag-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let small = dir.join("small.txt");
+        fs::write(&small, b"abc").unwrap();
+        assert!(file_matches_etag(&small, "\"900150983cd24fb0d6963f7d28e17f72\"").unwrap());
+        assert!(!file_matches_etag(&small, "\"d41d8cd98f00b204e9800998ecf8427e\"").unwrap());
+
+        let large = dir.join("large.bin");
+        let part_size = MULTIPART_PART_SIZE_BYTES;
+        let contents: Vec<u8> = (0..part_size + 10).map(|i| (i % 251) as u8).collect();
+        fs::write(&large, &contents).unwrap();
+        let mut digests = Vec::new();
+        for part in contents.chunks(part_size) {
+            digests.extend_from_slice(&Md5::digest(part));
+        }
+        let etag: String = Md5::digest(&digests)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        assert!(file_matches_etag(&large, &format!("{etag}-2")).unwrap());
+        assert!(!file_matches_etag(&large, &format!("{etag}-3")).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn folder_sync_trash_is_recognised_and_expires_by_batch() {
+        let rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert!(!rule.safe_delete);
+
+        assert!(is_folder_sync_trash_path(".object0-trash/20240101-000000/a.txt"));
+        assert!(!is_folder_sync_trash_path(".object0-trashcan/a.txt"));
+        assert!(!is_folder_sync_trash_path("docs/.object0-trash/a.txt"));
+
+        let old = (Utc::now() - Duration::days(10))
+            .format(FOLDER_SYNC_TRASH_STAMP_FORMAT)
+            .to_string();
+        assert!(folder_sync_trash_batch_expired(&old, 7));
+        assert!(!folder_sync_trash_batch_expired(&old, 30));
+        assert!(folder_sync_trash_batch_expired(&old, 0));
+        assert!(!folder_sync_trash_batch_expired("not-a-batch", 0));
+    }
+
+    #[test]
+    fn object_tags_are_validated_against_s3_limits() {
+        let tags = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        assert!(validate_object_tags(&HashMap::new()).is_ok());
+        assert!(validate_object_tags(&tags(&[("project", "object0"), ("tier", "")])).is_ok());
+        assert!(validate_object_tags(&tags(&[(" ", "x")])).is_err());
+        let long_key = "k".repeat(MAX_OBJECT_TAG_KEY_CHARS + 1);
+        assert!(validate_object_tags(&tags(&[(&long_key, "x")])).is_err());
+        let long_value = "v".repeat(MAX_OBJECT_TAG_VALUE_CHARS + 1);
+        assert!(validate_object_tags(&tags(&[("key", &long_value)])).is_err());
+        let too_many: HashMap<String, String> = (0..=MAX_OBJECT_TAGS)
+            .map(|index| (format!("key{index}"), String::new()))
+            .collect();
+        assert!(validate_object_tags(&too_many).is_err());
+    }
+
+    #[test]
+    fn object_versions_interleave_delete_markers_newest_first() {
+        let entry = |key: &str, version_id: &str, last_modified: &str, marker: bool| {
+            ObjectVersionEntry {
+                key: key.to_string(),
+                version_id: version_id.to_string(),
+                size: 0,
+                last_modified: last_modified.to_string(),
+                etag: String::new(),
+                is_latest: false,
+                is_delete_marker: marker,
+            }
+        };
+        let mut versions = vec![
+            entry("b.txt", "v1", "2026-01-01T00:00:00Z", false),
+            entry("a.txt", "v2", "2026-01-03T00:00:00Z", false),
+            entry("a.txt", "v1", "2026-01-01T00:00:00Z", false),
+            entry("a.txt", "m1", "2026-01-02T00:00:00Z", true),
+        ];
+        sort_object_versions(&mut versions);
+        let order: Vec<(&str, &str)> = versions
+            .iter()
+            .map(|version| (version.key.as_str(), version.version_id.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("a.txt", "v2"), ("a.txt", "m1"), ("a.txt", "v1"), ("b.txt", "v1")]
+        );
+
+        // Same timestamp: the latest version leads.
+        let mut versions = vec![
+            entry("a.txt", "v1", "2026-01-01T00:00:00Z", false),
+            ObjectVersionEntry {
+                is_latest: true,
+                ..entry("a.txt", "m1", "2026-01-01T00:00:00Z", true)
+            },
+        ];
+        sort_object_versions(&mut versions);
+        assert_eq!(versions[0].version_id, "m1");
+    }
+
+    #[test]
+    fn aws_shared_files_merge_into_importable_profiles() {
+        let credentials = "\
+[default]
+aws_access_key_id = AKIADEFAULT
+aws_secret_access_key = default-secret
+
+[work]
+aws_access_key_id = AKIAWORK
+aws_secret_access_key = work-secret
+aws_session_token = work-token
+";
+        let config = "\
+# comment
+[default]
+region = eu-west-2
+
+[profile work]
+region = us-east-1
+s3 =
+  endpoint_url = https://abc.r2.cloudflarestorage.com
+
+[profile sso]
+sso_start_url = https://example.awsapps.com/start
+sso_account_id = 123456789012
+
+[sso-session corp]
+sso_region = us-east-1
+";
+        let profiles = merge_aws_shared_profiles(credentials, config);
+        let names: Vec<&str> = profiles.iter().map(|profile| profile.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "work"]);
+        assert_eq!(profiles[0].region.as_deref(), Some("eu-west-2"));
+        assert_eq!(profiles[0].session_token, None);
+        assert_eq!(profiles[1].session_token.as_deref(), Some("work-token"));
+        assert_eq!(
+            profiles[1].endpoint.as_deref(),
+            Some("https://abc.r2.cloudflarestorage.com")
+        );
+        assert_eq!(aws_profile_candidate(&profiles[1]).provider, S3Provider::R2);
+        assert_eq!(provider_for_endpoint(None), S3Provider::Aws);
+        assert_eq!(
+            provider_for_endpoint(Some("http://localhost:9000")),
+            S3Provider::Custom
+        );
+    }
+
+    #[test]
+    fn folder_marker_keys_are_normalized() {
+        assert_eq!(folder_marker_key("photos").unwrap(), "photos/");
+        assert_eq!(folder_marker_key("/photos/2026//").unwrap(), "photos/2026/");
+        assert!(folder_marker_key(" / ").is_err());
+        assert!(folder_marker_key("photos/../secrets").is_err());
+        assert!(folder_marker_key("photos\\2026").is_err());
+    }
+
+    #[test]
+    fn object_search_matches_names_or_relative_keys() {
+        assert!(object_search_matches("invoice*", "", "2026/03/Invoice-042.pdf"));
+        assert!(object_search_matches("invoice", "", "2026/03/my-invoice.pdf"));
+        assert!(!object_search_matches("invoice*", "", "invoices/summary.txt"));
+        assert!(object_search_matches("invoice?-*", "docs/", "docs/invoices/invoice7-a.pdf"));
+        assert!(object_search_matches("2026/*.pdf", "docs/", "docs/2026/a.pdf"));
+        assert!(!object_search_matches("docs/*.pdf", "docs/", "docs/2026/a.pdf"));
+    }
+
+    #[test]
+    fn restore_header_and_tier_parsing() {
+        assert_eq!(
+            parse_restore_header(None, true),
+            (ObjectRestoreState::Archived, None)
+        );
+        assert_eq!(
+            parse_restore_header(None, false),
+            (ObjectRestoreState::NotArchived, None)
+        );
+        assert_eq!(
+            parse_restore_header(Some("ongoing-request=\"true\""), true),
+            (ObjectRestoreState::InProgress, None)
+        );
+        let (state, expiry) = parse_restore_header(
+            Some("ongoing-request=\"false\", expiry-date=\"Fri, 21 Dec 2012 00:00:00 GMT\""),
+            true,
+        );
+        assert_eq!(state, ObjectRestoreState::Restored);
+        assert_eq!(expiry.as_deref(), Some("2012-12-21T00:00:00+00:00"));
+        assert!(parse_restore_tier("bulk").is_ok());
+        assert!(parse_restore_tier("Instant").is_err());
+    }
+
+    #[test]
+    fn upload_encryption_falls_back_to_the_profile_default() {
+        assert_eq!(
+            parse_upload_encryption(Some(" AWS:KMS "), Some("alias/backups")).unwrap(),
+            (Some("aws:kms".to_string()), Some("alias/backups".to_string()))
+        );
+        assert_eq!(parse_upload_encryption(Some(""), None).unwrap(), (None, None));
+        assert!(parse_upload_encryption(Some("AES256"), Some("alias/backups")).is_err());
+        assert!(parse_upload_encryption(None, Some("alias/backups")).is_err());
+        assert!(parse_upload_encryption(Some("aws:kms:dsse"), None).is_err());
+
+        let profile = serde_json::from_value::<Profile>(json!({
+            "id": "p",
+            "name": "p",
+            "provider": "aws",
+            "accessKeyId": "AKIA",
+            "secretAccessKey": "secret",
+            "sse": "aws:kms",
+            "sseKmsKeyId": "alias/backups",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(
+            resolve_upload_encryption(&profile, None, None).unwrap(),
+            (
+                Some(ServerSideEncryption::AwsKms),
+                Some("alias/backups".to_string())
+            )
+        );
+        assert_eq!(
+            resolve_upload_encryption(&profile, Some("AES256"), None).unwrap(),
+            (Some(ServerSideEncryption::Aes256), None)
+        );
+    }
+
+    #[test]
+    fn persisted_jobs_round_trip_and_restart_from_scratch() {
+        let entry: PersistedJob = serde_json::from_value(json!({
+            "job": {
+                "id": "a",
+                "type": "download",
+                "status": "running",
+                "fileName": "a.txt",
+                "description": "Download",
+                "bytesTransferred": 512,
+                "bytesTotal": 1024,
+                "percentage": 50,
+                "speed": 100,
+                "eta": 5,
+                "error": null,
+                "createdAt": "2026-01-01T00:00:00Z",
+                "startedAt": "2026-01-01T00:00:01Z",
+                "completedAt": null,
+            },
+            "task": {
+                "id": "a",
+                "kind": {
+                    "download": {
+                        "profileId": "p",
+                        "bucket": "b",
+                        "key": "a.txt",
+                        "localPath": "~/Downloads/a.txt",
+                        "onExisting": "overwrite",
+                        "verifyChecksum": true,
+                        "versionId": null,
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        assert!(matches!(
+            &entry.task.kind,
+            JobTaskKind::Download { key, verify_checksum: true, .. } if key == "a.txt"
+        ));
+        let raw = serde_json::to_value(&entry).unwrap();
+        assert_eq!(raw["task"]["kind"]["download"]["profileId"], json!("p"));
+
+        let mut job = entry.job;
+        reset_requeued_job(&mut job);
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!((job.bytes_transferred, job.percentage, job.speed), (0, 0, 0));
+        assert_eq!(job.started_at, None);
+
+        let mut paused = job.clone();
+        paused.status = JobStatus::Paused;
+        paused.bytes_transferred = 256;
+        reset_requeued_job(&mut paused);
+        assert_eq!((paused.status, paused.bytes_transferred), (JobStatus::Paused, 256));
+    }
+
+    #[test]
+    fn queued_jobs_start_by_priority_then_queue_order() {
+        let task = |id: &str, priority: i64| JobTask {
+            id: id.to_string(),
+            kind: JobTaskKind::Delete {
+                profile_id: "p".to_string(),
+                bucket: "b".to_string(),
+                keys: vec![id.to_string()],
+            },
+            priority,
+        };
+        let mut queue: VecDeque<JobTask> = VecDeque::new();
+        assert_eq!(next_queued_job_index(&queue), None);
+
+        queue.extend([task("a", 0), task("b", 0), task("c", 5), task("d", 5)]);
+        let mut started = Vec::new();
+        while let Some(index) = next_queued_job_index(&queue) {
+            started.push(queue.remove(index).unwrap().id);
+        }
+        assert_eq!(started, vec!["c", "d", "a", "b"]);
+
+        // A task saved before priorities existed defaults to 0.
+        let restored: JobTask = serde_json::from_value(json!({
+            "id": "e",
+            "kind": { "delete": { "profileId": "p", "bucket": "b", "keys": ["e"] } }
+        }))
+        .unwrap();
+        assert_eq!(restored.priority, 0);
+    }
+
+    #[test]
+    fn failed_jobs_are_retried_under_the_same_id() {
+        let mut job: JobInfo = serde_json::from_value(json!({
+            "id": "a",
+            "type": "delete",
+            "status": "failed",
+            "fileName": "a.txt",
+            "description": "Delete",
+            "bytesTransferred": 3,
+            "bytesTotal": 4,
+            "percentage": 75,
+            "speed": 0,
+            "eta": 0,
+            "error": "Access denied",
+            "createdAt": "2026-01-01T00:00:00Z",
+            "startedAt": "2026-01-01T00:00:01Z",
+            "completedAt": "2026-01-01T00:00:02Z",
+        }))
+        .unwrap();
+        let mut jobs = JobRuntime::default();
+        jobs.jobs.insert("a".to_string(), job.clone());
+        jobs.retry_tasks.insert(
+            "a".to_string(),
+            JobTask {
+                id: "a".to_string(),
+                kind: JobTaskKind::Delete {
+                    profile_id: "p".to_string(),
+                    bucket: "b".to_string(),
+                    keys: vec!["a.txt".to_string()],
+                },
+                priority: 0,
+            },
+        );
+
+        let requeued = requeue_finished_job(&mut jobs, "a").unwrap();
+        assert_eq!(requeued.id, "a");
+        assert_eq!(requeued.status, JobStatus::Queued);
+        assert_eq!((requeued.error, requeued.bytes_transferred), (None, 0));
+        assert_eq!(jobs.queue.front().map(|task| task.id.as_str()), Some("a"));
+        assert!(jobs.cancel_flags.contains_key("a"));
+        assert!(requeue_finished_job(&mut jobs, "a").is_err());
+
+        // Completed jobs and jobs loaded from history (no task) can't retry.
+        job.id = "b".to_string();
+        job.status = JobStatus::Completed;
+        jobs.jobs.insert("b".to_string(), job.clone());
+        assert!(requeue_finished_job(&mut jobs, "b").is_err());
+        job.id = "c".to_string();
+        job.status = JobStatus::Cancelled;
+        jobs.jobs.insert("c".to_string(), job);
+        assert!(requeue_finished_job(&mut jobs, "c").is_err());
+    }
+
+    #[test]
+    fn archive_names_follow_the_chosen_format() {
+        assert_eq!(parse_archive_format(None).unwrap(), ArchiveFormat::TarGz);
+        assert_eq!(parse_archive_format(Some(" ZIP ")).unwrap(), ArchiveFormat::Zip);
+        assert!(parse_archive_format(Some("7z")).is_err());
+
+        assert_eq!(
+            archive_file_name("photos", ArchiveFormat::Zip).unwrap(),
+            "photos.zip"
+        );
+        assert_eq!(
+            archive_file_name("photos.TAR.GZ", ArchiveFormat::TarGz).unwrap(),
+            "photos.TAR.GZ"
+        );
+        assert_eq!(
+            archive_file_name("v1.2", ArchiveFormat::TarGz).unwrap(),
+            "v1.2.tar.gz"
+        );
+        assert_eq!(
+            archive_file_name("photos.tgz", ArchiveFormat::TarGz).unwrap(),
+            "photos.tgz"
+        );
+        assert!(archive_file_name("photos.tar.gz", ArchiveFormat::Zip).is_err());
+        assert!(archive_file_name("photos.zip", ArchiveFormat::TarGz).is_err());
+        assert!(archive_file_name("  ", ArchiveFormat::Zip).is_err());
+
+        assert_eq!(archive_entry_name(Path::new("a/b/c.txt")), "a/b/c.txt");
+    }
+
+    #[test]
+    fn scheduled_rules_run_at_the_next_cron_time() {
+        use chrono::TimeZone;
+
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        assert_eq!(rule.schedule, None);
+        rule.schedule = Some("0 2 * * *".to_string());
+        assert!(validate_folder_sync_rule(&rule).is_ok());
+        assert!(!folder_sync_rule_watches_local(&rule));
+
+        let after = chrono::Local.with_ymd_and_hms(2026, 1, 1, 1, 30, 0).unwrap();
+        let next = next_folder_sync_run("0 2 * * *", &after).unwrap();
+        assert_eq!(next, chrono::Local.with_ymd_and_hms(2026, 1, 1, 2, 0, 0).unwrap());
+        let next = next_folder_sync_run("@daily", &next).unwrap();
+        assert_eq!(next, chrono::Local.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap());
+
+        rule.schedule = Some("every night".to_string());
+        assert!(validate_folder_sync_rule(&rule).is_err());
+        rule.schedule = Some("0 2 * * *".to_string());
+        rule.manual_only = true;
+        assert!(validate_folder_sync_rule(&rule).is_err());
+
+        assert_eq!(
+            serde_json::to_value(FolderSyncStatus::Scheduled).unwrap(),
+            json!("scheduled")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn folder_sync_scan_applies_the_symlink_policy() {
+        let dir = std::env::temp_dir().join(format!("object0-symlinks-{}", Uuid::new_v4()));
+        let outside = std::env::temp_dir().join(format!("object0-target-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(dir.join("sub/a.txt"), b"a").unwrap();
+        fs::write(outside.join("b.txt"), b"bb").unwrap();
+        std::os::unix::fs::symlink(outside.join("b.txt"), dir.join("b.txt")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("sub/loop")).unwrap();
+
+        let scan = |policy| {
+            let (files, cycles) = scan_local_directory_with_symlinks(&dir, &[], policy);
+            let files: Vec<(String, i64)> = files
+                .into_iter()
+                .map(|file| (file.relative_path, file.size))
+                .collect();
+            (files, cycles)
+        };
+
+        let (files, cycles) = scan(SymlinkPolicy::Skip);
+        assert_eq!(files, vec![("sub/a.txt".to_string(), 1)]);
+        assert!(cycles.is_empty());
+
+        let (files, _) = scan(SymlinkPolicy::CopyTarget);
+        assert_eq!(
+            files,
+            vec![("b.txt".to_string(), 2), ("sub/a.txt".to_string(), 1)]
+        );
+
+        let (files, cycles) = scan(SymlinkPolicy::Follow);
+        assert_eq!(
+            files,
+            vec![
+                ("b.txt".to_string(), 2),
+                ("linked/b.txt".to_string(), 2),
+                ("sub/a.txt".to_string(), 1),
+            ]
+        );
+        assert_eq!(cycles, vec![dir.join("sub/loop").display().to_string()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn contained_local_path_refuses_folders_behind_symlinks() {
+        let dir = std::env::temp_dir().join(format!("object0-contained-{}", Uuid::new_v4()));
+        let outside = std::env::temp_dir().join(format!("object0-target-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("linked")).unwrap();
+        std::os::unix::fs::symlink(outside.join("b.txt"), dir.join("b.txt")).unwrap();
+
+        assert_eq!(
+            contained_local_path(&dir, Path::new("sub/a.txt")).unwrap(),
+            dir.join("sub/a.txt")
+        );
+        assert!(contained_local_path(&dir, Path::new("new/deeper/a.txt")).is_ok());
+        // The link itself sits in the root; removing or replacing it is fine.
+        assert!(contained_local_path(&dir, Path::new("b.txt")).is_ok());
+        assert!(contained_local_path(&dir, Path::new("linked/b.txt")).is_err());
+        assert!(contained_local_path(&dir, Path::new("linked/new/b.txt")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn open_external_refuses_executable_file_names() {
+        assert!(is_executable_file_name("setup.exe"));
+        assert!(is_executable_file_name("tools/Install.SH"));
+        assert!(is_executable_file_name("Shortcut.lnk"));
+        assert!(!is_executable_file_name("report.pdf"));
+        assert!(!is_executable_file_name("photos/cat.jpeg"));
+        assert!(!is_executable_file_name("README"));
+    }
+
+    #[test]
+    fn corrupt_config_files_are_copied_aside() {
+        let dir = std::env::temp_dir().join(format!("object0-corrupt-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("job-history.json");
+        fs::write(&path, b"[{\"id\":").unwrap();
+
+        let backup = back_up_corrupt_file(&path).unwrap();
+        let name = backup.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("job-history.json.corrupt-"));
+        assert_eq!(fs::read(&backup).unwrap(), b"[{\"id\":");
+        assert!(path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn vault_kdf_parameters_are_capped() {
+        let argon2 = |memory_kib, time_cost, parallelism| VaultKdfParams {
+            memory_kib: Some(memory_kib),
+            time_cost: Some(time_cost),
+            parallelism: Some(parallelism),
+            ..VaultKdfParams::default()
+        };
+        assert_eq!(
+            vault_kdf_config(VaultKdf::Argon2id, &argon2(64 * 1024, 3, 4)),
+            Ok(VAULT_KDF_ARGON2ID)
+        );
+        assert!(vault_kdf_config(VaultKdf::Argon2id, &argon2(u32::MAX, 3, 4)).is_err());
+        assert!(vault_kdf_config(VaultKdf::Argon2id, &argon2(64 * 1024, 1_000, 4)).is_err());
+        assert!(vault_kdf_config(VaultKdf::Argon2id, &argon2(64 * 1024, 3, 0)).is_err());
+        let pbkdf2 = |iterations| VaultKdfParams {
+            iterations: Some(iterations),
+            ..VaultKdfParams::default()
+        };
+        assert!(vault_kdf_config(VaultKdf::Pbkdf2, &pbkdf2(PBKDF2_ITERATIONS)).is_ok());
+        assert!(vault_kdf_config(VaultKdf::Pbkdf2, &pbkdf2(u32::MAX)).is_err());
+    }
+
+    #[test]
+    fn batched_deletes_report_progress_and_stop_when_cancelled() {
+        let profile = serde_json::from_value::<Profile>(json!({
+            "id": "profile-1",
+            "name": "Work",
+            "provider": "aws",
+            "region": "us-east-1",
+            "accessKeyId": "AKIA",
+            "secretAccessKey": "secret",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let client = to_s3_client(&profile).unwrap();
+        let cancel_flag = AtomicBool::new(false);
+
+        let mut reports = Vec::new();
+        let empty = s3_delete_keys_batched(&client, "bucket", &[], &cancel_flag, |done, total| {
+            reports.push((done, total))
+        });
+        assert_eq!(tauri::async_runtime::block_on(empty), Ok(0));
+        assert_eq!(reports, vec![(0, 0)]);
+
+        // Cancelled on the first report, so no batch is ever sent.
+        let keys: Vec<String> = (0..2500).map(|i| format!("k{i}")).collect();
+        let mut reports = Vec::new();
+        let cancelled =
+            s3_delete_keys_batched(&client, "bucket", &keys, &cancel_flag, |done, total| {
+                reports.push((done, total));
+                cancel_flag.store(true, Ordering::SeqCst);
+            });
+        let result = tauri::async_runtime::block_on(cancelled);
+        assert_eq!(result.unwrap_err(), JOB_CANCELLED);
+        assert_eq!(reports, vec![(0, 2500)]);
+    }
+
+    #[test]
+    fn watcher_restart_backs_off_until_it_succeeds_or_gives_up() {
+        let cancel_flag = AtomicBool::new(false);
+
+        let mut calls = 0;
+        let recovered = retry_with_backoff(5, 1, &cancel_flag, || {
+            calls += 1;
+            if calls == 3 {
+                Ok(())
+            } else {
+                Err(format!("attempt {calls} failed"))
+            }
+        });
+        assert!(tauri::async_runtime::block_on(recovered).is_ok());
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let exhausted = retry_with_backoff(3, 1, &cancel_flag, || {
+            calls += 1;
+            Err(format!("attempt {calls} failed"))
+        });
+        assert_eq!(
+            tauri::async_runtime::block_on(exhausted).unwrap_err(),
+            "attempt 3 failed (gave up after 3 attempts)"
+        );
+
+        // A stopped rule gives up quietly, without trying again.
+        cancel_flag.store(true, Ordering::SeqCst);
+        let mut calls = 0;
+        let cancelled = retry_with_backoff(3, 1, &cancel_flag, || {
+            calls += 1;
+            Err("unreachable".to_string())
+        });
+        assert!(tauri::async_runtime::block_on(cancelled).is_ok());
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn jobs_pending_at_a_clean_quit_are_queued_again_at_launch() {
+        let job: JobInfo = serde_json::from_value(json!({
+            "id": "a",
+            "type": "delete",
+            "status": "queued",
+            "fileName": "a.txt",
+            "description": "Delete",
+            "bytesTransferred": 0,
+            "bytesTotal": 1,
+            "percentage": 0,
+            "speed": 0,
+            "eta": 0,
+            "createdAt": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        let task: JobTask = serde_json::from_value(json!({
+            "id": "a",
+            "kind": { "delete": { "profileId": "p", "bucket": "b", "keys": ["a.txt"] } }
+        }))
+        .unwrap();
+        let mut jobs = JobRuntime::default();
+        jobs.jobs.insert("a".to_string(), job);
+        jobs.order.push("a".to_string());
+        jobs.queue.push_back(task);
+
+        // Shutdown: the queue file is written first, then the job is
+        // cancelled and lands in history under the same id.
+        let queue = job_queue_snapshot(&jobs);
+        mark_job_cancelled(jobs.jobs.get_mut("a").unwrap(), CancelReason::Shutdown);
+        let history = job_history_snapshot(&jobs);
+        assert_eq!(history.len(), 1);
+
+        // Launch: history is hydrated before the queue.
+        let mut restored = JobRuntime::default();
+        restore_job_history(&mut restored, history);
+        restore_job_queue(&mut restored, queue);
+        assert_eq!(restored.order, vec!["a".to_string()]);
+        assert_eq!(restored.jobs["a"].status, JobStatus::Queued);
+        assert_eq!(restored.jobs["a"].cancel_reason, None);
+        assert_eq!(restored.queue.front().map(|task| task.id.as_str()), Some("a"));
+    }
+
+    #[test]
+    fn case_collision_aliases_resolve_through_the_record_after_the_twin_is_gone() {
+        let mut rule: FolderSyncRuleRecord = serde_json::from_value(legacy_rule_json()).unwrap();
+        rule.case_collisions = CaseCollisionPolicy::Rename;
+        let alias = case_collision_copy_name("docs/Foo.txt");
+        let known = FolderSyncFileRecord {
+            relative_path: "docs/Foo.txt".to_string(),
+            local_mtime: 1,
+            local_size: 1,
+            remote_etag: "e".to_string(),
+            remote_last_modified: "2024-01-01T00:00:00Z".to_string(),
+            remote_size: 1,
+            synced_at: "2024-01-01T00:00:00Z".to_string(),
+            local_relative_path: Some(alias.clone()),
+        };
+        let remote = RemoteFileInfo {
+            size: 1,
+            etag: "e".to_string(),
+            last_modified: "2024-01-01T00:00:00Z".to_string(),
+            storage_class: None,
+        };
+
+        // Next pass: `docs/foo.txt` is gone, so nothing collides any more.
+        let mut local_map: HashMap<String, LocalFileInfo> = [(
+            alias.clone(),
+            LocalFileInfo {
+                relative_path: alias.clone(),
+                size: 1,
+                mtime_ms: 1,
+            },
+        )]
+        .into();
+        let (losers, aliases) =
+            apply_case_collision_aliases(&rule, &[], std::slice::from_ref(&known), &mut local_map);
+        assert!(losers.is_empty());
+        assert_eq!(aliases.get("docs/Foo.txt"), Some(&alias));
+        assert!(!local_map.contains_key(&alias));
+
+        // The copy still stands in for the key: unchanged, not a remote delete
+        // plus an upload under a new name.
+        let action = |local: Option<&LocalFileInfo>| {
+            resolve_folder_sync_action(local, Some(&remote), Some(&known), &rule, None)
+        };
+        assert!(action(local_map.get("docs/Foo.txt")).is_none());
+        assert!(action(None).is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn folder_sync_follow_lists_an_in_root_folder_under_its_real_path() {
+        let dir = std::env::temp_dir().join(format!("object0-alias-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("zzz")).unwrap();
+        fs::write(dir.join("zzz/file.txt"), b"z").unwrap();
+        std::os::unix::fs::symlink(dir.join("zzz"), dir.join("aaa")).unwrap();
+
+        let (files, cycles) = scan_local_directory_with_symlinks(&dir, &[], SymlinkPolicy::Follow);
+        let paths: Vec<String> = files.into_iter().map(|file| file.relative_path).collect();
+        assert_eq!(paths, vec!["zzz/file.txt".to_string()]);
+        assert!(cycles.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,127 @@
+//! Metered-network detection for the "pause folder sync on metered networks"
+//! setting. Only Linux (NetworkManager over D-Bus) can detect it today;
+//! elsewhere the monitor never starts and the setting is a no-op.
+
+use super::*;
+
+pub(crate) const METERED_DETECTION_SUPPORTED: bool = cfg!(target_os = "linux");
+
+pub(crate) async fn run_metered_network_monitor(app: AppHandle) {
+    if !METERED_DETECTION_SUPPORTED {
+        return;
+    }
+
+    loop {
+        let metered = tauri::async_runtime::spawn_blocking(detect_metered_network)
+            .await
+            .ok()
+            .flatten();
+        let enabled = current_settings(&app.state::<AppState>()).pause_sync_on_metered;
+        apply_metered_network_state(&app, metered, enabled);
+        tokio::time::sleep(StdDuration::from_secs(METERED_NETWORK_POLL_SECS)).await;
+    }
+}
+
+// Pauses on the transition into "metered + enabled" and resumes only the
+// rules it paused, so a rule already paused by hand stays paused.
+pub(crate) fn apply_metered_network_state(app: &AppHandle, metered: Option<bool>, enabled: bool) {
+    let should_pause = enabled && metered == Some(true);
+    let (changed, pause, resume) = {
+        let state = app.state::<AppState>();
+        let Ok(mut network) = lock_state(&state.network) else {
+            return;
+        };
+        let changed = network.metered != metered;
+        network.metered = metered;
+        let pause = should_pause && !network.sync_paused_for_metered;
+        let resume = (!should_pause && network.sync_paused_for_metered)
+            .then(|| std::mem::take(&mut network.metered_paused_rule_ids));
+        network.sync_paused_for_metered = should_pause;
+        (changed, pause, resume)
+    };
+
+    if pause {
+        let paused = pause_running_folder_sync_rules(app);
+        let state = app.state::<AppState>();
+        let _ = lock_state(&state.network)
+            .map(|mut network| network.metered_paused_rule_ids.extend(paused));
+    } else if let Some(rule_ids) = &resume {
+        resume_folder_sync_rules(app, |rule_id| rule_ids.contains(rule_id));
+    }
+    if changed || pause || resume.is_some() {
+        refresh_tray_menu(app);
+    }
+}
+
+// Re-applies the cached state after settings:set toggles the option.
+pub(crate) fn refresh_metered_network_pause(app: &AppHandle, enabled: bool) {
+    let metered = {
+        let state = app.state::<AppState>();
+        let value = lock_state(&state.network)
+            .map(|network| network.metered)
+            .unwrap_or(None);
+        value
+    };
+    apply_metered_network_state(app, metered, enabled);
+}
+
+// Rules started while paused for a metered network start paused too, and
+// resume with the others when the pause lifts.
+pub(crate) fn folder_sync_paused_for_metered(app: &AppHandle, rule_id: &str) -> bool {
+    let state = app.state::<AppState>();
+    let value = lock_state(&state.network)
+        .map(|mut network| {
+            if network.sync_paused_for_metered {
+                network.metered_paused_rule_ids.insert(rule_id.to_string());
+            }
+            network.sync_paused_for_metered
+        })
+        .unwrap_or(false);
+    value
+}
+
+pub(crate) fn forget_metered_paused_rules(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let Ok(mut network) = lock_state(&state.network) else {
+        return;
+    };
+    network.metered_paused_rule_ids.clear();
+}
+
+pub(crate) fn network_status(app: &AppHandle) -> Value {
+    let state = app.state::<AppState>();
+    let (metered, paused) = lock_state(&state.network)
+        .map(|network| (network.metered, network.sync_paused_for_metered))
+        .unwrap_or((None, false));
+    json!({
+        "meteredDetectionSupported": METERED_DETECTION_SUPPORTED,
+        "metered": metered,
+        "syncPausedForMetered": paused,
+    })
+}
+
+// `None` when NetworkManager isn't running or can't tell.
+fn detect_metered_network() -> Option<bool> {
+    let output = std::process::Command::new("busctl")
+        .args([
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_nm_metered(&String::from_utf8_lossy(&output.stdout))
+}
+
+// busctl prints the NMMetered enum as e.g. "u 4": 1/3 are yes/guess-yes,
+// 2/4 are no/guess-no, 0 is unknown.
+pub(crate) fn parse_nm_metered(output: &str) -> Option<bool> {
+    match output.trim().strip_prefix("u ")?.trim() {
+        "1" | "3" => Some(true),
+        "2" | "4" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,66 @@
+//! Desktop notifications: the global on/off switch from settings.json plus
+//! the folder-sync pass summary.
+
+use super::*;
+
+pub(crate) fn send_desktop_notification(app: &AppHandle, title: &str, body: &str) {
+    if !current_settings(&app.state::<AppState>()).notifications_enabled {
+        return;
+    }
+    if let Err(err) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show notification: {err}");
+    }
+}
+
+// "3 uploaded, 1 conflict"; `None` when the pass changed nothing, so quiet
+// passes never notify.
+pub(crate) fn folder_sync_pass_summary(counts: &FolderSyncPassCounts) -> Option<String> {
+    let plural = |count: usize, singular: &str, plural: &str| {
+        format!("{count} {}", if count == 1 { singular } else { plural })
+    };
+
+    let mut parts = Vec::new();
+    if counts.uploaded > 0 {
+        parts.push(format!("{} uploaded", counts.uploaded));
+    }
+    if counts.downloaded > 0 {
+        parts.push(format!("{} downloaded", counts.downloaded));
+    }
+    if counts.deleted_local > 0 {
+        parts.push(format!("{} deleted locally", counts.deleted_local));
+    }
+    if counts.deleted_remote > 0 {
+        parts.push(format!("{} deleted remotely", counts.deleted_remote));
+    }
+    if counts.conflicts > 0 {
+        parts.push(plural(counts.conflicts, "conflict", "conflicts"));
+    }
+    if counts.failed > 0 {
+        parts.push(format!("{} failed", counts.failed));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
+pub(crate) fn notify_folder_sync_pass(
+    app: &AppHandle,
+    rule: &FolderSyncRuleRecord,
+    counts: &FolderSyncPassCounts,
+) {
+    if !rule.notify_on_sync {
+        return;
+    }
+    let Some(summary) = folder_sync_pass_summary(counts) else {
+        return;
+    };
+
+    let folder = Path::new(&rule.local_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| rule.local_path.clone());
+    send_desktop_notification(app, &format!("Folder sync: {folder}"), &summary);
+}
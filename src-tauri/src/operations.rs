@@ -0,0 +1,69 @@
+//! Cancellable one-off RPC operations, as opposed to queued jobs. The caller
+//! picks an `operationId`, and `operation:cancel` (or the abort-all kill
+//! switch) ends the operation early.
+
+use super::*;
+use futures_util::future::{select, Either};
+use std::future::Future;
+
+// Also bounds the wait by `timeout`, so a hung provider surfaces as an error
+// instead of a spinner that never stops.
+pub(crate) async fn run_cancellable_operation<T, F>(
+    state: &AppState,
+    operation_id: Option<&str>,
+    timeout: StdDuration,
+    action: &str,
+    operation: F,
+) -> Result<T, String>
+where
+    F: Future<Output = Result<T, String>>,
+{
+    let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+    // Kept alive here when there's no id, so the receiver never fires.
+    let mut unregistered = None;
+    match operation_id {
+        Some(id) => {
+            let mut operations = lock_state(&state.operations)?;
+            if operations.contains_key(id) {
+                return Err(format!("Operation {id} is already running"));
+            }
+            operations.insert(id.to_string(), cancel_tx);
+        }
+        None => unregistered = Some(cancel_tx),
+    }
+
+    let timed = Box::pin(tokio::time::timeout(timeout, operation));
+    let result = match select(timed, cancel_rx).await {
+        Either::Left((Ok(result), _)) => result,
+        Either::Left((Err(_), _)) => Err(format!(
+            "Timed out after {}s trying to {action}; check the profile's endpoint",
+            timeout.as_secs()
+        )),
+        Either::Right(_) => Err(OPERATION_CANCELLED.to_string()),
+    };
+
+    drop(unregistered);
+    if let Some(id) = operation_id {
+        if let Ok(mut operations) = lock_state(&state.operations) {
+            operations.remove(id);
+        }
+    }
+    result
+}
+
+// `false` when the operation already finished or never existed.
+pub(crate) fn cancel_operation(state: &AppState, operation_id: &str) -> Result<bool, String> {
+    let cancel_tx = lock_state(&state.operations)?.remove(operation_id);
+    Ok(cancel_tx.is_some_and(|cancel_tx| cancel_tx.send(()).is_ok()))
+}
+
+pub(crate) fn cancel_all_operations(state: &AppState) -> usize {
+    let Ok(mut operations) = lock_state(&state.operations) else {
+        return 0;
+    };
+    operations
+        .drain()
+        .map(|(_, cancel_tx)| cancel_tx.send(()).is_ok())
+        .filter(|sent| *sent)
+        .count()
+}
@@ -1,7 +1,125 @@
-//! On-disk persistence for favorites, job history, and folder-sync rules +
+//! On-disk persistence for app settings, favorites, last browsed location,
+//! job history and queue, multipart upload sidecars, and folder-sync rules +
 //! per-rule file records.
 
 use super::*;
+use sha2::{Digest, Sha256};
+
+pub(crate) fn load_settings_from_disk() -> AppSettings {
+    let Ok(path) = settings_path() else {
+        return AppSettings::default();
+    };
+    if !path.exists() {
+        return AppSettings::default();
+    }
+
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str::<AppSettings>(&raw)
+            .map(upgrade_settings)
+            .unwrap_or_default(),
+        Err(_) => AppSettings::default(),
+    }
+}
+
+// Brings an older settings.json up to SETTINGS_VERSION. Version 0 (before the
+// field existed) has the same shape, so there is nothing to convert yet. A
+// newer file keeps its version.
+pub(crate) fn upgrade_settings(mut settings: AppSettings) -> AppSettings {
+    settings.version = settings.version.max(SETTINGS_VERSION);
+    settings
+}
+
+pub(crate) fn current_settings(state: &AppState) -> AppSettings {
+    lock_state(&state.settings)
+        .map(|settings| settings.clone())
+        .unwrap_or_default()
+}
+
+// Merges a partial settings:set payload over the current settings. Unknown
+// keys are kept (see `AppSettings.unknown`); the version can't be set.
+pub(crate) fn merge_settings_update(
+    current: &AppSettings,
+    update: Map<String, Value>,
+) -> Result<AppSettings, String> {
+    let mut settings_value = serde_json::to_value(current)
+        .map_err(|err| format!("Failed to serialize settings: {err}"))?;
+    let Some(settings_obj) = settings_value.as_object_mut() else {
+        return Err("Invalid stored settings format".to_string());
+    };
+    let sets_auto_lock = update.contains_key("autoLockSecs");
+    for (key, value) in update {
+        if key != "version" {
+            settings_obj.insert(key, value);
+        }
+    }
+    let settings = serde_json::from_value::<AppSettings>(settings_value)
+        .map(upgrade_settings)
+        .map_err(|err| format!("Invalid settings: {err}"))?;
+    if sets_auto_lock {
+        validate_auto_lock_secs(settings.auto_lock_secs)?;
+    }
+    Ok(settings)
+}
+
+// Shared by settings:set and vault:set-auto-lock; `None` turns auto-lock off.
+pub(crate) fn validate_auto_lock_secs(secs: Option<u64>) -> Result<(), String> {
+    if secs.is_some_and(|secs| secs < VAULT_AUTO_LOCK_MIN_SECS) {
+        return Err(format!(
+            "Auto-lock timeout must be at least {VAULT_AUTO_LOCK_MIN_SECS} seconds"
+        ));
+    }
+    Ok(())
+}
+
+// Every settings.json field resolved the way `load_settings_from_disk` does,
+// tagged "user" when the file sets it and "default" otherwise. A file that
+// doesn't parse counts as absent, since it loads as all defaults.
+pub(crate) fn effective_settings_entries(stored: Option<&Value>) -> Map<String, Value> {
+    let settings =
+        stored.and_then(|stored| serde_json::from_value::<AppSettings>(stored.clone()).ok());
+    let stored = stored
+        .filter(|_| settings.is_some())
+        .and_then(Value::as_object);
+    let resolved = json!(settings.unwrap_or_default());
+    let defaults = json!(AppSettings::default());
+
+    let mut entries = Map::new();
+    for (key, value) in resolved.as_object().into_iter().flatten() {
+        // Only fields this build knows about; `version` isn't a setting.
+        if key == "version" || defaults.get(key).is_none() {
+            continue;
+        }
+        let source = if stored.is_some_and(|stored| stored.contains_key(key)) {
+            "user"
+        } else {
+            "default"
+        };
+        entries.insert(
+            key.clone(),
+            effective_setting(value.clone(), defaults[key].clone(), source),
+        );
+    }
+    entries
+}
+
+pub(crate) fn effective_setting(value: Value, default: Value, source: &str) -> Value {
+    json!({ "value": value, "default": default, "source": source })
+}
+
+pub(crate) fn load_effective_settings_entries() -> Map<String, Value> {
+    let stored = settings_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok());
+    effective_settings_entries(stored.as_ref())
+}
+
+pub(crate) fn save_settings_to_disk(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    let payload = serde_json::to_string_pretty(settings)
+        .map_err(|err| format!("Failed to serialize settings: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
 
 pub(crate) fn load_favorites_from_disk() -> Vec<String> {
     let Ok(path) = favorites_path() else {
@@ -24,10 +142,328 @@ pub(crate) fn save_favorites_to_disk(favorites: &[String]) -> Result<(), String>
     write_atomic(&path, payload.as_bytes())
 }
 
+// Legacy favorites are `profileId:bucket` keys; malformed ones are dropped.
+pub(crate) fn bookmarks_from_legacy_favorites(favorites: &[String]) -> Vec<Bookmark> {
+    let mut bookmarks: Vec<Bookmark> = Vec::new();
+    for key in favorites {
+        let Some((profile_id, bucket)) = key.split_once(':') else {
+            continue;
+        };
+        if profile_id.is_empty() || bucket.is_empty() {
+            continue;
+        }
+        let bookmark = Bookmark {
+            profile_id: profile_id.to_string(),
+            bucket: bucket.to_string(),
+            prefix: String::new(),
+            label: None,
+        };
+        if !bookmarks.contains(&bookmark) {
+            bookmarks.push(bookmark);
+        }
+    }
+    bookmarks
+}
+
+// Reads bookmarks.json, migrating the legacy favorites list the first time.
+// favorites.json is left in place for older builds.
+pub(crate) fn load_bookmarks_from_disk() -> Vec<Bookmark> {
+    let Ok(path) = bookmarks_path() else {
+        return Vec::new();
+    };
+    if path.exists() {
+        let Ok(raw) = fs::read_to_string(&path) else {
+            return Vec::new();
+        };
+        return match serde_json::from_str::<BookmarksFile>(&raw) {
+            Ok(file) => file.bookmarks,
+            // The next save replaces the file, so a damaged one is copied
+            // aside first.
+            Err(_) => {
+                let _ = back_up_corrupt_file(&path);
+                Vec::new()
+            }
+        };
+    }
+
+    let bookmarks = bookmarks_from_legacy_favorites(&load_favorites_from_disk());
+    if let Err(err) = save_bookmarks_to_disk(&bookmarks) {
+        eprintln!("Failed to write migrated bookmarks: {err}");
+    }
+    bookmarks
+}
+
+pub(crate) fn save_bookmarks_to_disk(bookmarks: &[Bookmark]) -> Result<(), String> {
+    let path = bookmarks_path()?;
+    let file = BookmarksFile {
+        version: BOOKMARKS_VERSION,
+        bookmarks: bookmarks.to_vec(),
+    };
+    let payload = serde_json::to_string_pretty(&file)
+        .map_err(|err| format!("Failed to serialize bookmarks: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
+
+// Drops a removed profile's bookmarks.
+pub(crate) fn forget_profile_bookmarks(profile_id: &str) -> Result<(), String> {
+    let Ok(path) = bookmarks_path() else {
+        return Ok(());
+    };
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut bookmarks = load_bookmarks_from_disk();
+    let before = bookmarks.len();
+    bookmarks.retain(|bookmark| bookmark.profile_id != profile_id);
+    if bookmarks.len() == before {
+        return Ok(());
+    }
+    save_bookmarks_to_disk(&bookmarks)
+}
+
+pub(crate) fn load_nav_state_from_disk() -> NavState {
+    let Ok(path) = nav_state_path() else {
+        return NavState::default();
+    };
+    if !path.exists() {
+        return NavState::default();
+    }
+
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str::<NavState>(&raw).unwrap_or_default(),
+        Err(_) => NavState::default(),
+    }
+}
+
+pub(crate) fn save_nav_state_to_disk(nav: &NavState) -> Result<(), String> {
+    let path = nav_state_path()?;
+    let payload = serde_json::to_string_pretty(nav)
+        .map_err(|err| format!("Failed to serialize last location: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
+
+// Drops a removed profile's remembered location.
+pub(crate) fn forget_nav_location(profile_id: &str) -> Result<(), String> {
+    let mut nav = load_nav_state_from_disk();
+    let had_location = nav.locations.remove(profile_id).is_some();
+    let was_last = nav.last_profile_id.as_deref() == Some(profile_id);
+    if !had_location && !was_last {
+        return Ok(());
+    }
+    if was_last {
+        nav.last_profile_id = None;
+    }
+    save_nav_state_to_disk(&nav)
+}
+
+// One sidecar per (bucket, key, local file); hashed so any key is a valid
+// file name.
+fn multipart_sidecar_id(bucket: &str, key: &str, local_path: &Path) -> String {
+    let digest = Sha256::digest(format!("{bucket}\n{key}\n{}", local_path.display()));
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub(crate) fn load_multipart_sidecar(
+    bucket: &str,
+    key: &str,
+    local_path: &Path,
+) -> Option<MultipartUploadSidecar> {
+    let path = multipart_sidecar_path(&multipart_sidecar_id(bucket, key, local_path)).ok()?;
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub(crate) fn save_multipart_sidecar(sidecar: &MultipartUploadSidecar) -> Result<(), String> {
+    let id = multipart_sidecar_id(
+        &sidecar.bucket,
+        &sidecar.key,
+        Path::new(&sidecar.local_path),
+    );
+    let path = multipart_sidecar_path(&id)?;
+    let payload = serde_json::to_string(sidecar)
+        .map_err(|err| format!("Failed to serialize multipart sidecar: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
+
+pub(crate) fn remove_multipart_sidecar(bucket: &str, key: &str, local_path: &Path) {
+    if let Ok(path) = multipart_sidecar_path(&multipart_sidecar_id(bucket, key, local_path)) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// Every readable sidecar with its id.
+pub(crate) fn list_multipart_sidecars() -> Vec<(String, MultipartUploadSidecar)> {
+    let Ok(dir) = multipart_sidecar_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let id = path.file_stem()?.to_str()?.to_string();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let raw = fs::read_to_string(&path).ok()?;
+            Some((id, serde_json::from_str(&raw).ok()?))
+        })
+        .collect()
+}
+
+pub(crate) fn load_multipart_sidecar_by_id(id: &str) -> Option<MultipartUploadSidecar> {
+    // Ids are SHA-256 hex; anything else could walk out of the directory.
+    if id.len() != 64 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let raw = fs::read_to_string(multipart_sidecar_path(id).ok()?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub(crate) fn load_partial_download_sidecar(
+    bucket: &str,
+    key: &str,
+    local_path: &Path,
+) -> Option<PartialDownloadSidecar> {
+    let id = multipart_sidecar_id(bucket, key, local_path);
+    let raw = fs::read_to_string(partial_download_sidecar_path(&id).ok()?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub(crate) fn save_partial_download_sidecar(
+    sidecar: &PartialDownloadSidecar,
+) -> Result<(), String> {
+    let id = multipart_sidecar_id(
+        &sidecar.bucket,
+        &sidecar.key,
+        Path::new(&sidecar.local_path),
+    );
+    let path = partial_download_sidecar_path(&id)?;
+    let payload = serde_json::to_string(sidecar)
+        .map_err(|err| format!("Failed to serialize download sidecar: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
+
+// Forgets a paused download: the partial file and its sidecar.
+pub(crate) fn discard_partial_download(bucket: &str, key: &str, local_path: &Path) {
+    let _ = fs::remove_file(partial_download_path(local_path));
+    let id = multipart_sidecar_id(bucket, key, local_path);
+    if let Ok(path) = partial_download_sidecar_path(&id) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+pub(crate) fn config_dir_is_empty(dir: &Path) -> bool {
+    !LEGACY_CONFIG_ENTRIES
+        .iter()
+        .any(|entry| dir.join(entry).exists())
+}
+
+pub(crate) fn find_legacy_config_dir() -> Option<PathBuf> {
+    legacy_config_dirs()
+        .into_iter()
+        .find(|dir| !config_dir_is_empty(dir))
+}
+
+// Copies `source`'s config into `dest`, never overwriting. Originals stay
+// put unless `remove_legacy` confirms the move, and then only those whose
+// copy in `dest` matches byte for byte are deleted.
+pub(crate) fn migrate_legacy_config(
+    source: &Path,
+    dest: &Path,
+    remove_legacy: bool,
+) -> Result<LegacyConfigMigration, String> {
+    let mut report = LegacyConfigMigration {
+        source: Some(source.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    for entry in LEGACY_CONFIG_ENTRIES {
+        let from = source.join(entry);
+        if !from.exists() {
+            continue;
+        }
+        let to = dest.join(entry);
+        if to.exists() {
+            if !remove_legacy || !legacy_entry_matches(&from, &to) {
+                report.skipped.push(entry.to_string());
+                continue;
+            }
+        } else {
+            copy_legacy_entry(&from, &to)?;
+            report.copied.push(entry.to_string());
+        }
+        if remove_legacy {
+            let removed = if from.is_dir() {
+                fs::remove_dir_all(&from)
+            } else {
+                fs::remove_file(&from)
+            };
+            removed.map_err(|err| format!("Failed to remove {}: {err}", from.display()))?;
+            report.removed.push(entry.to_string());
+        }
+    }
+    Ok(report)
+}
+
+fn copy_legacy_entry(from: &Path, to: &Path) -> Result<(), String> {
+    let files: Vec<(PathBuf, PathBuf)> = if from.is_dir() {
+        WalkDir::new(from)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(from).ok()?.to_path_buf();
+                Some((entry.into_path(), to.join(relative)))
+            })
+            .collect()
+    } else {
+        vec![(from.to_path_buf(), to.to_path_buf())]
+    };
+    for (source, target) in files {
+        ensure_parent_dir(&target)?;
+        fs::copy(&source, &target).map_err(|err| {
+            format!(
+                "Failed to copy {} to {}: {err}",
+                source.display(),
+                target.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn legacy_entry_matches(from: &Path, to: &Path) -> bool {
+    if !from.is_dir() {
+        return matches!((fs::read(from), fs::read(to)), (Ok(a), Ok(b)) if a == b);
+    }
+    WalkDir::new(from)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .all(|entry| {
+            entry
+                .path()
+                .strip_prefix(from)
+                .is_ok_and(|relative| legacy_entry_matches(entry.path(), &to.join(relative)))
+        })
+}
+
+// The legacy directory to offer migrating from: only while the current
+// config directory is still empty, so an existing setup is never prompted.
+pub(crate) fn legacy_config_offer() -> Option<PathBuf> {
+    let dest = object0_config_dir().ok()?;
+    if !config_dir_is_empty(&dest) {
+        return None;
+    }
+    find_legacy_config_dir()
+}
+
 pub(crate) fn is_terminal_job_status(status: JobStatus) -> bool {
     matches!(
         status,
-        JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled | JobStatus::Skipped
     )
 }
 
@@ -39,11 +475,21 @@ pub(crate) fn load_job_history_from_disk() -> Vec<JobInfo> {
         return Vec::new();
     }
 
-    let Ok(raw) = fs::read_to_string(path) else {
+    let Ok(bytes) = fs::read(&path) else {
         return Vec::new();
     };
-    let Ok(mut history) = serde_json::from_str::<Vec<JobInfo>>(&raw) else {
-        return Vec::new();
+    let raw = String::from_utf8_lossy(&bytes);
+    let mut history = match serde_json::from_str::<Vec<JobInfo>>(&raw) {
+        Ok(history) => history,
+        // The next history write replaces the file, so a damaged one is
+        // copied aside and whatever can be salvaged is kept.
+        Err(_) => {
+            let history = salvage_job_history(&raw);
+            if back_up_corrupt_file(&path).is_ok() {
+                let _ = save_job_history_to_disk(&history);
+            }
+            history
+        }
     };
 
     history.retain(|job| is_terminal_job_status(job.status));
@@ -60,6 +506,110 @@ pub(crate) fn save_job_history_to_disk(history: &[JobInfo]) -> Result<(), String
     write_atomic(&path, payload.as_bytes())
 }
 
+pub(crate) fn load_job_queue_from_disk() -> Vec<PersistedJob> {
+    let Ok(path) = job_queue_path() else {
+        return Vec::new();
+    };
+    let Ok(raw) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    // Entries are parsed one by one so a job whose task no longer parses
+    // (say, from a newer version) only drops itself. Anything that doesn't
+    // load is backed up, since the next queue write replaces the file.
+    let entries = match serde_json::from_str::<Vec<Value>>(&raw) {
+        Ok(entries) => entries,
+        Err(_) => {
+            let _ = back_up_corrupt_file(&path);
+            return Vec::new();
+        }
+    };
+    let total = entries.len();
+    let queue: Vec<PersistedJob> = entries
+        .into_iter()
+        .filter_map(|entry| serde_json::from_value(entry).ok())
+        .collect();
+    if queue.len() < total {
+        let _ = back_up_corrupt_file(&path);
+    }
+    queue
+}
+
+pub(crate) fn save_job_queue_to_disk(queue: &[PersistedJob]) -> Result<(), String> {
+    let path = job_queue_path()?;
+    let payload = serde_json::to_string(queue)
+        .map_err(|err| format!("Failed to serialize job queue: {err}"))?;
+    write_atomic(&path, payload.as_bytes())
+}
+
+// Pulls whole job entries out of a damaged history file: every `{` is tried
+// as the start of a JobInfo, so a truncated tail or a garbled entry only
+// loses itself. Duplicates (by id) and non-terminal jobs are dropped.
+pub(crate) fn salvage_job_history(raw: &str) -> Vec<JobInfo> {
+    let mut history: Vec<JobInfo> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pos = 0;
+    while let Some(offset) = raw[pos..].find('{') {
+        let start = pos + offset;
+        let mut stream = serde_json::Deserializer::from_str(&raw[start..]).into_iter::<JobInfo>();
+        match stream.next() {
+            Some(Ok(job)) => {
+                pos = start + stream.byte_offset();
+                if is_terminal_job_status(job.status) && seen.insert(job.id.clone()) {
+                    history.push(job);
+                }
+            }
+            _ => pos = start + 1,
+        }
+    }
+    history.truncate(JOB_HISTORY_MAX);
+    history
+}
+
+// Copies a config file that failed to parse to `<name>.corrupt-<timestamp>`
+// next to it, before anything overwrites it.
+pub(crate) fn back_up_corrupt_file(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup_path = path.with_file_name(format!(
+        "{file_name}.corrupt-{}",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::copy(path, &backup_path)
+        .map_err(|err| format!("Failed to back up {}: {err}", path.display()))?;
+    Ok(backup_path)
+}
+
+// maintenance:repair-job-history. A file that doesn't parse is copied aside
+// as `job-history.json.corrupt-<timestamp>` before the salvaged entries are
+// written back.
+pub(crate) fn repair_job_history_file() -> Result<Value, String> {
+    let path = job_history_path()?;
+    if !path.exists() {
+        return Ok(json!({ "corrupt": false, "recovered": 0, "backupPath": null }));
+    }
+    let bytes =
+        fs::read(&path).map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    let raw = String::from_utf8_lossy(&bytes);
+    if let Ok(history) = serde_json::from_str::<Vec<JobInfo>>(&raw) {
+        return Ok(json!({
+            "corrupt": false,
+            "recovered": history.len(),
+            "backupPath": null,
+        }));
+    }
+
+    let history = salvage_job_history(&raw);
+    let backup_path = back_up_corrupt_file(&path)?;
+    save_job_history_to_disk(&history)?;
+    Ok(json!({
+        "corrupt": true,
+        "recovered": history.len(),
+        "backupPath": backup_path.to_string_lossy(),
+    }))
+}
+
 pub(crate) fn load_folder_sync_rules_from_disk() -> Vec<Value> {
     let Ok(path) = folder_sync_rules_path() else {
         return Vec::new();
@@ -119,11 +669,20 @@ pub(crate) fn load_folder_sync_file_records(rule_id: &str) -> Vec<FolderSyncFile
     }
 
     match fs::read_to_string(path) {
-        Ok(raw) => serde_json::from_str::<Vec<FolderSyncFileRecord>>(&raw).unwrap_or_default(),
+        Ok(raw) => parse_folder_sync_file_records(&raw),
         Err(_) => Vec::new(),
     }
 }
 
+// Records written by older builds may hold a quoted ETag.
+pub(crate) fn parse_folder_sync_file_records(raw: &str) -> Vec<FolderSyncFileRecord> {
+    let mut records = serde_json::from_str::<Vec<FolderSyncFileRecord>>(raw).unwrap_or_default();
+    for record in &mut records {
+        record.remote_etag = normalize_etag(Some(&record.remote_etag));
+    }
+    records
+}
+
 pub(crate) fn save_folder_sync_file_records(
     rule_id: &str,
     records: &[FolderSyncFileRecord],
@@ -134,26 +693,67 @@ pub(crate) fn save_folder_sync_file_records(
     write_atomic(&path, payload.as_bytes())
 }
 
-pub(crate) fn update_folder_sync_file_record(
-    rule_id: &str,
-    record: FolderSyncFileRecord,
-) -> Result<(), String> {
-    let mut records = load_folder_sync_file_records(rule_id);
-    if let Some(existing) = records
-        .iter_mut()
-        .find(|existing| existing.relative_path == record.relative_path)
-    {
-        *existing = record;
-    } else {
-        records.push(record);
+impl FolderSyncRecordStore {
+    pub(crate) fn load(rule_id: &str) -> Self {
+        let records = load_folder_sync_file_records(rule_id)
+            .into_iter()
+            .map(|record| (record.relative_path.clone(), record))
+            .collect();
+        Self {
+            rule_id: rule_id.to_string(),
+            records,
+            dirty: 0,
+            last_flush: Instant::now(),
+        }
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<FolderSyncFileRecord> {
+        let mut records: Vec<FolderSyncFileRecord> = self.records.values().cloned().collect();
+        records.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        records
+    }
+
+    pub(crate) fn upsert(&mut self, record: FolderSyncFileRecord) -> Result<(), String> {
+        self.records.insert(record.relative_path.clone(), record);
+        self.mark_dirty()
+    }
+
+    pub(crate) fn remove(&mut self, relative_path: &str) -> Result<(), String> {
+        if self.records.remove(relative_path).is_some() {
+            self.mark_dirty()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<(), String> {
+        if self.dirty == 0 {
+            return Ok(());
+        }
+        save_folder_sync_file_records(&self.rule_id, &self.to_vec())?;
+        self.dirty = 0;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+
+    // Periodic checkpoint so a crash mid-pass loses at most a bounded amount
+    // of progress.
+    fn mark_dirty(&mut self) -> Result<(), String> {
+        self.dirty += 1;
+        if self.dirty >= FOLDER_SYNC_RECORD_FLUSH_EVERY
+            || self.last_flush.elapsed()
+                >= StdDuration::from_secs(FOLDER_SYNC_RECORD_FLUSH_INTERVAL_SECS)
+        {
+            self.flush()?;
+        }
+        Ok(())
     }
-    save_folder_sync_file_records(rule_id, &records)
 }
 
-pub(crate) fn remove_folder_sync_file_record(rule_id: &str, relative_path: &str) -> Result<(), String> {
-    let mut records = load_folder_sync_file_records(rule_id);
-    records.retain(|record| record.relative_path != relative_path);
-    save_folder_sync_file_records(rule_id, &records)
+impl Drop for FolderSyncRecordStore {
+    fn drop(&mut self) {
+        // Early returns (pause, cancel, errors) still persist what completed.
+        let _ = self.flush();
+    }
 }
 
 pub(crate) fn update_folder_sync_rule_result(
@@ -0,0 +1,301 @@
+//! `provider:probe`: minimal calls that tell which optional S3 features an
+//! endpoint actually supports, cached per profile. Probes that write clean
+//! up after themselves.
+
+use super::*;
+use aws_sdk_s3::{
+    config::http::HttpResponse,
+    error::{ProvideErrorMetadata, SdkError},
+    operation::put_object::{PutObjectError, PutObjectOutput},
+    presigning::PresignedRequest,
+    types::{ChecksumAlgorithm, Tag, Tagging},
+};
+
+pub(crate) async fn probe_provider_features(
+    state: &AppState,
+    profile: &Profile,
+    bucket: &str,
+    features: &[&str],
+    refresh: bool,
+) -> Result<HashMap<String, ProviderProbeResult>, String> {
+    let mut results = HashMap::new();
+    let mut pending = Vec::new();
+    {
+        let cache = lock_state(&state.provider_probes)?;
+        for feature in features {
+            let cached = cache
+                .get(&profile.id)
+                .and_then(|probes| probes.get(*feature))
+                .filter(|result| !refresh && result.bucket == bucket);
+            match cached {
+                Some(result) => {
+                    results.insert(feature.to_string(), result.clone());
+                }
+                None => pending.push(*feature),
+            }
+        }
+    }
+    if pending.is_empty() {
+        return Ok(results);
+    }
+
+    let client = to_s3_client(profile)?;
+    for feature in pending {
+        let (supported, detail) = probe_feature(&client, bucket, feature).await;
+        let result = ProviderProbeResult {
+            bucket: bucket.to_string(),
+            supported,
+            detail,
+            probed_at: now_iso(),
+        };
+        lock_state(&state.provider_probes)?
+            .entry(profile.id.clone())
+            .or_default()
+            .insert(feature.to_string(), result.clone());
+        results.insert(feature.to_string(), result);
+    }
+    Ok(results)
+}
+
+pub(crate) fn forget_provider_probes(state: &AppState, profile_id: &str) {
+    if let Ok(mut cache) = lock_state(&state.provider_probes) {
+        cache.remove(profile_id);
+    }
+}
+
+async fn probe_feature(client: &S3Client, bucket: &str, feature: &str) -> (Option<bool>, String) {
+    let key = format!("{PROVIDER_PROBE_KEY_PREFIX}{}", Uuid::new_v4());
+    match feature {
+        "multipart" => match client
+            .create_multipart_upload()
+            .bucket(bucket)
+            .key(&key)
+            .send()
+            .await
+        {
+            Ok(output) => {
+                let upload_id = output.upload_id().unwrap_or_default().to_string();
+                let cleanup = client
+                    .abort_multipart_upload()
+                    .bucket(bucket)
+                    .key(&key)
+                    .upload_id(upload_id)
+                    .send()
+                    .await
+                    .map_err(|err| err.to_string());
+                (
+                    Some(true),
+                    with_cleanup_note("Multipart uploads supported", cleanup.map(|_| ())),
+                )
+            }
+            Err(err) => classify_probe_error(err),
+        },
+        "versioning" => match client.get_bucket_versioning().bucket(bucket).send().await {
+            Ok(output) => {
+                let status = output
+                    .status()
+                    .map(|status| status.as_str().to_string())
+                    .unwrap_or_else(|| "never enabled".to_string());
+                (
+                    Some(true),
+                    format!("Versioning supported (bucket: {status})"),
+                )
+            }
+            Err(err) => classify_probe_error(err),
+        },
+        "tagging" => {
+            let version_id = match put_probe_object(client, bucket, &key, None).await {
+                Ok(output) => output.version_id().map(str::to_string),
+                Err(err) => return classify_probe_error(err),
+            };
+            let tagging = Tag::builder()
+                .key("object0-probe")
+                .value("1")
+                .build()
+                .and_then(|tag| Tagging::builder().tag_set(tag).build());
+            let (supported, detail) = match tagging {
+                Ok(tagging) => match client
+                    .put_object_tagging()
+                    .bucket(bucket)
+                    .key(&key)
+                    .tagging(tagging)
+                    .send()
+                    .await
+                {
+                    Ok(_) => (Some(true), "Object tagging supported".to_string()),
+                    Err(err) => classify_probe_error(err),
+                },
+                Err(err) => (None, format!("Failed to build probe tags: {err}")),
+            };
+            let cleanup = delete_probe_object(client, bucket, &key, version_id).await;
+            (supported, with_cleanup_note(&detail, cleanup))
+        }
+        // Presigning is local SigV4 signing; nothing is sent to the endpoint.
+        "presign" => {
+            let config = match PresigningConfig::expires_in(StdDuration::from_secs(60)) {
+                Ok(config) => config,
+                Err(err) => return (None, format!("Invalid presign config: {err}")),
+            };
+            match client
+                .get_object()
+                .bucket(bucket)
+                .key(&key)
+                .presigned(config)
+                .await
+            {
+                Ok(_) => (Some(true), "Presigned URLs can be generated".to_string()),
+                Err(err) => (Some(false), format!("Presigning failed: {err}")),
+            }
+        }
+        "object-lock" => match client
+            .get_object_lock_configuration()
+            .bucket(bucket)
+            .send()
+            .await
+        {
+            Ok(_) => (Some(true), "Object lock enabled on this bucket".to_string()),
+            Err(err) if err.code() == Some("ObjectLockConfigurationNotFoundError") => (
+                Some(true),
+                "Object lock supported but not enabled on this bucket".to_string(),
+            ),
+            Err(err) => classify_probe_error(err),
+        },
+        "checksums" => {
+            let output = match put_probe_object(
+                client,
+                bucket,
+                &key,
+                Some(ChecksumAlgorithm::Crc32),
+            )
+            .await
+            {
+                Ok(output) => output,
+                Err(err) => return classify_probe_error(err),
+            };
+            let (supported, detail) = if output.checksum_crc32().is_some() {
+                (
+                    Some(true),
+                    "CRC32 checksums stored and returned".to_string(),
+                )
+            } else {
+                (
+                    Some(false),
+                    "Upload accepted but no checksum was returned".to_string(),
+                )
+            };
+            let version_id = output.version_id().map(str::to_string);
+            let cleanup = delete_probe_object(client, bucket, &key, version_id).await;
+            (supported, with_cleanup_note(&detail, cleanup))
+        }
+        _ => (None, format!("Unknown feature: {feature}")),
+    }
+}
+
+async fn put_probe_object(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    checksum: Option<ChecksumAlgorithm>,
+) -> Result<PutObjectOutput, SdkError<PutObjectError, HttpResponse>> {
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(ByteStream::from_static(b"object0 probe"))
+        .set_checksum_algorithm(checksum)
+        .send()
+        .await
+}
+
+// Deleting the exact version keeps versioned buckets free of delete markers.
+async fn delete_probe_object(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    version_id: Option<String>,
+) -> Result<(), String> {
+    client
+        .delete_object()
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+fn with_cleanup_note(detail: &str, cleanup: Result<(), String>) -> String {
+    match cleanup {
+        Ok(()) => detail.to_string(),
+        Err(err) => format!("{detail} (failed to remove probe artifact: {err})"),
+    }
+}
+
+// "Not implemented" style answers mean unsupported; anything else (denied,
+// network, unexpected) leaves support unknown rather than guessing.
+fn classify_probe_error<E>(err: SdkError<E, HttpResponse>) -> (Option<bool>, String)
+where
+    E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+{
+    let status = err
+        .raw_response()
+        .map(|response| response.status().as_u16());
+    let code = err.code().map(str::to_string);
+    let message = code
+        .clone()
+        .or_else(|| err.message().map(str::to_string))
+        .unwrap_or_else(|| err.to_string());
+    match (status, code.as_deref()) {
+        (Some(501 | 405), _) | (_, Some("NotImplemented" | "MethodNotAllowed")) => {
+            (Some(false), format!("Not supported: {message}"))
+        }
+        (Some(403), _) | (_, Some("AccessDenied")) => {
+            (None, format!("Access denied, support unknown: {message}"))
+        }
+        _ => (None, format!("Probe failed: {message}")),
+    }
+}
+
+// A ranged GET rather than HEAD: the URL is signed for GET only. Headers the
+// signature covers go along with it.
+pub(crate) async fn check_presigned_url(presigned: &PresignedRequest) -> PresignedUrlCheck {
+    let client = match reqwest::Client::builder()
+        .timeout(StdDuration::from_secs(SHARE_VALIDATE_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            return PresignedUrlCheck {
+                ok: false,
+                status: None,
+                error: Some(format!("Failed to build HTTP client: {err}")),
+            }
+        }
+    };
+    let mut request = client.get(presigned.uri()).header("Range", "bytes=0-0");
+    for (name, value) in presigned.headers() {
+        request = request.header(name, value);
+    }
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let ok = presigned_status_ok(status);
+            PresignedUrlCheck {
+                ok,
+                status: Some(status),
+                error: (!ok).then(|| format!("URL returned HTTP {status}")),
+            }
+        }
+        Err(err) => PresignedUrlCheck {
+            ok: false,
+            status: None,
+            error: Some(format!("URL request failed: {err}")),
+        },
+    }
+}
+
+// 416 is an empty object: the signature was accepted, there's just no byte 0.
+pub(crate) fn presigned_status_ok(status: u16) -> bool {
+    matches!(status, 200 | 206 | 416)
+}
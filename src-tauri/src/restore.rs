@@ -0,0 +1,120 @@
+//! Archive restores: `objects:restore` asks S3 to thaw a GLACIER or
+//! DEEP_ARCHIVE object for a number of days, and `objects:restore-status`
+//! reads the `x-amz-restore` header to tell when the copy is readable.
+
+use super::*;
+use aws_sdk_s3::{
+    error::ProvideErrorMetadata,
+    types::{GlacierJobParameters, RestoreRequest, Tier},
+};
+
+pub(crate) fn parse_restore_tier(tier: &str) -> Result<Tier, String> {
+    let tier = tier.trim();
+    RESTORE_TIERS
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(tier))
+        .map(|known| Tier::from(*known))
+        .ok_or_else(|| {
+            format!(
+                "Unknown restore tier \"{tier}\" (use {})",
+                RESTORE_TIERS.join(", ")
+            )
+        })
+}
+
+// A restore that's already running is not an error; asking again is how the
+// UI retries after a lost response.
+pub(crate) async fn s3_restore_object(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    days: i32,
+    tier: &str,
+) -> Result<(), String> {
+    if !(1..=MAX_RESTORE_DAYS).contains(&days) {
+        return Err(format!(
+            "Restore days must be between 1 and {MAX_RESTORE_DAYS}"
+        ));
+    }
+    let job_parameters = GlacierJobParameters::builder()
+        .tier(parse_restore_tier(tier)?)
+        .build()
+        .map_err(|err| format!("Invalid restore tier: {err}"))?;
+    let request = RestoreRequest::builder()
+        .days(days)
+        .glacier_job_parameters(job_parameters)
+        .build();
+    match client
+        .restore_object()
+        .bucket(bucket)
+        .key(key)
+        .restore_request(request)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(err) if err.code() == Some("RestoreAlreadyInProgress") => Ok(()),
+        Err(err) => Err(s3_error(
+            err,
+            &format!("restore {}", format_s3_uri(bucket, key)),
+        )),
+    }
+}
+
+pub(crate) async fn s3_object_restore_status(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+) -> Result<ObjectRestoreStatus, String> {
+    let output = client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("check the restore of {}", format_s3_uri(bucket, key)),
+            )
+        })?;
+    let storage_class = output
+        .storage_class()
+        .map(|class| class.as_str().to_string());
+    let archived = is_archived_storage_class(storage_class.as_deref());
+    let (state, expiry) = parse_restore_header(output.restore(), archived);
+    Ok(ObjectRestoreStatus {
+        storage_class,
+        state,
+        expiry,
+    })
+}
+
+// `x-amz-restore` is `ongoing-request="true"` while thawing and
+// `ongoing-request="false", expiry-date="<RFC 1123 date>"` once readable.
+// The expiry comes back as RFC 3339 when it parses.
+pub(crate) fn parse_restore_header(
+    header: Option<&str>,
+    archived: bool,
+) -> (ObjectRestoreState, Option<String>) {
+    let Some(header) = header else {
+        let state = if archived {
+            ObjectRestoreState::Archived
+        } else {
+            ObjectRestoreState::NotArchived
+        };
+        return (state, None);
+    };
+    if header.contains("ongoing-request=\"true\"") {
+        return (ObjectRestoreState::InProgress, None);
+    }
+    let expiry = header
+        .split_once("expiry-date=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(date, _)| {
+            chrono::DateTime::parse_from_rfc2822(date)
+                .map(|parsed| parsed.with_timezone(&Utc).to_rfc3339())
+                .unwrap_or_else(|_| date.to_string())
+        });
+    (ObjectRestoreState::Restored, expiry)
+}
@@ -7,19 +7,101 @@ use super::*;
 #[tauri::command]
 pub(crate) async fn rpc_request(
     app: AppHandle,
-    state: State<'_, AppState>,
     method: String,
     payload: Option<Value>,
 ) -> Result<Value, String> {
-    let payload = payload_or_null(payload);
     let method = RpcMethod::parse(&method)
         .ok_or_else(|| format!("RPC method not implemented yet: {method}"))?;
+    dispatch_rpc(app, method, payload_or_null(payload)).await
+}
+
+// Runs several calls over one IPC round-trip and answers with one
+// `{ ok, result | error }` entry per call, in request order. Consecutive
+// read-only calls run concurrently; anything that mutates state runs on its
+// own, after everything before it has finished, so ordering is preserved.
+#[tauri::command]
+pub(crate) async fn rpc_batch(
+    app: AppHandle,
+    calls: Vec<RpcBatchCall>,
+) -> Result<Vec<Value>, String> {
+    if calls.len() > RPC_BATCH_MAX_CALLS {
+        return Err(format!(
+            "Batch too large: {} calls (max {RPC_BATCH_MAX_CALLS})",
+            calls.len()
+        ));
+    }
+
+    let mut results: Vec<Value> = Vec::with_capacity(calls.len());
+    let mut read_only_run = Vec::new();
+
+    for call in calls {
+        let payload = payload_or_null(call.payload);
+        let method = match RpcMethod::parse(&call.method) {
+            Some(method) if method.is_batchable() => Ok(method),
+            Some(_) => Err(format!("RPC method cannot be batched: {}", call.method)),
+            None => Err(format!("RPC method not implemented yet: {}", call.method)),
+        };
+
+        if let Ok(method) = method {
+            if method.is_read_only() {
+                read_only_run.push(dispatch_rpc(app.clone(), method, payload));
+                continue;
+            }
+        }
+
+        results.extend(
+            join_all(std::mem::take(&mut read_only_run))
+                .await
+                .into_iter()
+                .map(rpc_batch_entry),
+        );
+        let result = match method {
+            Ok(method) => dispatch_rpc(app.clone(), method, payload).await,
+            Err(err) => Err(err),
+        };
+        results.push(rpc_batch_entry(result));
+    }
+
+    results.extend(
+        join_all(read_only_run)
+            .await
+            .into_iter()
+            .map(rpc_batch_entry),
+    );
+    Ok(results)
+}
+
+fn job_concurrency_json(jobs: &JobRuntime) -> Value {
+    json!({
+        "concurrency": jobs.concurrency,
+        "adaptive": jobs.adaptive.enabled,
+        "effectiveConcurrency": effective_job_concurrency(jobs),
+    })
+}
+
+fn rpc_batch_entry(result: Result<Value, String>) -> Value {
+    match result {
+        Ok(result) => json!({ "ok": true, "result": result }),
+        Err(error) => json!({ "ok": false, "error": error }),
+    }
+}
+
+pub(crate) async fn dispatch_rpc(
+    app: AppHandle,
+    method: RpcMethod,
+    payload: Value,
+) -> Result<Value, String> {
+    let state = app.state::<AppState>();
+    note_vault_activity(&state, method);
 
     match method {
         RpcMethod::VaultStatus => {
             let path = vault_path()?;
             let exists = path.exists();
-            let unlocked = lock_state(&state.vault)?.unlocked;
+            let (unlocked, needs_new_passphrase) = {
+                let vault = lock_state(&state.vault)?;
+                (vault.unlocked, is_recovery_session(&vault))
+            };
             let has_recovery_key = if exists {
                 has_recovery_key_on_disk(&path)?
             } else {
@@ -30,6 +112,7 @@ pub(crate) async fn rpc_request(
                 "exists": exists,
                 "unlocked": unlocked,
                 "hasRecoveryKey": has_recovery_key,
+                "needsNewPassphrase": needs_new_passphrase,
             }))
         }
         RpcMethod::VaultSetup => {
@@ -43,8 +126,9 @@ pub(crate) async fn rpc_request(
                 return Err("Vault already exists".to_string());
             }
 
+            let kdf = default_vault_kdf(input.kdf.unwrap_or(VaultKdf::Argon2id));
             let salt = random_bytes::<SALT_BYTES>();
-            let key = derive_key(&input.passphrase, &salt);
+            let key = derive_vault_key(&input.passphrase, &salt, kdf)?;
             let recovery_salt = random_bytes::<SALT_BYTES>();
             let recovery_key_plain = generate_recovery_key();
             let recovery_key = derive_key(&recovery_key_plain, &recovery_salt);
@@ -53,6 +137,7 @@ pub(crate) async fn rpc_request(
             vault.unlocked = true;
             vault.data = Some(VaultData::default());
             vault.key = Some(key);
+            vault.kdf = kdf;
             vault.salt = Some(salt.to_vec());
             vault.recovery_key = Some(recovery_key);
             vault.recovery_salt = Some(recovery_salt.to_vec());
@@ -92,19 +177,12 @@ pub(crate) async fn rpc_request(
 
             match unlock_with_passphrase(&path, &input.passphrase) {
                 Ok(unlock) => {
+                    let has_recovery_key = unlock.has_recovery_key;
                     let mut vault = lock_state(&state.vault)?;
-                    vault.unlocked = true;
-                    vault.data = Some(unlock.data);
-                    vault.key = Some(unlock.key);
-                    vault.salt = Some(unlock.salt);
-                    vault.recovery_salt = unlock.recovery_salt;
-                    vault.recovery_key = None;
+                    apply_vault_unlock(&mut vault, &path, unlock)?;
                     let profiles = profile_infos(&vault);
-
-                    if unlock.needs_rewrite {
-                        save_vault(&path, &vault)?;
-                    }
                     drop(vault);
+                    try_start_queued_jobs(app.clone());
 
                     if input.remember.unwrap_or(false) {
                         if let Err(err) = store_passphrase(&input.passphrase) {
@@ -117,9 +195,17 @@ pub(crate) async fn rpc_request(
                     Ok(json!({
                         "success": true,
                         "profiles": profiles,
-                        "hasRecoveryKey": unlock.has_recovery_key,
+                        "hasRecoveryKey": has_recovery_key,
+                        "recoveryKeyMissing": !has_recovery_key,
                     }))
                 }
+                Err(err) if err.starts_with(VAULT_CORRUPT) => Ok(json!({
+                    "success": false,
+                    "profiles": [],
+                    "hasRecoveryKey": has_recovery_key,
+                    "reason": "corrupt",
+                    "detail": err,
+                })),
                 Err(_) => Ok(json!({
                     "success": false,
                     "profiles": [],
@@ -147,6 +233,7 @@ pub(crate) async fn rpc_request(
                         "success": true,
                         "profiles": profile_infos(&vault),
                         "hasRecoveryKey": has_recovery_key,
+                        "recoveryKeyMissing": !has_recovery_key,
                     }));
                 }
             }
@@ -174,24 +261,28 @@ pub(crate) async fn rpc_request(
 
             match unlock_with_passphrase(&path, &passphrase) {
                 Ok(unlock) => {
+                    let has_recovery_key = unlock.has_recovery_key;
                     let mut vault = lock_state(&state.vault)?;
-                    vault.unlocked = true;
-                    vault.data = Some(unlock.data);
-                    vault.key = Some(unlock.key);
-                    vault.salt = Some(unlock.salt);
-                    vault.recovery_salt = unlock.recovery_salt;
-                    vault.recovery_key = None;
-
-                    if unlock.needs_rewrite {
-                        save_vault(&path, &vault)?;
-                    }
+                    apply_vault_unlock(&mut vault, &path, unlock)?;
+                    let profiles = profile_infos(&vault);
+                    drop(vault);
+                    try_start_queued_jobs(app.clone());
 
                     Ok(json!({
                         "success": true,
-                        "profiles": profile_infos(&vault),
-                        "hasRecoveryKey": unlock.has_recovery_key,
+                        "profiles": profiles,
+                        "hasRecoveryKey": has_recovery_key,
+                        "recoveryKeyMissing": !has_recovery_key,
                     }))
                 }
+                // The stored passphrase may be fine; keep it.
+                Err(err) if err.starts_with(VAULT_CORRUPT) => Ok(json!({
+                    "success": false,
+                    "profiles": [],
+                    "hasRecoveryKey": has_recovery_key,
+                    "reason": "corrupt",
+                    "detail": err,
+                })),
                 Err(_) => {
                     let _ = clear_stored_passphrase();
                     Ok(json!({
@@ -206,6 +297,8 @@ pub(crate) async fn rpc_request(
         RpcMethod::VaultLock => {
             let mut vault = lock_state(&state.vault)?;
             lock_vault_runtime(&mut vault);
+            drop(vault);
+            cancel_all_jobs(&app, CancelReason::VaultLocked);
             stop_all_folder_sync_rules(&app);
             refresh_tray_menu(&app);
             Ok(Value::Null)
@@ -239,14 +332,19 @@ pub(crate) async fn rpc_request(
                     vault.unlocked = true;
                     vault.data = Some(unlock.data);
                     vault.key = None;
+                    vault.kdf = unlock.kdf;
                     vault.salt = Some(unlock.salt);
                     vault.recovery_salt = Some(unlock.recovery_salt);
                     vault.recovery_key = Some(unlock.recovery_key);
+                    let profiles = profile_infos(&vault);
+                    drop(vault);
                     let _ = clear_stored_passphrase();
+                    try_start_queued_jobs(app.clone());
 
                     Ok(json!({
                         "success": true,
-                        "profiles": profile_infos(&vault),
+                        "profiles": profiles,
+                        "needsNewPassphrase": true,
                     }))
                 }
                 Err(_) => Ok(json!({ "success": false, "profiles": [] })),
@@ -263,7 +361,7 @@ pub(crate) async fn rpc_request(
             ensure_unlocked(&vault)?;
 
             let new_salt = random_bytes::<SALT_BYTES>();
-            let new_key = derive_key(&input.new_passphrase, &new_salt);
+            let new_key = derive_vault_key(&input.new_passphrase, &new_salt, vault.kdf)?;
             let new_recovery_salt = random_bytes::<SALT_BYTES>();
             let new_recovery_key_plain = generate_recovery_key();
             let new_recovery_key = derive_key(&new_recovery_key_plain, &new_recovery_salt);
@@ -304,6 +402,20 @@ pub(crate) async fn rpc_request(
             let path = vault_path()?;
             Ok(json!({ "hasRecoveryKey": has_recovery_key_on_disk(&path)? }))
         }
+        RpcMethod::VaultGetAutoLock => Ok(json!({
+            "autoLockSecs": current_settings(&state).auto_lock_secs,
+            "minSecs": VAULT_AUTO_LOCK_MIN_SECS,
+        })),
+        RpcMethod::VaultSetAutoLock => {
+            let input: VaultAutoLockInput = parse_payload(payload)?;
+            validate_auto_lock_secs(input.secs)?;
+            let mut current = lock_state(&state.settings)?;
+            let mut settings = current.clone();
+            settings.auto_lock_secs = input.secs;
+            save_settings_to_disk(&settings)?;
+            *current = settings;
+            Ok(json!({ "autoLockSecs": input.secs }))
+        }
         RpcMethod::VaultReset => {
             let path = vault_path()?;
             if path.exists() {
@@ -312,7 +424,9 @@ pub(crate) async fn rpc_request(
             let _ = clear_stored_passphrase();
 
             let mut vault = lock_state(&state.vault)?;
-            *vault = VaultRuntime::default();
+            lock_vault_runtime(&mut vault);
+            drop(vault);
+            cancel_all_jobs(&app, CancelReason::VaultLocked);
             stop_all_folder_sync_rules(&app);
             refresh_tray_menu(&app);
             Ok(json!({ "success": true }))
@@ -325,25 +439,11 @@ pub(crate) async fn rpc_request(
         }
         RpcMethod::ProfileAdd => {
             let input: ProfileInput = parse_payload(payload)?;
+            let profile = new_profile(input)?;
             let path = vault_path()?;
             let mut vault = lock_state(&state.vault)?;
             ensure_writable(&vault)?;
 
-            let timestamp = now_iso();
-            let profile = Profile {
-                id: Uuid::new_v4().to_string(),
-                name: input.name,
-                provider: input.provider,
-                access_key_id: input.access_key_id,
-                secret_access_key: input.secret_access_key,
-                session_token: input.session_token,
-                endpoint: input.endpoint,
-                region: input.region,
-                default_bucket: input.default_bucket,
-                created_at: timestamp.clone(),
-                updated_at: timestamp,
-            };
-
             let data = vault
                 .data
                 .as_mut()
@@ -351,7 +451,45 @@ pub(crate) async fn rpc_request(
             data.profiles.push(profile.clone());
             save_vault(&path, &vault)?;
 
-            Ok(json!(to_profile_info(&profile)))
+            let mut info = json!(to_profile_info(&profile));
+            if let Some(warning) = region_warning(&profile.provider, profile.region.as_deref()) {
+                info["regionWarning"] = json!(warning);
+            }
+            Ok(info)
+        }
+        RpcMethod::ProfileImportAws => {
+            let candidates: Vec<AwsProfileCandidate> = read_aws_shared_profiles()?
+                .iter()
+                .map(aws_profile_candidate)
+                .collect();
+            Ok(json!(candidates))
+        }
+        RpcMethod::ProfileImportAwsConfirm => {
+            let input: ProfileImportAwsConfirmInput = parse_payload(payload)?;
+            // Re-read so secrets never pass through the frontend.
+            let mut available = read_aws_shared_profiles()?;
+            let mut profiles = Vec::with_capacity(input.names.len());
+            for name in &input.names {
+                let Some(index) = available.iter().position(|profile| &profile.name == name) else {
+                    return Err(format!(
+                        "AWS profile \"{name}\" has no access keys to import"
+                    ));
+                };
+                profiles.push(new_profile(aws_profile_input(available.remove(index)))?);
+            }
+
+            let path = vault_path()?;
+            let mut vault = lock_state(&state.vault)?;
+            ensure_writable(&vault)?;
+            let data = vault
+                .data
+                .as_mut()
+                .ok_or_else(|| "Vault is locked".to_string())?;
+            data.profiles.extend(profiles.iter().cloned());
+            save_vault(&path, &vault)?;
+
+            let infos: Vec<ProfileInfo> = profiles.iter().map(to_profile_info).collect();
+            Ok(json!(infos))
         }
         RpcMethod::ProfileUpdate => {
             let input: ProfileUpdateInput = parse_payload(payload)?;
@@ -371,6 +509,26 @@ pub(crate) async fn rpc_request(
             else {
                 return Err("Profile not found".to_string());
             };
+            let use_accelerate = input.use_accelerate.unwrap_or(profile.use_accelerate);
+            let use_dualstack = input.use_dualstack.unwrap_or(profile.use_dualstack);
+            validate_endpoint_options(
+                &input.provider,
+                input.endpoint.as_deref(),
+                use_accelerate,
+                use_dualstack,
+            )?;
+            let require_checksums = input.require_checksums.unwrap_or(profile.require_checksums);
+            let disable_checksums = input.disable_checksums.unwrap_or(profile.disable_checksums);
+            validate_checksum_options(require_checksums, disable_checksums)?;
+            let (sse, sse_kms_key_id) =
+                match (input.sse.as_deref(), input.sse_kms_key_id.as_deref()) {
+                    (Some(sse), kms_key_id) => parse_upload_encryption(Some(sse), kms_key_id)?,
+                    // A new key for the encryption the profile already uses.
+                    (None, Some(kms_key_id)) => {
+                        parse_upload_encryption(profile.sse.as_deref(), Some(kms_key_id))?
+                    }
+                    (None, None) => (profile.sse.clone(), profile.sse_kms_key_id.clone()),
+                };
 
             profile.name = input.name;
             profile.provider = input.provider;
@@ -390,18 +548,43 @@ pub(crate) async fn rpc_request(
             profile.endpoint = input.endpoint;
             profile.region = input.region;
             profile.default_bucket = input.default_bucket;
+            if let Some(requester_pays) = input.requester_pays {
+                profile.requester_pays = requester_pays;
+            }
+            if let Some(ephemeral) = input.ephemeral {
+                profile.ephemeral = ephemeral;
+            }
+            profile.require_checksums = require_checksums;
+            profile.disable_checksums = disable_checksums;
+            profile.use_accelerate = use_accelerate;
+            profile.use_dualstack = use_dualstack;
+            profile.sse = sse;
+            profile.sse_kms_key_id = sse_kms_key_id;
+            if let Some(transform) = input.key_transform {
+                validate_key_transform(&transform)?;
+                profile.key_transform =
+                    Some(transform).filter(|transform| !key_transform_is_empty(transform));
+            }
             profile.updated_at = now_iso();
 
+            // An ephemeral profile's secret may not have been entered this session.
             if profile.access_key_id.trim().is_empty()
-                || profile.secret_access_key.trim().is_empty()
+                || (profile.secret_access_key.trim().is_empty() && !profile.ephemeral)
             {
                 return Err("Profile credentials cannot be empty".to_string());
             }
 
             let profile_info = to_profile_info(profile);
+            let warning = region_warning(&profile_info.provider, profile_info.region.as_deref());
             save_vault(&path, &vault)?;
+            // Endpoint or credentials may have changed.
+            forget_provider_probes(&state, &profile_info.id);
 
-            Ok(json!(profile_info))
+            let mut info = json!(profile_info);
+            if let Some(warning) = warning {
+                info["regionWarning"] = json!(warning);
+            }
+            Ok(info)
         }
         RpcMethod::ProfileRemove => {
             let input: IdInput = parse_payload(payload)?;
@@ -421,10 +604,54 @@ pub(crate) async fn rpc_request(
             }
 
             save_vault(&path, &vault)?;
+            let _ = forget_nav_location(&input.id);
+            let _ = forget_profile_bookmarks(&input.id);
+            forget_provider_probes(&state, &input.id);
             Ok(Value::Null)
         }
+        RpcMethod::ProfileProvideCredentials => {
+            let input: ProfileProvideCredentialsInput = parse_payload(payload)?;
+            if input.secret_access_key.trim().is_empty() {
+                return Err("Secret access key cannot be empty".to_string());
+            }
+            let mut vault = lock_state(&state.vault)?;
+            ensure_unlocked(&vault)?;
+            let data = vault
+                .data
+                .as_mut()
+                .ok_or_else(|| "Vault is locked".to_string())?;
+            let Some(profile) = data
+                .profiles
+                .iter_mut()
+                .find(|profile| profile.id == input.id)
+            else {
+                return Err("Profile not found".to_string());
+            };
+            if !profile.ephemeral {
+                return Err("Only ephemeral profiles take per-session credentials".to_string());
+            }
+
+            // Runtime only: save_vault strips these for ephemeral profiles.
+            if let Some(access_key_id) = input.access_key_id {
+                if !access_key_id.trim().is_empty() {
+                    profile.access_key_id = access_key_id;
+                }
+            }
+            profile.secret_access_key = input.secret_access_key;
+            profile.session_token = input.session_token.filter(|value| !value.trim().is_empty());
+            let profile_info = to_profile_info(profile);
+            drop(vault);
+            forget_provider_probes(&state, &profile_info.id);
+            Ok(json!(profile_info))
+        }
         RpcMethod::ProfileTest => {
             let input: ProfileTestInput = parse_payload(payload)?;
+            validate_endpoint_options(
+                &input.provider,
+                input.endpoint.as_deref(),
+                input.use_accelerate,
+                input.use_dualstack,
+            )?;
             let profile = Profile {
                 id: "test".to_string(),
                 name: "test".to_string(),
@@ -435,9 +662,19 @@ pub(crate) async fn rpc_request(
                 endpoint: input.endpoint,
                 region: Some(input.region),
                 default_bucket: input.default_bucket.clone(),
+                requester_pays: false,
+                ephemeral: false,
+                key_transform: None,
+                require_checksums: false,
+                disable_checksums: false,
+                use_accelerate: input.use_accelerate,
+                use_dualstack: input.use_dualstack,
+                sse: None,
+                sse_kms_key_id: None,
                 created_at: now_iso(),
                 updated_at: now_iso(),
             };
+            let warning = region_warning(&profile.provider, profile.region.as_deref());
 
             let client = match to_s3_client(&profile) {
                 Ok(client) => client,
@@ -446,6 +683,7 @@ pub(crate) async fn rpc_request(
                         "success": false,
                         "bucketCount": 0,
                         "error": error,
+                        "regionWarning": warning,
                     }));
                 }
             };
@@ -461,6 +699,29 @@ pub(crate) async fn rpc_request(
                         return Ok(json!({
                             "success": true,
                             "bucketCount": 1,
+                            "regionWarning": warning,
+                        }));
+                    }
+                    // Listing buckets never goes through the accelerate
+                    // endpoint, so it can't vouch for it.
+                    Err(err) if input.use_accelerate => {
+                        let status = err
+                            .raw_response()
+                            .map(|response| response.status().as_u16());
+                        let error = if matches!(status, Some(400)) {
+                            s3_error_message(
+                                S3ErrorKind::AccelerateNotConfigured,
+                                &format!("reach {default_bucket}"),
+                                String::new(),
+                            )
+                        } else {
+                            s3_error(err, &format!("reach {default_bucket}"))
+                        };
+                        return Ok(json!({
+                            "success": false,
+                            "bucketCount": 0,
+                            "error": error,
+                            "regionWarning": warning,
                         }));
                     }
                     Err(_) => {
@@ -473,15 +734,53 @@ pub(crate) async fn rpc_request(
                 Ok(output) => Ok(json!({
                     "success": true,
                     "bucketCount": output.buckets().len(),
+                    "regionWarning": warning,
                 })),
                 Err(err) => Ok(json!({
                     "success": false,
                     "bucketCount": 0,
                     "error": err.to_string(),
+                    "regionWarning": warning,
                 })),
             }
         }
 
+        RpcMethod::BucketsGetCors => {
+            let input: BucketInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let rules = s3_get_bucket_cors(&client, &input.bucket).await?;
+            Ok(json!({ "rules": rules }))
+        }
+        RpcMethod::BucketsSetCors => {
+            let input: BucketCorsSetInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_set_bucket_cors(&client, &input.bucket, &input.rules).await?;
+            Ok(Value::Null)
+        }
+        RpcMethod::BucketsGetPolicy => {
+            let input: BucketInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let policy = s3_get_bucket_policy(&client, &input.bucket).await?;
+            Ok(json!({ "policy": policy }))
+        }
+        RpcMethod::BucketsSetPolicy => {
+            let input: BucketPolicySetInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_set_bucket_policy(&client, &input.bucket, input.policy.as_deref()).await?;
+            Ok(Value::Null)
+        }
+        RpcMethod::BucketsGetLifecycle => {
+            let input: BucketInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let rules = s3_get_bucket_lifecycle(&client, &input.bucket).await?;
+            Ok(json!({ "rules": rules }))
+        }
+        RpcMethod::BucketsSetLifecycle => {
+            let input: BucketLifecycleSetInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_set_bucket_lifecycle(&client, &input.bucket, &input.rules).await?;
+            Ok(Value::Null)
+        }
         RpcMethod::BucketsList => {
             let input: ProfileIdInput = parse_payload(payload)?;
             let profile = profile_for_id(&state, &input.profile_id)?;
@@ -517,63 +816,67 @@ pub(crate) async fn rpc_request(
 
         RpcMethod::ObjectsList => {
             let input: ObjectsListInput = parse_payload(payload)?;
-            let client = s3_client_for_profile(&state, &input.profile_id)?;
-
-            let mut request = client
-                .list_objects_v2()
-                .bucket(input.bucket.clone())
-                .delimiter("/");
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
 
-            if let Some(prefix) = input.prefix.as_deref() {
-                request = request.prefix(prefix);
-            }
-            if let Some(max_keys) = input.max_keys {
-                request = request.max_keys(max_keys.into());
+            let mut listing = s3_list_folder(&client, &profile, &input).await?;
+            if let Some(field) = input.sort_field {
+                sort_object_listing(&mut listing, field, input.sort_dir);
             }
-            if let Some(start_after) = input.start_after.as_deref() {
-                request = request.start_after(start_after);
-            }
-
-            let output = request.send().await.map_err(|err| err.to_string())?;
-
-            let objects: Vec<Value> = output
-                .contents()
+            let prefixes: Vec<Value> = listing
+                .prefixes
                 .iter()
-                .map(|item| {
-                    json!({
-                        "key": item.key().unwrap_or_default(),
-                        "size": item.size().unwrap_or(0).max(0),
-                        "lastModified": item.last_modified().map(s3_datetime_to_iso).unwrap_or_default(),
-                        "etag": item.e_tag().unwrap_or_default().trim_matches('"'),
-                        "storageClass": item.storage_class().map(|value| value.as_str()),
-                    })
-                })
+                .map(|prefix| json!({ "prefix": prefix }))
                 .collect();
 
-            let prefixes: Vec<Value> = output
-                .common_prefixes()
-                .iter()
-                .filter_map(|prefix| prefix.prefix().map(|p| json!({ "prefix": p })))
-                .collect();
-
-            let next_cursor = output
-                .contents()
-                .last()
-                .and_then(|item| item.key().map(str::to_string));
-
             Ok(json!({
-                "objects": objects,
+                "objects": listing.objects,
                 "prefixes": prefixes,
-                "isTruncated": output.is_truncated().unwrap_or(false),
-                "nextCursor": next_cursor,
+                "isTruncated": listing.is_truncated,
+                "nextCursor": listing.next_cursor,
             }))
         }
+        RpcMethod::ObjectsRestore => {
+            let input: ObjectsRestoreInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_restore_object(&client, &input.bucket, &input.key, input.days, &input.tier).await?;
+            Ok(json!(
+                s3_object_restore_status(&client, &input.bucket, &input.key).await?
+            ))
+        }
+        RpcMethod::ObjectsRestoreStatus => {
+            let input: ObjectsStatInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            Ok(json!(
+                s3_object_restore_status(&client, &input.bucket, &input.key).await?
+            ))
+        }
+        RpcMethod::ObjectsCreateFolder => {
+            let input: ObjectsCreateFolderInput = parse_payload(payload)?;
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
+            let key =
+                s3_create_folder(&client, &profile.provider, &input.bucket, &input.prefix).await?;
+            Ok(json!({ "key": key }))
+        }
+        RpcMethod::ObjectsListVersions => {
+            let input: ObjectsListVersionsInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let prefix = input.prefix.as_deref().unwrap_or("");
+            Ok(json!(
+                s3_list_object_versions(&client, &input.bucket, prefix).await?
+            ))
+        }
         RpcMethod::ObjectsDelete => {
             let input: ObjectsDeleteInput = parse_payload(payload)?;
             if input.keys.is_empty() {
                 return Ok(Value::Null);
             }
 
+            if input.version_id.is_some() && input.keys.len() > 1 {
+                return Err("A version id can only be used when deleting a single key".to_string());
+            }
+
             let client = s3_client_for_profile(&state, &input.profile_id)?;
 
             if input.keys.len() == 1 {
@@ -581,6 +884,7 @@ pub(crate) async fn rpc_request(
                     .delete_object()
                     .bucket(input.bucket)
                     .key(input.keys[0].clone())
+                    .set_version_id(input.version_id)
                     .send()
                     .await
                     .map_err(|err| err.to_string())?;
@@ -615,12 +919,9 @@ pub(crate) async fn rpc_request(
             let input: ObjectsRenameInput = parse_payload(payload)?;
             let client = s3_client_for_profile(&state, &input.profile_id)?;
 
-            let source_key = utf8_percent_encode(&input.old_key, COPY_SOURCE_ENCODE_SET);
-            let copy_source = format!("{}/{}", input.bucket, source_key);
-
             client
                 .copy_object()
-                .copy_source(copy_source)
+                .copy_source(copy_source_header(&input.bucket, &input.old_key))
                 .bucket(input.bucket.clone())
                 .key(input.new_key)
                 .send()
@@ -637,28 +938,372 @@ pub(crate) async fn rpc_request(
 
             Ok(Value::Null)
         }
-        RpcMethod::ObjectsStat => {
+        RpcMethod::ObjectsDeletePrefix => {
+            let input: ObjectsDeletePrefixInput = parse_payload(payload)?;
+            // A folder prefix, so `photos` doesn't also take `photos2/`.
+            let prefix = normalize_prefix(&input.prefix);
+            if prefix.is_empty() && !input.allow_bucket_wide {
+                return Err(
+                    "Refusing to delete every object in the bucket without allowBucketWide"
+                        .to_string(),
+                );
+            }
+            let target = format_s3_uri(&input.bucket, &prefix);
+            let action = format!(
+                "objects:delete-prefix\n{}\n{}\n{prefix}",
+                input.profile_id, input.bucket
+            );
+
+            let Some(token) = input.confirm_token.as_deref() else {
+                profile_for_id(&state, &input.profile_id)?;
+                let token = issue_confirmation_token(&state, &action)?;
+                return Ok(json!({
+                    "confirmationRequired": true,
+                    "confirmToken": token,
+                    "expiresInSecs": CONFIRMATION_TOKEN_TTL_SECS,
+                    "summary": format!("Delete every object under {target}"),
+                }));
+            };
+            consume_confirmation_token(&state, &action, token)?;
+
+            let job_id = enqueue_job(
+                &app,
+                JobType::Delete,
+                target.clone(),
+                format!("Delete everything under {target}"),
+                0,
+                JobTaskKind::DeletePrefix {
+                    profile_id: input.profile_id,
+                    bucket: input.bucket,
+                    prefix,
+                },
+            )?;
+            Ok(json!({ "jobId": job_id }))
+        }
+        RpcMethod::ObjectsGetAcl => {
             let input: ObjectsStatInput = parse_payload(payload)?;
             let client = s3_client_for_profile(&state, &input.profile_id)?;
+            Ok(json!(
+                s3_get_object_acl(&client, &input.bucket, &input.key).await?
+            ))
+        }
+        RpcMethod::ObjectsSetAcl => {
+            let input: ObjectAclSetInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_set_object_acl(
+                &client,
+                &input.bucket,
+                &input.key,
+                input.acl.as_deref(),
+                input.grants.as_deref(),
+            )
+            .await?;
+            Ok(Value::Null)
+        }
+        RpcMethod::ObjectsGetTags => {
+            let input: ObjectsStatInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            Ok(json!(
+                s3_get_object_tags(&client, &input.bucket, &input.key).await?
+            ))
+        }
+        RpcMethod::ObjectsSetTags => {
+            let input: ObjectTagsSetInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            s3_set_object_tags(&client, &input.bucket, &input.key, &input.tags).await?;
+            Ok(Value::Null)
+        }
+        RpcMethod::ObjectsStat => {
+            let input: ObjectsStatInput = parse_payload(payload)?;
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
 
+            let s3_uri = format_s3_uri(&input.bucket, &input.key);
             let output = client
                 .head_object()
                 .bucket(input.bucket)
                 .key(input.key)
                 .send()
                 .await
-                .map_err(|err| err.to_string())?;
+                .map_err(|err| s3_error_with_requester_pays_hint(err, profile.requester_pays))?;
 
             Ok(json!({
+                "s3Uri": s3_uri,
                 "size": output.content_length().unwrap_or(0).max(0),
-                "etag": output.e_tag().unwrap_or_default().trim_matches('"'),
+                "etag": normalize_etag(output.e_tag()),
                 "lastModified": output.last_modified().map(s3_datetime_to_iso).unwrap_or_else(now_iso),
                 "type": output.content_type().unwrap_or("application/octet-stream"),
+                "serverSideEncryption": output.server_side_encryption().map(|sse| sse.as_str()),
+                "sseKmsKeyId": output.ssekms_key_id(),
             }))
         }
 
+        RpcMethod::ObjectsExists => {
+            let input: ObjectsStatInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+
+            match s3_head_if_exists(&client, &input.bucket, &input.key).await? {
+                Some(object) => Ok(json!({
+                    "exists": true,
+                    "size": object.size,
+                    "etag": object.etag,
+                    "lastModified": object.last_modified,
+                })),
+                None => Ok(json!({ "exists": false })),
+            }
+        }
+        RpcMethod::ObjectsPreview => {
+            let input: ObjectsPreviewInput = parse_payload(payload)?;
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
+            let max_bytes = input
+                .max_bytes
+                .unwrap_or(PREVIEW_DEFAULT_BYTES)
+                .clamp(1, PREVIEW_MAX_BYTES);
+
+            let head = s3_read_object_head(
+                &client,
+                &input.bucket,
+                &input.key,
+                max_bytes,
+                profile.requester_pays,
+            )
+            .await?;
+            let gzipped = head.gzip_encoded || input.key.to_ascii_lowercase().ends_with(".gz");
+
+            let fetched_all = head.bytes.len() as i64 >= head.size;
+            let (bytes, decompressed, truncated) =
+                if gzipped && input.decompress && !head.bytes.is_empty() {
+                    let (text, cut) = gunzip_prefix(&head.bytes, PREVIEW_MAX_DECOMPRESSED_BYTES)?;
+                    (text, true, cut || !fetched_all)
+                } else {
+                    (head.bytes, false, !fetched_all)
+                };
+
+            Ok(json!({
+                "content": String::from_utf8_lossy(&bytes),
+                "binary": bytes.contains(&0),
+                "truncated": truncated,
+                "decompressed": decompressed,
+                "size": head.size,
+                "contentType": head.content_type,
+            }))
+        }
+        RpcMethod::ObjectsGetText => {
+            let input: ObjectsGetTextInput = parse_payload(payload)?;
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
+            let max_bytes = input
+                .max_bytes
+                .unwrap_or(GET_TEXT_MAX_BYTES)
+                .clamp(1, GET_TEXT_MAX_BYTES);
+            let head = s3_read_object_head(
+                &client,
+                &input.bucket,
+                &input.key,
+                max_bytes,
+                profile.requester_pays,
+            )
+            .await?;
+
+            let truncated = (head.bytes.len() as i64) < head.size;
+            let text = decode_utf8_text(&head.bytes, truncated);
+            let is_utf8 = text.is_some();
+            Ok(json!({
+                "content": text.unwrap_or_else(|| String::from_utf8_lossy(&head.bytes).to_string()),
+                "truncated": truncated,
+                "isUtf8": is_utf8,
+                "size": head.size,
+                "contentType": head.content_type,
+            }))
+        }
+        RpcMethod::ObjectsSearch => {
+            let input: ObjectsSearchInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let result = run_cancellable_operation(
+                &state,
+                input.operation_id.as_deref(),
+                StdDuration::from_secs(OBJECT_SEARCH_TIMEOUT_SECS),
+                &format!("search {}", format_s3_uri(&input.bucket, &input.prefix)),
+                s3_search_objects(
+                    &client,
+                    &input.bucket,
+                    &input.prefix,
+                    &input.pattern,
+                    input
+                        .max_results
+                        .unwrap_or(OBJECT_SEARCH_DEFAULT_MAX_RESULTS)
+                        .max(1),
+                ),
+            )
+            .await?;
+            Ok(json!(result))
+        }
+        RpcMethod::ObjectsDiffPrefixes => {
+            let input: ObjectsDiffPrefixesInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let diff = run_cancellable_operation(
+                &state,
+                input.operation_id.as_deref(),
+                StdDuration::from_secs(PREFIX_DIFF_TIMEOUT_SECS),
+                &format!(
+                    "compare {} with {}",
+                    format_s3_uri(&input.bucket, &input.compare_prefix),
+                    format_s3_uri(&input.bucket, &input.base_prefix)
+                ),
+                diff_bucket_prefixes(
+                    &client,
+                    &input.bucket,
+                    &input.base_prefix,
+                    &input.compare_prefix,
+                ),
+            )
+            .await?;
+            Ok(json!({
+                "added": diff.to_add,
+                "changed": diff.to_update,
+                "removed": diff.to_delete,
+                "unchanged": diff.unchanged,
+            }))
+        }
+        RpcMethod::ObjectsOpenExternal => {
+            let input: ObjectsOpenExternalInput = parse_payload(payload)?;
+            let s3_uri = format_s3_uri(&input.bucket, &input.key);
+            if is_executable_file_name(&input.key) {
+                return Err(format!(
+                    "Refusing to open {s3_uri}: executable files can only be downloaded"
+                ));
+            }
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            let Some(object) = s3_head_if_exists(&client, &input.bucket, &input.key).await? else {
+                return Err(format!("Object not found: {s3_uri}"));
+            };
+
+            if object.size > OPEN_EXTERNAL_CONFIRM_BYTES {
+                let action = format!(
+                    "objects:open-external\n{}\n{}\n{}",
+                    input.profile_id, input.bucket, input.key
+                );
+                let Some(token) = input.confirm_token.as_deref() else {
+                    let token = issue_confirmation_token(&state, &action)?;
+                    return Ok(json!({
+                        "confirmationRequired": true,
+                        "confirmToken": token,
+                        "expiresInSecs": CONFIRMATION_TOKEN_TTL_SECS,
+                        "summary": format!(
+                            "Download {s3_uri} ({}) to open it",
+                            format_byte_size(object.size)
+                        ),
+                    }));
+                };
+                consume_confirmation_token(&state, &action, token)?;
+            }
+
+            let local_path = open_external_temp_path(&input.key);
+            ensure_disk_space(&local_path, object.size)?;
+            let file_name = local_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let job_id = enqueue_job(
+                &app,
+                JobType::Download,
+                file_name,
+                format!("Open {s3_uri}"),
+                object.size,
+                JobTaskKind::OpenExternal {
+                    profile_id: input.profile_id,
+                    bucket: input.bucket,
+                    key: input.key,
+                    local_path: local_path.to_string_lossy().into_owned(),
+                },
+            )?;
+            Ok(json!({ "jobId": job_id }))
+        }
+        RpcMethod::ObjectsStreamRange => {
+            let input: ObjectsStreamRangeInput = parse_payload(payload)?;
+            if input.start < 0 {
+                return Err("start can't be negative".to_string());
+            }
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let client = to_s3_client(&profile)?;
+            let length = input
+                .length
+                .unwrap_or(STREAM_RANGE_MAX_TOTAL_BYTES)
+                .clamp(1, STREAM_RANGE_MAX_TOTAL_BYTES);
+            let chunk_bytes = input
+                .chunk_bytes
+                .unwrap_or(STREAM_RANGE_DEFAULT_CHUNK_BYTES)
+                .clamp(1, STREAM_RANGE_MAX_CHUNK_BYTES);
+
+            let sent = AtomicI64::new(0);
+            let stream = s3_stream_object_range(
+                &client,
+                &input.bucket,
+                &input.key,
+                StreamRange {
+                    start: input.start,
+                    length,
+                    chunk_bytes,
+                },
+                profile.requester_pays,
+                |offset, bytes| {
+                    sent.fetch_add(bytes.len() as i64, Ordering::SeqCst);
+                    let _ = app.emit(
+                        "object:chunk",
+                        ObjectChunkEventPayload {
+                            operation_id: &input.operation_id,
+                            offset,
+                            data: encode_base64(bytes),
+                        },
+                    );
+                },
+            );
+            let result = run_cancellable_operation(
+                &state,
+                Some(&input.operation_id),
+                StdDuration::from_secs(STREAM_RANGE_TIMEOUT_SECS),
+                &format!("stream {}", format_s3_uri(&input.bucket, &input.key)),
+                stream,
+            )
+            .await;
+
+            let bytes_sent = sent.load(Ordering::SeqCst);
+            let done = ObjectChunkDoneEventPayload {
+                operation_id: input.operation_id.clone(),
+                start: input.start,
+                bytes_sent,
+                size: result.as_ref().ok().map(|(_, size)| *size),
+                eof: result
+                    .as_ref()
+                    .is_ok_and(|(_, size)| input.start + bytes_sent >= *size),
+                cancelled: result.as_ref().is_err_and(|err| err == OPERATION_CANCELLED),
+                error: result.as_ref().err().cloned(),
+            };
+            let _ = app.emit("object:chunk-done", done.clone());
+            result?;
+            Ok(json!(done))
+        }
+
         RpcMethod::TransferUpload => {
             let input: UploadInput = parse_payload(payload)?;
+            let key = upload_key_transform(&state, &input.profile_id)?.apply(&input.key);
+            // Reject a bad ACL now rather than when the job runs.
+            let acl = match input.acl.as_deref().map(str::trim) {
+                Some(acl) if !acl.is_empty() => Some(parse_canned_acl(acl)?.as_str().to_string()),
+                _ => None,
+            };
+            let content_type = non_empty_header(input.content_type, "Content type")?;
+            let cache_control = non_empty_header(input.cache_control, "Cache control")?;
+            let metadata = validate_upload_metadata(input.metadata)?;
+            let storage_class = match input.storage_class.as_deref().map(str::trim) {
+                Some(class) if !class.is_empty() => {
+                    Some(parse_storage_class(class)?.as_str().to_string())
+                }
+                _ => None,
+            };
+            let (sse, sse_kms_key_id) =
+                parse_upload_encryption(input.sse.as_deref(), input.sse_kms_key_id.as_deref())?;
             let bytes_total = if input.local_path.trim().is_empty() {
                 0
             } else {
@@ -678,13 +1323,21 @@ pub(crate) async fn rpc_request(
                 &app,
                 JobType::Upload,
                 file_name,
-                format!("Upload to {}/{}", input.bucket, input.key),
+                format!("Upload to {}/{}", input.bucket, key),
                 bytes_total,
                 JobTaskKind::Upload {
                     profile_id: input.profile_id,
                     bucket: input.bucket,
-                    key: input.key,
+                    key,
                     local_path: input.local_path,
+                    overwrite: input.overwrite.unwrap_or(true),
+                    acl,
+                    content_type,
+                    cache_control,
+                    metadata,
+                    storage_class,
+                    sse,
+                    sse_kms_key_id,
                 },
             )?;
             Ok(json!({ "jobId": job_id }))
@@ -709,6 +1362,9 @@ pub(crate) async fn rpc_request(
                     bucket: input.bucket,
                     key: input.key,
                     local_path: input.local_path,
+                    on_existing: input.on_existing,
+                    verify_checksum: input.verify_checksum,
+                    version_id: input.version_id,
                 },
             )?;
             Ok(json!({ "jobId": job_id }))
@@ -723,14 +1379,32 @@ pub(crate) async fn rpc_request(
                 return Ok(json!({ "jobIds": [] }));
             }
 
+            let transform = upload_key_transform(&state, &input.profile_id)?;
+            let overwrite = input.overwrite.unwrap_or(true);
+            let client = if overwrite {
+                None
+            } else {
+                Some(s3_client_for_profile(&state, &input.profile_id)?)
+            };
+
             let mut job_ids = Vec::new();
+            let mut skipped = Vec::new();
             for path in paths {
                 let file_name = path
                     .file_name()
                     .and_then(|name| name.to_str())
                     .unwrap_or("file")
                     .to_string();
-                let key = format!("{}{}", input.prefix, file_name);
+                let key = transform.apply(&format!("{}{}", input.prefix, file_name));
+                if let Some(client) = &client {
+                    if s3_head_if_exists(client, &input.bucket, &key)
+                        .await?
+                        .is_some()
+                    {
+                        skipped.push(key);
+                        continue;
+                    }
+                }
                 let bytes_total = fs::metadata(&path)
                     .map(|meta| meta.len() as i64)
                     .unwrap_or(0)
@@ -746,12 +1420,20 @@ pub(crate) async fn rpc_request(
                         bucket: input.bucket.clone(),
                         key,
                         local_path: path.to_string_lossy().to_string(),
+                        overwrite,
+                        acl: None,
+                        content_type: None,
+                        cache_control: None,
+                        metadata: HashMap::new(),
+                        storage_class: None,
+                        sse: None,
+                        sse_kms_key_id: None,
                     },
                 )?;
                 job_ids.push(job_id);
             }
 
-            Ok(json!({ "jobIds": job_ids }))
+            Ok(json!({ "jobIds": job_ids, "skipped": skipped }))
         }
         RpcMethod::TransferPickAndUploadFolder => {
             let input: PickUploadInput = parse_payload(payload)?;
@@ -765,17 +1447,33 @@ pub(crate) async fn rpc_request(
                 .unwrap_or("folder")
                 .to_string();
 
-            let mut files = Vec::new();
-            for entry in WalkDir::new(&dir_path).into_iter().filter_map(Result::ok) {
-                if entry.file_type().is_file() {
-                    files.push(entry.into_path());
-                }
-            }
-            if files.is_empty() {
+            let (files, skipped_files) =
+                collect_folder_upload_files(&dir_path, input.follow_symlinks);
+            let empty_dirs = if input.include_empty_dirs {
+                collect_empty_folder_dirs(&dir_path, input.follow_symlinks)
+            } else {
+                Vec::new()
+            };
+            if files.is_empty() && skipped_files.is_empty() && empty_dirs.is_empty() {
                 return Err("Selected folder is empty".to_string());
             }
 
+            let transform = upload_key_transform(&state, &input.profile_id)?;
+            let overwrite = input.overwrite.unwrap_or(true);
+            let existing_keys: HashSet<String> = if overwrite {
+                HashSet::new()
+            } else {
+                let client = s3_client_for_profile(&state, &input.profile_id)?;
+                let folder_prefix = format!("{}{}{}/", transform.prefix, input.prefix, dir_name);
+                s3_list_all_objects(&client, &input.bucket, &folder_prefix)
+                    .await?
+                    .into_iter()
+                    .map(|object| object.key)
+                    .collect()
+            };
+
             let mut job_ids = Vec::new();
+            let mut skipped = Vec::new();
             for file_path in files {
                 let Ok(relative) = file_path.strip_prefix(&dir_path) else {
                     continue;
@@ -784,7 +1482,12 @@ pub(crate) async fn rpc_request(
                 if relative_path.is_empty() {
                     continue;
                 }
-                let key = format!("{}{}/{}", input.prefix, dir_name, relative_path);
+                let key =
+                    transform.apply(&format!("{}{}/{}", input.prefix, dir_name, relative_path));
+                if existing_keys.contains(&key) {
+                    skipped.push(key);
+                    continue;
+                }
                 let bytes_total = fs::metadata(&file_path)
                     .map(|meta| meta.len() as i64)
                     .unwrap_or(0)
@@ -800,12 +1503,108 @@ pub(crate) async fn rpc_request(
                         bucket: input.bucket.clone(),
                         key,
                         local_path: file_path.to_string_lossy().to_string(),
+                        overwrite,
+                        acl: None,
+                        content_type: None,
+                        cache_control: None,
+                        metadata: HashMap::new(),
+                        storage_class: None,
+                        sse: None,
+                        sse_kms_key_id: None,
                     },
                 )?;
                 job_ids.push(job_id);
             }
 
-            Ok(json!({ "jobIds": job_ids }))
+            // Zero-byte `dir/` markers, uploaded like "New folder" does: an
+            // upload job with no local path.
+            for relative_dir in empty_dirs {
+                let folder = if relative_dir.is_empty() {
+                    dir_name.clone()
+                } else {
+                    format!("{dir_name}/{relative_dir}")
+                };
+                let key = transform.apply(&format!("{}{folder}/", input.prefix));
+                if existing_keys.contains(&key) {
+                    skipped.push(key);
+                    continue;
+                }
+                let job_id = enqueue_job(
+                    &app,
+                    JobType::Upload,
+                    format!("{folder}/"),
+                    format!("Upload to {}/{}", input.bucket, key),
+                    0,
+                    JobTaskKind::Upload {
+                        profile_id: input.profile_id.clone(),
+                        bucket: input.bucket.clone(),
+                        key,
+                        local_path: String::new(),
+                        overwrite,
+                        acl: None,
+                        content_type: None,
+                        cache_control: None,
+                        metadata: HashMap::new(),
+                        storage_class: None,
+                        sse: None,
+                        sse_kms_key_id: None,
+                    },
+                )?;
+                job_ids.push(job_id);
+            }
+
+            Ok(json!({
+                "jobIds": job_ids,
+                "skipped": skipped,
+                "skippedFiles": skipped_files,
+            }))
+        }
+        RpcMethod::TransferUploadFolderArchive => {
+            let input: UploadFolderArchiveInput = parse_payload(payload)?;
+            let local_dir = match input.local_path.as_deref().map(str::trim) {
+                Some(local_path) if !local_path.is_empty() => expand_user_path(local_path),
+                _ => {
+                    let Some(dir_path) = FileDialog::new().pick_folder() else {
+                        // User cancelled the native dialog — not an error.
+                        return Ok(json!({ "jobId": null }));
+                    };
+                    dir_path
+                }
+            };
+            if !local_dir.is_dir() {
+                return Err(format!("Not a folder: {}", local_dir.display()));
+            }
+
+            let dir_name = local_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("folder")
+                .to_string();
+            let key = input
+                .key
+                .filter(|key| !key.trim().is_empty())
+                .unwrap_or_else(|| format!("{}{}.tar.gz", input.prefix, dir_name));
+            let key = upload_key_transform(&state, &input.profile_id)?.apply(&key);
+            let bytes_total: i64 = scan_local_directory(&local_dir, &input.exclude_patterns)
+                .iter()
+                .map(|file| file.size)
+                .sum();
+
+            let job_id = enqueue_job(
+                &app,
+                JobType::Upload,
+                format!("{dir_name}.tar.gz"),
+                format!("Upload archive of {} to {}/{}", dir_name, input.bucket, key),
+                bytes_total,
+                JobTaskKind::UploadFolderArchive {
+                    profile_id: input.profile_id,
+                    bucket: input.bucket,
+                    key,
+                    local_dir: local_dir.to_string_lossy().to_string(),
+                    exclude_patterns: input.exclude_patterns,
+                },
+            )?;
+            Ok(json!({ "jobId": job_id }))
         }
         RpcMethod::TransferDownloadFolder => {
             let input: DownloadFolderInput = parse_payload(payload)?;
@@ -828,6 +1627,8 @@ pub(crate) async fn rpc_request(
                 .last()
                 .unwrap_or("download")
                 .to_string();
+            let bytes_total: i64 = objects.iter().map(|object| object.size.max(0)).sum();
+            ensure_disk_space(&destination, bytes_total)?;
 
             let mut job_ids = Vec::new();
             for RemoteObject { key, size, .. } in objects {
@@ -857,6 +1658,9 @@ pub(crate) async fn rpc_request(
                         bucket: input.bucket.clone(),
                         key,
                         local_path: local_path.to_string_lossy().to_string(),
+                        on_existing: input.on_existing,
+                        verify_checksum: false,
+                        version_id: None,
                     },
                 )?;
                 job_ids.push(job_id);
@@ -864,6 +1668,97 @@ pub(crate) async fn rpc_request(
 
             Ok(json!({ "jobIds": job_ids }))
         }
+        RpcMethod::TransferDownloadSelection => {
+            let input: DownloadSelectionInput = parse_payload(payload)?;
+            let client = s3_client_for_profile(&state, &input.profile_id)?;
+            if input.keys.is_empty() {
+                return Err("No objects selected".to_string());
+            }
+
+            let mut objects: Vec<(String, i64)> = Vec::new();
+            for key in &input.keys {
+                if key.ends_with('/') {
+                    let children = s3_list_all_objects(&client, &input.bucket, key).await?;
+                    objects.extend(
+                        children
+                            .into_iter()
+                            .map(|child| (child.key, child.size.max(0))),
+                    );
+                } else {
+                    let head = client
+                        .head_object()
+                        .bucket(input.bucket.clone())
+                        .key(key.clone())
+                        .send()
+                        .await
+                        .map_err(|err| err.to_string())?;
+                    objects.push((key.clone(), head.content_length().unwrap_or(0).max(0)));
+                }
+            }
+
+            let mut seen = HashSet::new();
+            objects.retain(|(key, _)| !key.ends_with('/') && seen.insert(key.clone()));
+            if objects.is_empty() {
+                return Err("Selected folders are empty".to_string());
+            }
+
+            // The folder holding the selection, so a selected folder keeps its
+            // own name under the destination.
+            let common_prefix = match input.common_prefix.as_deref() {
+                Some(prefix) if !prefix.is_empty() => normalize_prefix(prefix),
+                _ => {
+                    let selected: Vec<String> = input
+                        .keys
+                        .iter()
+                        .map(|key| key.trim_end_matches('/').to_string())
+                        .collect();
+                    common_key_prefix(&selected)
+                }
+            };
+
+            let destination = match input.destination {
+                Some(destination) if !destination.trim().is_empty() => {
+                    expand_user_path(&destination)
+                }
+                _ => FileDialog::new()
+                    .pick_folder()
+                    .ok_or_else(|| "No destination folder selected".to_string())?,
+            };
+
+            let mut job_ids = Vec::new();
+            let mut skipped = Vec::new();
+            for (key, size) in objects {
+                let relative_path = key
+                    .strip_prefix(&common_prefix)
+                    .unwrap_or(key.as_str())
+                    .to_string();
+                let Some(safe_relative) = sanitize_relative_path(&relative_path) else {
+                    // Remote keys that would escape the destination directory.
+                    skipped.push(key);
+                    continue;
+                };
+                let local_path = destination.join(&safe_relative);
+                let job_id = enqueue_job(
+                    &app,
+                    JobType::Download,
+                    relative_path,
+                    format!("Download {}/{}", input.bucket, key),
+                    size,
+                    JobTaskKind::Download {
+                        profile_id: input.profile_id.clone(),
+                        bucket: input.bucket.clone(),
+                        key,
+                        local_path: local_path.to_string_lossy().to_string(),
+                        on_existing: input.on_existing,
+                        verify_checksum: false,
+                        version_id: None,
+                    },
+                )?;
+                job_ids.push(job_id);
+            }
+
+            Ok(json!({ "jobIds": job_ids, "skipped": skipped }))
+        }
         RpcMethod::TransferCopy => {
             let input: CopyInput = parse_payload(payload)?;
             let file_name = input
@@ -927,24 +1822,21 @@ pub(crate) async fn rpc_request(
             let source_profile = profile_for_id(&state, &input.source_profile_id)?;
             let source_client = to_s3_client(&source_profile)?;
 
-            let mut expanded_keys = Vec::new();
-            for key in &input.keys {
-                if key.ends_with('/') {
-                    let children =
-                        s3_list_all_objects(&source_client, &input.source_bucket, key).await?;
-                    expanded_keys
-                        .extend(children.into_iter().map(|child| child.key));
-                } else {
-                    expanded_keys.push(key.clone());
-                }
-            }
+            let expanded_keys = s3_expand_keys(
+                &source_client,
+                &input.source_bucket,
+                &input.keys,
+                input.expand_concurrency,
+                false,
+            )
+            .await?;
             if expanded_keys.is_empty() {
                 return Err("No objects to transfer".to_string());
             }
 
             let mut seen = HashSet::new();
             let mut unique_keys = Vec::new();
-            for key in expanded_keys {
+            for (key, _) in expanded_keys {
                 if seen.insert(key.clone()) {
                     unique_keys.push(key);
                 }
@@ -1007,6 +1899,7 @@ pub(crate) async fn rpc_request(
         }
         RpcMethod::TransferDownloadArchive => {
             let input: DownloadArchiveInput = parse_payload(payload)?;
+            let format = parse_archive_format(input.format.as_deref())?;
             let client = s3_client_for_profile(&state, &input.profile_id)?;
 
             let mut resolved_keys = input.keys.clone();
@@ -1019,48 +1912,32 @@ pub(crate) async fn rpc_request(
                 return Err("No objects selected for archive".to_string());
             }
 
-            let mut expanded_keys: Vec<(String, i64)> = Vec::new();
-            for key in resolved_keys {
-                if key.ends_with('/') {
-                    let children = s3_list_all_objects(&client, &input.bucket, &key).await?;
-                    expanded_keys.extend(
-                        children
-                            .into_iter()
-                            .map(|child| (child.key, child.size.max(0))),
-                    );
-                } else {
-                    let head = client
-                        .head_object()
-                        .bucket(input.bucket.clone())
-                        .key(key.clone())
-                        .send()
-                        .await
-                        .map_err(|err| err.to_string())?;
-                    expanded_keys.push((key, head.content_length().unwrap_or(0).max(0)));
-                }
-            }
+            let expanded_keys = s3_expand_keys(
+                &client,
+                &input.bucket,
+                &resolved_keys,
+                input.expand_concurrency,
+                true,
+            )
+            .await?;
             if expanded_keys.is_empty() {
                 return Err("Selected folders are empty".to_string());
             }
 
             let default_name = input.archive_name.unwrap_or_else(|| {
                 if !prefix.is_empty() {
-                    let name = prefix
+                    prefix
                         .trim_end_matches('/')
                         .split('/')
                         .filter(|part| !part.is_empty())
                         .last()
-                        .unwrap_or("archive");
-                    format!("{name}.tar.gz")
+                        .unwrap_or("archive")
+                        .to_string()
                 } else {
-                    format!("{}-export.tar.gz", input.bucket)
+                    format!("{}-export", input.bucket)
                 }
             });
-            let archive_name = if default_name.ends_with(".tar.gz") {
-                default_name
-            } else {
-                format!("{default_name}.tar.gz")
-            };
+            let archive_name = archive_file_name(&default_name, format)?;
             let Some(destination_path) = FileDialog::new().set_file_name(&archive_name).save_file()
             else {
                 return Err("No destination folder selected".to_string());
@@ -1076,35 +1953,13 @@ pub(crate) async fn rpc_request(
                 }
             }
 
+            // Compression only helps, so the raw total is an upper bound.
+            ensure_disk_space(&destination_path, bytes_total)?;
+
             let common_prefix = if !prefix.is_empty() {
                 normalize_prefix(&prefix)
             } else {
-                let mut common: Option<String> = None;
-                for key in &unique_keys {
-                    let parent = if let Some((head, _)) = key.rsplit_once('/') {
-                        format!("{head}/")
-                    } else {
-                        String::new()
-                    };
-                    common = match common {
-                        Some(existing) => {
-                            let mut candidate = String::new();
-                            for (a, b) in existing.chars().zip(parent.chars()) {
-                                if a == b {
-                                    candidate.push(a);
-                                } else {
-                                    break;
-                                }
-                            }
-                            while !candidate.is_empty() && !candidate.ends_with('/') {
-                                candidate.pop();
-                            }
-                            Some(candidate)
-                        }
-                        None => Some(parent),
-                    };
-                }
-                common.unwrap_or_default()
+                common_key_prefix(&unique_keys)
             };
 
             let file_name = destination_path
@@ -1127,6 +1982,7 @@ pub(crate) async fn rpc_request(
                     keys: unique_keys,
                     common_prefix,
                     destination_path: destination_path.to_string_lossy().to_string(),
+                    format,
                 },
             )?;
 
@@ -1164,7 +2020,42 @@ pub(crate) async fn rpc_request(
         }
         RpcMethod::JobsCancel => {
             let input: JobIdInput = parse_payload(payload)?;
-            cancel_job(&app, &input.job_id);
+            cancel_job(&app, &input.job_id, CancelReason::User);
+            Ok(Value::Null)
+        }
+        RpcMethod::JobsPause => {
+            let input: JobIdInput = parse_payload(payload)?;
+            pause_job(&app, &input.job_id)?;
+            Ok(Value::Null)
+        }
+        RpcMethod::JobsResume => {
+            let input: JobIdInput = parse_payload(payload)?;
+            resume_job(&app, &input.job_id)?;
+            Ok(Value::Null)
+        }
+        RpcMethod::JobsRetry => {
+            let input: JobIdInput = parse_payload(payload)?;
+            retry_job(&app, &input.job_id)?;
+            Ok(json!({ "jobId": input.job_id }))
+        }
+        RpcMethod::JobsRetryAllFailed => {
+            let job_ids = retry_all_failed_jobs(&app)?;
+            Ok(json!({ "jobIds": job_ids }))
+        }
+        RpcMethod::JobsSetPriority => {
+            let input: JobSetPriorityInput = parse_payload(payload)?;
+            set_job_priority(&app, &input.job_id, input.priority)?;
+            Ok(Value::Null)
+        }
+        RpcMethod::JobsListResumable => Ok(json!(list_resumable_uploads(&app)?)),
+        RpcMethod::JobsResumeMultipart => {
+            let input: MultipartStateInput = parse_payload(payload)?;
+            let job_id = resume_interrupted_upload(&app, &input.id)?;
+            Ok(json!({ "jobId": job_id }))
+        }
+        RpcMethod::JobsDiscardMultipart => {
+            let input: MultipartStateInput = parse_payload(payload)?;
+            discard_interrupted_upload(&app, &input.id).await?;
             Ok(Value::Null)
         }
         RpcMethod::JobsClear => {
@@ -1175,7 +2066,10 @@ pub(crate) async fn rpc_request(
                 .filter_map(|(id, job)| {
                     let terminal = matches!(
                         job.status,
-                        JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+                        JobStatus::Completed
+                            | JobStatus::Failed
+                            | JobStatus::Cancelled
+                            | JobStatus::Skipped
                     );
                     if terminal && !jobs_runtime.running.contains(id) {
                         Some(id.clone())
@@ -1187,6 +2081,7 @@ pub(crate) async fn rpc_request(
 
             for id in removable {
                 jobs_runtime.jobs.remove(&id);
+                jobs_runtime.retry_tasks.remove(&id);
                 jobs_runtime.cancel_flags.remove(&id);
                 jobs_runtime.queue.retain(|task| task.id != id);
             }
@@ -1201,7 +2096,7 @@ pub(crate) async fn rpc_request(
         }
         RpcMethod::JobsGetConcurrency => {
             let jobs_runtime = lock_state(&state.jobs)?;
-            Ok(json!({ "concurrency": jobs_runtime.concurrency }))
+            Ok(job_concurrency_json(&jobs_runtime))
         }
         RpcMethod::JobsSetConcurrency => {
             let input: JobConcurrencyInput = parse_payload(payload)?;
@@ -1212,7 +2107,101 @@ pub(crate) async fn rpc_request(
             }
             try_start_queued_jobs(app.clone());
             let jobs_runtime = lock_state(&state.jobs)?;
-            Ok(json!({ "concurrency": jobs_runtime.concurrency }))
+            Ok(job_concurrency_json(&jobs_runtime))
+        }
+        RpcMethod::JobsSetAdaptiveConcurrency => {
+            let input: JobAdaptiveConcurrencyInput = parse_payload(payload)?;
+            {
+                let mut jobs_runtime = lock_state(&state.jobs)?;
+                set_adaptive_job_concurrency(&mut jobs_runtime, input.enabled);
+            }
+            try_start_queued_jobs(app.clone());
+            let jobs_runtime = lock_state(&state.jobs)?;
+            Ok(job_concurrency_json(&jobs_runtime))
+        }
+        RpcMethod::JobsGetBandwidthLimit => {
+            let jobs_runtime = lock_state(&state.jobs)?;
+            Ok(json!({ "maxBytesPerSec": jobs_runtime.max_bytes_per_sec }))
+        }
+        RpcMethod::JobsSetBandwidthLimit => {
+            let input: JobBandwidthLimitInput = parse_payload(payload)?;
+            let mut jobs_runtime = lock_state(&state.jobs)?;
+            set_bandwidth_limit(&mut jobs_runtime, input.max_bytes_per_sec);
+            Ok(json!({ "maxBytesPerSec": jobs_runtime.max_bytes_per_sec }))
+        }
+
+        RpcMethod::SettingsGet => Ok(json!(current_settings(&state))),
+        RpcMethod::SettingsSet => {
+            let update = payload
+                .as_object()
+                .cloned()
+                .ok_or_else(|| "Invalid payload: expected object".to_string())?;
+            let settings = {
+                let mut current = lock_state(&state.settings)?;
+                let settings = merge_settings_update(&current, update)?;
+                save_settings_to_disk(&settings)?;
+                *current = settings.clone();
+                settings
+            };
+            refresh_metered_network_pause(&app, settings.pause_sync_on_metered);
+            Ok(json!(settings))
+        }
+
+        RpcMethod::SettingsEffective => {
+            // settings.json fields, then runtime job limits and env overrides.
+            let mut entries = load_effective_settings_entries();
+            {
+                let jobs_runtime = lock_state(&state.jobs)?;
+                let concurrency = jobs_runtime.concurrency;
+                let adaptive = jobs_runtime.adaptive.enabled;
+                entries.insert(
+                    "jobs.concurrency".to_string(),
+                    effective_setting(
+                        json!(concurrency),
+                        json!(DEFAULT_JOB_CONCURRENCY),
+                        if concurrency == DEFAULT_JOB_CONCURRENCY {
+                            "default"
+                        } else {
+                            "user"
+                        },
+                    ),
+                );
+                entries.insert(
+                    "jobs.adaptiveConcurrency".to_string(),
+                    effective_setting(
+                        json!(adaptive),
+                        json!(false),
+                        if adaptive { "user" } else { "default" },
+                    ),
+                );
+            }
+            let env_source = |key: &str| {
+                if env_var_non_empty(key).is_some() {
+                    "env"
+                } else {
+                    "default"
+                }
+            };
+            entries.insert(
+                "updater.channel".to_string(),
+                effective_setting(
+                    json!(updater_channel()),
+                    json!(DEFAULT_UPDATER_CHANNEL),
+                    env_source("OBJECT0_UPDATER_CHANNEL"),
+                ),
+            );
+            entries.insert(
+                "updater.endpoint".to_string(),
+                effective_setting(
+                    json!(updater_local_info_endpoint()),
+                    json!(DEFAULT_UPDATER_ENDPOINT),
+                    env_source("OBJECT0_UPDATER_ENDPOINTS"),
+                ),
+            );
+            Ok(json!({
+                "path": settings_path()?.to_string_lossy(),
+                "entries": entries,
+            }))
         }
 
         RpcMethod::FavoritesLoad => Ok(json!(load_favorites_from_disk())),
@@ -1221,6 +2210,51 @@ pub(crate) async fn rpc_request(
             save_favorites_to_disk(&input.favorites)?;
             Ok(Value::Null)
         }
+        RpcMethod::BookmarksLoad => Ok(json!(load_bookmarks_from_disk())),
+        RpcMethod::BookmarksSave => {
+            let input: BookmarksSaveInput = parse_payload(payload)?;
+            save_bookmarks_to_disk(&input.bookmarks)?;
+            Ok(Value::Null)
+        }
+        RpcMethod::NavGetLast => {
+            // Without a profile id, returns the most recently browsed profile's spot.
+            let input: Option<NavGetLastInput> = parse_payload(payload)?;
+            let nav = load_nav_state_from_disk();
+            let Some(profile_id) = input
+                .and_then(|input| input.profile_id)
+                .or(nav.last_profile_id)
+            else {
+                return Ok(Value::Null);
+            };
+            Ok(match nav.locations.get(&profile_id) {
+                Some(location) => json!({
+                    "profileId": profile_id,
+                    "bucket": location.bucket,
+                    "prefix": location.prefix,
+                }),
+                None => Value::Null,
+            })
+        }
+        RpcMethod::NavSetLast => {
+            let input: NavSetLastInput = parse_payload(payload)?;
+            let mut nav = load_nav_state_from_disk();
+            nav.locations.insert(
+                input.profile_id.clone(),
+                NavLocation {
+                    bucket: input.bucket,
+                    prefix: input.prefix,
+                },
+            );
+            nav.last_profile_id = Some(input.profile_id);
+            save_nav_state_to_disk(&nav)?;
+            Ok(Value::Null)
+        }
+
+        RpcMethod::OperationCancel => {
+            let input: OperationCancelInput = parse_payload(payload)?;
+            let cancelled = cancel_operation(&state, &input.operation_id)?;
+            Ok(json!({ "cancelled": cancelled }))
+        }
 
         RpcMethod::ShareGenerate => {
             let input: ShareGenerateInput = parse_payload(payload)?;
@@ -1231,18 +2265,75 @@ pub(crate) async fn rpc_request(
             let config = PresigningConfig::expires_in(StdDuration::from_secs(ttl as u64))
                 .map_err(|err| format!("Invalid presign ttl: {err}"))?;
 
-            let presigned = client
-                .get_object()
-                .bucket(input.bucket)
-                .key(input.key.clone())
-                .presigned(config)
-                .await
-                .map_err(|err| err.to_string())?;
+            let put = match input
+                .method
+                .as_deref()
+                .map(str::to_ascii_lowercase)
+                .as_deref()
+            {
+                None | Some("get") => false,
+                Some("put") => true,
+                Some(other) => {
+                    return Err(format!(
+                        "Unsupported presign method \"{other}\"; expected get or put"
+                    ))
+                }
+            };
+            let content_type = non_empty_header(input.content_type.clone(), "Content-Type")?;
+            if content_type.is_some() && !put {
+                return Err("contentType only applies to PUT links".to_string());
+            }
+
+            let s3_uri = format_s3_uri(&input.bucket, &input.key);
+            let presign = async {
+                if put {
+                    client
+                        .put_object()
+                        .bucket(input.bucket.clone())
+                        .key(input.key.clone())
+                        .set_content_type(content_type)
+                        .presigned(config)
+                        .await
+                        .map_err(|err| err.to_string())
+                } else {
+                    client
+                        .get_object()
+                        .bucket(input.bucket.clone())
+                        .key(input.key.clone())
+                        .presigned(config)
+                        .await
+                        .map_err(|err| err.to_string())
+                }
+            };
+            let presigned = run_cancellable_operation(
+                &state,
+                input.operation_id.as_deref(),
+                StdDuration::from_secs(PRESIGN_TIMEOUT_SECS),
+                &format!("presign {s3_uri}"),
+                presign,
+            )
+            .await?;
+
+            // Fetching a PUT link would write the object, so it's never checked.
+            let validation = if input.validate && !put {
+                Some(check_presigned_url(&presigned).await)
+            } else {
+                None
+            };
+            // Headers the caller has to send exactly as signed.
+            let headers: HashMap<String, String> = presigned
+                .headers()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
 
             Ok(json!({
                 "url": presigned.uri().to_string(),
+                "httpMethod": presigned.method(),
+                "headers": headers,
                 "expiresAt": expires_at,
                 "key": input.key,
+                "s3Uri": s3_uri,
+                "validation": validation,
             }))
         }
 
@@ -1283,8 +2374,12 @@ pub(crate) async fn rpc_request(
             }
 
             let rule_value = Value::Object(rule);
-            let rule_record = serde_json::from_value::<FolderSyncRuleRecord>(rule_value.clone())
-                .map_err(|err| format!("Invalid folder sync rule: {err}"))?;
+            let mut rule_record =
+                serde_json::from_value::<FolderSyncRuleRecord>(rule_value.clone())
+                    .map_err(|err| format!("Invalid folder sync rule: {err}"))?;
+            pin_folder_sync_key_host(&mut rule_record);
+            validate_folder_sync_rule(&rule_record)?;
+            validate_folder_sync_local_root(&rule_record, &rules)?;
             rules.push(rule_record.clone());
             save_folder_sync_rules_records(&rules)?;
             if rule_record.enabled {
@@ -1305,6 +2400,7 @@ pub(crate) async fn rpc_request(
                 .to_string();
 
             let mut rules = load_folder_sync_rules_records();
+            let others = rules.clone();
             if let Some(rule) = rules.iter_mut().find(|rule| rule.id == id) {
                 let mut rule_value = serde_json::to_value(rule.clone())
                     .map_err(|err| format!("Failed to serialize stored rule: {err}"))?;
@@ -1314,8 +2410,12 @@ pub(crate) async fn rpc_request(
                 for (key, value) in update {
                     rule_obj.insert(key, value);
                 }
-                let updated_rule = serde_json::from_value::<FolderSyncRuleRecord>(rule_value)
-                    .map_err(|err| format!("Invalid folder sync update: {err}"))?;
+                let mut updated_rule =
+                    serde_json::from_value::<FolderSyncRuleRecord>(rule_value)
+                        .map_err(|err| format!("Invalid folder sync update: {err}"))?;
+                pin_folder_sync_key_host(&mut updated_rule);
+                validate_folder_sync_rule(&updated_rule)?;
+                validate_folder_sync_local_root(&updated_rule, &others)?;
                 *rule = updated_rule.clone();
                 save_folder_sync_rules_records(&rules)?;
 
@@ -1415,12 +2515,111 @@ pub(crate) async fn rpc_request(
             let diff = generate_folder_sync_diff_for_rule(&rule, &client, &known_records).await?;
             Ok(json!(diff))
         }
+        RpcMethod::FolderSyncDryRun => {
+            let input: IdInput = parse_payload(payload)?;
+            let rule = get_folder_sync_rule(&input.id)?;
+            Ok(json!(dry_run_folder_sync_rule(&app, &rule).await?))
+        }
+        RpcMethod::FolderSyncEmptyTrash => {
+            let input: FolderSyncEmptyTrashInput = parse_payload(payload)?;
+            let rule = get_folder_sync_rule(&input.id)?;
+            let older_than_days = input
+                .older_than_days
+                .unwrap_or(FOLDER_SYNC_TRASH_DEFAULT_RETENTION_DAYS);
+            let (local_files, remote_objects) =
+                empty_folder_sync_trash(&app, &rule, older_than_days).await?;
+            Ok(json!({ "localFiles": local_files, "remoteObjects": remote_objects }))
+        }
+        RpcMethod::FolderSyncStatusDetail => {
+            let input: IdInput = parse_payload(payload)?;
+            let rule = get_folder_sync_rule(&input.id)?;
+            let profile = profile_for_id(&state, &rule.profile_id)?;
+            let client = to_s3_client(&profile)?;
+            let known_records = load_folder_sync_file_records(&rule.id);
+            let diff = generate_folder_sync_diff_for_rule(&rule, &client, &known_records).await?;
+            let last_synced_at = known_records
+                .iter()
+                .map(|known| known.synced_at.as_str())
+                .max();
+            Ok(json!({
+                "files": folder_sync_status_detail(&diff, &known_records),
+                "diff": diff,
+                "trackedFiles": known_records.len(),
+                "lastSyncedAt": last_synced_at,
+            }))
+        }
         RpcMethod::FolderSyncPickFolder => {
             let path = FileDialog::new()
                 .pick_folder()
                 .map(|path| path.to_string_lossy().to_string());
             Ok(json!({ "path": path }))
         }
+        RpcMethod::FolderSyncExportRules => {
+            let input: Option<FolderSyncExportRulesInput> = parse_payload(payload)?;
+            let path = match input.and_then(|input| input.path) {
+                Some(path) if !path.trim().is_empty() => expand_user_path(&path),
+                _ => {
+                    let Some(path) = FileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .set_file_name("object0-sync-rules.json")
+                        .save_file()
+                    else {
+                        return Ok(json!({ "path": null, "count": 0 }));
+                    };
+                    path
+                }
+            };
+
+            let rules = load_folder_sync_rules_records();
+            // Profile names are only available while unlocked; without them the
+            // importing side has to map profiles explicitly.
+            let profiles = lock_state(&state.vault)?
+                .data
+                .as_ref()
+                .map(|data| data.profiles.clone())
+                .unwrap_or_default();
+            let document = export_folder_sync_rules(&rules, &profiles)?;
+            let payload = serde_json::to_string_pretty(&document)
+                .map_err(|err| format!("Failed to serialize folder sync rules: {err}"))?;
+            write_atomic(&path, payload.as_bytes())?;
+            Ok(json!({ "path": path.to_string_lossy(), "count": rules.len() }))
+        }
+        RpcMethod::FolderSyncImportRules => {
+            let input: FolderSyncImportRulesInput = parse_payload(payload)?;
+            let profiles = {
+                let vault = lock_state(&state.vault)?;
+                ensure_unlocked(&vault)?;
+                vault
+                    .data
+                    .as_ref()
+                    .map(|data| data.profiles.clone())
+                    .unwrap_or_default()
+            };
+            let path = match input.path {
+                Some(path) if !path.trim().is_empty() => expand_user_path(&path),
+                _ => {
+                    let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file()
+                    else {
+                        return Ok(json!({ "imported": [], "skipped": [] }));
+                    };
+                    path
+                }
+            };
+
+            let raw = fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+            let document: Value = serde_json::from_str(&raw)
+                .map_err(|err| format!("Invalid folder sync rules file: {err}"))?;
+            let mut rules = load_folder_sync_rules_records();
+            let (imported, skipped) =
+                import_folder_sync_rules(&document, &rules, &profiles, &input.profile_map)?;
+            if !imported.is_empty() {
+                rules.extend(imported.iter().cloned());
+                save_folder_sync_rules_records(&rules)?;
+                refresh_tray_menu(&app);
+            }
+            Ok(json!({ "imported": imported, "skipped": skipped }))
+        }
 
         RpcMethod::UpdaterCheck => {
             let (cached_version, cached_ready) = updater_cached_state(&app);
@@ -1492,5 +2691,93 @@ pub(crate) async fn rpc_request(
             "identifier": "dev.object0.app"
         })),
         RpcMethod::SystemPlatform => Ok(json!({ "os": std::env::consts::OS })),
+        RpcMethod::SystemNetworkStatus => Ok(network_status(&app)),
+        RpcMethod::AppSelfTest => Ok(run_self_test(&app).await),
+        RpcMethod::AppAbortAll => Ok(abort_all_activity(&app)),
+        RpcMethod::AppLegacyConfig => {
+            let source = legacy_config_offer().map(|dir| dir.to_string_lossy().to_string());
+            Ok(json!({ "source": source }))
+        }
+        RpcMethod::AppMigrateLegacyConfig => {
+            let input: Option<MigrateLegacyConfigInput> = parse_payload(payload)?;
+            let (source_path, remove_legacy) = input
+                .map(|input| (input.source_path, input.remove_legacy))
+                .unwrap_or_default();
+            let source = match source_path.as_deref().map(str::trim) {
+                Some(path) if !path.is_empty() => Some(expand_user_path(path)),
+                _ => find_legacy_config_dir(),
+            };
+            let Some(source) = source else {
+                return Ok(json!(LegacyConfigMigration::default()));
+            };
+            if !source.is_dir() {
+                return Err(format!("Not a folder: {}", source.display()));
+            }
+            let dest = object0_config_dir()?;
+            if source == dest {
+                return Err("That is the current config folder".to_string());
+            }
+            let report = migrate_legacy_config(&source, &dest, remove_legacy)?;
+            *lock_state(&state.settings)? = load_settings_from_disk();
+            hydrate_job_history_runtime(&app);
+            Ok(json!(report))
+        }
+        RpcMethod::MaintenanceRepairJobHistory => {
+            let report = repair_job_history_file()?;
+            // History that failed to load at startup was never hydrated;
+            // merge what was recovered and write back the combined list.
+            hydrate_job_history_runtime(&app);
+            persist_job_history_snapshot(&app);
+            Ok(report)
+        }
+        RpcMethod::ProviderRegions => Ok(json!({
+            "regions": AWS_REGIONS,
+            "defaultRegion": DEFAULT_AWS_REGION,
+        })),
+        RpcMethod::ProviderProbe => {
+            let input: ProviderProbeInput = parse_payload(payload)?;
+            let profile = profile_for_id(&state, &input.profile_id)?;
+            let bucket = input
+                .bucket
+                .filter(|bucket| !bucket.trim().is_empty())
+                .or_else(|| profile.default_bucket.clone())
+                .ok_or_else(|| "A bucket is required to probe provider features".to_string())?;
+            let features: Vec<&str> = match input.feature.as_deref() {
+                Some(feature) => vec![PROVIDER_PROBE_FEATURES
+                    .iter()
+                    .copied()
+                    .find(|known| *known == feature)
+                    .ok_or_else(|| format!("Unknown feature: {feature}"))?],
+                None => PROVIDER_PROBE_FEATURES.to_vec(),
+            };
+
+            let results =
+                probe_provider_features(&state, &profile, &bucket, &features, input.refresh)
+                    .await?;
+            Ok(json!({
+                "profileId": profile.id,
+                "bucket": bucket,
+                "features": results,
+            }))
+        }
+        RpcMethod::UtilDiskSpace => {
+            let input: DiskSpaceInput = parse_payload(payload)?;
+            let path = expand_user_path(&input.path);
+            let available = available_disk_space(&path)?;
+            Ok(json!({
+                "path": path.to_string_lossy(),
+                "availableBytes": available,
+            }))
+        }
+        RpcMethod::UtilParseS3Uri => {
+            let input: ParseS3UriInput = parse_payload(payload)?;
+            let (bucket, key) = parse_s3_uri(&input.uri)?;
+            Ok(json!({
+                "isPrefix": key.is_empty() || key.ends_with('/'),
+                "uri": format_s3_uri(&bucket, &key),
+                "bucket": bucket,
+                "key": key,
+            }))
+        }
     }
 }
@@ -12,22 +12,51 @@ pub enum RpcMethod {
     VaultChangePassphrase,
     VaultAddRecoveryKey,
     VaultHasRecoveryKey,
+    VaultGetAutoLock,
+    VaultSetAutoLock,
     VaultReset,
     ProfileList,
     ProfileAdd,
+    ProfileImportAws,
+    ProfileImportAwsConfirm,
     ProfileUpdate,
     ProfileRemove,
     ProfileTest,
+    ProfileProvideCredentials,
     BucketsList,
+    BucketsGetCors,
+    BucketsSetCors,
+    BucketsGetPolicy,
+    BucketsSetPolicy,
+    BucketsGetLifecycle,
+    BucketsSetLifecycle,
     ObjectsList,
+    ObjectsRestore,
+    ObjectsRestoreStatus,
+    ObjectsCreateFolder,
+    ObjectsListVersions,
     ObjectsDelete,
+    ObjectsDeletePrefix,
     ObjectsRename,
     ObjectsStat,
+    ObjectsExists,
+    ObjectsPreview,
+    ObjectsGetText,
+    ObjectsStreamRange,
+    ObjectsOpenExternal,
+    ObjectsSearch,
+    ObjectsDiffPrefixes,
+    ObjectsGetAcl,
+    ObjectsSetAcl,
+    ObjectsGetTags,
+    ObjectsSetTags,
     TransferUpload,
     TransferDownload,
     TransferPickAndUpload,
     TransferPickAndUploadFolder,
+    TransferUploadFolderArchive,
     TransferDownloadFolder,
+    TransferDownloadSelection,
     TransferCopy,
     TransferMove,
     TransferCrossBucket,
@@ -36,12 +65,31 @@ pub enum RpcMethod {
     SyncExecute,
     JobsList,
     JobsCancel,
+    JobsPause,
+    JobsResume,
+    JobsRetry,
+    JobsRetryAllFailed,
+    JobsSetPriority,
+    JobsListResumable,
+    JobsResumeMultipart,
+    JobsDiscardMultipart,
     JobsClear,
     JobsGetConcurrency,
     JobsSetConcurrency,
+    JobsSetAdaptiveConcurrency,
+    JobsGetBandwidthLimit,
+    JobsSetBandwidthLimit,
+    SettingsGet,
+    SettingsEffective,
+    SettingsSet,
     FavoritesLoad,
     FavoritesSave,
+    BookmarksLoad,
+    BookmarksSave,
+    NavGetLast,
+    NavSetLast,
     ShareGenerate,
+    OperationCancel,
     FolderSyncListRules,
     FolderSyncAddRule,
     FolderSyncUpdateRule,
@@ -54,12 +102,27 @@ pub enum RpcMethod {
     FolderSyncResumeAll,
     FolderSyncGetStatus,
     FolderSyncPreview,
+    FolderSyncDryRun,
+    FolderSyncEmptyTrash,
+    FolderSyncStatusDetail,
     FolderSyncPickFolder,
+    FolderSyncExportRules,
+    FolderSyncImportRules,
     UpdaterCheck,
     UpdaterDownload,
     UpdaterApply,
     UpdaterLocalInfo,
     SystemPlatform,
+    SystemNetworkStatus,
+    AppSelfTest,
+    AppAbortAll,
+    MaintenanceRepairJobHistory,
+    AppLegacyConfig,
+    AppMigrateLegacyConfig,
+    UtilParseS3Uri,
+    UtilDiskSpace,
+    ProviderProbe,
+    ProviderRegions,
 }
 
 impl RpcMethod {
@@ -77,22 +140,51 @@ impl RpcMethod {
             "vault:change-passphrase" => Some(Self::VaultChangePassphrase),
             "vault:add-recovery-key" => Some(Self::VaultAddRecoveryKey),
             "vault:has-recovery-key" => Some(Self::VaultHasRecoveryKey),
+            "vault:get-auto-lock" => Some(Self::VaultGetAutoLock),
+            "vault:set-auto-lock" => Some(Self::VaultSetAutoLock),
             "vault:reset" => Some(Self::VaultReset),
             "profile:list" => Some(Self::ProfileList),
             "profile:add" => Some(Self::ProfileAdd),
+            "profile:import-aws" => Some(Self::ProfileImportAws),
+            "profile:import-aws-confirm" => Some(Self::ProfileImportAwsConfirm),
             "profile:update" => Some(Self::ProfileUpdate),
             "profile:remove" => Some(Self::ProfileRemove),
             "profile:test" => Some(Self::ProfileTest),
+            "profile:provide-credentials" => Some(Self::ProfileProvideCredentials),
             "buckets:list" => Some(Self::BucketsList),
+            "buckets:get-cors" => Some(Self::BucketsGetCors),
+            "buckets:set-cors" => Some(Self::BucketsSetCors),
+            "buckets:get-policy" => Some(Self::BucketsGetPolicy),
+            "buckets:set-policy" => Some(Self::BucketsSetPolicy),
+            "buckets:get-lifecycle" => Some(Self::BucketsGetLifecycle),
+            "buckets:set-lifecycle" => Some(Self::BucketsSetLifecycle),
             "objects:list" => Some(Self::ObjectsList),
+            "objects:restore" => Some(Self::ObjectsRestore),
+            "objects:restore-status" => Some(Self::ObjectsRestoreStatus),
+            "objects:create-folder" => Some(Self::ObjectsCreateFolder),
+            "objects:list-versions" => Some(Self::ObjectsListVersions),
             "objects:delete" => Some(Self::ObjectsDelete),
+            "objects:delete-prefix" => Some(Self::ObjectsDeletePrefix),
             "objects:rename" => Some(Self::ObjectsRename),
             "objects:stat" => Some(Self::ObjectsStat),
+            "objects:exists" => Some(Self::ObjectsExists),
+            "objects:preview" => Some(Self::ObjectsPreview),
+            "objects:get-text" => Some(Self::ObjectsGetText),
+            "objects:stream-range" => Some(Self::ObjectsStreamRange),
+            "objects:open-external" => Some(Self::ObjectsOpenExternal),
+            "objects:search" => Some(Self::ObjectsSearch),
+            "objects:diff-prefixes" => Some(Self::ObjectsDiffPrefixes),
+            "objects:get-acl" => Some(Self::ObjectsGetAcl),
+            "objects:set-acl" => Some(Self::ObjectsSetAcl),
+            "objects:get-tags" => Some(Self::ObjectsGetTags),
+            "objects:set-tags" => Some(Self::ObjectsSetTags),
             "transfer:upload" => Some(Self::TransferUpload),
             "transfer:download" => Some(Self::TransferDownload),
             "transfer:pick-and-upload" => Some(Self::TransferPickAndUpload),
             "transfer:pick-and-upload-folder" => Some(Self::TransferPickAndUploadFolder),
+            "transfer:upload-folder-archive" => Some(Self::TransferUploadFolderArchive),
             "transfer:download-folder" => Some(Self::TransferDownloadFolder),
+            "transfer:download-selection" => Some(Self::TransferDownloadSelection),
             "transfer:copy" => Some(Self::TransferCopy),
             "transfer:move" => Some(Self::TransferMove),
             "transfer:cross-bucket" => Some(Self::TransferCrossBucket),
@@ -101,12 +193,31 @@ impl RpcMethod {
             "sync:execute" => Some(Self::SyncExecute),
             "jobs:list" => Some(Self::JobsList),
             "jobs:cancel" => Some(Self::JobsCancel),
+            "jobs:pause" => Some(Self::JobsPause),
+            "jobs:resume" => Some(Self::JobsResume),
+            "jobs:retry" => Some(Self::JobsRetry),
+            "jobs:retry-all-failed" => Some(Self::JobsRetryAllFailed),
+            "jobs:set-priority" => Some(Self::JobsSetPriority),
+            "jobs:list-resumable" => Some(Self::JobsListResumable),
+            "jobs:resume-multipart" => Some(Self::JobsResumeMultipart),
+            "jobs:discard-multipart" => Some(Self::JobsDiscardMultipart),
             "jobs:clear" => Some(Self::JobsClear),
             "jobs:get-concurrency" => Some(Self::JobsGetConcurrency),
             "jobs:set-concurrency" => Some(Self::JobsSetConcurrency),
+            "jobs:set-adaptive-concurrency" => Some(Self::JobsSetAdaptiveConcurrency),
+            "jobs:get-bandwidth-limit" => Some(Self::JobsGetBandwidthLimit),
+            "jobs:set-bandwidth-limit" => Some(Self::JobsSetBandwidthLimit),
+            "settings:get" => Some(Self::SettingsGet),
+            "settings:effective" => Some(Self::SettingsEffective),
+            "settings:set" => Some(Self::SettingsSet),
             "favorites:load" => Some(Self::FavoritesLoad),
             "favorites:save" => Some(Self::FavoritesSave),
+            "bookmarks:load" => Some(Self::BookmarksLoad),
+            "bookmarks:save" => Some(Self::BookmarksSave),
+            "nav:get-last" => Some(Self::NavGetLast),
+            "nav:set-last" => Some(Self::NavSetLast),
             "share:generate" => Some(Self::ShareGenerate),
+            "operation:cancel" => Some(Self::OperationCancel),
             "folder-sync:list-rules" => Some(Self::FolderSyncListRules),
             "folder-sync:add-rule" => Some(Self::FolderSyncAddRule),
             "folder-sync:update-rule" => Some(Self::FolderSyncUpdateRule),
@@ -119,13 +230,101 @@ impl RpcMethod {
             "folder-sync:resume-all" => Some(Self::FolderSyncResumeAll),
             "folder-sync:get-status" => Some(Self::FolderSyncGetStatus),
             "folder-sync:preview" => Some(Self::FolderSyncPreview),
+            "folder-sync:dry-run" => Some(Self::FolderSyncDryRun),
+            "folder-sync:empty-trash" => Some(Self::FolderSyncEmptyTrash),
+            "folder-sync:status-detail" => Some(Self::FolderSyncStatusDetail),
             "folder-sync:pick-folder" => Some(Self::FolderSyncPickFolder),
+            "folder-sync:export-rules" => Some(Self::FolderSyncExportRules),
+            "folder-sync:import-rules" => Some(Self::FolderSyncImportRules),
             "updater:check" => Some(Self::UpdaterCheck),
             "updater:download" => Some(Self::UpdaterDownload),
             "updater:apply" => Some(Self::UpdaterApply),
             "updater:local-info" => Some(Self::UpdaterLocalInfo),
             "system:platform" => Some(Self::SystemPlatform),
+            "system:network-status" => Some(Self::SystemNetworkStatus),
+            "app:self-test" => Some(Self::AppSelfTest),
+            "app:abort-all" => Some(Self::AppAbortAll),
+            "maintenance:repair-job-history" => Some(Self::MaintenanceRepairJobHistory),
+            "app:legacy-config" => Some(Self::AppLegacyConfig),
+            "app:migrate-legacy-config" => Some(Self::AppMigrateLegacyConfig),
+            "util:parse-s3-uri" => Some(Self::UtilParseS3Uri),
+            "util:disk-space" => Some(Self::UtilDiskSpace),
+            "provider:probe" => Some(Self::ProviderProbe),
+            "provider:regions" => Some(Self::ProviderRegions),
             _ => None,
         }
     }
+
+    // Read-only calls may run concurrently inside an `rpc_batch`.
+    pub fn is_read_only(self) -> bool {
+        matches!(
+            self,
+            Self::VaultStatus
+                | Self::VaultKeychainStatus
+                | Self::VaultHasRecoveryKey
+                | Self::VaultGetAutoLock
+                | Self::ProfileList
+                | Self::ProfileImportAws
+                | Self::BucketsList
+                | Self::BucketsGetCors
+                | Self::BucketsGetPolicy
+                | Self::BucketsGetLifecycle
+                | Self::ObjectsList
+                | Self::ObjectsListVersions
+                | Self::ObjectsStat
+                | Self::ObjectsExists
+                | Self::ObjectsPreview
+                | Self::ObjectsGetText
+                | Self::ObjectsStreamRange
+                | Self::ObjectsSearch
+                | Self::ObjectsDiffPrefixes
+                | Self::ObjectsGetAcl
+                | Self::ObjectsGetTags
+                | Self::ObjectsRestoreStatus
+                | Self::SyncPreview
+                | Self::JobsList
+                | Self::JobsListResumable
+                | Self::JobsGetConcurrency
+                | Self::JobsGetBandwidthLimit
+                | Self::SettingsGet
+                | Self::SettingsEffective
+                | Self::FavoritesLoad
+                | Self::BookmarksLoad
+                | Self::NavGetLast
+                | Self::ShareGenerate
+                | Self::FolderSyncListRules
+                | Self::FolderSyncGetStatus
+                | Self::FolderSyncPreview
+                | Self::FolderSyncDryRun
+                | Self::FolderSyncStatusDetail
+                | Self::UpdaterLocalInfo
+                | Self::SystemPlatform
+                | Self::SystemNetworkStatus
+                | Self::AppSelfTest
+                | Self::AppLegacyConfig
+                | Self::UtilParseS3Uri
+                | Self::UtilDiskSpace
+                | Self::ProviderRegions
+        )
+    }
+
+    // Methods that block on a native dialog or replace the running app can't
+    // be batched; everything else can, with mutations run sequentially.
+    pub fn is_batchable(self) -> bool {
+        !matches!(
+            self,
+            Self::TransferPickAndUpload
+                | Self::TransferPickAndUploadFolder
+                | Self::TransferUploadFolderArchive
+                | Self::TransferDownloadFolder
+                | Self::TransferDownloadSelection
+                | Self::TransferDownloadArchive
+                | Self::ObjectsOpenExternal
+                | Self::FolderSyncPickFolder
+                | Self::FolderSyncExportRules
+                | Self::FolderSyncImportRules
+                | Self::UpdaterDownload
+                | Self::UpdaterApply
+        )
+    }
 }
@@ -1,261 +1,1753 @@
-//! S3 object operations: list, multipart upload, download, tar.gz archive,
+//! S3 object operations: list, multipart upload, download, tar.gz/zip archive,
 //! server-side + temp-file copy, and batched delete.
 
 use super::*;
+use aws_sdk_s3::{
+    config::{
+        http::HttpResponse, interceptors::BeforeTransmitInterceptorContextMut, ConfigBag,
+        Intercept, RequestChecksumCalculation, RuntimeComponents,
+    },
+    error::{BoxError, ProvideErrorMetadata, SdkError},
+    primitives::SdkBody,
+    types::ServerSideEncryption,
+};
+use futures_util::{
+    future::{select, Either},
+    stream::{self, StreamExt, TryStreamExt},
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::SystemTime,
+};
+use tokio::io::AsyncSeekExt;
+
+// HEAD the key: `Ok(None)` only for a genuine 404, so a permission or network
+// failure is never mistaken for "free to write".
+pub(crate) async fn s3_head_if_exists(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<RemoteObject>, String> {
+    match client
+        .head_object()
+        .bucket(bucket.to_string())
+        .key(key.to_string())
+        .send()
+        .await
+    {
+        Ok(output) => Ok(Some(RemoteObject {
+            key: key.to_string(),
+            size: output.content_length().unwrap_or(0).max(0),
+            etag: normalize_etag(output.e_tag()),
+            last_modified: output
+                .last_modified()
+                .map(s3_datetime_to_iso)
+                .unwrap_or_default(),
+            storage_class: output
+                .storage_class()
+                .map(|class| class.as_str().to_string()),
+        })),
+        Err(err) => {
+            let status = err
+                .raw_response()
+                .map(|response| response.status().as_u16());
+            let not_found = err
+                .as_service_error()
+                .is_some_and(|service_err| service_err.is_not_found())
+                || status == Some(404);
+            if not_found {
+                Ok(None)
+            } else {
+                Err(s3_error(
+                    err,
+                    &format!("check {}", format_s3_uri(bucket, key)),
+                ))
+            }
+        }
+    }
+}
+
+// An empty object at `<prefix>/` so the folder lists before anything is
+// uploaded into it. Refuses to replace an existing marker.
+pub(crate) async fn s3_create_folder(
+    client: &S3Client,
+    provider: &S3Provider,
+    bucket: &str,
+    prefix: &str,
+) -> Result<String, String> {
+    let key = folder_marker_key(prefix)?;
+    if s3_head_if_exists(client, bucket, &key).await?.is_some() {
+        return Err(already_exists_error(bucket, &key));
+    }
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(&key)
+        .body(ByteStream::from(Vec::<u8>::new()))
+        .set_if_none_match(supports_conditional_writes(provider).then(|| "*".to_string()))
+        .send()
+        .await
+        .map_err(|err| s3_upload_error(err, bucket, &key))?;
+    Ok(key)
+}
+
+// A delimited listing for the object browser. One page unless `fetch_all`,
+// which keeps paging up to `OBJECT_LIST_FETCH_ALL_MAX_ENTRIES`.
+pub(crate) async fn s3_list_folder(
+    client: &S3Client,
+    profile: &Profile,
+    input: &ObjectsListInput,
+) -> Result<ObjectListing, String> {
+    let mut listing = ObjectListing::default();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let mut request = client
+            .list_objects_v2()
+            .bucket(input.bucket.clone())
+            .delimiter("/");
+        if let Some(prefix) = input.prefix.as_deref() {
+            request = request.prefix(prefix);
+        }
+        if let Some(token) = continuation_token.as_deref() {
+            request = request.continuation_token(token);
+        } else if let Some(start_after) = input.start_after.as_deref() {
+            request = request.start_after(start_after);
+        }
+        if input.fetch_all {
+            request = request.max_keys(S3_LIST_MAX_KEYS);
+        } else if let Some(max_keys) = input.max_keys {
+            request = request.max_keys(max_keys.into());
+        }
+
+        let output = request
+            .send()
+            .await
+            .map_err(|err| s3_error_with_requester_pays_hint(err, profile.requester_pays))?;
+
+        listing.objects.extend(output.contents().iter().map(|item| {
+            ObjectListEntry {
+                key: item.key().unwrap_or_default().to_string(),
+                size: item.size().unwrap_or(0).max(0),
+                last_modified: item
+                    .last_modified()
+                    .map(s3_datetime_to_iso)
+                    .unwrap_or_default(),
+                etag: normalize_etag(item.e_tag()),
+                storage_class: item
+                    .storage_class()
+                    .map_or("STANDARD", |value| value.as_str())
+                    .to_string(),
+            }
+        }));
+        listing.prefixes.extend(
+            output
+                .common_prefixes()
+                .iter()
+                .filter_map(|prefix| prefix.prefix())
+                .map(str::to_string),
+        );
+        if let Some(last) = output.contents().last().and_then(|item| item.key()) {
+            listing.next_cursor = Some(last.to_string());
+        }
+        listing.is_truncated = output.is_truncated().unwrap_or(false);
+
+        let entries = listing.objects.len() + listing.prefixes.len();
+        if !input.fetch_all || !listing.is_truncated || entries >= OBJECT_LIST_FETCH_ALL_MAX_ENTRIES
+        {
+            break;
+        }
+        continuation_token = output.next_continuation_token().map(str::to_string);
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+    Ok(listing)
+}
+
+// Every version and delete marker under `prefix`, grouped by key with the
+// newest first. Stops after `OBJECT_LIST_FETCH_ALL_MAX_ENTRIES`.
+pub(crate) async fn s3_list_object_versions(
+    client: &S3Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<ObjectVersionListing, String> {
+    let mut listing = ObjectVersionListing::default();
+    let mut key_marker: Option<String> = None;
+    let mut version_id_marker: Option<String> = None;
+    loop {
+        let output = client
+            .list_object_versions()
+            .bucket(bucket)
+            .prefix(prefix)
+            .max_keys(S3_LIST_MAX_KEYS)
+            .set_key_marker(key_marker.take())
+            .set_version_id_marker(version_id_marker.take())
+            .send()
+            .await
+            .map_err(|err| {
+                s3_error(
+                    err,
+                    &format!("list the versions in {}", format_s3_uri(bucket, prefix)),
+                )
+            })?;
+
+        listing
+            .versions
+            .extend(output.versions().iter().map(|version| {
+                ObjectVersionEntry {
+                    key: version.key().unwrap_or_default().to_string(),
+                    version_id: version.version_id().unwrap_or("null").to_string(),
+                    size: version.size().unwrap_or(0).max(0),
+                    last_modified: version
+                        .last_modified()
+                        .map(s3_datetime_to_iso)
+                        .unwrap_or_default(),
+                    etag: normalize_etag(version.e_tag()),
+                    is_latest: version.is_latest().unwrap_or(false),
+                    is_delete_marker: false,
+                }
+            }));
+        listing
+            .versions
+            .extend(output.delete_markers().iter().map(|marker| {
+                ObjectVersionEntry {
+                    key: marker.key().unwrap_or_default().to_string(),
+                    version_id: marker.version_id().unwrap_or("null").to_string(),
+                    size: 0,
+                    last_modified: marker
+                        .last_modified()
+                        .map(s3_datetime_to_iso)
+                        .unwrap_or_default(),
+                    etag: String::new(),
+                    is_latest: marker.is_latest().unwrap_or(false),
+                    is_delete_marker: true,
+                }
+            }));
+
+        if !output.is_truncated().unwrap_or(false) {
+            break;
+        }
+        if listing.versions.len() >= OBJECT_LIST_FETCH_ALL_MAX_ENTRIES {
+            listing.is_truncated = true;
+            break;
+        }
+        key_marker = output.next_key_marker().map(str::to_string);
+        version_id_marker = output.next_version_id_marker().map(str::to_string);
+        if key_marker.is_none() {
+            break;
+        }
+    }
+    sort_object_versions(&mut listing.versions);
+    Ok(listing)
+}
+
+// S3 returns versions and delete markers as separate lists; interleave them.
+// Timestamps only have second precision, so a tie puts the latest first.
+pub(crate) fn sort_object_versions(versions: &mut [ObjectVersionEntry]) {
+    versions.sort_by(|a, b| {
+        a.key
+            .cmp(&b.key)
+            .then_with(|| b.last_modified.cmp(&a.last_modified))
+            .then_with(|| b.is_latest.cmp(&a.is_latest))
+    });
+}
+
+// Ties fall back to the key so equal sizes/dates keep a stable order.
+// Prefixes only have names, so they sort by name in the requested direction.
+pub(crate) fn sort_object_listing(
+    listing: &mut ObjectListing,
+    field: ObjectSortField,
+    direction: SortDirection,
+) {
+    listing.objects.sort_by(|a, b| {
+        let ordering = match field {
+            ObjectSortField::Key => a.key.cmp(&b.key),
+            ObjectSortField::Size => a.size.cmp(&b.size).then_with(|| a.key.cmp(&b.key)),
+            ObjectSortField::LastModified => a
+                .last_modified
+                .cmp(&b.last_modified)
+                .then_with(|| a.key.cmp(&b.key)),
+        };
+        match direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    });
+    listing.prefixes.sort();
+    if field == ObjectSortField::Key && direction == SortDirection::Desc {
+        listing.prefixes.reverse();
+    }
+}
+
+// Expands a selection into `(key, size)` pairs: folder keys (ending in `/`)
+// are listed, and with `head_files` plain keys are HEADed for their size
+// (0 otherwise). Up to `concurrency` requests run at once, but results keep
+// the selection's order so job ordering is reproducible.
+pub(crate) async fn s3_expand_keys(
+    client: &S3Client,
+    bucket: &str,
+    keys: &[String],
+    concurrency: Option<usize>,
+    head_files: bool,
+) -> Result<Vec<(String, i64)>, String> {
+    let concurrency = concurrency
+        .unwrap_or(KEY_EXPAND_DEFAULT_CONCURRENCY)
+        .clamp(1, KEY_EXPAND_MAX_CONCURRENCY);
+    // Each future owns its inputs so the stream stays Send for the RPC handlers.
+    let batches: Vec<Vec<(String, i64)>> = stream::iter(keys.to_vec())
+        .map(|key| {
+            let client = client.clone();
+            let bucket = bucket.to_string();
+            async move {
+                if key.ends_with('/') {
+                    let children = s3_list_all_objects(&client, &bucket, &key).await?;
+                    Ok::<Vec<(String, i64)>, String>(
+                        children
+                            .into_iter()
+                            .map(|child| (child.key, child.size.max(0)))
+                            .collect(),
+                    )
+                } else if head_files {
+                    let head = client
+                        .head_object()
+                        .bucket(bucket)
+                        .key(key.clone())
+                        .send()
+                        .await
+                        .map_err(|err| err.to_string())?;
+                    Ok(vec![(key, head.content_length().unwrap_or(0).max(0))])
+                } else {
+                    Ok(vec![(key, 0)])
+                }
+            }
+        })
+        .buffered(concurrency)
+        .try_collect()
+        .await?;
+    Ok(batches.into_iter().flatten().collect())
+}
+
+fn remote_object_from_s3(item: &aws_sdk_s3::types::Object) -> RemoteObject {
+    RemoteObject {
+        key: item.key().unwrap_or_default().to_string(),
+        size: item.size().unwrap_or(0).max(0),
+        etag: normalize_etag(item.e_tag()),
+        last_modified: item
+            .last_modified()
+            .map(s3_datetime_to_iso)
+            .unwrap_or_else(now_iso),
+        storage_class: item.storage_class().map(|class| class.as_str().to_string()),
+    }
+}
+
+pub(crate) fn is_archived_storage_class(storage_class: Option<&str>) -> bool {
+    storage_class.is_some_and(|class| ARCHIVED_STORAGE_CLASSES.contains(&class))
+}
+
+impl<'a> S3ObjectPager<'a> {
+    pub(crate) fn new(client: &'a S3Client, bucket: &'a str, prefix: &'a str) -> Self {
+        Self {
+            client,
+            bucket,
+            prefix,
+            continuation_token: None,
+            done: false,
+        }
+    }
+
+    // `None` once the listing is exhausted.
+    pub(crate) async fn next_page(&mut self) -> Result<Option<Vec<RemoteObject>>, String> {
+        if self.done {
+            return Ok(None);
+        }
+        let mut request = self
+            .client
+            .list_objects_v2()
+            .bucket(self.bucket.to_string())
+            .max_keys(S3_LIST_MAX_KEYS)
+            .prefix(self.prefix.to_string());
+        if let Some(token) = self.continuation_token.take() {
+            request = request.continuation_token(token);
+        }
+
+        let output = request.send().await.map_err(|err| {
+            s3_error(
+                err,
+                &format!("list {}", format_s3_uri(self.bucket, self.prefix)),
+            )
+        })?;
+
+        self.continuation_token = if output.is_truncated().unwrap_or(false) {
+            output.next_continuation_token().map(str::to_string)
+        } else {
+            None
+        };
+        self.done = self.continuation_token.is_none();
+        Ok(Some(
+            output
+                .contents()
+                .iter()
+                .map(remote_object_from_s3)
+                .collect(),
+        ))
+    }
+}
+
+// Hands every object under `prefix` to `on_object` as pages arrive, keeping
+// at most one page in memory. Prefer this over s3_list_all_objects wherever
+// the caller folds the listing into something smaller.
+pub(crate) async fn s3_for_each_object<F>(
+    client: &S3Client,
+    bucket: &str,
+    prefix: &str,
+    mut on_object: F,
+) -> Result<(), String>
+where
+    F: FnMut(RemoteObject),
+{
+    let mut pager = S3ObjectPager::new(client, bucket, prefix);
+    while let Some(page) = pager.next_page().await? {
+        page.into_iter().for_each(&mut on_object);
+    }
+    Ok(())
+}
+
+// Without a `/` the pattern matches the file name, otherwise the key below
+// `prefix`. Case-insensitive; a pattern with no `*` or `?` matches anywhere.
+pub(crate) fn object_search_matches(pattern: &str, prefix: &str, key: &str) -> bool {
+    let relative = key.strip_prefix(prefix).unwrap_or(key);
+    let target = if pattern.contains('/') {
+        relative
+    } else {
+        relative
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(relative)
+    };
+    let pattern = pattern.to_lowercase();
+    let target = target.to_lowercase();
+    if pattern.contains(['*', '?']) {
+        wildcard_matches(&pattern, &target)
+    } else {
+        target.contains(&pattern)
+    }
+}
+
+// Pages through the subtree and stops as soon as one match past
+// `max_results` shows the result is truncated.
+pub(crate) async fn s3_search_objects(
+    client: &S3Client,
+    bucket: &str,
+    prefix: &str,
+    pattern: &str,
+    max_results: usize,
+) -> Result<ObjectSearchResult, String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err("Search pattern must not be empty".to_string());
+    }
+    let mut result = ObjectSearchResult::default();
+    let mut pager = S3ObjectPager::new(client, bucket, prefix);
+    while let Some(page) = pager.next_page().await? {
+        for object in page {
+            result.scanned += 1;
+            if !object_search_matches(pattern, prefix, &object.key) {
+                continue;
+            }
+            if result.objects.len() >= max_results {
+                result.is_truncated = true;
+                return Ok(result);
+            }
+            result.objects.push(ObjectListEntry {
+                key: object.key,
+                size: object.size,
+                last_modified: object.last_modified,
+                etag: object.etag,
+                storage_class: object
+                    .storage_class
+                    .unwrap_or_else(|| "STANDARD".to_string()),
+            });
+        }
+    }
+    Ok(result)
+}
 
+// Collects the whole listing; fine for folders whose objects all become jobs
+// anyway, but not for bucket-scale folds.
 pub(crate) async fn s3_list_all_objects(
     client: &S3Client,
     bucket: &str,
     prefix: &str,
 ) -> Result<Vec<RemoteObject>, String> {
-    let mut continuation_token: Option<String> = None;
     let mut all_objects: Vec<RemoteObject> = Vec::new();
+    s3_for_each_object(client, bucket, prefix, |object| all_objects.push(object)).await?;
+    Ok(all_objects)
+}
+
+// With `create_only`, the write carries `If-None-Match: *` so the provider
+// refuses it (412) if the key appeared after any pre-flight existence check.
+// A `ChecksumRequired` rejection is retried once with checksums and
+// `Content-MD5` attached; a multipart upload resumes from its sidecar rather
+// than starting over. Setting `pause_flag` stops a multipart upload between
+// parts with `JOB_PAUSED`.
+pub(crate) async fn s3_upload_file(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    local_path: &Path,
+    attributes: &UploadAttributes,
+    cancel_flag: &AtomicBool,
+    mut on_progress: impl FnMut(i64, i64),
+) -> Result<i64, String> {
+    let result = s3_upload_file_attempt(
+        client,
+        bucket,
+        key,
+        local_path,
+        attributes,
+        cancel_flag,
+        &mut on_progress,
+    )
+    .await;
+    match result {
+        Err(err) if err.starts_with(CHECKSUM_REQUIRED) => {
+            let attributes = UploadAttributes {
+                content_md5: true,
+                ..attributes.clone()
+            };
+            s3_upload_file_attempt(
+                &with_request_checksums(client),
+                bucket,
+                key,
+                local_path,
+                &attributes,
+                cancel_flag,
+                &mut on_progress,
+            )
+            .await
+        }
+        result => result,
+    }
+}
+
+pub(crate) fn parse_storage_class(storage_class: &str) -> Result<StorageClass, String> {
+    let storage_class = storage_class.trim().to_ascii_uppercase();
+    if OBJECT_STORAGE_CLASSES.contains(&storage_class.as_str()) {
+        Ok(StorageClass::from(storage_class.as_str()))
+    } else {
+        Err(format!(
+            "Unknown storage class \"{storage_class}\"; expected one of {}",
+            OBJECT_STORAGE_CLASSES.join(", ")
+        ))
+    }
+}
+
+// Canonical ("AES256" / "aws:kms") encryption and KMS key id, blanks
+// dropped. A key id without aws:kms is rejected rather than ignored.
+pub(crate) fn parse_upload_encryption(
+    sse: Option<&str>,
+    kms_key_id: Option<&str>,
+) -> Result<(Option<String>, Option<String>), String> {
+    let sse = sse.map(str::trim).filter(|value| !value.is_empty());
+    let kms_key_id = kms_key_id.map(str::trim).filter(|value| !value.is_empty());
+    let sse = match sse {
+        Some(sse) => Some(
+            UPLOAD_SSE_ALGORITHMS
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(sse))
+                .map(|known| known.to_string())
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown server-side encryption \"{sse}\" (use {})",
+                        UPLOAD_SSE_ALGORITHMS.join(", ")
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    if kms_key_id.is_some() && sse.as_deref() != Some("aws:kms") {
+        return Err("A KMS key id needs \"aws:kms\" server-side encryption".to_string());
+    }
+    Ok((sse, kms_key_id.map(str::to_string)))
+}
+
+// An upload's own encryption wins over the profile's default, key id and all.
+pub(crate) fn resolve_upload_encryption(
+    profile: &Profile,
+    sse: Option<&str>,
+    kms_key_id: Option<&str>,
+) -> Result<(Option<ServerSideEncryption>, Option<String>), String> {
+    let (sse, kms_key_id) = match sse {
+        Some(_) => parse_upload_encryption(sse, kms_key_id)?,
+        None => parse_upload_encryption(profile.sse.as_deref(), profile.sse_kms_key_id.as_deref())?,
+    };
+    Ok((sse.as_deref().map(ServerSideEncryption::from), kms_key_id))
+}
+
+// Folder-sync uploads have no per-file options, only the profile's defaults.
+pub(crate) fn upload_attributes_from_profile(
+    profile: &Profile,
+) -> Result<UploadAttributes, String> {
+    let (server_side_encryption, ssekms_key_id) = resolve_upload_encryption(profile, None, None)?;
+    Ok(UploadAttributes {
+        server_side_encryption,
+        ssekms_key_id,
+        ..UploadAttributes::default()
+    })
+}
+
+pub(crate) fn upload_metadata(attributes: &UploadAttributes) -> Option<HashMap<String, String>> {
+    (!attributes.metadata.is_empty()).then(|| attributes.metadata.clone())
+}
+
+async fn s3_upload_file_attempt(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    local_path: &Path,
+    attributes: &UploadAttributes,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut impl FnMut(i64, i64),
+) -> Result<i64, String> {
+    if cancel_flag.load(Ordering::SeqCst) {
+        return Err(JOB_CANCELLED.to_string());
+    }
+    let create_only = attributes.create_only;
+    let pause_flag = attributes.pause_flag.as_deref();
+
+    let total = fs::metadata(local_path)
+        .map(|meta| meta.len() as i64)
+        .unwrap_or(0)
+        .max(0);
+
+    if total <= MULTIPART_THRESHOLD_BYTES {
+        let content_md5 = if attributes.content_md5 {
+            Some(file_content_md5(local_path)?)
+        } else {
+            None
+        };
+        let file_body = ByteStream::from_path(local_path.to_path_buf())
+            .await
+            .map_err(|err| format!("Failed to stream {}: {err}", local_path.display()))?
+            .into_inner();
+
+        // Count bytes as the SDK pulls them so small uploads animate like
+        // multipart ones. A retry rebuilds the body and restarts the count.
+        let sent = Arc::new(AtomicI64::new(0));
+        let sent_by_body = sent.clone();
+        let bandwidth = attributes.bandwidth.clone();
+        let body = SdkBody::retryable(move || {
+            sent_by_body.store(0, Ordering::SeqCst);
+            SdkBody::from_body_1_x(UploadProgressBody {
+                inner: file_body.try_clone().unwrap_or_else(SdkBody::taken),
+                sent: sent_by_body.clone(),
+                bandwidth: bandwidth.as_ref().map(BandwidthLimiter::lease),
+                throttle: None,
+            })
+        });
+
+        let mut send = Box::pin(
+            client
+                .put_object()
+                .bucket(bucket.to_string())
+                .key(key.to_string())
+                .content_length(total)
+                .body(ByteStream::new(body))
+                .set_acl(attributes.acl.clone())
+                .set_content_type(attributes.content_type.clone())
+                .set_cache_control(attributes.cache_control.clone())
+                .set_metadata(upload_metadata(attributes))
+                .set_storage_class(attributes.storage_class.clone())
+                .set_server_side_encryption(attributes.server_side_encryption.clone())
+                .set_ssekms_key_id(attributes.ssekms_key_id.clone())
+                .set_if_none_match(create_only.then(|| "*".to_string()))
+                .set_content_md5(content_md5)
+                .send(),
+        );
+        let output = loop {
+            let tick = Box::pin(tokio::time::sleep(StdDuration::from_millis(
+                UPLOAD_PROGRESS_TICK_MS,
+            )));
+            match select(send.as_mut(), tick).await {
+                Either::Left((output, _)) => break output,
+                Either::Right(_) => {
+                    if cancel_flag.load(Ordering::SeqCst) {
+                        return Err(JOB_CANCELLED.to_string());
+                    }
+                    on_progress(sent.load(Ordering::SeqCst).min(total), total);
+                }
+            }
+        };
+        output.map_err(|err| s3_upload_error(err, bucket, key))?;
+
+        verify_uploaded_size(client, bucket, key, total).await?;
+        on_progress(total, total);
+        return Ok(total);
+    }
+
+    let local_mtime_ms = file_mtime_millis(local_path);
+    let resumed = match load_multipart_sidecar(bucket, key, local_path) {
+        Some(sidecar) => resume_multipart_upload(client, sidecar, total, local_mtime_ms).await?,
+        None => None,
+    };
+    let mut sidecar = match resumed {
+        Some(mut sidecar) => {
+            if attributes.owner.is_some() {
+                sidecar.owner = attributes.owner.clone();
+            }
+            sidecar
+        }
+        None => {
+            // A resumed upload keeps the headers it was created with.
+            let multipart = client
+                .create_multipart_upload()
+                .bucket(bucket.to_string())
+                .key(key.to_string())
+                .set_acl(attributes.acl.clone())
+                .set_content_type(attributes.content_type.clone())
+                .set_cache_control(attributes.cache_control.clone())
+                .set_metadata(upload_metadata(attributes))
+                .set_storage_class(attributes.storage_class.clone())
+                .set_server_side_encryption(attributes.server_side_encryption.clone())
+                .set_ssekms_key_id(attributes.ssekms_key_id.clone())
+                .send()
+                .await
+                .map_err(|err| s3_error(err, &format!("upload {}", format_s3_uri(bucket, key))))?;
+            let upload_id = multipart
+                .upload_id()
+                .map(str::to_string)
+                .ok_or_else(|| "Missing multipart upload id".to_string())?;
+            MultipartUploadSidecar {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+                local_path: local_path.to_string_lossy().to_string(),
+                upload_id,
+                file_size: total,
+                file_mtime_ms: local_mtime_ms,
+                part_size: MULTIPART_PART_SIZE_BYTES,
+                parts: Vec::new(),
+                created_at: now_iso(),
+                create_only,
+                owner: attributes.owner.clone(),
+            }
+        }
+    };
+    // Best-effort: without a sidecar the upload still works, it just can't resume.
+    let _ = save_multipart_sidecar(&sidecar);
+    let upload_id = sidecar.upload_id.clone();
 
+    let mut file = tokio_fs::File::open(local_path)
+        .await
+        .map_err(|err| format!("Failed to open {}: {err}", local_path.display()))?;
+    let mut transferred: i64 = sidecar
+        .parts
+        .last()
+        .map(|part| part.offset + part.size)
+        .unwrap_or(0);
+    if transferred > 0 {
+        file.seek(io::SeekFrom::Start(transferred as u64))
+            .await
+            .map_err(|err| format!("Failed to seek {}: {err}", local_path.display()))?;
+        on_progress(transferred, total);
+    }
+    let mut part_number = sidecar.parts.len() as i32 + 1;
+    let mut bandwidth = attributes.bandwidth.as_ref().map(BandwidthLimiter::lease);
+    // S3 failures leave the upload and sidecar in place for a retry to resume;
+    // cancellation and local errors abort it.
+    let mut resumable = false;
+
+    let upload_result: Result<(), String> = async {
+        loop {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return Err(JOB_CANCELLED.to_string());
+            }
+            if pause_flag.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+                // Keep the upload and sidecar; resume lists the parts and
+                // carries on from the next part number.
+                resumable = true;
+                return Err(JOB_PAUSED.to_string());
+            }
+
+            let mut buffer = vec![0u8; MULTIPART_PART_SIZE_BYTES];
+            let read_total = read_full_chunk(&mut file, &mut buffer)
+                .await
+                .map_err(|err| format!("Failed reading {}: {err}", local_path.display()))?;
+
+            if read_total == 0 {
+                break;
+            }
+            buffer.truncate(read_total);
+
+            let output = client
+                .upload_part()
+                .bucket(bucket.to_string())
+                .key(key.to_string())
+                .upload_id(upload_id.clone())
+                .part_number(part_number)
+                .set_content_md5(attributes.content_md5.then(|| content_md5(&buffer)))
+                .body(ByteStream::from(buffer))
+                .send()
+                .await
+                .map_err(|err| {
+                    resumable = true;
+                    s3_error(
+                        err,
+                        &format!(
+                            "upload part {part_number} of {}",
+                            format_s3_uri(bucket, key)
+                        ),
+                    )
+                })?;
+
+            sidecar.parts.push(MultipartSidecarPart {
+                part_number,
+                e_tag: output.e_tag().map(str::to_string),
+                offset: transferred,
+                size: read_total as i64,
+            });
+            let _ = save_multipart_sidecar(&sidecar);
+
+            transferred += read_total as i64;
+            on_progress(transferred, total);
+            part_number += 1;
+            if let Some(lease) = bandwidth.as_mut() {
+                lease.throttle(read_total, cancel_flag, pause_flag).await;
+            }
+        }
+
+        if sidecar.parts.is_empty() {
+            return Err("Multipart upload produced no parts".to_string());
+        }
+
+        let parts = sidecar
+            .parts
+            .iter()
+            .map(|part| {
+                CompletedPart::builder()
+                    .set_e_tag(part.e_tag.clone())
+                    .part_number(part.part_number)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        let completed_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(parts))
+            .build();
+
+        client
+            .complete_multipart_upload()
+            .bucket(bucket.to_string())
+            .key(key.to_string())
+            .upload_id(upload_id.clone())
+            .multipart_upload(completed_upload)
+            .set_if_none_match(create_only.then(|| "*".to_string()))
+            .send()
+            .await
+            .map_err(|err| {
+                let message = s3_upload_error(err, bucket, key);
+                resumable = !message.starts_with(JOB_ALREADY_EXISTS);
+                message
+            })?;
+
+        Ok(())
+    }
+    .await;
+
+    if let Err(err) = upload_result {
+        if !resumable {
+            discard_multipart_upload(client, &sidecar).await;
+        }
+        return Err(err);
+    }
+    remove_multipart_sidecar(bucket, key, local_path);
+
+    if transferred != total {
+        return Err(format!(
+            "{} changed size during upload ({transferred} of {total} bytes read)",
+            local_path.display()
+        ));
+    }
+    verify_uploaded_size(client, bucket, key, total).await?;
+    on_progress(total, total);
+    Ok(total)
+}
+
+// Picks up an interrupted upload of the same file. The local file must be
+// unchanged, and only parts S3 still lists with the recorded ETag are kept.
+// Any ListParts failure other than NoSuchUpload is returned and the sidecar
+// kept, so a network blip doesn't throw away the uploaded parts.
+async fn resume_multipart_upload(
+    client: &S3Client,
+    mut sidecar: MultipartUploadSidecar,
+    file_size: i64,
+    file_mtime_ms: Option<i64>,
+) -> Result<Option<MultipartUploadSidecar>, String> {
+    if sidecar.file_size != file_size
+        || sidecar.file_mtime_ms != file_mtime_ms
+        || sidecar.part_size != MULTIPART_PART_SIZE_BYTES
+    {
+        discard_multipart_upload(client, &sidecar).await;
+        return Ok(None);
+    }
+
+    let Some(uploaded) = s3_list_uploaded_parts(client, &sidecar).await? else {
+        // Completed, aborted, or expired.
+        discard_multipart_upload(client, &sidecar).await;
+        return Ok(None);
+    };
+    sidecar.parts = surviving_multipart_parts(&sidecar.parts, &uploaded);
+    Ok(Some(sidecar))
+}
+
+// None when S3 no longer knows the upload (NoSuchUpload).
+async fn s3_list_uploaded_parts(
+    client: &S3Client,
+    sidecar: &MultipartUploadSidecar,
+) -> Result<Option<HashMap<i32, Option<String>>>, String> {
+    let mut uploaded = HashMap::new();
+    let mut marker: Option<String> = None;
     loop {
-        let mut request = client
-            .list_objects_v2()
+        let output = client
+            .list_parts()
+            .bucket(sidecar.bucket.clone())
+            .key(sidecar.key.clone())
+            .upload_id(sidecar.upload_id.clone())
+            .set_part_number_marker(marker.clone())
+            .send()
+            .await;
+        let output = match output {
+            Ok(output) => output,
+            Err(err) if err.code() == Some("NoSuchUpload") => return Ok(None),
+            Err(err) => {
+                return Err(s3_error(
+                    err,
+                    &format!(
+                        "resume the upload to {}",
+                        format_s3_uri(&sidecar.bucket, &sidecar.key)
+                    ),
+                ))
+            }
+        };
+        for part in output.parts() {
+            if let Some(part_number) = part.part_number() {
+                uploaded.insert(part_number, part.e_tag().map(str::to_string));
+            }
+        }
+        marker = output.next_part_number_marker().map(str::to_string);
+        if !output.is_truncated().unwrap_or(false) || marker.is_none() {
+            break;
+        }
+    }
+    Ok(Some(uploaded))
+}
+
+// The longest run of parts from part 1 that S3 confirms; the upload resumes
+// right after it, so a gap or ETag mismatch drops everything that follows.
+pub(crate) fn surviving_multipart_parts(
+    recorded: &[MultipartSidecarPart],
+    uploaded: &HashMap<i32, Option<String>>,
+) -> Vec<MultipartSidecarPart> {
+    let mut offset = 0;
+    recorded
+        .iter()
+        .enumerate()
+        .take_while(|(index, part)| {
+            let contiguous = part.part_number == *index as i32 + 1 && part.offset == offset;
+            offset += part.size;
+            contiguous && uploaded.get(&part.part_number) == Some(&part.e_tag)
+        })
+        .map(|(_, part)| part.clone())
+        .collect()
+}
+
+// Explicit aborts and unrecoverable failures: free the parts S3 is storing
+// and forget the sidecar.
+pub(crate) async fn discard_multipart_upload(client: &S3Client, sidecar: &MultipartUploadSidecar) {
+    let _ = client
+        .abort_multipart_upload()
+        .bucket(sidecar.bucket.clone())
+        .key(sidecar.key.clone())
+        .upload_id(sidecar.upload_id.clone())
+        .send()
+        .await;
+    remove_multipart_sidecar(
+        &sidecar.bucket,
+        &sidecar.key,
+        Path::new(&sidecar.local_path),
+    );
+}
+
+impl http_body::Body for UploadProgressBody {
+    type Data = bytes::Bytes;
+    type Error = <SdkBody as http_body::Body>::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if let Some(throttle) = this.throttle.as_mut() {
+            if throttle.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.throttle = None;
+        }
+        let polled = http_body::Body::poll_frame(Pin::new(&mut this.inner), cx);
+        if let Poll::Ready(Some(Ok(frame))) = &polled {
+            if let Some(data) = frame.data_ref() {
+                this.sent.fetch_add(data.len() as i64, Ordering::SeqCst);
+                let delay = this
+                    .bandwidth
+                    .as_mut()
+                    .map(|lease| lease.reserve(data.len()))
+                    .unwrap_or_default();
+                if !delay.is_zero() {
+                    this.throttle = Some(Box::pin(tokio::time::sleep(delay)));
+                }
+            }
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        http_body::Body::is_end_stream(&self.inner)
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        http_body::Body::size_hint(&self.inner)
+    }
+}
+
+impl BandwidthLimiter {
+    pub(crate) fn limit(&self) -> Option<u64> {
+        match self.max_bytes_per_sec.load(Ordering::SeqCst) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    pub(crate) fn set_limit(&self, limit: Option<u64>) {
+        self.max_bytes_per_sec
+            .store(limit.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    pub(crate) fn lease(self: &Arc<Self>) -> BandwidthLease {
+        self.active_transfers.fetch_add(1, Ordering::SeqCst);
+        BandwidthLease {
+            limiter: self.clone(),
+            allowance: 0.0,
+            refilled_at: Instant::now(),
+        }
+    }
+}
+
+impl BandwidthLease {
+    // How long to wait after moving `bytes` so this transfer stays within its
+    // share of the cap. Up to a second of unused share carries over as burst.
+    pub(crate) fn reserve(&mut self, bytes: usize) -> StdDuration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.refilled_at = now;
+        let Some(limit) = self.limiter.limit() else {
+            self.allowance = 0.0;
+            return StdDuration::ZERO;
+        };
+        let active = self.limiter.active_transfers.load(Ordering::SeqCst).max(1);
+        let rate = limit as f64 / active as f64;
+        self.allowance = (self.allowance + elapsed * rate).min(rate) - bytes as f64;
+        if self.allowance >= 0.0 {
+            StdDuration::ZERO
+        } else {
+            StdDuration::from_secs_f64(-self.allowance / rate)
+        }
+    }
+
+    // Sleeps off `reserve(bytes)` in short slices, returning early once the
+    // job is cancelled or paused so the caller's own checks can act on it.
+    pub(crate) async fn throttle(
+        &mut self,
+        bytes: usize,
+        cancel_flag: &AtomicBool,
+        pause_flag: Option<&AtomicBool>,
+    ) {
+        let mut remaining = self.reserve(bytes);
+        let slice = StdDuration::from_millis(BANDWIDTH_THROTTLE_SLICE_MS);
+        while !remaining.is_zero() {
+            if cancel_flag.load(Ordering::SeqCst)
+                || pause_flag.is_some_and(|flag| flag.load(Ordering::SeqCst))
+            {
+                return;
+            }
+            let step = remaining.min(slice);
+            tokio::time::sleep(step).await;
+            remaining -= step;
+        }
+    }
+}
+
+impl Drop for BandwidthLease {
+    fn drop(&mut self) {
+        self.limiter.active_transfers.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Confirms the stored object is exactly as large as the file we meant to
+// send, so a short read can never pass as a successful upload.
+async fn verify_uploaded_size(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    expected: i64,
+) -> Result<(), String> {
+    let Some(stored) = s3_head_if_exists(client, bucket, key).await? else {
+        return Err(format!(
+            "Upload verification failed: {key} not found after upload"
+        ));
+    };
+    if stored.size != expected {
+        return Err(format!(
+            "Upload verification failed for {key}: stored {} bytes, expected {expected}",
+            stored.size
+        ));
+    }
+    Ok(())
+}
+
+// With `pause_flag`, bytes land in a partial file next to `local_path` that
+// a pause keeps; the next call asks only for the rest, provided the object's
+// ETag hasn't changed, and the finished file is renamed into place.
+pub(crate) async fn s3_download_file(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    local_path: &Path,
+    attributes: DownloadAttributes,
+    cancel_flag: &AtomicBool,
+    mut on_progress: impl FnMut(i64, i64),
+) -> Result<i64, String> {
+    if cancel_flag.load(Ordering::SeqCst) {
+        return Err(JOB_CANCELLED.to_string());
+    }
+    let pause_flag = attributes.pause_flag.as_deref();
+
+    if let Some(parent) = local_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+
+    let partial = pause_flag.map(|_| partial_download_path(local_path));
+    let mut resume_from: Option<(i64, String)> = None;
+    if let Some(partial) = &partial {
+        let written = fs::metadata(partial)
+            .map(|meta| meta.len() as i64)
+            .unwrap_or(0);
+        resume_from = load_partial_download_sidecar(bucket, key, local_path)
+            .filter(|sidecar| written > 0 && written < sidecar.total)
+            .map(|sidecar| (written, sidecar.e_tag));
+        if resume_from.is_none() {
+            discard_partial_download(bucket, key, local_path);
+        }
+    }
+
+    let get_object = |resume: Option<&(i64, String)>| {
+        client
+            .get_object()
             .bucket(bucket.to_string())
-            .max_keys(S3_LIST_MAX_KEYS)
-            .prefix(prefix.to_string());
+            .key(key.to_string())
+            .set_version_id(attributes.version_id.clone())
+            .set_range(resume.map(|(offset, _)| format!("bytes={offset}-")))
+            .set_if_match(resume.map(|(_, e_tag)| e_tag.clone()))
+            .send()
+    };
+    let action = format!("download {}", format_s3_uri(bucket, key));
+    let output = match get_object(resume_from.as_ref()).await {
+        Ok(output) => output,
+        // 412: the object changed since the pause; 416: nothing left to
+        // fetch. Either way the partial file is no use, so start over.
+        Err(err)
+            if resume_from.is_some()
+                && matches!(
+                    err.raw_response()
+                        .map(|response| response.status().as_u16()),
+                    Some(412 | 416)
+                ) =>
+        {
+            discard_partial_download(bucket, key, local_path);
+            resume_from = None;
+            get_object(None)
+                .await
+                .map_err(|err| s3_error(err, &action))?
+        }
+        Err(err) => return Err(s3_error(err, &action)),
+    };
+    let offset = resume_from.as_ref().map(|(offset, _)| *offset).unwrap_or(0);
+    let total = offset + output.content_length().unwrap_or(0).max(0);
+    let mtime = output
+        .last_modified()
+        .filter(|_| attributes.preserve_mtime)
+        .and_then(|last_modified| SystemTime::try_from(*last_modified).ok());
+    let mode = output
+        .metadata()
+        .and_then(|metadata| metadata.get("mode"))
+        .filter(|_| attributes.restore_mode)
+        .and_then(|mode| parse_mode_metadata(mode));
+    // With SSE-KMS or SSE-C the ETag isn't the body's MD5 even for a single PUT.
+    let expected_md5 = etag_md5_hex(output.e_tag()).filter(|_| {
+        output.sse_customer_algorithm().is_none()
+            && !matches!(
+                output.server_side_encryption(),
+                Some(ServerSideEncryption::AwsKms | ServerSideEncryption::AwsKmsDsse)
+            )
+    });
+
+    // Without an ETag there is no safe way to resume, so no sidecar.
+    if let (Some(_), Some(e_tag)) = (&partial, output.e_tag()) {
+        let _ = save_partial_download_sidecar(&PartialDownloadSidecar {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            local_path: local_path.to_string_lossy().to_string(),
+            e_tag: e_tag.to_string(),
+            total,
+            created_at: now_iso(),
+        });
+    }
 
-        if let Some(token) = continuation_token.as_deref() {
-            request = request.continuation_token(token.to_string());
+    let target = partial.as_deref().unwrap_or(local_path);
+    let file = tokio_fs::File::options()
+        .write(true)
+        .create(true)
+        .append(offset > 0)
+        .truncate(offset == 0)
+        .open(target)
+        .await
+        .map_err(|err| format!("Failed to create {}: {err}", target.display()))?;
+    let mut writer = BufWriter::new(file);
+    let mut body = output.body;
+    let mut bandwidth = attributes.bandwidth.as_ref().map(BandwidthLimiter::lease);
+    let mut transferred: i64 = offset;
+    if offset > 0 {
+        on_progress(transferred, total);
+    }
+
+    while let Some(bytes) = body
+        .try_next()
+        .await
+        .map_err(|err| format!("Download stream failed: {err}"))?
+    {
+        if cancel_flag.load(Ordering::SeqCst) {
+            drop(writer);
+            let _ = tokio_fs::remove_file(target).await;
+            if partial.is_some() {
+                discard_partial_download(bucket, key, local_path);
+            }
+            return Err(JOB_CANCELLED.to_string());
+        }
+        if pause_flag.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+            writer
+                .flush()
+                .await
+                .map_err(|err| format!("Failed flushing {}: {err}", target.display()))?;
+            return Err(JOB_PAUSED.to_string());
+        }
+
+        writer
+            .write_all(&bytes)
+            .await
+            .map_err(|err| format!("Failed writing {}: {err}", target.display()))?;
+
+        transferred += bytes.len() as i64;
+        on_progress(transferred, total);
+        if let Some(lease) = bandwidth.as_mut() {
+            lease.throttle(bytes.len(), cancel_flag, pause_flag).await;
+        }
+    }
+
+    writer
+        .flush()
+        .await
+        .map_err(|err| format!("Failed flushing {}: {err}", target.display()))?;
+    drop(writer);
+
+    if attributes.verify_checksum {
+        if let Err(err) = verify_downloaded_file(target, expected_md5, total).await {
+            let _ = tokio_fs::remove_file(target).await;
+            if partial.is_some() {
+                discard_partial_download(bucket, key, local_path);
+            }
+            return Err(err);
         }
+    }
 
-        let output = request.send().await.map_err(|err| err.to_string())?;
+    if let Some(partial) = &partial {
+        fs::rename(partial, local_path).map_err(|err| {
+            format!(
+                "Failed to move download into {}: {err}",
+                local_path.display()
+            )
+        })?;
+        discard_partial_download(bucket, key, local_path);
+    }
 
-        for item in output.contents() {
-            all_objects.push(RemoteObject {
-                key: item.key().unwrap_or_default().to_string(),
-                size: item.size().unwrap_or(0).max(0),
-                etag: item
-                    .e_tag()
-                    .unwrap_or_default()
-                    .trim_matches('"')
-                    .to_string(),
-                last_modified: item
-                    .last_modified()
-                    .map(s3_datetime_to_iso)
-                    .unwrap_or_else(now_iso),
-            });
-        }
+    // Best effort: the contents are already in place, so a filesystem that
+    // refuses either attribute shouldn't fail the download.
+    if let Some(mtime) = mtime {
+        let _ = fs::File::options()
+            .write(true)
+            .open(local_path)
+            .and_then(|file| file.set_modified(mtime));
+    }
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(local_path, fs::Permissions::from_mode(mode));
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
 
-        if output.is_truncated().unwrap_or(false) {
-            continuation_token = output.next_continuation_token().map(str::to_string);
-        } else {
-            break;
-        }
+    Ok(transferred.max(total))
+}
+
+// Multipart objects (and encrypted ones) have no usable MD5, so they are only
+// held to the size the GET announced.
+async fn verify_downloaded_file(
+    path: &Path,
+    expected_md5: Option<String>,
+    expected_size: i64,
+) -> Result<(), String> {
+    let size = tokio_fs::metadata(path)
+        .await
+        .map_err(|err| format!("Failed to stat {}: {err}", path.display()))?
+        .len() as i64;
+    if size != expected_size {
+        return Err(format!(
+            "{CHECKSUM_MISMATCH}: {} is {size} bytes, expected {expected_size}",
+            path.display()
+        ));
+    }
+    let Some(expected_md5) = expected_md5 else {
+        return Ok(());
+    };
+    let hash_path = path.to_path_buf();
+    let actual = tauri::async_runtime::spawn_blocking(move || file_md5_hex(&hash_path))
+        .await
+        .map_err(|err| format!("Checksum task failed: {err}"))??;
+    if actual != expected_md5 {
+        return Err(format!(
+            "{CHECKSUM_MISMATCH}: {} has MD5 {actual}, ETag says {expected_md5}",
+            path.display()
+        ));
     }
+    Ok(())
+}
 
-    Ok(all_objects)
+pub(crate) fn download_attributes_from_settings(state: &AppState) -> DownloadAttributes {
+    let settings = current_settings(state);
+    DownloadAttributes {
+        preserve_mtime: settings.download_preserve_mtime,
+        restore_mode: settings.download_restore_mode,
+        version_id: None,
+        verify_checksum: false,
+        bandwidth: None,
+        pause_flag: None,
+    }
 }
 
-pub(crate) async fn s3_upload_file(
+// Permission bits from a `mode` metadata value: decimal st_mode as s3fs
+// writes it, or octal with a leading 0 / 0o. Setuid, setgid and sticky bits
+// are dropped; a downloaded file never gets them.
+pub(crate) fn parse_mode_metadata(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let mode = if let Some(octal) = value.strip_prefix("0o") {
+        u32::from_str_radix(octal, 8).ok()?
+    } else if value.len() > 1 && value.starts_with('0') {
+        u32::from_str_radix(&value[1..], 8).ok()?
+    } else {
+        value.parse::<u32>().ok()?
+    };
+    Some(mode & 0o777).filter(|mode| *mode != 0)
+}
+
+// Tars and gzips `files` (relative to `local_dir`) on the fly into a single
+// object. Compressed output is shipped as multipart parts as soon as a full
+// part is buffered, so memory stays bounded regardless of folder size;
+// archives smaller than one part go up as a plain PUT. Progress counts
+// uncompressed bytes written into the archive.
+pub(crate) async fn s3_upload_directory_tar_gz(
     client: &S3Client,
     bucket: &str,
     key: &str,
-    local_path: &Path,
+    local_dir: &Path,
+    files: &[LocalFileInfo],
     cancel_flag: &AtomicBool,
     mut on_progress: impl FnMut(i64, i64),
 ) -> Result<i64, String> {
     if cancel_flag.load(Ordering::SeqCst) {
         return Err(JOB_CANCELLED.to_string());
     }
-
-    let total = fs::metadata(local_path)
-        .map(|meta| meta.len() as i64)
-        .unwrap_or(0)
-        .max(0);
-
-    if total <= MULTIPART_THRESHOLD_BYTES {
-        let body = ByteStream::from_path(local_path.to_path_buf())
-            .await
-            .map_err(|err| format!("Failed to stream {}: {err}", local_path.display()))?;
-
-        client
-            .put_object()
-            .bucket(bucket.to_string())
-            .key(key.to_string())
-            .body(body)
-            .send()
-            .await
-            .map_err(|err| err.to_string())?;
-
-        on_progress(total, total);
-        return Ok(total);
+    if files.is_empty() {
+        return Err("Folder has no files to archive".to_string());
     }
 
-    let multipart = client
-        .create_multipart_upload()
-        .bucket(bucket.to_string())
-        .key(key.to_string())
-        .send()
-        .await
-        .map_err(|err| err.to_string())?;
-    let upload_id = multipart
-        .upload_id()
-        .map(str::to_string)
-        .ok_or_else(|| "Missing multipart upload id".to_string())?;
-
-    let mut file = tokio_fs::File::open(local_path)
-        .await
-        .map_err(|err| format!("Failed to open {}: {err}", local_path.display()))?;
-    let mut transferred: i64 = 0;
-    let mut part_number: i32 = 1;
-    let mut parts: Vec<CompletedPart> = Vec::new();
-
-    let upload_result: Result<(), String> = async {
-        loop {
+    const TAR_BLOCK_SIZE: usize = 512;
+    const TAR_END_BLOCKS: [u8; TAR_BLOCK_SIZE * 2] = [0; TAR_BLOCK_SIZE * 2];
+    const TAR_PAD_BLOCK: [u8; TAR_BLOCK_SIZE] = [0; TAR_BLOCK_SIZE];
+    const READ_CHUNK_BYTES: usize = 256 * 1024;
+
+    let total: i64 = files.iter().map(|file| file.size.max(0)).sum();
+    let mut multipart = StreamingMultipart {
+        upload_id: None,
+        parts: Vec::new(),
+    };
+    let mut encoder = GzEncoder::new(Vec::<u8>::new(), Compression::default());
+    let mut written: i64 = 0;
+    on_progress(0, total);
+
+    let result: Result<(), String> = async {
+        let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+        for file in files {
             if cancel_flag.load(Ordering::SeqCst) {
                 return Err(JOB_CANCELLED.to_string());
             }
+            let Some(relative) = sanitize_relative_path(&file.relative_path) else {
+                continue;
+            };
+            let path = local_dir.join(&relative);
+            let mut handle = tokio_fs::File::open(&path)
+                .await
+                .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+            let size = handle
+                .metadata()
+                .await
+                .map_err(|err| format!("Failed to stat {}: {err}", path.display()))?
+                .len();
 
-            let mut buffer = vec![0u8; MULTIPART_PART_SIZE_BYTES];
-            let mut read_total: usize = 0;
-            while read_total < buffer.len() {
-                let read = file
-                    .read(&mut buffer[read_total..])
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_path(&relative).map_err(|err| {
+                format!("Invalid archive entry path {}: {err}", relative.display())
+            })?;
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_mtime((file.mtime_ms / 1000).max(0) as u64);
+            header.set_cksum();
+            encoder.write_all(header.as_bytes()).map_err(|err| {
+                format!(
+                    "Failed writing tar header for {}: {err}",
+                    relative.display()
+                )
+            })?;
+
+            let mut remaining = size;
+            while remaining > 0 {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    return Err(JOB_CANCELLED.to_string());
+                }
+                let want = remaining.min(chunk.len() as u64) as usize;
+                let read = handle
+                    .read(&mut chunk[..want])
                     .await
-                    .map_err(|err| format!("Failed reading {}: {err}", local_path.display()))?;
+                    .map_err(|err| format!("Failed reading {}: {err}", path.display()))?;
                 if read == 0 {
-                    break;
+                    return Err(format!(
+                        "{} shrank while it was being archived",
+                        path.display()
+                    ));
+                }
+                encoder.write_all(&chunk[..read]).map_err(|err| {
+                    format!("Failed writing tar data for {}: {err}", relative.display())
+                })?;
+                remaining -= read as u64;
+                written += read as i64;
+                on_progress(written, total.max(written));
+
+                while encoder.get_ref().len() >= MULTIPART_PART_SIZE_BYTES {
+                    let rest = encoder.get_mut().split_off(MULTIPART_PART_SIZE_BYTES);
+                    let part = std::mem::replace(encoder.get_mut(), rest);
+                    s3_upload_streaming_part(client, bucket, key, &mut multipart, part).await?;
                 }
-                read_total += read;
             }
 
-            if read_total == 0 {
-                break;
+            let padding =
+                (TAR_BLOCK_SIZE - (size % TAR_BLOCK_SIZE as u64) as usize) % TAR_BLOCK_SIZE;
+            if padding > 0 {
+                encoder
+                    .write_all(&TAR_PAD_BLOCK[..padding])
+                    .map_err(|err| {
+                        format!(
+                            "Failed writing tar padding for {}: {err}",
+                            relative.display()
+                        )
+                    })?;
             }
-            buffer.truncate(read_total);
+        }
 
-            let output = client
-                .upload_part()
+        encoder
+            .write_all(&TAR_END_BLOCKS)
+            .map_err(|err| format!("Failed finalizing tar payload: {err}"))?;
+        let tail = std::mem::replace(
+            &mut encoder,
+            GzEncoder::new(Vec::new(), Compression::default()),
+        )
+        .finish()
+        .map_err(|err| format!("Failed finalizing gzip stream: {err}"))?;
+
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err(JOB_CANCELLED.to_string());
+        }
+
+        if multipart.upload_id.is_none() {
+            client
+                .put_object()
                 .bucket(bucket.to_string())
                 .key(key.to_string())
-                .upload_id(upload_id.clone())
-                .part_number(part_number)
-                .body(ByteStream::from(buffer))
+                .content_type("application/gzip")
+                .body(ByteStream::from(tail))
                 .send()
                 .await
                 .map_err(|err| err.to_string())?;
-
-            let completed_part = CompletedPart::builder()
-                .set_e_tag(output.e_tag().map(str::to_string))
-                .part_number(part_number)
-                .build();
-            parts.push(completed_part);
-
-            transferred += read_total as i64;
-            on_progress(transferred, total);
-            part_number += 1;
+            return Ok(());
         }
 
-        if parts.is_empty() {
-            return Err("Multipart upload produced no parts".to_string());
+        if !tail.is_empty() {
+            s3_upload_streaming_part(client, bucket, key, &mut multipart, tail).await?;
         }
-
         let completed_upload = CompletedMultipartUpload::builder()
-            .set_parts(Some(parts))
+            .set_parts(Some(std::mem::take(&mut multipart.parts)))
             .build();
-
         client
             .complete_multipart_upload()
             .bucket(bucket.to_string())
             .key(key.to_string())
-            .upload_id(upload_id.clone())
+            .upload_id(multipart.upload_id.clone().unwrap_or_default())
             .multipart_upload(completed_upload)
             .send()
             .await
             .map_err(|err| err.to_string())?;
-
         Ok(())
     }
     .await;
 
-    if let Err(err) = upload_result {
-        let _ = client
-            .abort_multipart_upload()
-            .bucket(bucket.to_string())
-            .key(key.to_string())
-            .upload_id(upload_id)
-            .send()
-            .await;
+    if let Err(err) = result {
+        if let Some(upload_id) = multipart.upload_id {
+            let _ = client
+                .abort_multipart_upload()
+                .bucket(bucket.to_string())
+                .key(key.to_string())
+                .upload_id(upload_id)
+                .send()
+                .await;
+        }
         return Err(err);
     }
 
-    on_progress(total, total);
-    Ok(total)
+    on_progress(written, written);
+    Ok(written)
 }
 
-pub(crate) async fn s3_download_file(
+async fn s3_upload_streaming_part(
     client: &S3Client,
     bucket: &str,
     key: &str,
-    local_path: &Path,
-    cancel_flag: &AtomicBool,
-    mut on_progress: impl FnMut(i64, i64),
-) -> Result<i64, String> {
-    if cancel_flag.load(Ordering::SeqCst) {
-        return Err(JOB_CANCELLED.to_string());
-    }
-
-    if let Some(parent) = local_path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
-    }
+    multipart: &mut StreamingMultipart,
+    body: Vec<u8>,
+) -> Result<(), String> {
+    let upload_id = match &multipart.upload_id {
+        Some(upload_id) => upload_id.clone(),
+        None => {
+            let created = client
+                .create_multipart_upload()
+                .bucket(bucket.to_string())
+                .key(key.to_string())
+                .content_type("application/gzip")
+                .send()
+                .await
+                .map_err(|err| err.to_string())?;
+            let upload_id = created
+                .upload_id()
+                .map(str::to_string)
+                .ok_or_else(|| "Missing multipart upload id".to_string())?;
+            multipart.upload_id = Some(upload_id.clone());
+            upload_id
+        }
+    };
 
+    let part_number = multipart.parts.len() as i32 + 1;
     let output = client
-        .get_object()
+        .upload_part()
         .bucket(bucket.to_string())
         .key(key.to_string())
+        .upload_id(upload_id)
+        .part_number(part_number)
+        .body(ByteStream::from(body))
         .send()
         .await
         .map_err(|err| err.to_string())?;
-    let total = output.content_length().unwrap_or(0).max(0);
+    multipart.parts.push(
+        CompletedPart::builder()
+            .set_e_tag(output.e_tag().map(str::to_string))
+            .part_number(part_number)
+            .build(),
+    );
+    Ok(())
+}
 
-    let file = tokio_fs::File::create(local_path)
-        .await
-        .map_err(|err| format!("Failed to create {}: {err}", local_path.display()))?;
-    let mut writer = BufWriter::new(file);
-    let mut body = output.body;
-    let mut transferred: i64 = 0;
+impl ArchiveFormat {
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::TarGz => "tar.gz",
+            Self::Zip => "zip",
+        }
+    }
+}
 
-    while let Some(bytes) = body
-        .try_next()
-        .await
-        .map_err(|err| format!("Download stream failed: {err}"))?
+pub(crate) fn parse_archive_format(format: Option<&str>) -> Result<ArchiveFormat, String> {
+    match format
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
     {
-        if cancel_flag.load(Ordering::SeqCst) {
-            let _ = tokio_fs::remove_file(local_path).await;
-            return Err(JOB_CANCELLED.to_string());
-        }
+        None | Some("") | Some("tar.gz") => Ok(ArchiveFormat::TarGz),
+        Some("zip") => Ok(ArchiveFormat::Zip),
+        Some(other) => Err(format!("Unsupported archive format: {other}")),
+    }
+}
 
-        writer
-            .write_all(&bytes)
-            .await
-            .map_err(|err| format!("Failed writing {}: {err}", local_path.display()))?;
+// Adds the format's extension when the name has none; a name ending in the
+// other format's extension is rejected rather than producing `a.zip.tar.gz`.
+pub(crate) fn archive_file_name(name: &str, format: ArchiveFormat) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Archive name cannot be empty".to_string());
+    }
+    let lower = name.to_ascii_lowercase();
+    let extension = format.extension();
+    let accepted: &[&str] = match format {
+        ArchiveFormat::TarGz => &[".tar.gz", ".tgz"],
+        ArchiveFormat::Zip => &[".zip"],
+    };
+    if accepted.iter().any(|accepted| lower.ends_with(accepted)) {
+        return Ok(name.to_string());
+    }
+    if [".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .any(|other| lower.ends_with(other))
+    {
+        return Err(format!("{name} is not a .{extension} file name"));
+    }
+    Ok(format!("{name}.{extension}"))
+}
 
-        transferred += bytes.len() as i64;
-        on_progress(transferred, total);
+// Entry paths inside the archive always use `/`, whatever the platform.
+pub(crate) fn archive_entry_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl ArchiveWriter {
+    const TAR_BLOCK_SIZE: usize = 512;
+
+    fn create(path: &Path, format: ArchiveFormat) -> Result<Self, String> {
+        let archive_file = fs::File::create(path)
+            .map_err(|err| format!("Failed to create archive {}: {err}", path.display()))?;
+        let writer = io::BufWriter::new(archive_file);
+        Ok(match format {
+            ArchiveFormat::TarGz => Self::TarGz(GzEncoder::new(writer, Compression::default())),
+            ArchiveFormat::Zip => Self::Zip(zip::ZipWriter::new(writer)),
+        })
     }
 
-    writer
-        .flush()
-        .await
-        .map_err(|err| format!("Failed flushing {}: {err}", local_path.display()))?;
+    fn start_entry(&mut self, path: &Path, size: i64) -> Result<(), String> {
+        match self {
+            Self::TarGz(encoder) => {
+                let mut header = tar::Header::new_gnu();
+                header.set_entry_type(tar::EntryType::Regular);
+                header.set_path(path).map_err(|err| {
+                    format!("Invalid archive entry path {}: {err}", path.display())
+                })?;
+                header.set_size(size as u64);
+                header.set_mode(0o644);
+                header.set_mtime(0);
+                header.set_cksum();
+                encoder.write_all(header.as_bytes()).map_err(|err| {
+                    format!("Failed writing tar header for {}: {err}", path.display())
+                })
+            }
+            Self::Zip(writer) => {
+                let entry_name = archive_entry_name(path);
+                let compressed = guess_content_type(&entry_name)
+                    .is_some_and(|content_type| ZIP_STORED_CONTENT_TYPES.contains(&content_type));
+                let method = if size < ZIP_STORED_MAX_BYTES || compressed {
+                    zip::CompressionMethod::Stored
+                } else {
+                    zip::CompressionMethod::Deflated
+                };
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(method)
+                    .large_file(size >= u32::MAX as i64)
+                    .unix_permissions(0o644);
+                writer.start_file(entry_name, options).map_err(|err| {
+                    format!("Failed writing zip entry for {}: {err}", path.display())
+                })
+            }
+        }
+    }
 
-    Ok(transferred.max(total))
+    fn write_data(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match self {
+            Self::TarGz(encoder) => encoder.write_all(bytes),
+            Self::Zip(writer) => writer.write_all(bytes),
+        }
+    }
+
+    // Tar pads each entry out to a whole block; zip needs nothing.
+    fn finish_entry(&mut self, size: i64) -> io::Result<()> {
+        let Self::TarGz(encoder) = self else {
+            return Ok(());
+        };
+        let block = Self::TAR_BLOCK_SIZE as i64;
+        let padding = (block - size % block) % block;
+        encoder.write_all(&[0; Self::TAR_BLOCK_SIZE][..padding as usize])
+    }
+
+    fn finish(self) -> Result<(), String> {
+        let mut writer = match self {
+            Self::TarGz(mut encoder) => {
+                encoder
+                    .write_all(&[0; Self::TAR_BLOCK_SIZE * 2])
+                    .map_err(|err| format!("Failed finalizing tar payload: {err}"))?;
+                encoder
+                    .finish()
+                    .map_err(|err| format!("Failed finalizing gzip stream: {err}"))?
+            }
+            Self::Zip(writer) => writer
+                .finish()
+                .map_err(|err| format!("Failed finalizing zip archive: {err}"))?,
+        };
+        writer
+            .flush()
+            .map_err(|err| format!("Failed flushing archive: {err}"))
+    }
 }
 
-pub(crate) async fn s3_download_archive_tar_gz(
+pub(crate) async fn s3_download_archive(
     client: &S3Client,
     bucket: &str,
-    keys: &[String],
-    common_prefix: &str,
-    destination_path: &Path,
+    request: ArchiveRequest<'_>,
     cancel_flag: &AtomicBool,
     mut on_progress: impl FnMut(i64, i64),
+    // (entry path, files completed, files total): when each entry starts and
+    // finishes. Sizes are only known per object, so this is the steady count.
+    mut on_file: impl FnMut(&str, usize, usize),
 ) -> Result<i64, String> {
+    let ArchiveRequest {
+        keys,
+        common_prefix,
+        destination_path,
+        format,
+    } = request;
     if cancel_flag.load(Ordering::SeqCst) {
         return Err(JOB_CANCELLED.to_string());
     }
@@ -268,40 +1760,41 @@ pub(crate) async fn s3_download_archive_tar_gz(
             .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
     }
 
+    // Build the archive beside the destination and only rename it into place
+    // once complete, so a cancelled or failed job never leaves a truncated
+    // archive under the name the user picked.
+    let partial_path = PathBuf::from(format!("{}.object0-partial", destination_path.display()));
+
     let result: Result<i64, String> = async {
-        let archive_file = fs::File::create(destination_path).map_err(|err| {
-            format!(
-                "Failed to create archive {}: {err}",
-                destination_path.display()
-            )
-        })?;
-        let writer = io::BufWriter::new(archive_file);
-        let mut encoder = GzEncoder::new(writer, Compression::default());
+        let mut archive = ArchiveWriter::create(&partial_path, format)?;
 
         let mut transferred: i64 = 0;
         let mut total: i64 = 0;
-        const TAR_BLOCK_SIZE: usize = 512;
-        const TAR_END_BLOCKS: [u8; TAR_BLOCK_SIZE * 2] = [0; TAR_BLOCK_SIZE * 2];
-        const TAR_PAD_BLOCK: [u8; TAR_BLOCK_SIZE] = [0; TAR_BLOCK_SIZE];
+
+        // The prefix's own folder marker has no entry of its own; leave it out
+        // of the file count so the count still reaches its total.
+        let entries: Vec<(&String, String)> = keys
+            .iter()
+            .filter_map(|key| {
+                let relative = if !common_prefix.is_empty() && key.starts_with(common_prefix) {
+                    key[common_prefix.len()..].to_string()
+                } else {
+                    key.clone()
+                };
+                (!relative.is_empty()).then_some((key, relative))
+            })
+            .collect();
 
         on_progress(0, 0);
 
-        for key in keys {
+        for (index, (key, relative)) in entries.iter().enumerate() {
             if cancel_flag.load(Ordering::SeqCst) {
                 return Err(JOB_CANCELLED.to_string());
             }
 
-            let relative = if !common_prefix.is_empty() && key.starts_with(common_prefix) {
-                key[common_prefix.len()..].to_string()
-            } else {
-                key.clone()
-            };
-            if relative.is_empty() {
-                continue;
-            }
-
             let safe_relative = sanitize_relative_path(&relative)
                 .ok_or_else(|| format!("Invalid object key for archive entry: {key}"))?;
+            on_file(relative, index, entries.len());
 
             let output = client
                 .get_object()
@@ -309,7 +1802,9 @@ pub(crate) async fn s3_download_archive_tar_gz(
                 .key(key.to_string())
                 .send()
                 .await
-                .map_err(|err| err.to_string())?;
+                .map_err(|err| {
+                    s3_error(err, &format!("download {}", format_s3_uri(bucket, key)))
+                })?;
 
             let expected_size = if let Some(size) = output.content_length() {
                 size.max(0)
@@ -320,31 +1815,13 @@ pub(crate) async fn s3_download_archive_tar_gz(
                     .key(key.to_string())
                     .send()
                     .await
-                    .map_err(|err| err.to_string())?
+                    .map_err(|err| s3_error(err, &format!("read {}", format_s3_uri(bucket, key))))?
                     .content_length()
                     .unwrap_or(0)
                     .max(0)
             };
 
-            let mut header = tar::Header::new_gnu();
-            header.set_entry_type(tar::EntryType::Regular);
-            header.set_path(&safe_relative).map_err(|err| {
-                format!(
-                    "Invalid archive entry path {}: {err}",
-                    safe_relative.display()
-                )
-            })?;
-            header.set_size(expected_size as u64);
-            header.set_mode(0o644);
-            header.set_mtime(0);
-            header.set_cksum();
-
-            encoder.write_all(header.as_bytes()).map_err(|err| {
-                format!(
-                    "Failed writing tar header for {}: {err}",
-                    safe_relative.display()
-                )
-            })?;
+            archive.start_entry(&safe_relative, expected_size)?;
 
             let mut body = output.body;
             let mut file_transferred: i64 = 0;
@@ -358,9 +1835,9 @@ pub(crate) async fn s3_download_archive_tar_gz(
                     return Err(JOB_CANCELLED.to_string());
                 }
 
-                encoder.write_all(&bytes).map_err(|err| {
+                archive.write_data(&bytes).map_err(|err| {
                     format!(
-                        "Failed writing tar data for {}: {err}",
+                        "Failed writing archive data for {}: {err}",
                         safe_relative.display()
                     )
                 })?;
@@ -373,59 +1850,63 @@ pub(crate) async fn s3_download_archive_tar_gz(
             if file_transferred != expected_size {
                 return Err(format!(
                     "Unexpected size for {} (expected {}, downloaded {})",
-                    safe_relative.display(),
-                    expected_size,
-                    file_transferred
-                ));
-            }
-
-            let padding = (TAR_BLOCK_SIZE as i64 - (file_transferred % TAR_BLOCK_SIZE as i64))
-                % TAR_BLOCK_SIZE as i64;
-            if padding > 0 {
-                encoder
-                    .write_all(&TAR_PAD_BLOCK[..padding as usize])
-                    .map_err(|err| {
-                        format!(
-                            "Failed writing tar padding for {}: {err}",
-                            safe_relative.display()
-                        )
-                    })?;
+                    safe_relative.display(),
+                    expected_size,
+                    file_transferred
+                ));
             }
 
+            archive.finish_entry(file_transferred).map_err(|err| {
+                format!(
+                    "Failed writing archive padding for {}: {err}",
+                    safe_relative.display()
+                )
+            })?;
+
             transferred += file_transferred;
             total += expected_size;
             on_progress(transferred, total);
+            on_file(relative, index + 1, entries.len());
         }
 
-        encoder
-            .write_all(&TAR_END_BLOCKS)
-            .map_err(|err| format!("Failed finalizing tar payload: {err}"))?;
-        encoder
-            .finish()
-            .map_err(|err| format!("Failed finalizing gzip stream: {err}"))?;
+        archive.finish()?;
 
         if cancel_flag.load(Ordering::SeqCst) {
             return Err(JOB_CANCELLED.to_string());
         }
 
+        fs::rename(&partial_path, destination_path).map_err(|err| {
+            format!(
+                "Failed to move archive into place at {}: {err}",
+                destination_path.display()
+            )
+        })?;
+
         Ok(transferred.max(total))
     }
     .await;
 
     if result.is_err() {
-        let _ = fs::remove_file(destination_path);
+        let _ = fs::remove_file(&partial_path);
     }
 
     result
 }
 
+// `bucket/key` for CopyObject, with the key encoded once. Keys are used as
+// stored, so one that already looks encoded (`a%20b`) gets its `%` escaped.
+pub(crate) fn copy_source_header(bucket: &str, key: &str) -> String {
+    format!(
+        "{bucket}/{}",
+        utf8_percent_encode(key, COPY_SOURCE_ENCODE_SET)
+    )
+}
+
 pub(crate) async fn s3_copy_object_via_temp_file(
     source_client: &S3Client,
     source_bucket: &str,
     source_key: &str,
-    dest_client: &S3Client,
-    dest_bucket: &str,
-    dest_key: &str,
+    dest: CopyDestination<'_>,
     cancel_flag: &AtomicBool,
     mut on_progress: impl FnMut(i64, i64),
 ) -> Result<i64, String> {
@@ -439,9 +1920,15 @@ pub(crate) async fn s3_copy_object_via_temp_file(
         .key(source_key.to_string())
         .send()
         .await
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("read {}", format_s3_uri(source_bucket, source_key)),
+            )
+        })?;
     let size = head.content_length().unwrap_or(0).max(0);
 
+    ensure_disk_space(&std::env::temp_dir(), size)?;
     let temp_path = std::env::temp_dir().join(format!("object0-copy-{}", Uuid::new_v4()));
 
     let result = async {
@@ -450,6 +1937,7 @@ pub(crate) async fn s3_copy_object_via_temp_file(
             source_bucket,
             source_key,
             &temp_path,
+            DownloadAttributes::default(),
             cancel_flag,
             |transferred, _| on_progress((transferred / 2).min(size), size),
         )
@@ -460,10 +1948,11 @@ pub(crate) async fn s3_copy_object_via_temp_file(
         }
 
         s3_upload_file(
-            dest_client,
-            dest_bucket,
-            dest_key,
+            dest.client,
+            dest.bucket,
+            dest.key,
             &temp_path,
+            dest.attributes,
             cancel_flag,
             |transferred, _| on_progress((size / 2 + transferred / 2).min(size), size),
         )
@@ -498,25 +1987,225 @@ pub(crate) async fn s3_copy_object(
         .key(source_key.to_string())
         .send()
         .await
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("read {}", format_s3_uri(source_bucket, source_key)),
+            )
+        })?;
     let size = head.content_length().unwrap_or(0).max(0);
 
-    let source_key_encoded = utf8_percent_encode(source_key, COPY_SOURCE_ENCODE_SET);
-    let copy_source = format!("{}/{}", source_bucket, source_key_encoded);
+    // CopyObject tops out at 5 GiB and gives no progress, so objects above
+    // that go part by part, as do large copies into another bucket. Within a
+    // bucket a single CopyObject keeps the tags.
+    let cross_bucket = source_bucket != dest_bucket && size > MULTIPART_THRESHOLD_BYTES;
+    if size > COPY_OBJECT_MAX_BYTES || cross_bucket {
+        s3_multipart_copy(
+            MultipartCopySource {
+                bucket: source_bucket,
+                key: source_key,
+                head: &head,
+            },
+            dest_client,
+            dest_bucket,
+            dest_key,
+            cancel_flag,
+            &mut on_progress,
+        )
+        .await?;
+        on_progress(size, size);
+        return Ok(size);
+    }
 
     dest_client
         .copy_object()
         .bucket(dest_bucket.to_string())
         .key(dest_key.to_string())
-        .copy_source(copy_source)
+        .copy_source(copy_source_header(source_bucket, source_key))
         .send()
         .await
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("copy to {}", format_s3_uri(dest_bucket, dest_key)),
+            )
+        })?;
 
     on_progress(size, size);
     Ok(size)
 }
 
+// Server-side UploadPartCopy in MULTIPART_PART_SIZE_BYTES ranges (larger when
+// the object would need more than MULTIPART_MAX_PARTS). UploadPartCopy doesn't
+// carry headers over, so they, the encryption and the tags are copied from
+// the source, and every part is pinned to its ETag so a concurrent overwrite
+// can't mix versions.
+async fn s3_multipart_copy(
+    source: MultipartCopySource<'_>,
+    dest_client: &S3Client,
+    dest_bucket: &str,
+    dest_key: &str,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut impl FnMut(i64, i64),
+) -> Result<(), String> {
+    let head = source.head;
+    let size = head.content_length().unwrap_or(0).max(0);
+    let min_part_size = (size + MULTIPART_MAX_PARTS - 1) / MULTIPART_MAX_PARTS;
+    let part_size = (MULTIPART_PART_SIZE_BYTES as i64).max(min_part_size);
+    let dest_uri = format_s3_uri(dest_bucket, dest_key);
+    let tagging = if head.tag_count().unwrap_or(0) > 0 {
+        let mut tags: Vec<(String, String)> =
+            s3_get_object_tags(dest_client, source.bucket, source.key)
+                .await?
+                .into_iter()
+                .collect();
+        tags.sort();
+        let encode = |value: &str| utf8_percent_encode(value, COPY_SOURCE_ENCODE_SET).to_string();
+        let pairs: Vec<String> = tags
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+            .collect();
+        Some(pairs.join("&"))
+    } else {
+        None
+    };
+    let source = copy_source_header(source.bucket, source.key);
+
+    let multipart = dest_client
+        .create_multipart_upload()
+        .bucket(dest_bucket.to_string())
+        .key(dest_key.to_string())
+        .set_content_type(head.content_type().map(str::to_string))
+        .set_cache_control(head.cache_control().map(str::to_string))
+        .set_content_disposition(head.content_disposition().map(str::to_string))
+        .set_content_encoding(head.content_encoding().map(str::to_string))
+        .set_content_language(head.content_language().map(str::to_string))
+        .set_metadata(head.metadata().cloned())
+        .set_server_side_encryption(head.server_side_encryption().cloned())
+        .set_ssekms_key_id(head.ssekms_key_id().map(str::to_string))
+        .set_bucket_key_enabled(head.bucket_key_enabled())
+        .set_tagging(tagging)
+        .send()
+        .await
+        .map_err(|err| s3_error(err, &format!("copy to {dest_uri}")))?;
+    let upload_id = multipart
+        .upload_id()
+        .map(str::to_string)
+        .ok_or_else(|| "Missing multipart upload id".to_string())?;
+
+    let result: Result<(), String> = async {
+        let mut parts = Vec::new();
+        let mut offset: i64 = 0;
+        let mut part_number = 1;
+        while offset < size {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return Err(JOB_CANCELLED.to_string());
+            }
+            let end = (offset + part_size).min(size) - 1;
+            let output = dest_client
+                .upload_part_copy()
+                .bucket(dest_bucket.to_string())
+                .key(dest_key.to_string())
+                .upload_id(upload_id.clone())
+                .part_number(part_number)
+                .copy_source(source.clone())
+                .copy_source_range(format!("bytes={offset}-{end}"))
+                .set_copy_source_if_match(head.e_tag().map(str::to_string))
+                .send()
+                .await
+                .map_err(|err| s3_error(err, &format!("copy part {part_number} to {dest_uri}")))?;
+            parts.push(
+                CompletedPart::builder()
+                    .set_e_tag(
+                        output
+                            .copy_part_result()
+                            .and_then(|result| result.e_tag())
+                            .map(str::to_string),
+                    )
+                    .part_number(part_number)
+                    .build(),
+            );
+            offset = end + 1;
+            part_number += 1;
+            on_progress(offset, size);
+        }
+
+        dest_client
+            .complete_multipart_upload()
+            .bucket(dest_bucket.to_string())
+            .key(dest_key.to_string())
+            .upload_id(upload_id.clone())
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|err| s3_error(err, &format!("copy to {dest_uri}")))?;
+        Ok(())
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = dest_client
+            .abort_multipart_upload()
+            .bucket(dest_bucket.to_string())
+            .key(dest_key.to_string())
+            .upload_id(upload_id)
+            .send()
+            .await;
+    }
+    result
+}
+
+// Same-bucket move: a metadata-preserving server-side copy, confirmed with a
+// HEAD of the destination before the source is deleted. No temp-file
+// fallback; that path would drop the object's metadata.
+pub(crate) async fn s3_move_object_within_bucket(
+    client: &S3Client,
+    bucket: &str,
+    source_key: &str,
+    dest_key: &str,
+    cancel_flag: &AtomicBool,
+    on_progress: impl FnMut(i64, i64),
+) -> Result<i64, String> {
+    if source_key == dest_key {
+        return Err("Source and destination are the same object".to_string());
+    }
+
+    let size = s3_copy_object(
+        client,
+        bucket,
+        source_key,
+        client,
+        bucket,
+        dest_key,
+        cancel_flag,
+        on_progress,
+    )
+    .await?;
+    verify_copied_size(client, bucket, dest_key, size).await?;
+
+    if cancel_flag.load(Ordering::SeqCst) {
+        return Err(JOB_CANCELLED.to_string());
+    }
+    s3_delete_keys(client, bucket, &[source_key.to_string()]).await?;
+    Ok(size)
+}
+
+// Moves only delete the source once the copy is confirmed to be in place.
+pub(crate) async fn verify_copied_size(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    expected: i64,
+) -> Result<(), String> {
+    verify_uploaded_size(client, bucket, key, expected)
+        .await
+        .map_err(|err| format!("{err}; source kept"))
+}
+
 pub(crate) async fn s3_delete_keys(client: &S3Client, bucket: &str, keys: &[String]) -> Result<(), String> {
     if keys.is_empty() {
         return Ok(());
@@ -529,10 +2218,32 @@ pub(crate) async fn s3_delete_keys(client: &S3Client, bucket: &str, keys: &[Stri
             .key(keys[0].clone())
             .send()
             .await
-            .map_err(|err| err.to_string())?;
+            .map_err(|err| s3_error(err, &format!("delete {}", format_s3_uri(bucket, &keys[0]))))?;
         return Ok(());
     }
 
+    let mut failures = Vec::new();
+    for chunk in keys.chunks(S3_DELETE_MAX_KEYS) {
+        failures.extend(s3_delete_objects_batch(client, bucket, chunk).await?);
+    }
+    if let Some((_, message)) = failures.first() {
+        return Err(format!(
+            "{message} ({} of {} deletes failed)",
+            failures.len(),
+            keys.len()
+        ));
+    }
+
+    Ok(())
+}
+
+// One DeleteObjects request. It answers 200 even when individual keys fail,
+// so those come back as (key, message) pairs rather than an error.
+pub(crate) async fn s3_delete_objects_batch(
+    client: &S3Client,
+    bucket: &str,
+    keys: &[String],
+) -> Result<Vec<(String, String)>, String> {
     let mut objects = Vec::with_capacity(keys.len());
     for key in keys {
         let object = ObjectIdentifier::builder()
@@ -547,18 +2258,182 @@ pub(crate) async fn s3_delete_keys(client: &S3Client, bucket: &str, keys: &[Stri
         .build()
         .map_err(|err| format!("Invalid delete payload: {err}"))?;
 
-    client
+    let output = client
         .delete_objects()
         .bucket(bucket.to_string())
         .delete(delete)
         .send()
         .await
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!(
+                    "delete {} objects from {}",
+                    keys.len(),
+                    format_s3_uri(bucket, "")
+                ),
+            )
+        })?;
+
+    Ok(output
+        .errors()
+        .iter()
+        .map(|failed| {
+            let key = failed.key().unwrap_or_default().to_string();
+            let kind = classify_s3_error(failed.code(), None);
+            let detail = failed.message().unwrap_or("unknown error").to_string();
+            let message = s3_error_message(
+                kind,
+                &format!("delete {}", format_s3_uri(bucket, &key)),
+                detail,
+            );
+            (key, message)
+        })
+        .collect())
+}
+
+// Deletes everything under `prefix` one listing page at a time (checking for
+// cancellation between batches), so the key list never grows past a page.
+// Progress reports (deleted, listed so far). Keys that fail don't stop the
+// rest; they're reported together at the end.
+pub(crate) async fn s3_delete_prefix<F>(
+    client: &S3Client,
+    bucket: &str,
+    prefix: &str,
+    cancel_flag: &AtomicBool,
+    mut on_progress: F,
+) -> Result<i64, String>
+where
+    F: FnMut(i64, i64),
+{
+    let mut pager = S3ObjectPager::new(client, bucket, prefix);
+    let mut listed = 0i64;
+    let mut deleted = 0i64;
+    let mut failures = Vec::new();
+    loop {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err(JOB_CANCELLED.to_string());
+        }
+        let Some(page) = pager.next_page().await? else {
+            break;
+        };
+        let keys: Vec<String> = page
+            .into_iter()
+            .map(|object| object.key)
+            .filter(|key| !key.is_empty())
+            .collect();
+        listed += keys.len() as i64;
+        on_progress(deleted, listed);
+
+        for chunk in keys.chunks(S3_DELETE_MAX_KEYS) {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return Err(JOB_CANCELLED.to_string());
+            }
+            let batch_failures = s3_delete_objects_batch(client, bucket, chunk)
+                .await
+                .map_err(|err| format!("{err} (after {deleted} deleted)"))?;
+            failures.extend(batch_failures);
+            deleted += chunk.len() as i64;
+            on_progress(deleted, listed);
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(summarize_delete_failures(&failures, listed as usize));
+    }
+    Ok(deleted)
+}
+
+// Deletes in DeleteObjects-sized batches, reporting (deleted, total) after
+// each one. Per-key failures don't stop later batches; they're summarized at
+// the end. A request-level error on any batch does stop the run.
+pub(crate) async fn s3_delete_keys_batched<F>(
+    client: &S3Client,
+    bucket: &str,
+    keys: &[String],
+    cancel_flag: &AtomicBool,
+    mut on_progress: F,
+) -> Result<i64, String>
+where
+    F: FnMut(i64, i64),
+{
+    let total = keys.len() as i64;
+    let mut deleted = 0i64;
+    let mut failures = Vec::new();
+    on_progress(0, total);
+    for chunk in keys.chunks(S3_DELETE_MAX_KEYS) {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err(JOB_CANCELLED.to_string());
+        }
+        let batch_failures = s3_delete_objects_batch(client, bucket, chunk)
+            .await
+            .map_err(|err| format!("{err} (after {deleted} of {total} deleted)"))?;
+        failures.extend(batch_failures);
+        deleted += chunk.len() as i64;
+        on_progress(deleted, total);
+    }
+
+    if !failures.is_empty() {
+        return Err(summarize_delete_failures(&failures, keys.len()));
+    }
+    Ok(total)
+}
+
+pub(crate) fn summarize_delete_failures(failures: &[(String, String)], total: usize) -> String {
+    let mut summary = format!("{} of {total} deletes failed: ", failures.len());
+    let shown: Vec<&str> = failures
+        .iter()
+        .take(DELETE_FAILURES_REPORTED)
+        .map(|(_, message)| message.as_str())
+        .collect();
+    summary.push_str(&shown.join("; "));
+    if failures.len() > DELETE_FAILURES_REPORTED {
+        summary.push_str(&format!(
+            " (+{} more)",
+            failures.len() - DELETE_FAILURES_REPORTED
+        ));
+    }
+    summary
+}
+
+// Acceleration and dual-stack pick an AWS endpoint, so they don't combine
+// with other providers or an explicit endpoint URL.
+pub(crate) fn validate_endpoint_options(
+    provider: &S3Provider,
+    endpoint: Option<&str>,
+    use_accelerate: bool,
+    use_dualstack: bool,
+) -> Result<(), String> {
+    if !use_accelerate && !use_dualstack {
+        return Ok(());
+    }
+    if *provider != S3Provider::Aws {
+        return Err(
+            "Transfer Acceleration and dual-stack endpoints are only available for Amazon S3"
+                .to_string(),
+        );
+    }
+    if endpoint.is_some_and(|endpoint| !endpoint.trim().is_empty()) {
+        return Err(
+            "Transfer Acceleration and dual-stack endpoints can't be combined with a custom endpoint"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
 
+// The two flags pull the SDK's checksum setting opposite ways.
+pub(crate) fn validate_checksum_options(require: bool, disable: bool) -> Result<(), String> {
+    if require && disable {
+        return Err("A profile can't both require and disable checksums".to_string());
+    }
     Ok(())
 }
 
 pub(crate) fn to_s3_client(profile: &Profile) -> Result<S3Client, String> {
+    if profile_needs_credentials(profile) {
+        return Err(needs_credentials_error(profile));
+    }
     if profile.access_key_id.trim().is_empty() || profile.secret_access_key.trim().is_empty() {
         return Err("Profile credentials are missing".to_string());
     }
@@ -568,7 +2443,7 @@ pub(crate) fn to_s3_client(profile: &Profile) -> Result<S3Client, String> {
         .as_deref()
         .map(str::trim)
         .filter(|value| !value.is_empty())
-        .unwrap_or("us-east-1");
+        .unwrap_or(DEFAULT_AWS_REGION);
 
     let credentials = Credentials::new(
         profile.access_key_id.clone(),
@@ -596,9 +2471,375 @@ pub(crate) fn to_s3_client(profile: &Profile) -> Result<S3Client, String> {
         config_builder = config_builder.force_path_style(true);
     }
 
+    if profile.requester_pays {
+        config_builder = config_builder.interceptor(RequesterPaysInterceptor);
+    }
+
+    if profile.use_accelerate {
+        config_builder = config_builder.accelerate(true);
+    }
+    if profile.use_dualstack {
+        config_builder = config_builder.use_dual_stack(true);
+    }
+
+    // Neither flag leaves the SDK's own default in place.
+    if profile.require_checksums {
+        config_builder =
+            config_builder.request_checksum_calculation(RequestChecksumCalculation::WhenSupported);
+    } else if profile.disable_checksums {
+        config_builder =
+            config_builder.request_checksum_calculation(RequestChecksumCalculation::WhenRequired);
+    }
+
     Ok(S3Client::from_conf(config_builder.build()))
 }
 
+// The same client with checksums attached to every upload, for retrying a
+// request the provider rejected with `ChecksumRequired`.
+pub(crate) fn with_request_checksums(client: &S3Client) -> S3Client {
+    S3Client::from_conf(
+        client
+            .config()
+            .to_builder()
+            .request_checksum_calculation(RequestChecksumCalculation::WhenSupported)
+            .build(),
+    )
+}
+
+// Adds the requester-pays header to every request (list, get, head, put,
+// copy), so profiles pointed at requester-pays buckets work without
+// threading the flag through each call site.
+#[derive(Debug)]
+struct RequesterPaysInterceptor;
+
+impl Intercept for RequesterPaysInterceptor {
+    fn name(&self) -> &'static str {
+        "RequesterPaysInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        context
+            .request_mut()
+            .headers_mut()
+            .insert("x-amz-request-payer", "requester");
+        Ok(())
+    }
+}
+
+// The first `max_bytes` of an object via a ranged GET, with the full size.
+pub(crate) async fn s3_read_object_head(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    max_bytes: i64,
+    requester_pays: bool,
+) -> Result<ObjectHead, String> {
+    let output = match client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .range(format!("bytes=0-{}", max_bytes - 1))
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        // S3 answers a range request on an empty object with 416.
+        Err(err) if err.code() == Some("InvalidRange") => {
+            return Ok(ObjectHead {
+                bytes: Vec::new(),
+                size: 0,
+                content_type: "application/octet-stream".to_string(),
+                gzip_encoded: false,
+            });
+        }
+        Err(err) => return Err(s3_error_with_requester_pays_hint(err, requester_pays)),
+    };
+
+    // Ranged responses report the slice; the full size is after the `/`.
+    let size = output
+        .content_range()
+        .and_then(|range| range.rsplit('/').next())
+        .and_then(|total| total.parse::<i64>().ok())
+        .or(output.content_length())
+        .unwrap_or(0)
+        .max(0);
+    let content_type = output
+        .content_type()
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    let gzip_encoded = output
+        .content_encoding()
+        .map(|encoding| encoding.eq_ignore_ascii_case("gzip"))
+        .unwrap_or(false);
+    let bytes = output
+        .body
+        .collect()
+        .await
+        .map_err(|err| format!("Failed reading {}: {err}", format_s3_uri(bucket, key)))?
+        .into_bytes()
+        .to_vec();
+    Ok(ObjectHead {
+        bytes,
+        size,
+        content_type,
+        gzip_encoded,
+    })
+}
+
+// Streams `[start, start + length)` of an object to `on_chunk` in pieces of
+// `chunk_bytes` (the last may be shorter), so memory stays bounded however
+// large the range. Returns (bytes sent, full object size).
+pub(crate) async fn s3_stream_object_range(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    range: StreamRange,
+    requester_pays: bool,
+    mut on_chunk: impl FnMut(i64, &[u8]),
+) -> Result<(i64, i64), String> {
+    let StreamRange {
+        start,
+        length,
+        chunk_bytes,
+    } = range;
+    let output = match client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .range(format!("bytes={start}-{}", start + length - 1))
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        // Starting at or past the end: nothing left to send.
+        Err(err) if err.code() == Some("InvalidRange") => {
+            let size = s3_head_if_exists(client, bucket, key)
+                .await?
+                .map(|object| object.size)
+                .unwrap_or(0);
+            return Ok((0, size));
+        }
+        Err(err) => return Err(s3_error_with_requester_pays_hint(err, requester_pays)),
+    };
+    let size = output
+        .content_range()
+        .and_then(|range| range.rsplit('/').next())
+        .and_then(|total| total.parse::<i64>().ok())
+        .or(output.content_length())
+        .unwrap_or(0)
+        .max(0);
+
+    let mut body = output.body;
+    let mut buffer = Vec::with_capacity(chunk_bytes);
+    let mut offset = start;
+    while let Some(bytes) = body
+        .try_next()
+        .await
+        .map_err(|err| format!("Failed reading {}: {err}", format_s3_uri(bucket, key)))?
+    {
+        push_stream_chunks(&mut buffer, &bytes, chunk_bytes, |chunk| {
+            on_chunk(offset, chunk);
+            offset += chunk.len() as i64;
+        });
+    }
+    if !buffer.is_empty() {
+        on_chunk(offset, &buffer);
+        offset += buffer.len() as i64;
+    }
+    Ok((offset - start, size))
+}
+
+// Appends `bytes` to `buffer`, handing off every full `chunk_bytes` piece.
+pub(crate) fn push_stream_chunks(
+    buffer: &mut Vec<u8>,
+    mut bytes: &[u8],
+    chunk_bytes: usize,
+    mut emit: impl FnMut(&[u8]),
+) {
+    while !bytes.is_empty() {
+        let take = (chunk_bytes - buffer.len()).min(bytes.len());
+        buffer.extend_from_slice(&bytes[..take]);
+        bytes = &bytes[take..];
+        if buffer.len() == chunk_bytes {
+            emit(buffer);
+            buffer.clear();
+        }
+    }
+}
+
+// Requester-pays buckets reject unflagged requests with a bare 403, which
+// reads like a credentials problem; point at the profile setting instead.
+pub(crate) fn s3_error_with_requester_pays_hint<E>(
+    err: SdkError<E, HttpResponse>,
+    requester_pays: bool,
+) -> String
+where
+    E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+{
+    let denied = err
+        .raw_response()
+        .map(|response| response.status().as_u16())
+        == Some(403)
+        || err.code() == Some("AccessDenied");
+    let message = err.to_string();
+    if denied && !requester_pays {
+        format!(
+            "{message}. If this is a requester-pays bucket, enable \"Requester pays\" on the profile."
+        )
+    } else {
+        message
+    }
+}
+
+pub(crate) fn already_exists_error(bucket: &str, key: &str) -> String {
+    format!("{JOB_ALREADY_EXISTS}: {bucket}/{key} already exists")
+}
+
+// Providers known to honour `If-None-Match: *` on PutObject and
+// CompleteMultipartUpload. Others get the HEAD pre-check only.
+pub(crate) fn supports_conditional_writes(provider: &S3Provider) -> bool {
+    matches!(provider, S3Provider::Aws | S3Provider::R2)
+}
+
+// Only AWS routes by region; S3-compatible providers mostly ignore it, so
+// anything is accepted there. A warning, not an error: new regions launch
+// faster than this list is updated.
+pub(crate) fn region_warning(provider: &S3Provider, region: Option<&str>) -> Option<String> {
+    if *provider != S3Provider::Aws {
+        return None;
+    }
+    match region.map(str::trim).filter(|value| !value.is_empty()) {
+        None => Some(format!(
+            "No region set; requests will be sent to {DEFAULT_AWS_REGION}"
+        )),
+        Some(region) if AWS_REGIONS.contains(&region) => None,
+        Some(region) => Some(format!(
+            "\"{region}\" is not a known AWS region; requests may be misrouted"
+        )),
+    }
+}
+
+pub(crate) fn s3_upload_error<E>(err: SdkError<E, HttpResponse>, bucket: &str, key: &str) -> String
+where
+    E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+{
+    if err
+        .raw_response()
+        .map(|response| response.status().as_u16())
+        == Some(412)
+    {
+        already_exists_error(bucket, key)
+    } else {
+        s3_error(err, &format!("upload {}", format_s3_uri(bucket, key)))
+    }
+}
+
+// Maps the S3 failures users can act on to a kind; codes win over statuses
+// because HEAD responses carry no error body.
+pub(crate) fn classify_s3_error(code: Option<&str>, status: Option<u16>) -> S3ErrorKind {
+    match code {
+        Some("AccessDenied" | "AllAccessDisabled") => S3ErrorKind::AccessDenied,
+        Some("NoSuchKey" | "NotFound") => S3ErrorKind::NoSuchKey,
+        Some("NoSuchBucket") => S3ErrorKind::NoSuchBucket,
+        Some("SlowDown" | "Throttling" | "TooManyRequests") => S3ErrorKind::SlowDown,
+        Some("InvalidObjectState") => S3ErrorKind::InvalidObjectState,
+        Some("NotImplemented" | "MethodNotAllowed") => S3ErrorKind::Unsupported,
+        Some("AccessControlListNotSupported") => S3ErrorKind::AclsDisabled,
+        _ => match status {
+            Some(403) => S3ErrorKind::AccessDenied,
+            Some(404) => S3ErrorKind::NoSuchKey,
+            Some(405 | 501) => S3ErrorKind::Unsupported,
+            Some(429 | 503) => S3ErrorKind::SlowDown,
+            _ => S3ErrorKind::Other,
+        },
+    }
+}
+
+// AWS answers a missing checksum with a generic InvalidRequest, so the
+// message is what identifies it.
+pub(crate) fn is_checksum_required_error(code: Option<&str>, message: Option<&str>) -> bool {
+    match code {
+        Some("MissingContentMD5" | "MissingChecksum") => true,
+        Some("InvalidRequest") => message.is_some_and(|message| {
+            let message = message.to_ascii_lowercase();
+            (message.contains("content-md5") || message.contains("x-amz-checksum"))
+                && message.contains("required")
+        }),
+        _ => false,
+    }
+}
+
+// "S3 Transfer Acceleration is not configured on this bucket", sent as
+// InvalidRequest.
+pub(crate) fn is_accelerate_not_configured_error(
+    code: Option<&str>,
+    message: Option<&str>,
+) -> bool {
+    code == Some("InvalidRequest")
+        && message.is_some_and(|message| {
+            message
+                .to_ascii_lowercase()
+                .contains("transfer acceleration is not configured")
+        })
+}
+
+// `action` completes "trying to ..."; messages start with the S3 code so the
+// UI can branch on it. Unclassified errors keep the SDK's own text.
+pub(crate) fn s3_error_message(kind: S3ErrorKind, action: &str, detail: String) -> String {
+    match kind {
+        S3ErrorKind::AccessDenied => format!(
+            "AccessDenied: permission denied trying to {action}. Check the profile's credentials and the bucket policy."
+        ),
+        S3ErrorKind::NoSuchKey => format!(
+            "NoSuchKey: the object doesn't exist (trying to {action}). It may have been moved or deleted."
+        ),
+        S3ErrorKind::NoSuchBucket => format!(
+            "NoSuchBucket: the bucket doesn't exist (trying to {action}). Check the bucket name and the profile's endpoint/region."
+        ),
+        S3ErrorKind::SlowDown => format!(
+            "SlowDown: the provider is throttling requests (trying to {action}). Wait a moment or lower job concurrency."
+        ),
+        S3ErrorKind::InvalidObjectState => format!(
+            "InvalidObjectState: the object is in Glacier (trying to {action}); initiate a restore first and try again once it completes."
+        ),
+        S3ErrorKind::Unsupported => format!(
+            "Unsupported: this provider doesn't support the operation (trying to {action})."
+        ),
+        S3ErrorKind::ChecksumRequired => format!(
+            "{CHECKSUM_REQUIRED}: the provider requires a checksum (trying to {action}). Turn on \"Require checksums\" for this profile."
+        ),
+        S3ErrorKind::AccelerateNotConfigured => format!(
+            "{ACCELERATE_NOT_CONFIGURED}: Transfer Acceleration isn't enabled on this bucket (trying to {action}). Enable it on the bucket or turn it off for this profile."
+        ),
+        S3ErrorKind::AclsDisabled => format!(
+            "AccessControlListNotSupported: this bucket has ACLs disabled (Object Ownership is \"bucket owner enforced\"), so trying to {action} failed. Use the bucket policy instead."
+        ),
+        S3ErrorKind::Other => detail,
+    }
+}
+
+pub(crate) fn s3_error<E>(err: SdkError<E, HttpResponse>, action: &str) -> String
+where
+    E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+{
+    let status = err
+        .raw_response()
+        .map(|response| response.status().as_u16());
+    let kind = if is_checksum_required_error(err.code(), err.message()) {
+        S3ErrorKind::ChecksumRequired
+    } else if is_accelerate_not_configured_error(err.code(), err.message()) {
+        S3ErrorKind::AccelerateNotConfigured
+    } else {
+        classify_s3_error(err.code(), status)
+    };
+    s3_error_message(kind, action, err.to_string())
+}
+
 pub(crate) fn s3_datetime_to_iso(dt: &aws_sdk_s3::primitives::DateTime) -> String {
     dt.to_millis()
         .ok()
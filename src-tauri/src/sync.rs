@@ -1,47 +1,63 @@
 //! Bucket-to-bucket sync: object-map building and diff generate/execute.
 
 use super::*;
+use sha2::{Digest, Sha256};
 
-pub(crate) fn build_sync_object_map(
-    objects: Vec<RemoteObject>,
-    prefix: &str,
-) -> HashMap<String, SyncObjectInfo> {
-    let mut map = HashMap::new();
-    let normalized_prefix = normalize_prefix(prefix);
-
-    for RemoteObject {
+// The object's key relative to `normalized_prefix`, skipping the prefix itself
+// and folder markers.
+pub(crate) fn sync_object_entry(
+    object: RemoteObject,
+    normalized_prefix: &str,
+) -> Option<(String, SyncObjectInfo)> {
+    let RemoteObject {
         key,
         size,
         etag,
         last_modified,
-    } in objects
-    {
-        let relative = if normalized_prefix.is_empty() {
-            key.clone()
-        } else if key.starts_with(&normalized_prefix) {
-            key[normalized_prefix.len()..].to_string()
-        } else {
-            continue;
-        };
+        storage_class,
+    } = object;
+    let relative = key.strip_prefix(normalized_prefix)?;
+    if relative.is_empty() || relative.ends_with('/') {
+        return None;
+    }
+    Some((
+        relative.to_string(),
+        SyncObjectInfo {
+            size: size.max(0),
+            etag,
+            last_modified,
+            storage_class,
+        },
+    ))
+}
 
-        if relative.is_empty() {
-            continue;
-        }
-        if relative.ends_with('/') {
-            continue;
-        }
+// Only the destination side is held as a map; the source listing is streamed
+// through a SyncDiffFold page by page.
+async fn fold_sync_diff(
+    source: (&S3Client, &str, &str),
+    dest: (&S3Client, &str, &str),
+    mode: SyncMode,
+    archived_objects: ArchivedObjectPolicy,
+) -> Result<SyncDiffRecord, String> {
+    let (source_client, source_bucket, source_prefix) = source;
+    let (dest_client, dest_bucket, dest_prefix) = dest;
 
-        map.insert(
-            relative,
-            SyncObjectInfo {
-                size: size.max(0),
-                etag,
-                last_modified,
-            },
-        );
-    }
+    let mut dest_map = HashMap::new();
+    s3_for_each_object(dest_client, dest_bucket, dest_prefix, |object| {
+        if let Some((key, info)) = sync_object_entry(object, dest_prefix) {
+            dest_map.insert(key, info);
+        }
+    })
+    .await?;
 
-    map
+    let mut fold = SyncDiffFold::new(dest_map, mode, archived_objects);
+    s3_for_each_object(source_client, source_bucket, source_prefix, |object| {
+        if let Some((key, info)) = sync_object_entry(object, source_prefix) {
+            fold.push(key, &info);
+        }
+    })
+    .await?;
+    Ok(fold.finish())
 }
 
 pub(crate) async fn generate_sync_diff(state: &AppState, input: &SyncInput) -> Result<SyncDiffRecord, String> {
@@ -53,43 +69,66 @@ pub(crate) async fn generate_sync_diff(state: &AppState, input: &SyncInput) -> R
     let source_prefix = normalize_prefix(&input.source_prefix);
     let dest_prefix = normalize_prefix(&input.dest_prefix);
 
-    let source_objects =
-        s3_list_all_objects(&source_client, &input.source_bucket, &source_prefix).await?;
-    let dest_objects = s3_list_all_objects(&dest_client, &input.dest_bucket, &dest_prefix).await?;
-
-    let source_map = build_sync_object_map(source_objects, &input.source_prefix);
-    let dest_map = build_sync_object_map(dest_objects, &input.dest_prefix);
-
-    let mut to_add = Vec::new();
-    let mut to_update = Vec::new();
-    let mut to_delete = Vec::new();
-    let mut unchanged = 0i64;
+    fold_sync_diff(
+        (&source_client, &input.source_bucket, &source_prefix),
+        (&dest_client, &input.dest_bucket, &dest_prefix),
+        input.mode,
+        input.archived_objects,
+    )
+    .await
+}
 
-    let mut keys: Vec<String> = source_map.keys().cloned().collect();
-    keys.sort();
+impl SyncDiffFold {
+    pub(crate) fn new(
+        dest_map: HashMap<String, SyncObjectInfo>,
+        mode: SyncMode,
+        archived_objects: ArchivedObjectPolicy,
+    ) -> Self {
+        Self {
+            dest_map,
+            mode,
+            archived_objects,
+            to_add: Vec::new(),
+            to_update: Vec::new(),
+            skipped_archived: Vec::new(),
+            unchanged: 0,
+        }
+    }
 
-    for key in keys {
-        let Some(src) = source_map.get(&key) else {
-            continue;
-        };
-        if let Some(dest) = dest_map.get(&key) {
+    pub(crate) fn push(&mut self, key: String, src: &SyncObjectInfo) {
+        // Copying out of GLACIER/DEEP_ARCHIVE fails without a restore.
+        let skip = self.archived_objects == ArchivedObjectPolicy::Skip
+            && is_archived_storage_class(src.storage_class.as_deref());
+        if let Some(dest) = self.dest_map.remove(&key) {
             if src.etag != dest.etag || src.size != dest.size {
-                to_update.push(SyncDiffEntryRecord {
-                    key: key.clone(),
+                let list = if skip {
+                    &mut self.skipped_archived
+                } else {
+                    &mut self.to_update
+                };
+                list.push(SyncDiffEntryRecord {
+                    id: sync_diff_entry_id(&key),
+                    key,
                     source_size: Some(src.size),
                     dest_size: Some(dest.size),
                     source_etag: Some(src.etag.clone()),
-                    dest_etag: Some(dest.etag.clone()),
+                    dest_etag: Some(dest.etag),
                     source_last_modified: Some(src.last_modified.clone()),
-                    dest_last_modified: Some(dest.last_modified.clone()),
+                    dest_last_modified: Some(dest.last_modified),
                     selected: true,
                 });
             } else {
-                unchanged += 1;
+                self.unchanged += 1;
             }
-        } else {
-            to_add.push(SyncDiffEntryRecord {
-                key: key.clone(),
+        } else if self.mode != SyncMode::Overwrite {
+            let list = if skip {
+                &mut self.skipped_archived
+            } else {
+                &mut self.to_add
+            };
+            list.push(SyncDiffEntryRecord {
+                id: sync_diff_entry_id(&key),
+                key,
                 source_size: Some(src.size),
                 dest_size: None,
                 source_etag: Some(src.etag.clone()),
@@ -101,46 +140,90 @@ pub(crate) async fn generate_sync_diff(state: &AppState, input: &SyncInput) -> R
         }
     }
 
-    if input.mode == SyncMode::Mirror {
-        let mut dest_only: Vec<String> = dest_map
-            .keys()
-            .filter(|key| !source_map.contains_key(*key))
-            .cloned()
-            .collect();
-        dest_only.sort();
-
-        for key in dest_only {
-            let Some(dest) = dest_map.get(&key) else {
-                continue;
-            };
-            to_delete.push(SyncDiffEntryRecord {
-                key: key.clone(),
-                source_size: None,
-                dest_size: Some(dest.size),
-                source_etag: None,
-                dest_etag: Some(dest.etag.clone()),
-                source_last_modified: None,
-                dest_last_modified: Some(dest.last_modified.clone()),
-                selected: true,
-            });
-        }
+    // Overwrite only reports updates; only Mirror reports destination-only
+    // keys as deletes.
+    pub(crate) fn finish(self) -> SyncDiffRecord {
+        let to_delete = if self.mode == SyncMode::Mirror {
+            self.dest_map
+                .into_iter()
+                .map(|(key, dest)| SyncDiffEntryRecord {
+                    id: sync_diff_entry_id(&key),
+                    key,
+                    source_size: None,
+                    dest_size: Some(dest.size),
+                    source_etag: None,
+                    dest_etag: Some(dest.etag),
+                    source_last_modified: None,
+                    dest_last_modified: Some(dest.last_modified),
+                    selected: true,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut record =
+            build_sync_diff_record(self.to_add, self.to_update, to_delete, self.unchanged);
+        record.skipped_archived = self.skipped_archived;
+        record.skipped_archived.sort_by(|a, b| a.key.cmp(&b.key));
+        record
     }
+}
 
-    if input.mode == SyncMode::Overwrite {
-        return Ok(SyncDiffRecord {
-            to_add: Vec::new(),
-            to_update,
-            to_delete: Vec::new(),
-            unchanged,
-        });
+// objects:diff-prefixes: a read-only Mirror diff of two prefixes in one
+// bucket, with `compare` as the source and `base` as the destination.
+pub(crate) async fn diff_bucket_prefixes(
+    client: &S3Client,
+    bucket: &str,
+    base_prefix: &str,
+    compare_prefix: &str,
+) -> Result<SyncDiffRecord, String> {
+    let base_prefix = normalize_prefix(base_prefix);
+    let compare_prefix = normalize_prefix(compare_prefix);
+    if base_prefix == compare_prefix {
+        return Err("Pick two different prefixes to compare".to_string());
     }
 
-    Ok(SyncDiffRecord {
+    fold_sync_diff(
+        (client, bucket, &compare_prefix),
+        (client, bucket, &base_prefix),
+        SyncMode::Mirror,
+        ArchivedObjectPolicy::Attempt,
+    )
+    .await
+}
+
+// Stable across refreshes so the UI can keep `selected` state per entry.
+pub(crate) fn sync_diff_entry_id(key: &str) -> String {
+    let digest = Sha256::digest(key.as_bytes());
+    digest[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Sorts every list by key and fills in the summary totals.
+pub(crate) fn build_sync_diff_record(
+    mut to_add: Vec<SyncDiffEntryRecord>,
+    mut to_update: Vec<SyncDiffEntryRecord>,
+    mut to_delete: Vec<SyncDiffEntryRecord>,
+    unchanged: i64,
+) -> SyncDiffRecord {
+    for list in [&mut to_add, &mut to_update, &mut to_delete] {
+        list.sort_by(|a, b| a.key.cmp(&b.key));
+    }
+    let to_add_bytes = to_add.iter().filter_map(|entry| entry.source_size).sum();
+    let to_update_bytes = to_update.iter().filter_map(|entry| entry.source_size).sum();
+    let to_delete_count = to_delete.len();
+    SyncDiffRecord {
         to_add,
         to_update,
         to_delete,
+        skipped_archived: Vec::new(),
         unchanged,
-    })
+        to_add_bytes,
+        to_update_bytes,
+        to_delete_count,
+    }
 }
 
 pub(crate) fn execute_sync_diff(
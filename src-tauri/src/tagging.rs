@@ -0,0 +1,99 @@
+//! Object tags: `objects:get-tags` / `objects:set-tags`. Writing replaces the
+//! whole tag set in one request, and an empty set deletes it. Providers
+//! without tagging (some MinIO releases) surface as `S3ErrorKind::Unsupported`.
+
+use super::*;
+use aws_sdk_s3::types::{Tag, Tagging};
+
+pub(crate) async fn s3_get_object_tags(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+) -> Result<HashMap<String, String>, String> {
+    let output = client
+        .get_object_tagging()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            s3_error(
+                err,
+                &format!("read the tags of {}", format_s3_uri(bucket, key)),
+            )
+        })?;
+    Ok(output
+        .tag_set()
+        .iter()
+        .map(|tag| (tag.key().to_string(), tag.value().to_string()))
+        .collect())
+}
+
+pub(crate) async fn s3_set_object_tags(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    tags: &HashMap<String, String>,
+) -> Result<(), String> {
+    validate_object_tags(tags)?;
+    let s3_uri = format_s3_uri(bucket, key);
+    if tags.is_empty() {
+        client
+            .delete_object_tagging()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| s3_error(err, &format!("clear the tags of {s3_uri}")))?;
+        return Ok(());
+    }
+    let mut tag_set = Vec::with_capacity(tags.len());
+    for (tag_key, value) in tags {
+        tag_set.push(
+            Tag::builder()
+                .key(tag_key)
+                .value(value)
+                .build()
+                .map_err(|err| format!("Invalid tag {tag_key}: {err}"))?,
+        );
+    }
+    let tagging = Tagging::builder()
+        .set_tag_set(Some(tag_set))
+        .build()
+        .map_err(|err| format!("Invalid tag set: {err}"))?;
+    client
+        .put_object_tagging()
+        .bucket(bucket)
+        .key(key)
+        .tagging(tagging)
+        .send()
+        .await
+        .map_err(|err| s3_error(err, &format!("update the tags of {s3_uri}")))?;
+    Ok(())
+}
+
+// Mirrors S3's own limits so a bad set fails before any request is sent.
+pub(crate) fn validate_object_tags(tags: &HashMap<String, String>) -> Result<(), String> {
+    if tags.len() > MAX_OBJECT_TAGS {
+        return Err(format!(
+            "An object can have at most {MAX_OBJECT_TAGS} tags ({} given)",
+            tags.len()
+        ));
+    }
+    for (key, value) in tags {
+        if key.trim().is_empty() {
+            return Err("Tag keys can't be empty".to_string());
+        }
+        if key.chars().count() > MAX_OBJECT_TAG_KEY_CHARS {
+            return Err(format!(
+                "Tag key \"{key}\" is longer than {MAX_OBJECT_TAG_KEY_CHARS} characters"
+            ));
+        }
+        if value.chars().count() > MAX_OBJECT_TAG_VALUE_CHARS {
+            return Err(format!(
+                "Value of tag \"{key}\" is longer than {MAX_OBJECT_TAG_VALUE_CHARS} characters"
+            ));
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! System tray menu construction and tray-event handling.
 
 use super::*;
+use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 
 pub(crate) fn show_main_window(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
@@ -11,20 +12,40 @@ pub(crate) fn show_main_window(app: &AppHandle) {
 }
 
 pub(crate) fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, String> {
-    let (syncing, watching, paused, errors) = folder_sync_status_counts(app);
-    let any_active = syncing > 0 || watching > 0;
+    let (syncing, watching, paused, errors, dry_run) = folder_sync_status_counts(app);
+    let any_active = syncing > 0 || watching > 0 || dry_run > 0;
 
     let mut status = "No active sync rules".to_string();
     if syncing > 0 {
         status = format!("Syncing {syncing} rule(s)...");
     } else if watching > 0 {
         status = format!("Watching {watching} rule(s)");
+    } else if dry_run > 0 {
+        status = format!("Dry run: {dry_run} rule(s), no changes made");
     } else if paused > 0 {
         status = format!("Paused ({paused} rule(s))");
     }
+    if !any_active && paused == 0 && folder_sync_waiting_for_vault(app) {
+        status = "Vault locked • unlock to start sync".to_string();
+    }
+    if dry_run > 0 && (syncing > 0 || watching > 0) {
+        status = format!("{status} • {dry_run} in dry run");
+    }
     if errors > 0 {
         status = format!("{status} • {errors} error(s)");
     }
+    let (metered, paused_for_metered) = {
+        let state = app.state::<AppState>();
+        let value = lock_state(&state.network)
+            .map(|network| (network.metered, network.sync_paused_for_metered))
+            .unwrap_or((None, false));
+        value
+    };
+    if paused_for_metered {
+        status = format!("{status} • paused on metered network");
+    } else if metered == Some(true) {
+        status = format!("{status} • metered network");
+    }
 
     let status_item = MenuItem::with_id(app, "tray-status", status, false, None::<&str>)
         .map_err(|err| format!("Failed to build tray status item: {err}"))?;
@@ -49,11 +70,28 @@ pub(crate) fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, Strin
         )
         .map_err(|err| format!("Failed to build tray resume item: {err}"))?
     };
+    let abort_item = MenuItem::with_id(
+        app,
+        TRAY_MENU_ABORT_ALL,
+        "Abort All Activity…",
+        true,
+        None::<&str>,
+    )
+    .map_err(|err| format!("Failed to build tray abort item: {err}"))?;
     let quit_item = MenuItem::with_id(app, TRAY_MENU_QUIT, "Quit", true, None::<&str>)
         .map_err(|err| format!("Failed to build tray quit item: {err}"))?;
 
-    Menu::with_items(app, &[&status_item, &open_item, &action_item, &quit_item])
-        .map_err(|err| format!("Failed to build tray menu: {err}"))
+    Menu::with_items(
+        app,
+        &[
+            &status_item,
+            &open_item,
+            &action_item,
+            &abort_item,
+            &quit_item,
+        ],
+    )
+    .map_err(|err| format!("Failed to build tray menu: {err}"))
 }
 
 pub(crate) fn refresh_tray_menu(app: &AppHandle) {
@@ -62,7 +100,7 @@ pub(crate) fn refresh_tray_menu(app: &AppHandle) {
             let _ = tray.set_menu(Some(menu));
         }
 
-        let (syncing, _, _, _) = folder_sync_status_counts(app);
+        let (syncing, _, _, _, _) = folder_sync_status_counts(app);
         let title = if syncing > 0 {
             "object0 • syncing"
         } else {
@@ -77,9 +115,29 @@ pub(crate) fn handle_tray_menu_action(app: &AppHandle, action_id: &str) {
         TRAY_MENU_OPEN => show_main_window(app),
         TRAY_MENU_PAUSE_ALL => pause_all_folder_sync_rules(app),
         TRAY_MENU_RESUME_ALL => resume_all_folder_sync_rules(app),
+        TRAY_MENU_ABORT_ALL => {
+            // The confirmation blocks, so keep it off the event loop.
+            let app = app.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let confirmed = MessageDialog::new()
+                    .set_level(MessageLevel::Warning)
+                    .set_title("Abort all activity?")
+                    .set_description(
+                        "Cancels every running and queued transfer and stops all folder sync \
+                         rules. Partially uploaded files are discarded.",
+                    )
+                    .set_buttons(MessageButtons::OkCancel)
+                    .show();
+                if matches!(confirmed, MessageDialogResult::Ok) {
+                    abort_all_activity(&app);
+                }
+            });
+            return;
+        }
         TRAY_MENU_QUIT => {
             let state = app.state::<AppState>();
             state.is_quitting.store(true, Ordering::SeqCst);
+            cancel_jobs_for_shutdown(app);
             stop_all_folder_sync_rules(app);
             app.exit(0);
             return;
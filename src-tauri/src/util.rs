@@ -2,6 +2,7 @@
 //! path normalization/sanitization, and glob matching.
 
 use super::*;
+use md5::{Digest, Md5};
 
 pub(crate) fn expand_user_path(path: &str) -> PathBuf {
     if let Some(stripped) = path.strip_prefix("~/") {
@@ -12,6 +13,18 @@ pub(crate) fn expand_user_path(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+// Symlinks are resolved when the path exists, so two spellings of the same
+// folder compare equal.
+pub(crate) fn resolve_local_root(path: &str) -> PathBuf {
+    let expanded = expand_user_path(path);
+    fs::canonicalize(&expanded).unwrap_or(expanded)
+}
+
+// One root equals or contains the other (component-wise, not by string).
+pub(crate) fn local_roots_overlap(a: &Path, b: &Path) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
 pub(crate) fn sanitize_relative_path(relative_path: &str) -> Option<PathBuf> {
     // Reject backslashes outright. On non-Windows builds `\` is NOT a path
     // separator, so a key like `..\..\secret` would slip past the component
@@ -33,6 +46,237 @@ pub(crate) fn sanitize_relative_path(relative_path: &str) -> Option<PathBuf> {
     }
 }
 
+// `relative_path` under `root`, as long as the folder holding it resolves to
+// somewhere inside `root`. A scan that follows symlinks can list paths whose
+// folder lives elsewhere; local deletes and writes refuse those.
+pub(crate) fn contained_local_path(root: &Path, relative_path: &Path) -> Result<PathBuf, String> {
+    let path = root.join(relative_path);
+    let canonical_root = fs::canonicalize(root)
+        .map_err(|err| format!("Failed to resolve {}: {err}", root.display()))?;
+    // Folders that don't exist yet can't be links; the nearest one that does
+    // decides.
+    let mut folder = path.parent();
+    while let Some(dir) = folder {
+        if let Ok(canonical) = fs::canonicalize(dir) {
+            if canonical.starts_with(&canonical_root) {
+                return Ok(path);
+            }
+            break;
+        }
+        folder = dir.parent();
+    }
+    Err(format!(
+        "{} resolves outside the sync folder",
+        path.display()
+    ))
+}
+
+// The key of a folder's zero-byte marker: `prefix` without a leading slash,
+// ending in exactly one. `.`/`..` segments and backslashes are rejected.
+pub(crate) fn folder_marker_key(prefix: &str) -> Result<String, String> {
+    let trimmed = prefix.trim().trim_start_matches('/').trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err("Folder name must not be empty".to_string());
+    }
+    if sanitize_relative_path(trimmed).is_none() {
+        return Err(format!("Invalid folder path \"{prefix}\""));
+    }
+    Ok(format!("{trimmed}/"))
+}
+
+// Name templates use `{token}` placeholders: `{name}` is the file stem,
+// `{ext}` the extension including its dot (or empty), `{timestamp}` a UTC
+// `YYYYMMDD-HHMMSS` stamp and `{host}` this machine's hostname.
+pub(crate) fn validate_name_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Name template must not be empty".to_string());
+    }
+    if template.contains('/') || template.contains('\\') {
+        return Err("Name template must not contain path separators".to_string());
+    }
+    if !template.contains("{name}") {
+        return Err("Name template must include {name}".to_string());
+    }
+
+    check_template_tokens(template, &NAME_TEMPLATE_TOKENS, "name template")
+}
+
+fn check_template_tokens(template: &str, allowed: &[&str], label: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("Unclosed token in {label}: {template}"))?;
+        let token = &after[..end];
+        if !allowed.contains(&token) {
+            return Err(format!("Unknown {label} token: {{{token}}}"));
+        }
+        rest = &after[end + 1..];
+    }
+    Ok(())
+}
+
+pub(crate) fn expand_name_template(
+    template: &str,
+    file_name: &str,
+    timestamp: &str,
+    host: &str,
+) -> String {
+    let path = Path::new(file_name);
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_name.to_string());
+    let ext = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+
+    template
+        .replace("{name}", &name)
+        .replace("{ext}", &ext)
+        .replace("{timestamp}", timestamp)
+        .replace("{host}", host)
+}
+
+// Probes by flipping the case of the root's own name; roots without letters
+// fall back to the platform default.
+pub(crate) fn local_fs_is_case_insensitive(root: &Path) -> bool {
+    let platform_default = cfg!(any(target_os = "macos", target_os = "windows"));
+    let Some(name) = root.file_name().and_then(|name| name.to_str()) else {
+        return platform_default;
+    };
+    let flipped: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_lowercase() {
+                ch.to_uppercase().collect::<String>()
+            } else {
+                ch.to_lowercase().collect::<String>()
+            }
+        })
+        .collect();
+    if flipped == name || !root.exists() {
+        return platform_default;
+    }
+    root.with_file_name(flipped).exists()
+}
+
+// Stable per key, so the same copy is found again on the next pass.
+pub(crate) fn case_collision_copy_name(relative_path: &str) -> String {
+    let (dir, file_name) = match relative_path.rsplit_once('/') {
+        Some((dir, file_name)) => (Some(dir), file_name),
+        None => (None, relative_path),
+    };
+    let id = sync_diff_entry_id(relative_path);
+    let renamed = expand_name_template(
+        &format!("{{name}} (case {}){{ext}}", &id[..8]),
+        file_name,
+        "",
+        "",
+    );
+    match dir {
+        Some(dir) => format!("{dir}/{renamed}"),
+        None => renamed,
+    }
+}
+
+pub(crate) fn render_name_template(template: &str, file_name: &str) -> String {
+    let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+    expand_name_template(template, file_name, &timestamp, &local_hostname())
+}
+
+pub(crate) fn validate_key_transform(transform: &KeyTransform) -> Result<(), String> {
+    check_template_tokens(&transform.prefix, &KEY_TRANSFORM_TOKENS, "key prefix")?;
+    check_template_tokens(&transform.suffix, &KEY_TRANSFORM_TOKENS, "key suffix")?;
+    if transform.prefix.starts_with('/') {
+        return Err("Key prefix must not start with /".to_string());
+    }
+    if transform.suffix.contains('/') {
+        return Err("Key suffix must not contain /".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn key_transform_is_empty(transform: &KeyTransform) -> bool {
+    transform.prefix.is_empty() && transform.suffix.is_empty()
+}
+
+pub(crate) fn expand_key_transform(
+    transform: &KeyTransform,
+    host: &str,
+    date: &str,
+) -> ResolvedKeyTransform {
+    let expand = |template: &str| template.replace("{host}", host).replace("{date}", date);
+    ResolvedKeyTransform {
+        prefix: expand(&transform.prefix),
+        suffix: expand(&transform.suffix),
+    }
+}
+
+pub(crate) fn resolve_key_transform(transform: &KeyTransform) -> ResolvedKeyTransform {
+    let date = Utc::now().format("%Y-%m-%d").to_string();
+    expand_key_transform(transform, &local_hostname(), &date)
+}
+
+// The profile's transform for one upload call; empty when it has none.
+pub(crate) fn upload_key_transform(
+    state: &AppState,
+    profile_id: &str,
+) -> Result<ResolvedKeyTransform, String> {
+    let profile = profile_for_id(state, profile_id)?;
+    Ok(profile
+        .key_transform
+        .as_ref()
+        .map(resolve_key_transform)
+        .unwrap_or_default())
+}
+
+impl ResolvedKeyTransform {
+    // Folder markers (`dir/`) only get the prefix; a suffix would stop them
+    // reading as folders.
+    pub(crate) fn apply(&self, key: &str) -> String {
+        if key.ends_with('/') {
+            return format!("{}{key}", self.prefix);
+        }
+        format!("{}{key}{}", self.prefix, self.suffix)
+    }
+
+    // The original key, or `None` for keys the transform didn't produce.
+    pub(crate) fn strip<'a>(&self, key: &'a str) -> Option<&'a str> {
+        let inner = key
+            .strip_prefix(self.prefix.as_str())?
+            .strip_suffix(self.suffix.as_str())?;
+        (!inner.is_empty()).then_some(inner)
+    }
+}
+
+pub(crate) fn local_hostname() -> String {
+    let from_env = ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok());
+    let raw = from_env
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            std::process::Command::new("hostname")
+                .output()
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        })
+        .unwrap_or_default();
+    let host: String = raw
+        .trim()
+        .chars()
+        .filter(|ch| !matches!(ch, '/' | '\\') && !ch.is_control())
+        .collect();
+    if host.is_empty() {
+        "unknown-host".to_string()
+    } else {
+        host
+    }
+}
+
 pub(crate) fn normalize_slashes(path: &Path) -> String {
     path.components()
         .filter_map(|part| match part {
@@ -43,10 +287,292 @@ pub(crate) fn normalize_slashes(path: &Path) -> String {
         .join("/")
 }
 
+// Where a download lands under `policy`; None means it's skipped.
+pub(crate) fn resolve_download_destination(
+    path: &Path,
+    policy: DownloadExistingPolicy,
+) -> Option<PathBuf> {
+    if policy == DownloadExistingPolicy::Overwrite || !path.exists() {
+        return Some(path.to_path_buf());
+    }
+    if policy == DownloadExistingPolicy::Skip {
+        return None;
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{stem} ({n}){ext}")))
+        .find(|candidate| !candidate.exists())
+}
+
+pub(crate) fn partial_download_path(local_path: &Path) -> PathBuf {
+    let mut name = local_path.file_name().unwrap_or_default().to_os_string();
+    name.push(PARTIAL_DOWNLOAD_SUFFIX);
+    local_path.with_file_name(name)
+}
+
+pub(crate) fn open_external_dir() -> PathBuf {
+    std::env::temp_dir().join(OPEN_EXTERNAL_DIR_NAME)
+}
+
+// `<temp>/object0-open/<uuid>/<name>`: the key's own file name, so the OS
+// picks the app by extension, in a folder of its own so names never clash.
+pub(crate) fn open_external_temp_path(key: &str) -> PathBuf {
+    let file_name = key
+        .split('/')
+        .rfind(|part| !part.is_empty())
+        .and_then(sanitize_relative_path)
+        .unwrap_or_else(|| PathBuf::from("object"));
+    open_external_dir()
+        .join(Uuid::new_v4().to_string())
+        .join(file_name)
+}
+
+// Whether the OS would run a file with this name rather than open it.
+pub(crate) fn is_executable_file_name(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .is_some_and(|ext| OPEN_EXTERNAL_BLOCKED_EXTENSIONS.contains(&ext.as_str()))
+}
+
+pub(crate) fn schedule_open_external_cleanup(local_path: &Path) {
+    let Some(dir) = local_path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(StdDuration::from_secs(OPEN_EXTERNAL_CLEANUP_DELAY_SECS)).await;
+        let _ = fs::remove_dir_all(dir);
+    });
+}
+
+// Files opened in a previous run are no longer needed by their viewers.
+pub(crate) fn clear_open_external_dir() {
+    let _ = fs::remove_dir_all(open_external_dir());
+}
+
+// Free space on the volume holding `path`. The path itself may not exist
+// yet (a download destination), so the nearest existing ancestor is asked.
+pub(crate) fn available_disk_space(path: &Path) -> Result<u64, String> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(|| format!("No existing folder for {}", path.display()))?;
+    fs4::available_space(existing).map_err(|err| {
+        format!(
+            "Failed to read free space for {}: {err}",
+            existing.display()
+        )
+    })
+}
+
+// Fails fast when a transfer of `needed` bytes can't fit under `path`.
+pub(crate) fn ensure_disk_space(path: &Path, needed: i64) -> Result<(), String> {
+    if needed <= 0 {
+        return Ok(());
+    }
+    let available = available_disk_space(path)?;
+    if (available as i64) < needed {
+        return Err(format!(
+            "Not enough disk space at {}: {} needed, {} available",
+            path.display(),
+            format_byte_size(needed),
+            format_byte_size(available as i64)
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn format_byte_size(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes.max(0) as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+pub(crate) fn guess_content_type(key: &str) -> Option<&'static str> {
+    let name = key.rsplit('/').next().unwrap_or(key);
+    let (stem, ext) = name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    let ext = ext.to_ascii_lowercase();
+    CONTENT_TYPES_BY_EXTENSION
+        .iter()
+        .find(|(known, _)| *known == ext)
+        .map(|(_, content_type)| *content_type)
+}
+
+// Blank means "not set"; anything else must be a valid header value.
+pub(crate) fn non_empty_header(
+    value: Option<String>,
+    label: &str,
+) -> Result<Option<String>, String> {
+    let Some(value) = value.map(|value| value.trim().to_string()) else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if !is_header_value(&value) {
+        return Err(format!("{label} must be printable ASCII"));
+    }
+    Ok(Some(value))
+}
+
+// S3 lower-cases metadata names and only carries ASCII values over REST.
+pub(crate) fn validate_upload_metadata(
+    metadata: HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut validated = HashMap::new();
+    for (name, value) in metadata {
+        let name = name.trim().to_ascii_lowercase();
+        let name = name
+            .strip_prefix("x-amz-meta-")
+            .unwrap_or(&name)
+            .to_string();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!("Invalid metadata name \"{name}\""));
+        }
+        if !is_header_value(&value) {
+            return Err(format!("Metadata \"{name}\" must be printable ASCII"));
+        }
+        if validated
+            .insert(name.clone(), value.trim().to_string())
+            .is_some()
+        {
+            return Err(format!("Duplicate metadata name \"{name}\""));
+        }
+    }
+    Ok(validated)
+}
+
+fn is_header_value(value: &str) -> bool {
+    value.chars().all(|c| c == ' ' || c.is_ascii_graphic())
+}
+
 pub(crate) fn join_prefix_key(prefix: &str, key: &str) -> String {
     format!("{}{}", normalize_prefix(prefix), key)
 }
 
+// Gunzips as much of `compressed` as is there (a ranged read only has the
+// head of the stream), stopping at `limit` bytes of output. The flag is true
+// when output was cut short by either.
+pub(crate) fn gunzip_prefix(compressed: &[u8], limit: usize) -> Result<(Vec<u8>, bool), String> {
+    let mut decoder = flate2::read::GzDecoder::new(compressed);
+    let mut output = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        match io::Read::read(&mut decoder, &mut chunk) {
+            Ok(0) => return Ok((output, false)),
+            Ok(read) => {
+                output.extend_from_slice(&chunk[..read]);
+                if output.len() > limit {
+                    output.truncate(limit);
+                    return Ok((output, true));
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && !output.is_empty() => {
+                return Ok((output, true));
+            }
+            Err(err) => return Err(format!("Failed to decompress preview: {err}")),
+        }
+    }
+}
+
+// `None` unless the bytes are UTF-8. A truncated read may end mid-character;
+// that incomplete tail is dropped rather than counted as invalid.
+pub(crate) fn decode_utf8_text(bytes: &[u8], truncated: bool) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        Err(err) if truncated && err.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..err.valid_up_to()]).to_string())
+        }
+        Err(_) => None,
+    }
+}
+
+// AWS CLI style `s3://bucket/key`. The key is taken verbatim (no percent
+// decoding, like the CLI); an empty key or one ending in `/` names a prefix.
+pub(crate) fn parse_s3_uri(uri: &str) -> Result<(String, String), String> {
+    let trimmed = uri.trim();
+    let rest = trimmed
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("s3://"))
+        .map(|_| &trimmed[5..])
+        .ok_or_else(|| "S3 URI must start with s3://".to_string())?;
+    let (bucket, key) = rest.split_once('/').unwrap_or((rest, ""));
+
+    if bucket.is_empty() {
+        return Err("S3 URI is missing a bucket name".to_string());
+    }
+    if !(3..=63).contains(&bucket.len())
+        || !bucket
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_'))
+    {
+        return Err(format!("Invalid bucket name in S3 URI: {bucket}"));
+    }
+    if key.starts_with('/') {
+        return Err("S3 URI key can't start with a second slash".to_string());
+    }
+
+    Ok((bucket.to_string(), key.to_string()))
+}
+
+pub(crate) fn format_s3_uri(bucket: &str, key: &str) -> String {
+    format!("s3://{bucket}/{key}")
+}
+
+// Longest shared "directory" prefix (ending in `/`) of the given keys, so
+// stripping it keeps each key's layout relative to the selection.
+pub(crate) fn common_key_prefix(keys: &[String]) -> String {
+    let mut common: Option<String> = None;
+    for key in keys {
+        let parent = if let Some((head, _)) = key.rsplit_once('/') {
+            format!("{head}/")
+        } else {
+            String::new()
+        };
+        common = match common {
+            Some(existing) => {
+                let mut candidate = String::new();
+                for (a, b) in existing.chars().zip(parent.chars()) {
+                    if a == b {
+                        candidate.push(a);
+                    } else {
+                        break;
+                    }
+                }
+                while !candidate.is_empty() && !candidate.ends_with('/') {
+                    candidate.pop();
+                }
+                Some(candidate)
+            }
+            None => Some(parent),
+        };
+    }
+    common.unwrap_or_default()
+}
+
 pub(crate) fn wildcard_matches(pattern: &str, text: &str) -> bool {
     let pattern = pattern.as_bytes();
     let text = text.as_bytes();
@@ -100,18 +626,144 @@ pub(crate) fn file_mtime_millis(path: &Path) -> Option<i64> {
         .map(|d| d.as_millis() as i64)
 }
 
-pub(crate) fn scan_local_directory(local_path: &Path, exclude_patterns: &[String]) -> Vec<LocalFileInfo> {
+// Regular files under `root` for a folder upload, plus everything skipped and
+// why: special files, symlinks (unless followed, as folder sync never does),
+// and entries that can't be read, such as broken links when following.
+pub(crate) fn collect_folder_upload_files(
+    root: &Path,
+    follow_symlinks: bool,
+) -> (Vec<PathBuf>, Vec<SkippedLocalFile>) {
     let mut files = Vec::new();
-    if !local_path.exists() {
-        return files;
+    let mut skipped = Vec::new();
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .map(normalize_slashes)
+            .unwrap_or_else(|_| path.display().to_string())
+    };
+
+    for entry in WalkDir::new(root).follow_links(follow_symlinks) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                let reason = if err.loop_ancestor().is_some() {
+                    "symlink loop".to_string()
+                } else {
+                    match err.io_error() {
+                        Some(io_err) => format!("unreadable: {io_err}"),
+                        None => err.to_string(),
+                    }
+                };
+                skipped.push(SkippedLocalFile {
+                    path: err.path().map(relative).unwrap_or_default(),
+                    reason,
+                });
+                continue;
+            }
+        };
+        let file_type = entry.file_type();
+        if file_type.is_file() {
+            files.push(entry.into_path());
+        } else if !file_type.is_dir() {
+            skipped.push(SkippedLocalFile {
+                path: relative(entry.path()),
+                reason: special_file_reason(&file_type).to_string(),
+            });
+        }
     }
 
-    for entry in WalkDir::new(local_path)
-        .follow_links(false)
+    (files, skipped)
+}
+
+// Directories with nothing in them, `root` included, as paths relative to
+// `root` ("" for the root itself). Unreadable entries are left to
+// `collect_folder_upload_files` to report.
+pub(crate) fn collect_empty_folder_dirs(root: &Path, follow_symlinks: bool) -> Vec<String> {
+    WalkDir::new(root)
+        .follow_links(follow_symlinks)
         .into_iter()
         .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+        .filter(|entry| {
+            fs::read_dir(entry.path())
+                .map(|mut children| children.next().is_none())
+                .unwrap_or(false)
+        })
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(normalize_slashes))
+        .collect()
+}
+
+fn special_file_reason(file_type: &fs::FileType) -> &'static str {
+    if file_type.is_symlink() {
+        return "symlink (not followed)";
+    }
+    #[cfg(unix)]
     {
-        if !entry.file_type().is_file() {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_socket() {
+            return "socket";
+        }
+        if file_type.is_fifo() {
+            return "named pipe";
+        }
+        if file_type.is_block_device() || file_type.is_char_device() {
+            return "device file";
+        }
+    }
+    "not a regular file"
+}
+
+pub(crate) fn scan_local_directory(local_path: &Path, exclude_patterns: &[String]) -> Vec<LocalFileInfo> {
+    scan_local_directory_with_symlinks(local_path, exclude_patterns, SymlinkPolicy::Skip).0
+}
+
+// Also returns the symlinks that lead back into a folder already being
+// walked (`Follow` only); they are not entered. A link to a folder inside the
+// root is not entered either, so that folder is listed under its real path. A
+// folder outside the root reached through two links is scanned once, under
+// whichever path sorts first.
+pub(crate) fn scan_local_directory_with_symlinks(
+    local_path: &Path,
+    exclude_patterns: &[String],
+    symlinks: SymlinkPolicy,
+) -> (Vec<LocalFileInfo>, Vec<String>) {
+    let mut files = Vec::new();
+    let mut cycles = Vec::new();
+    if !local_path.exists() {
+        return (files, cycles);
+    }
+
+    let follow = symlinks == SymlinkPolicy::Follow;
+    let canonical_root = fs::canonicalize(local_path).unwrap_or_else(|_| local_path.to_path_buf());
+    let mut visited_dirs: HashSet<PathBuf> = HashSet::new();
+    let mut walker = WalkDir::new(local_path)
+        .follow_links(follow)
+        .sort_by_file_name()
+        .into_iter();
+    while let Some(result) = walker.next() {
+        let entry = match result {
+            Ok(entry) => entry,
+            Err(err) => {
+                if let (Some(path), Some(_)) = (err.path(), err.loop_ancestor()) {
+                    cycles.push(path.display().to_string());
+                }
+                continue;
+            }
+        };
+        if follow && entry.file_type().is_dir() {
+            let canonical =
+                fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path().to_path_buf());
+            if entry.path_is_symlink() && canonical.starts_with(&canonical_root) {
+                walker.skip_current_dir();
+            } else if !visited_dirs.insert(canonical) {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        let is_file = entry.file_type().is_file()
+            || (symlinks == SymlinkPolicy::CopyTarget
+                && entry.path_is_symlink()
+                && fs::metadata(entry.path()).is_ok_and(|meta| meta.is_file()));
+        if !is_file {
             continue;
         }
 
@@ -123,7 +775,11 @@ pub(crate) fn scan_local_directory(local_path: &Path, exclude_patterns: &[String
             continue;
         }
 
-        let size = entry.metadata().map(|m| m.len() as i64).unwrap_or(0).max(0);
+        // Size and mtime of the target when the entry is a symlink.
+        let size = fs::metadata(entry.path())
+            .map(|m| m.len() as i64)
+            .unwrap_or(0)
+            .max(0);
         // The file was just walked, so a stat failure is rare; epoch is an
         // acceptable "treat as changed" fallback for change detection here.
         let mtime_ms = file_mtime_millis(entry.path()).unwrap_or(0);
@@ -135,7 +791,7 @@ pub(crate) fn scan_local_directory(local_path: &Path, exclude_patterns: &[String
         });
     }
 
-    files
+    (files, cycles)
 }
 
 pub(crate) fn parse_iso_millis(value: &str) -> Option<i64> {
@@ -145,6 +801,109 @@ pub(crate) fn parse_iso_millis(value: &str) -> Option<i64> {
 }
 
 
+// The one form ETags are stored and compared in: no surrounding quotes and no
+// weak `W/` marker, whichever API (or older sync record) they came from.
+// Multipart part ETags are echoed back to S3 verbatim and don't go through here.
+pub(crate) fn normalize_etag(raw: Option<&str>) -> String {
+    let raw = raw.unwrap_or_default().trim();
+    raw.strip_prefix("W/")
+        .unwrap_or(raw)
+        .trim_matches('"')
+        .to_string()
+}
+
+// The MD5 a single-part ETag stands for; multipart ETags ("<md5>-<parts>")
+// and anything else that isn't 32 hex digits give None.
+pub(crate) fn etag_md5_hex(raw: Option<&str>) -> Option<String> {
+    let etag = normalize_etag(raw).to_ascii_lowercase();
+    (etag.len() == 32 && etag.chars().all(|c| c.is_ascii_hexdigit())).then_some(etag)
+}
+
+pub(crate) fn file_md5_hex(path: &Path) -> Result<String, String> {
+    Ok(file_md5(path)?
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+// `Content-MD5` header values: the digest in base64.
+pub(crate) fn file_content_md5(path: &Path) -> Result<String, String> {
+    Ok(encode_base64(&file_md5(path)?))
+}
+
+pub(crate) fn content_md5(bytes: &[u8]) -> String {
+    encode_base64(&Md5::digest(bytes))
+}
+
+fn file_md5(path: &Path) -> Result<Vec<u8>, String> {
+    let mut file =
+        fs::File::open(path).map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    let mut hasher = Md5::new();
+    io::copy(&mut file, &mut hasher)
+        .map_err(|err| format!("Failed reading {}: {err}", path.display()))?;
+    Ok(hasher.finalize().to_vec())
+}
+
+// Whether the file's contents produce `etag`: its MD5 for a single-part
+// upload, or the MD5 of the part MD5s plus "-<parts>" for a multipart one.
+// The part size isn't recorded on the object, so the common ones (ours and
+// the AWS tools' 8 MiB, 5 MiB, 16 MiB, and an even split rounded up to a MiB)
+// are tried. Other ETags (SSE-KMS, SSE-C) can't be matched and return false.
+pub(crate) fn file_matches_etag(path: &Path, etag: &str) -> Result<bool, String> {
+    let etag = normalize_etag(Some(etag)).to_ascii_lowercase();
+    if etag_md5_hex(Some(&etag)).is_some() {
+        return Ok(file_md5_hex(path)? == etag);
+    }
+    let Some((_, parts)) = etag.split_once('-') else {
+        return Ok(false);
+    };
+    let Ok(parts) = parts.parse::<u64>() else {
+        return Ok(false);
+    };
+    let size = fs::metadata(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?
+        .len();
+    if parts == 0 || size == 0 {
+        return Ok(false);
+    }
+
+    const MIB: u64 = 1024 * 1024;
+    let even_split = size.div_ceil(parts).div_ceil(MIB) * MIB;
+    let mut part_sizes = vec![MULTIPART_PART_SIZE_BYTES as u64, 5 * MIB, 16 * MIB];
+    if !part_sizes.contains(&even_split) {
+        part_sizes.push(even_split);
+    }
+    for part_size in part_sizes {
+        if size.div_ceil(part_size) == parts && file_multipart_etag(path, part_size)? == etag {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn file_multipart_etag(path: &Path, part_size: u64) -> Result<String, String> {
+    let mut file =
+        fs::File::open(path).map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    let mut digests = Md5::new();
+    let mut parts = 0;
+    loop {
+        let mut hasher = Md5::new();
+        let read = io::copy(&mut io::Read::take(&mut file, part_size), &mut hasher)
+            .map_err(|err| format!("Failed reading {}: {err}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        digests.update(hasher.finalize());
+        parts += 1;
+    }
+    let hex: String = digests
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Ok(format!("{hex}-{parts}"))
+}
+
 pub(crate) fn normalize_prefix(prefix: &str) -> String {
     if prefix.is_empty() {
         String::new()
@@ -186,6 +945,23 @@ pub(crate) fn ensure_parent_dir(path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+// Fills `buffer` unless EOF comes first; a single `read` may legitimately
+// return fewer bytes than asked. Returns the number of bytes read.
+pub(crate) async fn read_full_chunk<R: tokio::io::AsyncRead + Unpin>(
+    reader: &mut R,
+    buffer: &mut [u8],
+) -> io::Result<usize> {
+    let mut read_total = 0;
+    while read_total < buffer.len() {
+        let read = reader.read(&mut buffer[read_total..]).await?;
+        if read == 0 {
+            break;
+        }
+        read_total += read;
+    }
+    Ok(read_total)
+}
+
 /// Write `contents` to `path` atomically: write a uniquely-named temp file in
 /// the same directory, then rename it over the target. Because the temp name is
 /// unique and `fs::rename` is atomic on one filesystem, a reader (or a crash, or
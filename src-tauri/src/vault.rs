@@ -6,109 +6,206 @@ use super::*;
 pub(crate) fn read_vault_file(path: &Path) -> Result<VaultFileDisk, String> {
     let raw = fs::read_to_string(path)
         .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
-    let value: Value =
-        serde_json::from_str(&raw).map_err(|err| format!("Invalid vault JSON: {err}"))?;
+    let value: Value = serde_json::from_str(&raw)
+        .map_err(|err| format!("{VAULT_CORRUPT}: invalid JSON ({err})"))?;
     let version = value
         .get("version")
         .and_then(Value::as_u64)
         .ok_or_else(|| "Vault missing version field".to_string())?;
 
     match version {
-        1 => serde_json::from_value::<VaultFileV1>(value)
-            .map(VaultFileDisk::V1)
-            .map_err(|err| format!("Invalid V1 vault format: {err}")),
+        1 => {
+            let v1 = serde_json::from_value::<VaultFileV1>(value)
+                .map_err(|err| format!("Invalid V1 vault format: {err}"))?;
+            check_v1_vault_buffers(&v1)?;
+            Ok(VaultFileDisk::V1(v1))
+        }
         2 => serde_json::from_value::<VaultFileV2>(value)
             .map(VaultFileDisk::V2)
             .map_err(|err| format!("Invalid V2 vault format: {err}")),
         3 => serde_json::from_value::<VaultFileV3>(value)
             .map(VaultFileDisk::V3)
             .map_err(|err| format!("Invalid V3 vault format: {err}")),
+        4 => serde_json::from_value::<VaultFileV4>(value)
+            .map(VaultFileDisk::V4)
+            .map_err(|err| format!("Invalid V4 vault format: {err}")),
         _ => Err(format!("Unsupported vault version: {version}")),
     }
 }
 
-pub(crate) fn unlock_with_passphrase(path: &Path, passphrase: &str) -> Result<UnlockPayload, String> {
-    let file = read_vault_file(path)?;
+// V1 stores the GCM tag apart from the ciphertext. A truncated file would
+// otherwise only fail decryption, which reads as a wrong passphrase.
+fn check_v1_vault_buffers(v1: &VaultFileV1) -> Result<(), String> {
+    let data = decode_base64(&v1.data).map_err(|err| format!("{VAULT_CORRUPT}: data: {err}"))?;
+    let auth_tag =
+        decode_base64(&v1.auth_tag).map_err(|err| format!("{VAULT_CORRUPT}: authTag: {err}"))?;
+    if data.is_empty() || auth_tag.is_empty() {
+        return Err(format!("{VAULT_CORRUPT}: empty data or authTag"));
+    }
+    if data.len() + auth_tag.len() < GCM_TAG_BYTES {
+        return Err(format!("{VAULT_CORRUPT}: encrypted payload is truncated"));
+    }
+    Ok(())
+}
+
+// New vaults, and older ones rewritten on unlock, use Argon2id.
+impl Default for VaultKdfConfig {
+    fn default() -> Self {
+        VAULT_KDF_ARGON2ID
+    }
+}
+
+pub(crate) fn vault_kdf_config(
+    kdf: VaultKdf,
+    params: &VaultKdfParams,
+) -> Result<VaultKdfConfig, String> {
+    let param = |value: Option<u32>, name: &str, max: u32| -> Result<u32, String> {
+        let value =
+            value.ok_or_else(|| format!("{VAULT_CORRUPT}: missing KDF parameter {name}"))?;
+        if value == 0 || value > max {
+            return Err(format!(
+                "{VAULT_CORRUPT}: KDF parameter {name} must be between 1 and {max}"
+            ));
+        }
+        Ok(value)
+    };
+    match kdf {
+        VaultKdf::Pbkdf2 => Ok(VaultKdfConfig::Pbkdf2 {
+            iterations: param(params.iterations, "iterations", PBKDF2_MAX_ITERATIONS)?,
+        }),
+        VaultKdf::Argon2id => Ok(VaultKdfConfig::Argon2id {
+            memory_kib: param(params.memory_kib, "memoryKib", ARGON2_MAX_MEMORY_KIB)?,
+            time_cost: param(params.time_cost, "timeCost", ARGON2_MAX_TIME_COST)?,
+            parallelism: param(params.parallelism, "parallelism", ARGON2_MAX_PARALLELISM)?,
+        }),
+    }
+}
+
+pub(crate) fn vault_kdf_fields(kdf: VaultKdfConfig) -> (VaultKdf, VaultKdfParams) {
+    match kdf {
+        VaultKdfConfig::Pbkdf2 { iterations } => (
+            VaultKdf::Pbkdf2,
+            VaultKdfParams {
+                iterations: Some(iterations),
+                ..VaultKdfParams::default()
+            },
+        ),
+        VaultKdfConfig::Argon2id {
+            memory_kib,
+            time_cost,
+            parallelism,
+        } => (
+            VaultKdf::Argon2id,
+            VaultKdfParams {
+                memory_kib: Some(memory_kib),
+                time_cost: Some(time_cost),
+                parallelism: Some(parallelism),
+                ..VaultKdfParams::default()
+            },
+        ),
+    }
+}
 
-    match file {
+pub(crate) fn default_vault_kdf(kdf: VaultKdf) -> VaultKdfConfig {
+    match kdf {
+        VaultKdf::Pbkdf2 => VAULT_KDF_PBKDF2,
+        VaultKdf::Argon2id => VAULT_KDF_ARGON2ID,
+    }
+}
+
+pub(crate) fn unlock_with_passphrase(
+    path: &Path,
+    passphrase: &str,
+) -> Result<UnlockPayload, String> {
+    // (version, salt, iv, ciphertext incl. tag, kdf, recovery salt).
+    let (version, salt, iv, ciphertext, kdf, recovery_salt) = match read_vault_file(path)? {
         VaultFileDisk::V1(v1) => {
-            let salt = decode_base64(&v1.salt)?;
-            let iv = decode_base64(&v1.iv)?;
             let mut ciphertext = decode_base64(&v1.data)?;
-            let auth_tag = decode_base64(&v1.auth_tag)?;
-            ciphertext.extend(auth_tag);
-
-            let key = derive_key(passphrase, &salt);
-            let plaintext = decrypt_payload(&key, &iv, &ciphertext)?;
-            let data: VaultData = serde_json::from_slice(&plaintext)
-                .map_err(|err| format!("Invalid decrypted vault payload: {err}"))?;
-
-            Ok(UnlockPayload {
-                data,
-                key,
-                salt,
-                has_recovery_key: false,
-                recovery_salt: None,
-                needs_rewrite: v1.version < CURRENT_VAULT_VERSION,
-            })
+            ciphertext.extend(decode_base64(&v1.auth_tag)?);
+            (
+                v1.version,
+                v1.salt,
+                v1.iv,
+                ciphertext,
+                VAULT_KDF_PBKDF2,
+                None,
+            )
         }
         VaultFileDisk::V2(v2) => {
-            let salt = decode_base64(&v2.salt)?;
-            let iv = decode_base64(&v2.iv)?;
             let ciphertext = decode_base64(&v2.data)?;
-            let key = derive_key(passphrase, &salt);
-            let plaintext = decrypt_payload(&key, &iv, &ciphertext)?;
-            let data: VaultData = serde_json::from_slice(&plaintext)
-                .map_err(|err| format!("Invalid decrypted vault payload: {err}"))?;
-
-            Ok(UnlockPayload {
-                data,
-                key,
-                salt,
-                has_recovery_key: false,
-                recovery_salt: None,
-                needs_rewrite: v2.version < CURRENT_VAULT_VERSION,
-            })
+            (
+                v2.version,
+                v2.salt,
+                v2.iv,
+                ciphertext,
+                VAULT_KDF_PBKDF2,
+                None,
+            )
         }
         VaultFileDisk::V3(v3) => {
-            let salt = decode_base64(&v3.salt)?;
-            let iv = decode_base64(&v3.iv)?;
             let ciphertext = decode_base64(&v3.data)?;
-            let key = derive_key(passphrase, &salt);
-            let plaintext = decrypt_payload(&key, &iv, &ciphertext)?;
-            let data: VaultData = serde_json::from_slice(&plaintext)
-                .map_err(|err| format!("Invalid decrypted vault payload: {err}"))?;
-
-            let recovery_salt = if let Some(recovery) = &v3.recovery {
-                Some(decode_base64(&recovery.salt)?)
-            } else {
-                None
-            };
-
-            Ok(UnlockPayload {
-                data,
-                key,
-                salt,
-                has_recovery_key: v3.recovery.is_some(),
+            let recovery_salt = v3.recovery.map(|recovery| recovery.salt);
+            (
+                v3.version,
+                v3.salt,
+                v3.iv,
+                ciphertext,
+                VAULT_KDF_PBKDF2,
                 recovery_salt,
-                needs_rewrite: false,
-            })
+            )
         }
-    }
+        VaultFileDisk::V4(v4) => {
+            let ciphertext = decode_base64(&v4.data)?;
+            let kdf = vault_kdf_config(v4.kdf, &v4.kdf_params)?;
+            let recovery_salt = v4.recovery.map(|recovery| recovery.salt);
+            (v4.version, v4.salt, v4.iv, ciphertext, kdf, recovery_salt)
+        }
+    };
+    let salt = decode_base64(&salt)?;
+    let iv = decode_base64(&iv)?;
+    let recovery_salt = recovery_salt.as_deref().map(decode_base64).transpose()?;
+
+    let key = derive_vault_key(passphrase, &salt, kdf)?;
+    let plaintext = decrypt_payload(&key, &iv, &ciphertext)?;
+    let data: VaultData = serde_json::from_slice(&plaintext)
+        .map_err(|err| format!("Invalid decrypted vault payload: {err}"))?;
+
+    // Pre-V4 files are rewritten as V4 with a fresh Argon2id key.
+    let needs_rewrite = version < CURRENT_VAULT_VERSION;
+    let (key, kdf, salt) = if needs_rewrite {
+        let salt = random_bytes::<SALT_BYTES>().to_vec();
+        let key = derive_vault_key(passphrase, &salt, VAULT_KDF_ARGON2ID)?;
+        (key, VAULT_KDF_ARGON2ID, salt)
+    } else {
+        (key, kdf, salt)
+    };
+
+    Ok(UnlockPayload {
+        data,
+        key,
+        kdf,
+        salt,
+        has_recovery_key: recovery_salt.is_some(),
+        recovery_salt,
+        needs_rewrite,
+    })
 }
 
 pub(crate) fn unlock_with_recovery_key(
     path: &Path,
     recovery_key_plain: &str,
 ) -> Result<RecoveryUnlockPayload, String> {
-    let v3 = match read_vault_file(path)? {
-        VaultFileDisk::V3(v3) => v3,
+    // A V3 vault's next passphrase is derived with the default KDF.
+    let (salt, recovery, kdf) = match read_vault_file(path)? {
+        VaultFileDisk::V3(v3) => (v3.salt, v3.recovery, VAULT_KDF_ARGON2ID),
+        VaultFileDisk::V4(v4) => {
+            let kdf = vault_kdf_config(v4.kdf, &v4.kdf_params)?;
+            (v4.salt, v4.recovery, kdf)
+        }
         _ => return Err("Vault has no recovery key configured".to_string()),
     };
 
-    let recovery = v3
-        .recovery
-        .ok_or_else(|| "Vault has no recovery key configured".to_string())?;
+    let recovery = recovery.ok_or_else(|| "Vault has no recovery key configured".to_string())?;
 
     let recovery_salt = decode_base64(&recovery.salt)?;
     let recovery_iv = decode_base64(&recovery.iv)?;
@@ -118,10 +215,11 @@ pub(crate) fn unlock_with_recovery_key(
         .map_err(|_| "Invalid recovery key".to_string())?;
     let data: VaultData = serde_json::from_slice(&plaintext)
         .map_err(|err| format!("Invalid decrypted vault payload: {err}"))?;
-    let salt = decode_base64(&v3.salt)?;
+    let salt = decode_base64(&salt)?;
 
     Ok(RecoveryUnlockPayload {
         data,
+        kdf,
         salt,
         recovery_salt,
         recovery_key,
@@ -142,30 +240,42 @@ pub(crate) fn save_vault(path: &Path, vault: &VaultRuntime) -> Result<(), String
         .as_ref()
         .ok_or_else(|| "Cannot save: vault has no salt".to_string())?;
 
-    let plaintext =
-        serde_json::to_vec(data).map_err(|err| format!("Failed to serialize vault data: {err}"))?;
+    let plaintext = serde_json::to_vec(&persisted_vault_data(data))
+        .map(Zeroizing::new)
+        .map_err(|err| format!("Failed to serialize vault data: {err}"))?;
     let (iv, ciphertext) = encrypt_payload(key, &plaintext)?;
 
-    let mut file = VaultFileV3 {
+    let (kdf, kdf_params) = vault_kdf_fields(vault.kdf);
+    let mut file = VaultFileV4 {
         version: CURRENT_VAULT_VERSION,
+        kdf,
+        kdf_params,
         salt: encode_base64(salt),
         iv: encode_base64(&iv),
         data: encode_base64(&ciphertext),
         recovery: None,
     };
 
-    if let (Some(recovery_key), Some(recovery_salt)) = (&vault.recovery_key, &vault.recovery_salt) {
-        let (recovery_iv, recovery_ciphertext) = encrypt_payload(recovery_key, &plaintext)?;
-        file.recovery = Some(VaultRecoveryBlob {
-            salt: encode_base64(recovery_salt),
-            iv: encode_base64(&recovery_iv),
-            data: encode_base64(&recovery_ciphertext),
-        });
-    } else if vault.recovery_salt.is_some() {
-        if let Ok(VaultFileDisk::V3(existing)) = read_vault_file(path) {
-            file.recovery = existing.recovery;
+    file.recovery = match (
+        recovery_blob_plan(vault),
+        &vault.recovery_key,
+        &vault.recovery_salt,
+    ) {
+        (RecoveryBlobPlan::Reencrypt, Some(recovery_key), Some(recovery_salt)) => {
+            let (recovery_iv, recovery_ciphertext) = encrypt_payload(recovery_key, &plaintext)?;
+            Some(VaultRecoveryBlob {
+                salt: encode_base64(recovery_salt),
+                iv: encode_base64(&recovery_iv),
+                data: encode_base64(&recovery_ciphertext),
+            })
         }
-    }
+        (RecoveryBlobPlan::PreserveExisting, _, _) => match read_vault_file(path) {
+            Ok(VaultFileDisk::V3(existing)) => existing.recovery,
+            Ok(VaultFileDisk::V4(existing)) => existing.recovery,
+            _ => None,
+        },
+        _ => None,
+    };
 
     ensure_parent_dir(path)?;
     let serialized = serde_json::to_string_pretty(&file)
@@ -173,6 +283,26 @@ pub(crate) fn save_vault(path: &Path, vault: &VaultRuntime) -> Result<(), String
     fs::write(path, serialized).map_err(|err| format!("Failed to write {}: {err}", path.display()))
 }
 
+// Passphrase sessions (`key` set, `recovery_key` unset) can't re-encrypt the
+// recovery blob, so they carry the on-disk one over unchanged: it still opens
+// with the user's recovery key, but holds the data as of the last session that
+// had that key. Sessions holding the recovery key (after setup,
+// add-recovery-key or a passphrase change) re-encrypt it with the current data.
+pub(crate) fn recovery_blob_plan(vault: &VaultRuntime) -> RecoveryBlobPlan {
+    match (&vault.recovery_key, &vault.recovery_salt) {
+        (Some(_), Some(_)) => RecoveryBlobPlan::Reencrypt,
+        (None, Some(_)) => RecoveryBlobPlan::PreserveExisting,
+        (_, None) => RecoveryBlobPlan::Omit,
+    }
+}
+
+// A recovery-key unlock decrypts the data but leaves no passphrase key, so the
+// session is read-only until `vault:change-passphrase` sets a new passphrase
+// (which also issues a fresh recovery key).
+pub(crate) fn is_recovery_session(vault: &VaultRuntime) -> bool {
+    vault.unlocked && vault.key.is_none() && vault.recovery_key.is_some()
+}
+
 pub(crate) fn has_recovery_key_on_disk(path: &Path) -> Result<bool, String> {
     if !path.exists() {
         return Ok(false);
@@ -180,17 +310,187 @@ pub(crate) fn has_recovery_key_on_disk(path: &Path) -> Result<bool, String> {
 
     match read_vault_file(path)? {
         VaultFileDisk::V3(v3) => Ok(v3.recovery.is_some()),
+        VaultFileDisk::V4(v4) => Ok(v4.recovery.is_some()),
         _ => Ok(false),
     }
 }
 
+// Background mode's launch-time unlock: the keychain-stored passphrase only,
+// never a prompt. `false` leaves the vault locked for the UI to deal with.
+pub(crate) fn unlock_vault_from_keychain(state: &AppState) -> bool {
+    let Ok(path) = vault_path() else {
+        return false;
+    };
+    if !path.exists() {
+        return false;
+    }
+    let KeychainReadResult::Available(Some(passphrase)) = read_stored_passphrase() else {
+        return false;
+    };
+    let Ok(unlock) = unlock_with_passphrase(&path, &passphrase) else {
+        return false;
+    };
+    let Ok(mut vault) = lock_state(&state.vault) else {
+        return false;
+    };
+    if !vault.unlocked {
+        let _ = apply_vault_unlock(&mut vault, &path, unlock);
+    }
+    true
+}
+
+// Installs a successful unlock in the runtime, rewriting the vault file when
+// it was read from an older format. Shared by every unlock path.
+pub(crate) fn apply_vault_unlock(
+    vault: &mut VaultRuntime,
+    path: &Path,
+    unlock: UnlockPayload,
+) -> Result<(), String> {
+    vault.unlocked = true;
+    vault.data = Some(unlock.data);
+    vault.key = Some(unlock.key);
+    vault.kdf = unlock.kdf;
+    vault.salt = Some(unlock.salt);
+    vault.recovery_salt = unlock.recovery_salt;
+    vault.recovery_key = None;
+    // Start the idle clock at the unlock, even when no RPC triggered it.
+    vault.last_activity = Some(Instant::now());
+    if unlock.needs_rewrite {
+        save_vault(path, vault)?;
+    }
+    Ok(())
+}
+
+// The keys are `Zeroizing` and wipe themselves as they're dropped; salts and
+// the decrypted profile secrets are plain buffers, so they're wiped here.
 pub(crate) fn lock_vault_runtime(vault: &mut VaultRuntime) {
     vault.unlocked = false;
+    if let Some(data) = vault.data.as_mut() {
+        for profile in &mut data.profiles {
+            profile.access_key_id.zeroize();
+            profile.secret_access_key.zeroize();
+            profile.session_token.zeroize();
+        }
+    }
     vault.data = None;
     vault.key = None;
-    vault.salt = None;
+    vault.salt.zeroize();
     vault.recovery_key = None;
-    vault.recovery_salt = None;
+    vault.recovery_salt.zeroize();
+    vault.last_activity = None;
+}
+
+pub(crate) fn note_vault_activity(state: &AppState, method: RpcMethod) {
+    // The UI polls vault:status; counting it would keep the vault open.
+    if method == RpcMethod::VaultStatus {
+        return;
+    }
+    if let Ok(mut vault) = lock_state(&state.vault) {
+        vault.last_activity = Some(Instant::now());
+    }
+}
+
+pub(crate) fn vault_auto_lock_due(
+    vault: &VaultRuntime,
+    auto_lock_secs: Option<u64>,
+    now: Instant,
+) -> bool {
+    match (vault.unlocked, auto_lock_secs, vault.last_activity) {
+        (true, Some(secs), Some(last)) => {
+            now.saturating_duration_since(last) >= StdDuration::from_secs(secs)
+        }
+        _ => false,
+    }
+}
+
+// Running or queued jobs count as activity: locking would cancel them.
+pub(crate) async fn run_vault_auto_lock_monitor(app: AppHandle) {
+    loop {
+        tokio::time::sleep(StdDuration::from_secs(VAULT_AUTO_LOCK_CHECK_SECS)).await;
+        let state = app.state::<AppState>();
+        let auto_lock_secs = current_settings(&state).auto_lock_secs;
+        let jobs_active = lock_state(&state.jobs)
+            .map(|jobs| !jobs.running.is_empty() || !jobs.queue.is_empty())
+            .unwrap_or(true);
+        if jobs_active {
+            continue;
+        }
+        let locked = match lock_state(&state.vault) {
+            Ok(mut vault) if vault_auto_lock_due(&vault, auto_lock_secs, Instant::now()) => {
+                lock_vault_runtime(&mut vault);
+                true
+            }
+            _ => false,
+        };
+        if locked {
+            stop_all_folder_sync_rules(&app);
+            refresh_tray_menu(&app);
+            let _ = app.emit("vault:locked", json!({ "reason": "auto-lock" }));
+        }
+    }
+}
+
+// What actually reaches disk: ephemeral profiles keep their access key id
+// but drop the secret and session token.
+pub(crate) fn persisted_vault_data(data: &VaultData) -> VaultData {
+    let mut data = data.clone();
+    for profile in data.profiles.iter_mut().filter(|profile| profile.ephemeral) {
+        profile.secret_access_key = String::new();
+        profile.session_token = None;
+    }
+    data
+}
+
+pub(crate) fn profile_needs_credentials(profile: &Profile) -> bool {
+    profile.ephemeral && profile.secret_access_key.trim().is_empty()
+}
+
+pub(crate) fn needs_credentials_error(profile: &Profile) -> String {
+    format!(
+        "{PROFILE_NEEDS_CREDENTIALS}: enter the secret for \"{}\" to use it this session",
+        profile.name
+    )
+}
+
+// Validates the input and stamps a new id; the caller stores it.
+pub(crate) fn new_profile(input: ProfileInput) -> Result<Profile, String> {
+    if let Some(transform) = &input.key_transform {
+        validate_key_transform(transform)?;
+    }
+    validate_endpoint_options(
+        &input.provider,
+        input.endpoint.as_deref(),
+        input.use_accelerate,
+        input.use_dualstack,
+    )?;
+    validate_checksum_options(input.require_checksums, input.disable_checksums)?;
+    let (sse, sse_kms_key_id) =
+        parse_upload_encryption(input.sse.as_deref(), input.sse_kms_key_id.as_deref())?;
+    let timestamp = now_iso();
+    Ok(Profile {
+        id: Uuid::new_v4().to_string(),
+        name: input.name,
+        provider: input.provider,
+        access_key_id: input.access_key_id,
+        secret_access_key: input.secret_access_key,
+        session_token: input.session_token,
+        endpoint: input.endpoint,
+        region: input.region,
+        default_bucket: input.default_bucket,
+        requester_pays: input.requester_pays,
+        ephemeral: input.ephemeral,
+        key_transform: input
+            .key_transform
+            .filter(|transform| !key_transform_is_empty(transform)),
+        require_checksums: input.require_checksums,
+        disable_checksums: input.disable_checksums,
+        use_accelerate: input.use_accelerate,
+        use_dualstack: input.use_dualstack,
+        sse,
+        sse_kms_key_id,
+        created_at: timestamp.clone(),
+        updated_at: timestamp,
+    })
 }
 
 pub(crate) fn to_profile_info(profile: &Profile) -> ProfileInfo {
@@ -201,6 +501,16 @@ pub(crate) fn to_profile_info(profile: &Profile) -> ProfileInfo {
         endpoint: profile.endpoint.clone(),
         region: profile.region.clone(),
         default_bucket: profile.default_bucket.clone(),
+        requester_pays: profile.requester_pays,
+        ephemeral: profile.ephemeral,
+        key_transform: profile.key_transform.clone(),
+        require_checksums: profile.require_checksums,
+        disable_checksums: profile.disable_checksums,
+        use_accelerate: profile.use_accelerate,
+        use_dualstack: profile.use_dualstack,
+        sse: profile.sse.clone(),
+        sse_kms_key_id: profile.sse_kms_key_id.clone(),
+        needs_credentials: profile_needs_credentials(profile),
         created_at: profile.created_at.clone(),
         updated_at: profile.updated_at.clone(),
     }
@@ -223,6 +533,12 @@ pub(crate) fn ensure_unlocked(vault: &VaultRuntime) -> Result<(), String> {
 
 pub(crate) fn ensure_writable(vault: &VaultRuntime) -> Result<(), String> {
     ensure_unlocked(vault)?;
+    if is_recovery_session(vault) {
+        return Err(
+            "Vault was unlocked with the recovery key; set a new passphrase before making changes"
+                .to_string(),
+        );
+    }
     if vault.key.is_none() || vault.salt.is_none() {
         return Err("Vault must be rekeyed before writing".to_string());
     }
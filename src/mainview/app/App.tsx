@@ -53,6 +53,10 @@ export default function App() {
     if (concurrency) {
       rpcCall("jobs:set-concurrency", { concurrency });
     }
+    const maxBytesPerSec = useUIStore.getState().jobBandwidthLimit;
+    if (maxBytesPerSec) {
+      rpcCall("jobs:set-bandwidth-limit", { maxBytesPerSec });
+    }
     // Resolve the host OS once, app-wide, so the window decorations (custom
     // controls on Windows/Linux, native on macOS) work on every screen —
     // including the lock/loading screens that have no TopBar.
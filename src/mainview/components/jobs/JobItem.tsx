@@ -24,6 +24,8 @@ import {
   IconFileZipper,
   IconGaugeHigh,
   IconHourglassStart,
+  IconPause,
+  IconPlay,
   IconScissors,
   IconSpinner,
   IconTrashCan,
@@ -72,6 +74,8 @@ function StatusIcon({ status }: { status: string }) {
       return <IconCircleXmark className={`${cls} text-destructive`} />;
     case "cancelled":
       return <IconBan className={`${cls} text-warning`} />;
+    case "paused":
+      return <IconPause className={`${cls} text-foreground/40`} />;
     default:
       return null;
   }
@@ -79,14 +83,20 @@ function StatusIcon({ status }: { status: string }) {
 
 const JobItemInner = function JobItemInner({ job }: JobItemProps) {
   const cancelJob = useJobStore((s) => s.cancelJob);
+  const pauseJob = useJobStore((s) => s.pauseJob);
+  const resumeJob = useJobStore((s) => s.resumeJob);
   const [open, setOpen] = useState(false);
 
-  const isActive = job.status === "running" || job.status === "queued";
+  const isPaused = job.status === "paused";
+  const isActive =
+    job.status === "running" || job.status === "queued" || isPaused;
   const isRunning = job.status === "running";
+  const canPause = job.type === "upload" || job.type === "download";
   const isDone =
     job.status === "completed" ||
     job.status === "failed" ||
-    job.status === "cancelled";
+    job.status === "cancelled" ||
+    job.status === "skipped";
 
   const hasProgress = isActive && !!job.bytesTotal && job.bytesTotal > 0;
   const pct = job.percentage ?? 0;
@@ -284,6 +294,28 @@ const JobItemInner = function JobItemInner({ job }: JobItemProps) {
           </div>
         )}
 
+        {/* Pause / resume button */}
+        {isActive && canPause && (
+          <Button
+            type="button"
+            variant="ghost"
+            size="icon-xs"
+            className="shrink-0 text-foreground/40 hover:text-foreground"
+            onClick={(e) => {
+              e.stopPropagation();
+              if (isPaused) resumeJob(job.id);
+              else pauseJob(job.id);
+            }}
+            title={isPaused ? "Resume" : "Pause"}
+          >
+            {isPaused ? (
+              <IconPlay className="size-3.5" />
+            ) : (
+              <IconPause className="size-3.5" />
+            )}
+          </Button>
+        )}
+
         {/* Cancel button */}
         {isActive && (
           <Button
@@ -10,6 +10,7 @@ import {
   IconGaugeHigh,
   IconListCheck,
   IconPlay,
+  IconTrashCan,
   IconXmark,
 } from "@/lib/icons";
 import { useJobStore, useUIStore } from "@/stores";
@@ -20,18 +21,27 @@ const VIRTUAL_SECTION_HEIGHT = 26;
 const VIRTUAL_JOB_HEIGHT = 56;
 
 export function JobPanel() {
-  const { jobs, refreshJobs, clearCompleted } = useJobStore();
+  const {
+    jobs,
+    refreshJobs,
+    clearCompleted,
+    resumableUploads,
+    refreshResumableUploads,
+    resumeUpload,
+    discardUpload,
+  } = useJobStore();
   const setJobPanelOpen = useUIStore((s) => s.setJobPanelOpen);
   const listRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
     const frame = window.requestAnimationFrame(() => {
       refreshJobs();
+      refreshResumableUploads();
     });
     return () => {
       window.cancelAnimationFrame(frame);
     };
-  }, [refreshJobs]);
+  }, [refreshJobs, refreshResumableUploads]);
 
   const {
     activeJobs,
@@ -158,6 +168,58 @@ export function JobPanel() {
         </div>
       )}
 
+      {/* Uploads interrupted by a restart */}
+      {resumableUploads.length > 0 && (
+        <div className="border-border/60 border-b bg-warning/5 px-3 py-1.5">
+          <div className="mb-1 font-semibold text-[10px] text-foreground/40 uppercase tracking-wider">
+            Interrupted uploads
+          </div>
+          {resumableUploads.map((upload) => (
+            <div
+              key={upload.id}
+              className="flex items-center gap-2 py-0.5 text-[11px]"
+            >
+              <span
+                className="min-w-0 flex-1 truncate"
+                title={upload.localPath}
+              >
+                {upload.bucket}/{upload.key}
+              </span>
+              <span className="shrink-0 text-[10px] text-foreground/40 tabular-nums">
+                {formatBytes(upload.bytesUploaded)}
+                <span className="text-foreground/30"> / </span>
+                {formatBytes(upload.fileSize)}
+              </span>
+              <Button
+                type="button"
+                variant="ghost"
+                size="icon-xs"
+                className="shrink-0 text-foreground/40 hover:text-foreground"
+                disabled={!upload.localUnchanged}
+                onClick={() => resumeUpload(upload.id)}
+                title={
+                  upload.localUnchanged
+                    ? "Resume"
+                    : "The local file changed; discard and upload again"
+                }
+              >
+                <IconPlay className="size-3.5" />
+              </Button>
+              <Button
+                type="button"
+                variant="ghost"
+                size="icon-xs"
+                className="shrink-0 text-foreground/40 hover:text-destructive"
+                onClick={() => discardUpload(upload.id)}
+                title="Discard"
+              >
+                <IconTrashCan className="size-3.5" />
+              </Button>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Jobs list */}
       <div ref={listRef} className="min-h-0 flex-1 overflow-y-auto">
         {jobs.length === 0 ? (
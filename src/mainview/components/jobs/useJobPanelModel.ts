@@ -32,7 +32,7 @@ export function useJobPanelModel(jobs: JobInfo[]) {
         running.push(job);
         continue;
       }
-      if (job.status === "queued") {
+      if (job.status === "queued" || job.status === "paused") {
         active.push(job);
         queued.push(job);
         continue;
@@ -40,7 +40,8 @@ export function useJobPanelModel(jobs: JobInfo[]) {
       if (
         job.status === "completed" ||
         job.status === "failed" ||
-        job.status === "cancelled"
+        job.status === "cancelled" ||
+        job.status === "skipped"
       ) {
         completed.push(job);
       }
@@ -213,6 +213,9 @@ export function ObjectTable({
                 <span aria-hidden>{getSortIndicator("size")}</span>
               </button>
             </TableHead>
+            <TableHead className="w-32 select-none" scope="col">
+              Class
+            </TableHead>
             <TableHead
               className="w-36 select-none text-right"
               scope="col"
@@ -277,6 +280,7 @@ export function ObjectTable({
                 </div>
               </TableCell>
               <TableCell className="text-right text-foreground/40">—</TableCell>
+              <TableCell className="text-foreground/40">—</TableCell>
               <TableCell className="text-right text-foreground/40">—</TableCell>
             </ObjectContextMenu>
           ))}
@@ -344,6 +348,9 @@ export function ObjectTable({
                 <TableCell className="text-right font-mono text-foreground/60">
                   {formatBytes(obj.size)}
                 </TableCell>
+                <TableCell className="truncate font-mono text-[11px] text-foreground/50">
+                  {obj.storageClass}
+                </TableCell>
                 <TableCell className="text-right text-foreground/60">
                   {formatRelativeDate(obj.lastModified)}
                 </TableCell>
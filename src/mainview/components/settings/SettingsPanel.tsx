@@ -13,7 +13,12 @@ import {
   SheetTitle,
 } from "@/components/ui/sheet";
 import { Switch } from "@/components/ui/switch";
-import { CONCURRENCY_OPTIONS, PAGE_SIZES } from "@/lib/constants";
+import {
+  BANDWIDTH_LIMIT_OPTIONS,
+  CONCURRENCY_OPTIONS,
+  PAGE_SIZES,
+} from "@/lib/constants";
+import { formatSpeed } from "@/lib/formatters";
 import { IconGear, IconSpinner, IconTrashCan, IconXmark } from "@/lib/icons";
 import { rpcCall } from "@/lib/rpc-client";
 import { useShareHistoryStore, useThemeStore, useUIStore } from "@/stores";
@@ -34,6 +39,8 @@ export function SettingsPanel() {
   const setPersistShareHistory = useUIStore((s) => s.setPersistShareHistory);
   const jobConcurrency = useUIStore((s) => s.jobConcurrency);
   const setJobConcurrency = useUIStore((s) => s.setJobConcurrency);
+  const jobBandwidthLimit = useUIStore((s) => s.jobBandwidthLimit);
+  const setJobBandwidthLimit = useUIStore((s) => s.setJobBandwidthLimit);
   const clearShareHistory = useShareHistoryStore((s) => s.clearAll);
   const {
     hasStoredPassphrase,
@@ -252,6 +259,36 @@ export function SettingsPanel() {
                     </SelectContent>
                   </Select>
                 </div>
+                <div className="flex items-center justify-between">
+                  <div>
+                    <span className="text-sm">Bandwidth Limit</span>
+                    <p className="text-[10px] text-foreground/40">
+                      Shared evenly by running transfers
+                    </p>
+                  </div>
+                  <Select
+                    value={String(jobBandwidthLimit ?? 0)}
+                    onValueChange={(v) => {
+                      if (v == null) return;
+                      const maxBytesPerSec = Number(v) || null;
+                      setJobBandwidthLimit(maxBytesPerSec);
+                      rpcCall("jobs:set-bandwidth-limit", { maxBytesPerSec });
+                    }}
+                  >
+                    <SelectTrigger size="sm" className="w-32">
+                      <SelectValue>
+                        {(value) => bandwidthLimitLabel(Number(value))}
+                      </SelectValue>
+                    </SelectTrigger>
+                    <SelectContent>
+                      {BANDWIDTH_LIMIT_OPTIONS.map((n) => (
+                        <SelectItem key={n} value={String(n)}>
+                          {bandwidthLimitLabel(n)}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
               </div>
             </section>
 
@@ -322,3 +359,7 @@ export function SettingsPanel() {
     </Sheet>
   );
 }
+
+function bandwidthLimitLabel(bytesPerSec: number): string {
+  return bytesPerSec > 0 ? formatSpeed(bytesPerSec) : "Unlimited";
+}
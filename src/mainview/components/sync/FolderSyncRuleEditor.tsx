@@ -1,5 +1,6 @@
 import type {
   ConflictResolution,
+  FolderSyncCompareMode,
   FolderSyncRule,
   FolderSyncRuleInput,
   SyncDirection,
@@ -54,6 +55,9 @@ export function FolderSyncRuleEditor({
   );
   const [conflictResolution, setConflictResolution] =
     useState<ConflictResolution>(editRule?.conflictResolution ?? "newer-wins");
+  const [compareMode, setCompareMode] = useState<FolderSyncCompareMode>(
+    editRule?.compareMode ?? "size",
+  );
   const [pollIntervalMs, setPollIntervalMs] = useState(
     editRule?.pollIntervalMs ?? DEFAULT_POLL_INTERVAL_MS,
   );
@@ -103,6 +107,7 @@ export function FolderSyncRuleEditor({
         localPath,
         direction,
         conflictResolution,
+        compareMode,
         pollIntervalMs,
         excludePatterns: excludePatterns
           .split("\n")
@@ -287,6 +292,37 @@ export function FolderSyncRuleEditor({
         </Select>
       </div>
 
+      {/* First-sync comparison */}
+      <div className="space-y-1.5">
+        <Label className="text-xs">Existing Files on First Sync</Label>
+        <Select
+          value={compareMode}
+          onValueChange={(v) => {
+            if (v != null) setCompareMode(v as FolderSyncCompareMode);
+          }}
+        >
+          <SelectTrigger size="sm" className="w-full">
+            <SelectValue>
+              {(value) => {
+                const labels: Record<string, string> = {
+                  size: "Same size (fastest)",
+                  "mtime-size": "Same size, local not newer",
+                  etag: "Same content (hash vs ETag)",
+                };
+                return labels[value as string] ?? (value as string);
+              }}
+            </SelectValue>
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="size">Same size (fastest)</SelectItem>
+            <SelectItem value="mtime-size">
+              Same size, local not newer
+            </SelectItem>
+            <SelectItem value="etag">Same content (hash vs ETag)</SelectItem>
+          </SelectContent>
+        </Select>
+      </div>
+
       {/* Poll interval */}
       <div className="space-y-1.5">
         <Label className="text-xs">Poll Interval (for remote changes)</Label>
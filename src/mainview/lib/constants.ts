@@ -6,6 +6,16 @@ export const DEFAULT_PAGE_SIZE = 100;
 export const CONCURRENCY_OPTIONS = [1, 2, 3, 5, 8, 10] as const;
 export const DEFAULT_CONCURRENCY = 3;
 
+// Bytes per second; 0 is unlimited.
+export const BANDWIDTH_LIMIT_OPTIONS = [
+  0,
+  256 * 1024,
+  1024 * 1024,
+  5 * 1024 * 1024,
+  10 * 1024 * 1024,
+  50 * 1024 * 1024,
+] as const;
+
 export const PROVIDERS: { value: Provider; label: string }[] = [
   { value: "aws", label: "Amazon S3" },
   { value: "r2", label: "Cloudflare R2" },
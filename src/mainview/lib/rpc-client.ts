@@ -65,6 +65,27 @@ export async function rpcCall<M extends keyof RPCSchema>(
   });
 }
 
+// ── Run several RPC calls in one round-trip ──
+// Results come back in request order; one failing call doesn't fail the rest.
+export type RPCBatchCall = {
+  [M in keyof RPCSchema]: { method: M; payload: RPCSchema[M]["req"] };
+}[keyof RPCSchema];
+
+export type RPCBatchResult =
+  | { ok: true; result: unknown }
+  | { ok: false; error: string };
+
+export async function rpcBatch(
+  calls: RPCBatchCall[],
+): Promise<RPCBatchResult[]> {
+  return invoke<RPCBatchResult[]>("rpc_batch", {
+    calls: calls.map(({ method, payload }) => ({
+      method,
+      payload: payload ?? null,
+    })),
+  });
+}
+
 // ── Subscribe to backend events ──
 export function onEvent<E extends keyof RPCEvents>(
   event: E,
@@ -2,6 +2,8 @@ import type {
   FolderSyncConflict,
   FolderSyncConflictEvent,
   FolderSyncDiff,
+  FolderSyncDryRunReport,
+  FolderSyncEmptyTrashResult,
   FolderSyncErrorEvent,
   FolderSyncRule,
   FolderSyncRuleInput,
@@ -36,6 +38,7 @@ interface FolderSyncStore {
   loading: boolean;
   previewDiff: FolderSyncDiff | null;
   previewRuleId: string | null;
+  dryRunReport: FolderSyncDryRunReport | null;
 
   // ── UI state ──
   folderSyncPanelOpen: boolean;
@@ -53,6 +56,11 @@ interface FolderSyncStore {
   refreshStatuses: () => Promise<void>;
   previewRule: (id: string) => Promise<void>;
   clearPreview: () => void;
+  dryRunRule: (id: string) => Promise<FolderSyncDryRunReport>;
+  emptyTrash: (
+    id: string,
+    olderThanDays?: number,
+  ) => Promise<FolderSyncEmptyTrashResult>;
   clearConflicts: (ruleId?: string) => void;
   pickFolder: () => Promise<string | null>;
   startAll: () => Promise<void>;
@@ -81,6 +89,7 @@ export const useFolderSyncStore = create<FolderSyncStore>()((set, get) => ({
   loading: false,
   previewDiff: null,
   previewRuleId: null,
+  dryRunReport: null,
   folderSyncPanelOpen: false,
   addRuleDialogOpen: false,
   editingRule: null,
@@ -99,6 +108,7 @@ export const useFolderSyncStore = create<FolderSyncStore>()((set, get) => ({
           lastChange: data.lastChange,
           currentFile: data.currentFile,
           progress: data.progress,
+          dryRun: data.dryRun,
         }),
       }));
     });
@@ -193,7 +203,18 @@ export const useFolderSyncStore = create<FolderSyncStore>()((set, get) => ({
     set({ previewDiff: diff });
   },
 
-  clearPreview: () => set({ previewDiff: null, previewRuleId: null }),
+  clearPreview: () =>
+    set({ previewDiff: null, previewRuleId: null, dryRunReport: null }),
+
+  dryRunRule: async (id) => {
+    set({ dryRunReport: null, previewRuleId: id });
+    const report = await rpcCall("folder-sync:dry-run", { id });
+    set({ dryRunReport: report });
+    return report;
+  },
+
+  emptyTrash: (id, olderThanDays) =>
+    rpcCall("folder-sync:empty-trash", { id, olderThanDays }),
 
   clearConflicts: (ruleId) =>
     set((state) => ({
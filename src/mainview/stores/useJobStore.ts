@@ -2,6 +2,7 @@ import type {
   JobCompleteEvent,
   JobInfo,
   ProgressEvent,
+  ResumableUpload,
 } from "@shared/job.types";
 import { create } from "zustand";
 import { rpcCall } from "@/lib/rpc-client";
@@ -9,9 +10,15 @@ import { rpcCall } from "@/lib/rpc-client";
 interface JobState {
   jobs: JobInfo[];
   loading: boolean;
+  resumableUploads: ResumableUpload[];
 
   refreshJobs: () => Promise<void>;
   cancelJob: (jobId: string) => Promise<void>;
+  pauseJob: (jobId: string) => Promise<void>;
+  resumeJob: (jobId: string) => Promise<void>;
+  refreshResumableUploads: () => Promise<void>;
+  resumeUpload: (id: string) => Promise<void>;
+  discardUpload: (id: string) => Promise<void>;
   clearCompleted: () => Promise<void>;
   updateFromProgress: (event: ProgressEvent) => void;
   updateFromComplete: (event: JobCompleteEvent) => void;
@@ -20,6 +27,7 @@ interface JobState {
 export const useJobStore = create<JobState>()((set, get) => ({
   jobs: [],
   loading: false,
+  resumableUploads: [],
 
   refreshJobs: async () => {
     try {
@@ -41,6 +49,49 @@ export const useJobStore = create<JobState>()((set, get) => ({
     }
   },
 
+  pauseJob: async (jobId) => {
+    try {
+      await rpcCall("jobs:pause", { jobId });
+    } catch {
+      // Ignore
+    }
+  },
+
+  resumeJob: async (jobId) => {
+    try {
+      await rpcCall("jobs:resume", { jobId });
+    } catch {
+      // Ignore
+    }
+  },
+
+  refreshResumableUploads: async () => {
+    try {
+      const resumableUploads = await rpcCall("jobs:list-resumable", undefined);
+      set({ resumableUploads });
+    } catch {
+      // Best-effort
+    }
+  },
+
+  resumeUpload: async (id) => {
+    try {
+      await rpcCall("jobs:resume-multipart", { id });
+    } catch {
+      // Ignore
+    }
+    get().refreshResumableUploads();
+  },
+
+  discardUpload: async (id) => {
+    try {
+      await rpcCall("jobs:discard-multipart", { id });
+    } catch {
+      // Ignore
+    }
+    get().refreshResumableUploads();
+  },
+
   clearCompleted: async () => {
     try {
       await rpcCall("jobs:clear", undefined);
@@ -73,6 +124,7 @@ export const useJobStore = create<JobState>()((set, get) => ({
           speed: event.speed,
           eta: event.eta,
           error: event.error,
+          priority: event.priority,
           // Mark startedAt the first time a job moves to running
           startedAt:
             existing.startedAt ||
@@ -92,6 +144,7 @@ export const useJobStore = create<JobState>()((set, get) => ({
           speed: event.speed,
           eta: event.eta,
           error: event.error,
+          priority: event.priority,
           createdAt: new Date().toISOString(),
         });
       }
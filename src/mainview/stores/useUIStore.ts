@@ -43,6 +43,8 @@ interface UIState {
   desktopNotifications: boolean;
   persistShareHistory: boolean;
   jobConcurrency: number;
+  // Bytes per second across all transfers; null is unlimited.
+  jobBandwidthLimit: number | null;
   folderSyncPanelOpen: boolean;
   folderSyncListDensity: FolderSyncListDensity;
   objectSearchOpen: boolean;
@@ -70,6 +72,7 @@ interface UIState {
   setDesktopNotifications: (on: boolean) => void;
   setPersistShareHistory: (on: boolean) => void;
   setJobConcurrency: (n: number) => void;
+  setJobBandwidthLimit: (limit: number | null) => void;
   setFolderSyncPanelOpen: (open: boolean) => void;
   setFolderSyncListDensity: (density: FolderSyncListDensity) => void;
   setObjectSearchOpen: (open: boolean) => void;
@@ -96,6 +99,7 @@ export const useUIStore = create<UIState>()(
       desktopNotifications: true,
       persistShareHistory: false,
       jobConcurrency: 3,
+      jobBandwidthLimit: null,
       folderSyncPanelOpen: false,
       folderSyncListDensity: "comfortable",
       objectSearchOpen: false,
@@ -135,6 +139,7 @@ export const useUIStore = create<UIState>()(
       setDesktopNotifications: (on) => set({ desktopNotifications: on }),
       setPersistShareHistory: (on) => set({ persistShareHistory: on }),
       setJobConcurrency: (n) => set({ jobConcurrency: n }),
+      setJobBandwidthLimit: (limit) => set({ jobBandwidthLimit: limit }),
       setFolderSyncPanelOpen: (open) => set({ folderSyncPanelOpen: open }),
       setFolderSyncListDensity: (density) =>
         set({ folderSyncListDensity: density }),
@@ -151,6 +156,7 @@ export const useUIStore = create<UIState>()(
         desktopNotifications: state.desktopNotifications,
         persistShareHistory: state.persistShareHistory,
         jobConcurrency: state.jobConcurrency,
+        jobBandwidthLimit: state.jobBandwidthLimit,
         syncEntryPreference: state.syncEntryPreference,
         folderSyncListDensity: state.folderSyncListDensity,
       }),
@@ -1,6 +1,9 @@
 // ── Folder Sync Types ──
 // Bidirectional local ↔ S3 sync (like Google Drive / rclone bisync)
 
+import type { KeyTransform } from "./profile.types";
+import type { ArchivedObjectPolicy } from "./s3.types";
+
 export type SyncDirection =
   | "bidirectional"
   | "local-to-remote"
@@ -17,7 +20,10 @@ export type FolderSyncRuleStatus =
   | "syncing"
   | "watching"
   | "error"
-  | "paused";
+  | "paused"
+  | "dry-run" // rule only reports what it would do
+  | "manual" // manual-only rule waiting for sync-now
+  | "scheduled"; // scheduled rule waiting for its next run
 
 // ── Persisted sync rule ──
 export interface FolderSyncRule {
@@ -30,7 +36,27 @@ export interface FolderSyncRule {
   enabled: boolean;
   conflictResolution: ConflictResolution;
   pollIntervalMs: number; // default 30000 (30s)
+  maxPollIntervalMs?: number | null; // adaptive polling ceiling, default 600000 (10m)
   excludePatterns: string[]; // e.g. [".DS_Store", "thumbs.db", ".git/**"]
+  syncConcurrency: number; // max parallel transfers per pass, default 4
+  conflictRenameTemplate: string; // keep-both local copy name, e.g. "{name}.conflict-{timestamp}{ext}"
+  notifyOnSync: boolean; // desktop notification after passes with changes/conflicts
+  allowDeleteLocal: boolean; // propagate remote deletions locally, default true
+  allowDeleteRemote: boolean; // propagate local deletions to the bucket, default true
+  safeDelete?: boolean; // deletions go to .object0-trash instead of being permanent
+  dryRun?: boolean; // passes report via folder-sync:dry-run, transfer nothing
+  // Remote keys are <prefix><bucketPrefix><path><suffix>; {host} only, and
+  // it's replaced with this machine's hostname when the rule is saved.
+  keyTransform?: KeyTransform | null;
+  archivedObjects?: ArchivedObjectPolicy; // default "skip": no archived downloads
+  // default "skip"; "rename" downloads as "<name> (case <id>)<ext>"
+  caseCollisions?: CaseCollisionPolicy;
+  compareMode?: FolderSyncCompareMode; // default "size"
+  manualOnly?: boolean; // passes only on folder-sync:sync-now, no polling
+  manualWatch?: boolean; // keep the watcher for lastChange on manual rules
+  // cron in local time, e.g. "0 2 * * *"; runs only then, no polling/watcher
+  schedule?: string | null;
+  symlinkPolicy?: SymlinkPolicy; // default "skip"
   lastSyncAt?: string; // ISO timestamp
   lastSyncStatus?: "success" | "error" | "partial";
   lastSyncError?: string;
@@ -44,12 +70,16 @@ export interface FolderSyncState {
   filesWatching: number;
   lastChange?: string;
   currentFile?: string;
+  // Wait between passes; above pollIntervalMs when a large, quiet remote
+  // has backed off.
+  effectivePollIntervalMs?: number | null;
   progress?: {
     completed: number;
     total: number;
     bytesTransferred: number;
     bytesTotal: number;
   };
+  dryRun?: boolean; // progress of a folder-sync:dry-run
 }
 
 // ── Per-file tracking record (persisted per rule) ──
@@ -69,7 +99,19 @@ export type FolderSyncAction =
   | "download"
   | "delete-local"
   | "delete-remote"
-  | "conflict";
+  | "conflict"
+  | "skip"; // archived or case-colliding remote object left alone
+
+// Keys differing only by case on a case-insensitive local filesystem.
+export type CaseCollisionPolicy = "skip" | "rename";
+
+// Symlinks under the local folder: left out, walked like real files and
+// folders, or (copy-target) symlinked files synced as their targets.
+export type SymlinkPolicy = "skip" | "follow" | "copy-target";
+
+// How a file both sides have, with no sync record yet, counts as in sync:
+// equal size; equal size and local not newer; or local MD5 matches the ETag.
+export type FolderSyncCompareMode = "size" | "etag" | "mtime-size";
 
 export interface FolderSyncDiffEntry {
   relativePath: string;
@@ -80,6 +122,7 @@ export interface FolderSyncDiffEntry {
   remoteSize?: number;
   remoteLastModified?: string;
   remoteEtag?: string;
+  localRelativePath?: string | null; // renamed copy of a case-colliding key
 }
 
 export interface FolderSyncDiff {
@@ -88,9 +131,50 @@ export interface FolderSyncDiff {
   deleteLocal: FolderSyncDiffEntry[];
   deleteRemote: FolderSyncDiffEntry[];
   conflicts: FolderSyncDiffEntry[];
+  skipped: FolderSyncDiffEntry[]; // archived or case-colliding downloads
+  // Paths equal but for case; the first in each set keeps its name.
+  caseCollisions: string[][];
+  symlinkCycles: string[]; // looping symlinks left unfollowed
   unchanged: number;
 }
 
+// folder-sync:dry-run: what a pass would do, in the order it would do it.
+export interface FolderSyncResolvedAction {
+  relativePath: string;
+  action: FolderSyncAction;
+  reason: string;
+  target: string | null; // local path or s3:// URI written or removed
+}
+
+// folder-sync:empty-trash: what was purged from each side's trash.
+export interface FolderSyncEmptyTrashResult {
+  localFiles: number;
+  remoteObjects: number;
+}
+
+export interface FolderSyncDryRunReport {
+  diff: FolderSyncDiff; // after conflict resolution, keep-both included
+  actions: FolderSyncResolvedAction[];
+}
+
+// A pending change with what the last successful sync recorded for the file.
+// `synced*` fields are absent when the file has no sync record ("untracked").
+export interface FolderSyncStatusDetailEntry extends FolderSyncDiffEntry {
+  origin: "local" | "remote" | "both" | "untracked";
+  lastSyncedAt?: string;
+  syncedLocalSize?: number;
+  syncedLocalMtime?: number;
+  syncedRemoteSize?: number;
+  syncedRemoteEtag?: string;
+}
+
+export interface FolderSyncStatusDetail {
+  files: FolderSyncStatusDetailEntry[];
+  diff: FolderSyncDiff;
+  trackedFiles: number;
+  lastSyncedAt: string | null; // most recent record, ISO
+}
+
 // ── RPC request/response types ──
 export interface FolderSyncRuleInput {
   profileId: string;
@@ -100,7 +184,36 @@ export interface FolderSyncRuleInput {
   direction: SyncDirection;
   conflictResolution: ConflictResolution;
   pollIntervalMs?: number;
+  maxPollIntervalMs?: number | null;
   excludePatterns?: string[];
+  syncConcurrency?: number;
+  conflictRenameTemplate?: string;
+  notifyOnSync?: boolean;
+  allowDeleteLocal?: boolean;
+  allowDeleteRemote?: boolean;
+  safeDelete?: boolean;
+  dryRun?: boolean;
+  keyTransform?: KeyTransform | null;
+  archivedObjects?: ArchivedObjectPolicy;
+  caseCollisions?: CaseCollisionPolicy;
+  compareMode?: FolderSyncCompareMode;
+  manualOnly?: boolean;
+  manualWatch?: boolean;
+  schedule?: string | null;
+  symlinkPolicy?: SymlinkPolicy;
+}
+
+// ── Rule export/import ──
+export interface FolderSyncImportSkipped {
+  bucket?: string;
+  bucketPrefix?: string;
+  localPath?: string;
+  reason: string;
+}
+
+export interface FolderSyncImportResult {
+  imported: FolderSyncRule[]; // added disabled, for review before enabling
+  skipped: FolderSyncImportSkipped[];
 }
 
 export interface FolderSyncConflict {
@@ -120,12 +233,16 @@ export interface FolderSyncStatusEvent {
   lastChange?: string;
   filesWatching: number;
   currentFile?: string;
+  // Wait between passes; above pollIntervalMs when a large, quiet remote
+  // has backed off.
+  effectivePollIntervalMs?: number | null;
   progress?: {
     completed: number;
     total: number;
     bytesTransferred: number;
     bytesTotal: number;
   };
+  dryRun?: boolean; // progress of a folder-sync:dry-run
 }
 
 export interface FolderSyncConflictEvent {
@@ -137,6 +254,20 @@ export interface FolderSyncConflictEvent {
   remoteLastModified: string;
 }
 
+// Sent after each dry-run pass with what it would have done.
+export interface FolderSyncDryRunEvent {
+  ruleId: string;
+  at: string; // ISO
+  diff: FolderSyncDiff;
+}
+
+export interface FolderSyncWarningEvent {
+  ruleId: string;
+  kind: "case-collision";
+  paths: string[];
+  message: string;
+}
+
 export interface FolderSyncErrorEvent {
   ruleId: string;
   error: string;
@@ -14,7 +14,12 @@ export type JobStatus =
   | "running"
   | "completed"
   | "failed"
-  | "cancelled";
+  | "cancelled"
+  | "skipped" // e.g. a download onto an existing file; `error` says why
+  | "paused"; // uploads and downloads only; jobs:resume queues it again
+
+// Why a cancelled job stopped; `error` carries the matching message.
+export type CancelReason = "user" | "vault-locked" | "abort-all" | "shutdown";
 
 // ── Job info ──
 export interface JobInfo {
@@ -29,6 +34,8 @@ export interface JobInfo {
   speed: number;
   eta: number;
   error?: string;
+  cancelReason?: CancelReason | null;
+  priority: number; // higher starts first; 0 by default
   createdAt: string;
   startedAt?: string;
   completedAt?: string;
@@ -46,6 +53,16 @@ export interface ProgressEvent {
   speed: number;
   eta: number;
   error?: string;
+  priority: number;
+}
+
+// ── Archive progress event ──
+// Per-entry progress for archive downloads; currentFile is the entry path.
+export interface ArchiveProgressEvent {
+  jobId: string;
+  currentFile: string;
+  filesCompleted: number;
+  filesTotal: number;
 }
 
 // ── Job complete event ──
@@ -54,4 +71,33 @@ export interface JobCompleteEvent {
   fileName?: string;
   success: boolean;
   error?: string;
+  cancelReason?: CancelReason | null;
+}
+
+// ── Interrupted multipart uploads ──
+// Left behind when the app quit or crashed mid-upload; `id` names the saved
+// state for jobs:resume-multipart and jobs:discard-multipart.
+export interface ResumableUpload {
+  id: string;
+  jobId: string; // the job that was running; gone since the restart
+  profileId: string;
+  bucket: string;
+  key: string;
+  localPath: string;
+  fileSize: number;
+  bytesUploaded: number;
+  createdAt: string;
+  localUnchanged: boolean; // false: the file changed, only discard works
+}
+
+// ── Running-job limit ──
+export interface JobConcurrencyInfo {
+  concurrency: number; // manual limit; the ceiling when adaptive is on
+  adaptive: boolean;
+  effectiveConcurrency: number;
+}
+
+// ── Transfer rate cap, shared evenly by running uploads and downloads ──
+export interface JobBandwidthLimitInfo {
+  maxBytesPerSec: number | null; // null: unlimited
 }
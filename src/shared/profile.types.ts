@@ -36,6 +36,17 @@ export const PROVIDER_REGIONS: Record<Provider, string> = {
   custom: "us-east-1",
 };
 
+// ── Upload key transform ──
+// Wrapped around uploaded keys: `{host}` is this machine's hostname,
+// `{date}` today's UTC YYYY-MM-DD. e.g. { prefix: "{host}/{date}/" }
+export interface KeyTransform {
+  prefix?: string; // must not start with "/"
+  suffix?: string; // must not contain "/"
+}
+
+// Server-side encryption for uploads.
+export type UploadSse = "AES256" | "aws:kms";
+
 // ── Full profile (stored encrypted in vault) ──
 export interface Profile {
   id: string;
@@ -47,6 +58,20 @@ export interface Profile {
   endpoint?: string;
   region?: string;
   defaultBucket?: string;
+  requesterPays?: boolean; // send x-amz-request-payer: requester
+  ephemeral?: boolean; // secret is re-entered each session, never saved
+  keyTransform?: KeyTransform | null; // uploads only, not folder sync
+  // CRC32 on every upload, whatever the SDK default is
+  requireChecksums?: boolean;
+  // Checksums only where required (retried once if refused), for providers
+  // that reject the SDK's default ones
+  disableChecksums?: boolean;
+  // AWS only, no custom endpoint. Accelerate needs it enabled on the bucket.
+  useAccelerate?: boolean;
+  useDualstack?: boolean; // IPv6-capable endpoints
+  // Default encryption for uploads; an upload's own `sse` replaces both.
+  sse?: UploadSse | null;
+  sseKmsKeyId?: string | null;
   createdAt: string;
   updatedAt: string;
 }
@@ -59,8 +84,22 @@ export interface ProfileInfo {
   endpoint?: string;
   region?: string;
   defaultBucket?: string;
+  requesterPays?: boolean;
+  ephemeral?: boolean;
+  keyTransform?: KeyTransform | null;
+  requireChecksums?: boolean;
+  disableChecksums?: boolean;
+  useAccelerate?: boolean;
+  useDualstack?: boolean;
+  sse?: UploadSse | null;
+  sseKmsKeyId?: string | null;
+  // Ephemeral and no secret entered this session; operations fail with a
+  // "NeedsCredentials:" error until profile:provide-credentials is called.
+  needsCredentials?: boolean;
   createdAt: string;
   updatedAt: string;
+  // Set by profile:add/update when an AWS profile's region is unknown.
+  regionWarning?: string;
 }
 
 // ── Profile input for creating/updating ──
@@ -73,6 +112,28 @@ export interface ProfileInput {
   endpoint?: string;
   region?: string;
   defaultBucket?: string;
+  requesterPays?: boolean;
+  ephemeral?: boolean;
+  keyTransform?: KeyTransform | null; // empty prefix + suffix clears it
+  requireChecksums?: boolean;
+  disableChecksums?: boolean;
+  useAccelerate?: boolean;
+  useDualstack?: boolean;
+  // On update: omitted keeps the default, "" clears it.
+  sse?: UploadSse | "";
+  sseKmsKeyId?: string;
+}
+
+// ── AWS CLI profile offered by profile:import-aws ──
+// Only sections with static keys; the secret stays in ~/.aws until the
+// import is confirmed.
+export interface AwsProfileCandidate {
+  name: string;
+  provider: Provider; // guessed from the endpoint; "aws" without one
+  accessKeyId: string;
+  region: string | null;
+  endpoint: string | null;
+  hasSessionToken: boolean;
 }
 
 // ── Strip secrets from profile for UI ──
@@ -84,7 +145,39 @@ export function toProfileInfo(profile: Profile): ProfileInfo {
     endpoint: profile.endpoint,
     region: profile.region,
     defaultBucket: profile.defaultBucket,
+    requesterPays: profile.requesterPays,
+    ephemeral: profile.ephemeral,
+    keyTransform: profile.keyTransform,
+    requireChecksums: profile.requireChecksums,
+    disableChecksums: profile.disableChecksums,
+    useAccelerate: profile.useAccelerate,
+    useDualstack: profile.useDualstack,
+    sse: profile.sse,
+    sseKmsKeyId: profile.sseKmsKeyId,
+    needsCredentials: !!profile.ephemeral && !profile.secretAccessKey,
     createdAt: profile.createdAt,
     updatedAt: profile.updatedAt,
   };
 }
+
+// ── Provider capability probing ──
+export type ProviderFeature =
+  | "multipart"
+  | "versioning"
+  | "tagging"
+  | "presign"
+  | "object-lock"
+  | "checksums";
+
+export interface ProviderProbeResult {
+  bucket: string;
+  supported: boolean | null; // null when the probe couldn't tell (e.g. access denied)
+  detail: string;
+  probedAt: string; // ISO timestamp
+}
+
+export interface ProviderCapabilities {
+  profileId: string;
+  bucket: string;
+  features: Partial<Record<ProviderFeature, ProviderProbeResult>>;
+}
@@ -1,31 +1,92 @@
 import type {
   FolderSyncConflictEvent,
   FolderSyncDiff,
+  FolderSyncDryRunEvent,
+  FolderSyncDryRunReport,
+  FolderSyncEmptyTrashResult,
   FolderSyncErrorEvent,
+  FolderSyncImportResult,
   FolderSyncRule,
   FolderSyncRuleInput,
   FolderSyncState,
+  FolderSyncStatusDetail,
   FolderSyncStatusEvent,
+  FolderSyncWarningEvent,
 } from "./folder-sync.types";
-import type { JobCompleteEvent, JobInfo, ProgressEvent } from "./job.types";
-import type { ProfileInfo, ProfileInput } from "./profile.types";
 import type {
+  ArchiveProgressEvent,
+  JobBandwidthLimitInfo,
+  JobCompleteEvent,
+  JobConcurrencyInfo,
+  JobInfo,
+  ProgressEvent,
+  ResumableUpload,
+} from "./job.types";
+import type {
+  AwsProfileCandidate,
+  ProfileInfo,
+  ProfileInput,
+  ProviderCapabilities,
+  ProviderFeature,
+} from "./profile.types";
+import type {
+  BucketCorsRule,
   BucketInfo,
+  BucketLifecycleRule,
+  CannedAcl,
   CopyReq,
   CrossTransferReq,
+  DeletePrefixReq,
+  DeletePrefixRes,
+  DiffPrefixesReq,
+  DiffPrefixesRes,
   DownloadArchiveReq,
   DownloadFolderReq,
   DownloadReq,
+  DownloadSelectionReq,
   MoveReq,
+  ObjectAcl,
+  ObjectAclGrant,
+  ObjectChunkDoneEvent,
+  ObjectChunkEvent,
   ObjectListReq,
   ObjectListRes,
+  ObjectRestoreStatus,
+  ObjectSearchReq,
+  ObjectSearchRes,
+  ObjectVersionListRes,
+  OpenExternalReq,
+  OpenExternalRes,
+  RestoreTier,
+  S3ExistsResult,
+  S3PreviewReq,
+  S3PreviewResult,
   S3StatResult,
+  S3TextResult,
+  S3UriParts,
   ShareReq,
   ShareRes,
+  StreamRangeReq,
   SyncDiff,
   SyncReq,
+  UploadFolderArchiveReq,
   UploadReq,
 } from "./s3.types";
+import type { AppSettings, EffectiveSettings } from "./settings.types";
+
+// A saved bucket/prefix location; prefix is "" for the bucket root.
+export interface Bookmark {
+  profileId: string;
+  bucket: string;
+  prefix: string;
+  label?: string | null;
+}
+
+export interface NavLocation {
+  profileId: string;
+  bucket: string;
+  prefix: string;
+}
 
 type ProfileUpdateReq = Omit<
   ProfileInput,
@@ -41,22 +102,39 @@ export type KeychainUnlockFailureReason =
   | "vault_missing"
   | "no_stored_passphrase"
   | "stale_stored_passphrase"
-  | "keychain_unavailable";
+  | "keychain_unavailable"
+  | "corrupt"; // vault file is damaged, not a passphrase problem
+
+// Passphrase key derivation for vault:setup; vaults default to argon2id.
+export type VaultKdf = "pbkdf2" | "argon2id";
 
 // ── RPC Schema: maps method names → request/response types ──
 export interface RPCSchema {
   // ── Vault ──
   "vault:status": {
     req: undefined;
-    res: { exists: boolean; unlocked: boolean; hasRecoveryKey: boolean };
+    res: {
+      exists: boolean;
+      unlocked: boolean;
+      hasRecoveryKey: boolean;
+      // Unlocked via recovery key: read-only until vault:change-passphrase.
+      needsNewPassphrase: boolean;
+    };
   };
   "vault:setup": {
-    req: { passphrase: string; remember?: boolean };
+    req: { passphrase: string; remember?: boolean; kdf?: VaultKdf };
     res: { success: boolean; recoveryKey?: string };
   };
   "vault:unlock": {
     req: { passphrase: string; remember?: boolean };
-    res: { success: boolean; profiles: ProfileInfo[]; hasRecoveryKey: boolean };
+    res: {
+      success: boolean;
+      profiles: ProfileInfo[];
+      hasRecoveryKey: boolean;
+      recoveryKeyMissing?: boolean;
+      reason?: "corrupt"; // absent on a wrong passphrase
+      detail?: string;
+    };
   };
   "vault:auto-unlock": {
     req: undefined;
@@ -64,6 +142,7 @@ export interface RPCSchema {
       success: boolean;
       profiles: ProfileInfo[];
       hasRecoveryKey: boolean;
+      recoveryKeyMissing?: boolean;
       reason?: KeychainUnlockFailureReason;
       detail?: string;
     };
@@ -74,6 +153,7 @@ export interface RPCSchema {
       success: boolean;
       profiles: ProfileInfo[];
       hasRecoveryKey: boolean;
+      recoveryKeyMissing?: boolean;
       reason?: KeychainUnlockFailureReason;
       detail?: string;
     };
@@ -93,7 +173,11 @@ export interface RPCSchema {
   };
   "vault:recover-key": {
     req: { recoveryKey: string };
-    res: { success: boolean; profiles: ProfileInfo[] };
+    res: {
+      success: boolean;
+      profiles: ProfileInfo[];
+      needsNewPassphrase?: boolean;
+    };
   };
   "vault:change-passphrase": {
     req: { newPassphrase: string; remember?: boolean };
@@ -107,6 +191,15 @@ export interface RPCSchema {
     req: undefined;
     res: { hasRecoveryKey: boolean };
   };
+  "vault:get-auto-lock": {
+    req: undefined;
+    res: { autoLockSecs: number | null; minSecs: number };
+  };
+  // Persisted as settings.autoLockSecs; null turns auto-lock off.
+  "vault:set-auto-lock": {
+    req: { secs: number | null };
+    res: { autoLockSecs: number | null };
+  };
   "vault:reset": {
     req: undefined;
     res: { success: boolean };
@@ -115,11 +208,28 @@ export interface RPCSchema {
   // ── Profiles ──
   "profile:list": { req: undefined; res: ProfileInfo[] };
   "profile:add": { req: ProfileInput; res: ProfileInfo };
+  // Reads ~/.aws/credentials and ~/.aws/config (or AWS_SHARED_CREDENTIALS_FILE
+  // / AWS_CONFIG_FILE); nothing is stored until the confirm call.
+  "profile:import-aws": { req: undefined; res: AwsProfileCandidate[] };
+  "profile:import-aws-confirm": {
+    req: { names: string[] };
+    res: ProfileInfo[];
+  };
   "profile:update": {
     req: ProfileUpdateReq;
     res: ProfileInfo;
   };
   "profile:remove": { req: { id: string }; res: undefined };
+  // Ephemeral profiles only; kept in memory until the vault locks.
+  "profile:provide-credentials": {
+    req: {
+      id: string;
+      accessKeyId?: string;
+      secretAccessKey: string;
+      sessionToken?: string;
+    };
+    res: ProfileInfo;
+  };
   "profile:test": {
     req: {
       provider: string;
@@ -128,19 +238,109 @@ export interface RPCSchema {
       accessKeyId: string;
       secretAccessKey: string;
       defaultBucket?: string;
+      useAccelerate?: boolean; // checked against defaultBucket when set
+      useDualstack?: boolean;
+    };
+    res: {
+      success: boolean;
+      bucketCount: number;
+      error?: string;
+      regionWarning?: string | null; // unknown or missing AWS region
     };
-    res: { success: boolean; bucketCount: number; error?: string };
+  };
+
+  // Known AWS regions for the region picker.
+  "provider:regions": {
+    req: undefined;
+    res: { regions: string[]; defaultRegion: string };
+  };
+
+  // Results are cached per profile; `refresh` probes again.
+  "provider:probe": {
+    req: {
+      profileId: string;
+      bucket?: string;
+      feature?: ProviderFeature;
+      refresh?: boolean;
+    };
+    res: ProviderCapabilities;
   };
 
   // ── Buckets ──
   "buckets:list": { req: { profileId: string }; res: BucketInfo[] };
+  // Providers without CORS/policy/lifecycle support fail with
+  // "Unsupported: ...".
+  "buckets:get-cors": {
+    req: { profileId: string; bucket: string };
+    res: { rules: BucketCorsRule[] };
+  };
+  // Empty `rules` deletes the CORS configuration.
+  "buckets:set-cors": {
+    req: { profileId: string; bucket: string; rules: BucketCorsRule[] };
+    res: undefined;
+  };
+  "buckets:get-policy": {
+    req: { profileId: string; bucket: string };
+    res: { policy: string | null }; // raw JSON, null when unset
+  };
+  // `policy` must parse as a JSON object; null or "" deletes it.
+  "buckets:set-policy": {
+    req: { profileId: string; bucket: string; policy: string | null };
+    res: undefined;
+  };
+  "buckets:get-lifecycle": {
+    req: { profileId: string; bucket: string };
+    res: { rules: BucketLifecycleRule[] };
+  };
+  // Empty `rules` deletes the lifecycle configuration.
+  "buckets:set-lifecycle": {
+    req: {
+      profileId: string;
+      bucket: string;
+      rules: BucketLifecycleRule[];
+    };
+    res: undefined;
+  };
 
   // ── Objects ──
   "objects:list": { req: ObjectListReq; res: ObjectListRes };
+  // Asking again while a restore runs is not an error.
+  "objects:restore": {
+    req: {
+      profileId: string;
+      bucket: string;
+      key: string;
+      days: number; // 1-365
+      tier: RestoreTier;
+    };
+    res: ObjectRestoreStatus;
+  };
+  "objects:restore-status": {
+    req: { profileId: string; bucket: string; key: string };
+    res: ObjectRestoreStatus;
+  };
+  // Writes an empty "<prefix>/" marker; fails if it already exists.
+  "objects:create-folder": {
+    req: { profileId: string; bucket: string; prefix: string };
+    res: { key: string };
+  };
+  "objects:list-versions": {
+    req: { profileId: string; bucket: string; prefix?: string };
+    res: ObjectVersionListRes;
+  };
+  // `versionId` removes that version for good (single key only) instead of
+  // adding a delete marker.
   "objects:delete": {
-    req: { profileId: string; bucket: string; keys: string[] };
+    req: {
+      profileId: string;
+      bucket: string;
+      keys: string[];
+      versionId?: string;
+    };
     res: undefined;
   };
+  // Two-step: see DeletePrefixReq.
+  "objects:delete-prefix": { req: DeletePrefixReq; res: DeletePrefixRes };
   "objects:rename": {
     req: {
       profileId: string;
@@ -150,26 +350,96 @@ export interface RPCSchema {
     };
     res: undefined;
   };
+  "objects:get-acl": {
+    req: { profileId: string; bucket: string; key: string };
+    res: ObjectAcl;
+  };
+  // Pass exactly one of `acl` or `grants`; grants replace the whole ACL.
+  "objects:set-acl": {
+    req: {
+      profileId: string;
+      bucket: string;
+      key: string;
+      acl?: CannedAcl;
+      grants?: ObjectAclGrant[];
+    };
+    res: undefined;
+  };
+  "objects:get-tags": {
+    req: { profileId: string; bucket: string; key: string };
+    res: Record<string, string>;
+  };
+  // Replaces the whole tag set; an empty map removes every tag.
+  "objects:set-tags": {
+    req: {
+      profileId: string;
+      bucket: string;
+      key: string;
+      tags: Record<string, string>;
+    };
+    res: undefined;
+  };
   "objects:stat": {
     req: { profileId: string; bucket: string; key: string };
     res: S3StatResult;
   };
+  "objects:exists": {
+    req: { profileId: string; bucket: string; key: string };
+    res: S3ExistsResult;
+  };
+  "objects:preview": { req: S3PreviewReq; res: S3PreviewResult };
+  // Safe for the clipboard when isUtf8 && !truncated.
+  "objects:get-text": {
+    req: { profileId: string; bucket: string; key: string; maxBytes?: number };
+    res: S3TextResult;
+  };
+  // Sends object:chunk events, then object:chunk-done; cancellable.
+  "objects:stream-range": { req: StreamRangeReq; res: ObjectChunkDoneEvent };
+  // Temp file is removed after an hour or on the next launch.
+  "objects:open-external": { req: OpenExternalReq; res: OpenExternalRes };
+  // Read-only; nothing is enqueued.
+  "objects:diff-prefixes": { req: DiffPrefixesReq; res: DiffPrefixesRes };
+  "objects:search": { req: ObjectSearchReq; res: ObjectSearchRes };
 
   // ── Transfers ──
   "transfer:upload": { req: UploadReq; res: { jobId: string } };
   "transfer:pick-and-upload": {
-    req: { profileId: string; bucket: string; prefix: string };
-    res: { jobIds: string[] };
+    req: {
+      profileId: string;
+      bucket: string;
+      prefix: string;
+      overwrite?: boolean;
+    };
+    // `skipped` lists keys left alone because they exist and overwrite is off.
+    res: { jobIds: string[]; skipped: string[] };
   };
   "transfer:pick-and-upload-folder": {
-    req: { profileId: string; bucket: string; prefix: string };
-    res: { jobIds: string[] };
+    req: {
+      profileId: string;
+      bucket: string;
+      prefix: string;
+      overwrite?: boolean;
+      followSymlinks?: boolean; // default false, like folder sync
+      includeEmptyDirs?: boolean; // zero-byte `dir/` markers; default false
+    };
+    // `skipped` lists keys left alone because they exist and overwrite is off;
+    // `skippedFiles` lists local entries that aren't regular files.
+    res: {
+      jobIds: string[];
+      skipped: string[];
+      skippedFiles: SkippedLocalFile[];
+    };
   };
   "transfer:download": { req: DownloadReq; res: { jobId: string } };
   "transfer:download-folder": {
     req: DownloadFolderReq;
     res: { jobIds: string[] };
   };
+  "transfer:download-selection": {
+    req: DownloadSelectionReq;
+    // `skipped` lists keys whose path would leave the destination folder.
+    res: { jobIds: string[]; skipped: string[] };
+  };
   "transfer:copy": { req: CopyReq; res: { jobId: string } };
   "transfer:move": { req: MoveReq; res: { jobId: string } };
   "transfer:cross-bucket": {
@@ -180,6 +450,11 @@ export interface RPCSchema {
     req: DownloadArchiveReq;
     res: { jobId: string };
   };
+  "transfer:upload-folder-archive": {
+    req: UploadFolderArchiveReq;
+    // `jobId` is null when the folder picker was cancelled.
+    res: { jobId: string | null };
+  };
 
   // ── Sync ──
   "sync:preview": { req: SyncReq; res: SyncDiff };
@@ -191,19 +466,61 @@ export interface RPCSchema {
   // ── Jobs ──
   "jobs:list": { req: undefined; res: JobInfo[] };
   "jobs:cancel": { req: { jobId: string }; res: undefined };
+  "jobs:pause": { req: { jobId: string }; res: undefined };
+  "jobs:resume": { req: { jobId: string }; res: undefined };
+  // Same job id: the row goes back to queued rather than a new job appearing.
+  "jobs:retry": { req: { jobId: string }; res: { jobId: string } };
+  "jobs:retry-all-failed": { req: undefined; res: { jobIds: string[] } };
+  "jobs:set-priority": {
+    req: { jobId: string; priority: number };
+    res: undefined;
+  };
+  "jobs:list-resumable": { req: undefined; res: ResumableUpload[] };
+  "jobs:resume-multipart": { req: { id: string }; res: { jobId: string } };
+  "jobs:discard-multipart": { req: { id: string }; res: undefined };
   "jobs:clear": { req: undefined; res: undefined };
-  "jobs:get-concurrency": { req: undefined; res: { concurrency: number } };
+  "jobs:get-concurrency": { req: undefined; res: JobConcurrencyInfo };
   "jobs:set-concurrency": {
     req: { concurrency: number };
-    res: { concurrency: number };
+    res: JobConcurrencyInfo;
+  };
+  "jobs:set-adaptive-concurrency": {
+    req: { enabled: boolean };
+    res: JobConcurrencyInfo;
+  };
+  "jobs:get-bandwidth-limit": { req: undefined; res: JobBandwidthLimitInfo };
+  "jobs:set-bandwidth-limit": {
+    req: { maxBytesPerSec: number | null }; // raised to at least 64 KB/s
+    res: JobBandwidthLimitInfo;
   };
 
+  // ── Settings ──
+  "settings:get": { req: undefined; res: AppSettings };
+  "settings:set": { req: Partial<AppSettings>; res: AppSettings };
+  "settings:effective": { req: undefined; res: EffectiveSettings };
+
   // ── Favorites ──
   "favorites:load": { req: undefined; res: string[] };
   "favorites:save": { req: { favorites: string[] }; res: undefined };
+  // Structured bookmarks; the first load migrates the flat favorites list.
+  "bookmarks:load": { req: undefined; res: Bookmark[] };
+  "bookmarks:save": { req: { bookmarks: Bookmark[] }; res: undefined };
+
+  // ── Last browsed location ──
+  // Without a profileId, returns the most recently browsed profile's spot.
+  "nav:get-last": {
+    req: { profileId?: string } | undefined;
+    res: NavLocation | null;
+  };
+  "nav:set-last": { req: NavLocation; res: undefined };
 
   // ── Share ──
   "share:generate": { req: ShareReq; res: ShareRes };
+  // Cancels an in-flight call that was given this operationId.
+  "operation:cancel": {
+    req: { operationId: string };
+    res: { cancelled: boolean };
+  };
 
   // ── Folder Sync ──
   "folder-sync:list-rules": { req: undefined; res: FolderSyncRule[] };
@@ -226,7 +543,30 @@ export interface RPCSchema {
     req: { id: string };
     res: FolderSyncDiff;
   };
+  "folder-sync:dry-run": {
+    req: { id: string };
+    res: FolderSyncDryRunReport;
+  };
+  "folder-sync:empty-trash": {
+    req: { id: string; olderThanDays?: number }; // default 30; 0 purges all
+    res: FolderSyncEmptyTrashResult;
+  };
+  // Preview plus per-file last-synced records; doesn't sync or re-baseline.
+  "folder-sync:status-detail": {
+    req: { id: string };
+    res: FolderSyncStatusDetail;
+  };
   "folder-sync:pick-folder": { req: undefined; res: { path: string | null } };
+  // Both prompt for a file when `path` is omitted; a cancelled dialog is a no-op.
+  "folder-sync:export-rules": {
+    req: { path?: string } | undefined;
+    res: { path: string | null; count: number };
+  };
+  "folder-sync:import-rules": {
+    // Exported profile id → local profile id; unmapped rules match by profile name.
+    req: { path?: string; profileMap?: Record<string, string> };
+    res: FolderSyncImportResult;
+  };
   "folder-sync:start-all": { req: undefined; res: undefined };
   "folder-sync:stop-all": { req: undefined; res: undefined };
   "folder-sync:pause-all": { req: undefined; res: undefined };
@@ -262,12 +602,100 @@ export interface RPCSchema {
     req: undefined;
     res: { os: "macos" | "windows" | "linux" };
   };
+  // `metered` is null until detected or where detection is unsupported.
+  "system:network-status": {
+    req: undefined;
+    res: {
+      meteredDetectionSupported: boolean;
+      metered: boolean | null;
+      syncPausedForMetered: boolean;
+    };
+  };
+  "util:parse-s3-uri": { req: { uri: string }; res: S3UriParts };
+  // Free space on the volume holding `path` (which need not exist yet).
+  "util:disk-space": {
+    req: { path: string };
+    res: { path: string; availableBytes: number };
+  };
+  // Kill switch: cancels all jobs and stops every folder-sync rule.
+  "app:abort-all": { req: undefined; res: AbortAllSummary };
+  // Diagnostic bundle for bug reports; contains no secrets.
+  "app:self-test": {
+    req: undefined;
+    res: {
+      ok: boolean;
+      appVersion: string;
+      os: string;
+      arch: string;
+      configDir: {
+        path: string | null;
+        writable: boolean;
+        error: string | null;
+      };
+      keychain: {
+        available: boolean;
+        hasStoredPassphrase: boolean;
+        error: string | null;
+      };
+      vault: {
+        exists: boolean;
+        version: number | null;
+        currentVersion: number;
+        unlocked: boolean;
+        error: string | null;
+      };
+      updater: {
+        baseUrl: string;
+        channel: string;
+        reachable: boolean;
+        latencyMs: number | null;
+        error: string | null;
+      };
+    };
+  };
+  // Legacy config folder to offer migrating from; null unless the current
+  // config folder is still empty.
+  "app:legacy-config": { req: undefined; res: { source: string | null } };
+  // Copies config from a legacy folder (default: the first one found) without
+  // overwriting; `removeLegacy` then deletes originals that match their copy.
+  "app:migrate-legacy-config": {
+    req: { sourcePath?: string; removeLegacy?: boolean } | undefined;
+    res: {
+      source: string | null;
+      copied: string[];
+      skipped: string[]; // already in the current folder
+      removed: string[];
+    };
+  };
+  // Salvages entries from a corrupt job-history.json after backing it up.
+  "maintenance:repair-job-history": {
+    req: undefined;
+    res: {
+      corrupt: boolean;
+      recovered: number;
+      backupPath: string | null;
+    };
+  };
+}
+
+// Relative to the picked folder; reason is e.g. "socket" or "symlink loop".
+export interface SkippedLocalFile {
+  path: string;
+  reason: string;
+}
+
+export interface AbortAllSummary {
+  cancelledQueued: number;
+  cancelledRunning: number;
+  stoppedRules: number;
+  cancelledOperations: number;
 }
 
 // ── Event types (Bun → Webview push) ──
 export interface RPCEvents {
   "job:progress": ProgressEvent;
   "job:complete": JobCompleteEvent;
+  "job:archive-progress": ArchiveProgressEvent;
   "update:available": {
     version: string;
     updateAvailable: boolean;
@@ -276,6 +704,12 @@ export interface RPCEvents {
   "folder-sync:status": FolderSyncStatusEvent;
   "folder-sync:conflict": FolderSyncConflictEvent;
   "folder-sync:error": FolderSyncErrorEvent;
+  "folder-sync:warning": FolderSyncWarningEvent;
+  "folder-sync:dry-run": FolderSyncDryRunEvent;
+  "object:chunk": ObjectChunkEvent;
+  "object:chunk-done": ObjectChunkDoneEvent;
+  "app:aborted": AbortAllSummary; // also sent for the tray entry
+  "vault:locked": { reason: "auto-lock" };
 }
 
 // ── RPC message envelope ──
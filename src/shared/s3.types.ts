@@ -1,10 +1,45 @@
+import type { UploadSse } from "./profile.types";
+
 // ── S3 Object ──
 export interface S3Object {
   key: string;
   size: number;
   lastModified: string;
   etag: string;
-  storageClass?: string;
+  storageClass: string; // "STANDARD" when the provider leaves it out
+}
+
+// ── Archive restore (objects:restore / objects:restore-status) ──
+// Expedited isn't available for DEEP_ARCHIVE.
+export type RestoreTier = "Expedited" | "Standard" | "Bulk";
+
+export type ObjectRestoreState =
+  | "not-archived"
+  | "archived" // no restore requested, or the restored copy expired
+  | "in-progress"
+  | "restored"; // readable until `expiry`
+
+export interface ObjectRestoreStatus {
+  storageClass: string | null;
+  state: ObjectRestoreState;
+  expiry: string | null; // ISO 8601
+}
+
+// ── Object version (objects:list-versions) ──
+export interface S3ObjectVersion {
+  key: string;
+  versionId: string; // "null" for objects written before versioning
+  size: number;
+  lastModified: string;
+  etag: string; // empty for delete markers
+  isLatest: boolean;
+  isDeleteMarker: boolean;
+}
+
+// Grouped by key, newest first. `isTruncated` when the listing hit its cap.
+export interface ObjectVersionListRes {
+  versions: S3ObjectVersion[];
+  isTruncated: boolean;
 }
 
 // ── Object listing sort ──
@@ -22,6 +57,64 @@ export interface BucketInfo {
   creationDate?: string;
 }
 
+// ── Bucket CORS (buckets:get-cors / set-cors) ──
+export type CorsMethod = "GET" | "PUT" | "POST" | "DELETE" | "HEAD";
+
+export interface BucketCorsRule {
+  id?: string;
+  allowedMethods: CorsMethod[];
+  allowedOrigins: string[];
+  allowedHeaders?: string[];
+  exposeHeaders?: string[];
+  maxAgeSeconds?: number;
+}
+
+// ── Bucket lifecycle (buckets:get-lifecycle / set-lifecycle) ──
+export type LifecycleStorageClass =
+  | "STANDARD_IA"
+  | "ONEZONE_IA"
+  | "INTELLIGENT_TIERING"
+  | "GLACIER_IR"
+  | "GLACIER"
+  | "DEEP_ARCHIVE";
+
+// Storage classes an upload may request.
+export type ObjectStorageClass =
+  | "STANDARD"
+  | "REDUCED_REDUNDANCY"
+  | LifecycleStorageClass
+  | "EXPRESS_ONEZONE";
+
+export interface BucketLifecycleTransition {
+  days?: number;
+  date?: string; // RFC 3339 midnight UTC; replaces days
+  storageClass: LifecycleStorageClass;
+}
+
+export interface BucketLifecycleNoncurrentTransition {
+  noncurrentDays: number;
+  storageClass: LifecycleStorageClass;
+  newerNoncurrentVersions?: number;
+}
+
+// Every action S3 returns is modelled, so a rule read and set back is unchanged.
+export interface BucketLifecycleRule {
+  id?: string;
+  enabled?: boolean; // default true
+  prefix?: string; // no prefix and no tags = whole bucket
+  tags?: { key: string; value: string }[];
+  objectSizeGreaterThan?: number; // bytes, exclusive
+  objectSizeLessThan?: number; // bytes, exclusive
+  transitions?: BucketLifecycleTransition[];
+  expirationDays?: number; // must be after every transition
+  expirationDate?: string; // RFC 3339 midnight UTC; instead of expirationDays
+  expiredObjectDeleteMarker?: boolean;
+  noncurrentTransitions?: BucketLifecycleNoncurrentTransition[];
+  noncurrentExpirationDays?: number;
+  noncurrentNewerVersions?: number;
+  abortIncompleteMultipartDays?: number;
+}
+
 // ── Object list request ──
 export interface ObjectListReq {
   profileId: string;
@@ -29,8 +122,12 @@ export interface ObjectListReq {
   prefix?: string;
   maxKeys?: number;
   startAfter?: string;
+  // Sorts only what the call returns: one page unless fetchAll is set.
   sortField?: "key" | "size" | "lastModified";
-  sortDir?: "asc" | "desc";
+  sortDir?: "asc" | "desc"; // default "asc"
+  // Page through the folder (up to 10,000 objects + prefixes) so a sort
+  // covers all of it; isTruncated reports hitting the cap.
+  fetchAll?: boolean;
 }
 
 // ── Object list response ──
@@ -48,22 +145,179 @@ export interface S3StatResult {
   etag: string;
   lastModified: string;
   type: string;
+  s3Uri: string; // canonical s3://bucket/key
+  serverSideEncryption: string | null; // e.g. "AES256", "aws:kms"
+  sseKmsKeyId: string | null;
+}
+
+// ── Object preview (ranged read of the head) ──
+export interface S3PreviewReq {
+  profileId: string;
+  bucket: string;
+  key: string;
+  maxBytes?: number; // default 64 KiB, max 1 MiB
+  decompress?: boolean; // gunzip .gz / Content-Encoding: gzip, default true
+}
+
+export interface S3PreviewResult {
+  content: string;
+  binary: boolean;
+  truncated: boolean; // more of the object (or decompressed stream) exists
+  decompressed: boolean;
+  size: number; // full object size
+  contentType: string;
+}
+
+// ── Whole-object text read (objects:get-text), e.g. for the clipboard ──
+export interface S3TextResult {
+  content: string; // lossy-decoded when isUtf8 is false
+  truncated: boolean; // object is larger than maxBytes (default/max 1 MiB)
+  isUtf8: boolean;
+  size: number; // full object size
+  contentType: string;
+}
+
+// ── Prefix delete (objects:delete-prefix) ──
+// Called twice: the first call only issues a token, and echoing it back
+// (within expiresInSecs) queues the delete job.
+export interface DeletePrefixReq {
+  profileId: string;
+  bucket: string;
+  prefix: string; // treated as a folder: "photos" means "photos/"
+  allowBucketWide?: boolean; // required for an empty prefix
+  confirmToken?: string;
+}
+
+export type DeletePrefixRes =
+  | {
+      confirmationRequired: true;
+      confirmToken: string;
+      expiresInSecs: number;
+      summary: string;
+    }
+  | { jobId: string };
+
+// ── Open with the default app (objects:open-external) ──
+// Downloads to a temp file via a job, then opens it. Objects over 100 MiB
+// take two calls, like objects:delete-prefix.
+export interface OpenExternalReq {
+  profileId: string;
+  bucket: string;
+  key: string;
+  confirmToken?: string;
+}
+
+export type OpenExternalRes = DeletePrefixRes;
+
+// ── Ranged streaming (objects:stream-range), e.g. a log viewer ──
+export interface StreamRangeReq {
+  profileId: string;
+  bucket: string;
+  key: string;
+  operationId: string; // pass to operation:cancel to stop early
+  start: number;
+  length?: number; // default/max 64 MiB per call
+  chunkBytes?: number; // default 64 KiB, max 1 MiB
+}
+
+export interface ObjectChunkEvent {
+  operationId: string;
+  offset: number; // absolute byte offset of data
+  data: string; // base64
+}
+
+// Also the objects:stream-range response.
+export interface ObjectChunkDoneEvent {
+  operationId: string;
+  start: number;
+  bytesSent: number;
+  size: number | null; // full object size
+  eof: boolean;
+  cancelled: boolean;
+  error: string | null;
+}
+
+// ── Parsed AWS CLI style s3:// URI ──
+export interface S3UriParts {
+  bucket: string;
+  key: string; // "" or trailing "/" names a prefix
+  isPrefix: boolean;
+  uri: string;
 }
 
+// A missing key is `{ exists: false }`; permission errors still reject.
+export type S3ExistsResult =
+  | { exists: true; size: number; etag: string; lastModified: string }
+  | { exists: false };
+
 // ── Upload request ──
 export interface UploadReq {
   profileId: string;
   bucket: string;
   key: string;
   localPath: string;
+  overwrite?: boolean; // default true; false fails the job with "AlreadyExists"
+  acl?: CannedAcl; // omitted: bucket default
+  contentType?: string; // omitted: guessed from the key's extension
+  cacheControl?: string;
+  metadata?: Record<string, string>; // sent as x-amz-meta-<name>
+  storageClass?: ObjectStorageClass; // omitted: bucket default
+  sse?: UploadSse; // omitted: the profile's default
+  sseKmsKeyId?: string; // aws:kms only; omitted: the bucket's KMS key
+}
+
+// ── Object ACLs ──
+// Buckets with Object Ownership "bucket owner enforced" (the default for new
+// AWS buckets) reject ACLs with an "AccessControlListNotSupported:" error.
+export type CannedAcl =
+  | "private"
+  | "public-read"
+  | "public-read-write"
+  | "authenticated-read"
+  | "aws-exec-read"
+  | "bucket-owner-read"
+  | "bucket-owner-full-control";
+
+export type AclPermission =
+  | "FULL_CONTROL"
+  | "READ"
+  | "READ_ACP"
+  | "WRITE"
+  | "WRITE_ACP";
+
+export interface ObjectAclGrant {
+  // CanonicalUser uses `id`, Group uses `uri`, AmazonCustomerByEmail uses
+  // `emailAddress`.
+  granteeType: "CanonicalUser" | "Group" | "AmazonCustomerByEmail";
+  id?: string;
+  displayName?: string;
+  uri?: string;
+  emailAddress?: string;
+  permission: AclPermission;
+}
+
+export interface ObjectAcl {
+  ownerId: string | null;
+  ownerDisplayName: string | null;
+  grants: ObjectAclGrant[];
 }
 
 // ── Download request ──
+// What a download does when its local file already exists; "rename" writes
+// "<name> (n)<ext>" beside it. Default "overwrite".
+export type DownloadExistingPolicy = "overwrite" | "skip" | "rename";
+
 export interface DownloadReq {
   profileId: string;
   bucket: string;
   key: string;
   localPath: string;
+  onExisting?: DownloadExistingPolicy;
+  // Check the file against the ETag's MD5 (size only for multipart objects);
+  // a mismatch fails the job with "Checksum mismatch". Default false.
+  verifyChecksum?: boolean;
+  // Download a specific version; omitted fetches the latest.
+  versionId?: string;
 }
 
 // ── Download folder request ──
@@ -71,6 +325,17 @@ export interface DownloadFolderReq {
   profileId: string;
   bucket: string;
   prefix: string;
+  onExisting?: DownloadExistingPolicy;
+}
+
+// ── Download selection request ──
+export interface DownloadSelectionReq {
+  profileId: string;
+  bucket: string;
+  keys: string[]; // folder keys (ending in "/") are expanded
+  commonPrefix?: string; // stripped from each key; the selection's parent folder if omitted
+  destination?: string; // prompts for a folder if omitted
+  onExisting?: DownloadExistingPolicy;
 }
 
 // ── Download as archive request ──
@@ -79,7 +344,21 @@ export interface DownloadArchiveReq {
   bucket: string;
   keys: string[];
   prefix?: string;
-  archiveName?: string;
+  archiveName?: string; // extension added when missing; must match format
+  expandConcurrency?: number; // folder listings at once; default 4, max 16
+  format?: ArchiveFormat; // default "tar.gz"
+}
+
+export type ArchiveFormat = "tar.gz" | "zip";
+
+// ── Upload a local folder as one .tar.gz object ──
+export interface UploadFolderArchiveReq {
+  profileId: string;
+  bucket: string;
+  key?: string; // defaults to `${prefix}${folderName}.tar.gz`
+  prefix?: string;
+  localPath?: string; // prompts for a folder when omitted
+  excludePatterns?: string[];
 }
 
 // ── Copy request ──
@@ -107,6 +386,7 @@ export interface CrossTransferReq {
   destBucket: string;
   destPrefix: string;
   mode: TransferMode;
+  expandConcurrency?: number; // folder listings at once; default 4, max 16
 }
 
 // ── Sync request ──
@@ -118,19 +398,65 @@ export interface SyncReq {
   destBucket: string;
   destPrefix: string;
   mode: SyncMode;
+  archivedObjects?: ArchivedObjectPolicy; // default "skip"
 }
 
 export type SyncMode = "mirror" | "additive" | "overwrite";
 
+// GLACIER / DEEP_ARCHIVE objects: leave them out of sync, or try anyway.
+export type ArchivedObjectPolicy = "skip" | "attempt";
+
 // ── Sync diff ──
 export interface SyncDiff {
   toAdd: SyncDiffEntry[];
   toUpdate: SyncDiffEntry[];
   toDelete: SyncDiffEntry[];
+  skippedArchived: SyncDiffEntry[]; // "skipped: archived", never copied
+  unchanged: number;
+  toAddBytes: number;
+  toUpdateBytes: number;
+  toDeleteCount: number;
+}
+
+// ── Recursive search (objects:search) ──
+// Without a "/" the pattern matches file names, otherwise the key below
+// `prefix`. Case-insensitive; `*` and `?` are wildcards, and a pattern with
+// neither matches anywhere in the name.
+export interface ObjectSearchReq {
+  profileId: string;
+  bucket: string;
+  prefix?: string;
+  pattern: string;
+  maxResults?: number; // default 1000
+  operationId?: string; // pass to operation:cancel to stop early
+}
+
+export interface ObjectSearchRes {
+  objects: S3Object[];
+  isTruncated: boolean; // more than maxResults matched
+  scanned: number;
+}
+
+// ── Prefix diff (objects:diff-prefixes) ──
+export interface DiffPrefixesReq {
+  profileId: string;
+  bucket: string;
+  basePrefix: string; // e.g. "v1/"
+  comparePrefix: string; // e.g. "v2/"
+  operationId?: string; // pass to operation:cancel to stop early
+}
+
+// Keys are relative to each prefix. `source*` fields describe comparePrefix
+// and `dest*` fields basePrefix.
+export interface DiffPrefixesRes {
+  added: SyncDiffEntry[]; // only under comparePrefix
+  changed: SyncDiffEntry[]; // size or ETag differs
+  removed: SyncDiffEntry[]; // only under basePrefix
   unchanged: number;
 }
 
 export interface SyncDiffEntry {
+  id: string; // stable hash of key
   key: string;
   sourceSize?: number;
   destSize?: number;
@@ -171,13 +497,28 @@ export interface ShareReq {
   bucket: string;
   key: string;
   expiresIn: number; // seconds
+  operationId?: string; // lets operation:cancel abandon a slow presign
+  validate?: boolean; // fetch one byte of the URL to confirm it works (GET only)
+  method?: "get" | "put"; // default "get"; "put" for browser-direct uploads
+  contentType?: string; // PUT only; the upload must send the same header
+}
+
+// ── Presigned URL check (share:generate with validate) ──
+export interface PresignedUrlCheck {
+  ok: boolean;
+  status?: number | null; // HTTP status; null when no response came back
+  error?: string | null;
 }
 
 // ── Share response ──
 export interface ShareRes {
   url: string;
+  httpMethod: "GET" | "PUT";
+  headers: Record<string, string>; // send these exactly as signed
   expiresAt: string; // ISO timestamp
   key: string;
+  s3Uri: string; // canonical s3://bucket/key
+  validation?: PresignedUrlCheck | null; // only when validate was set
 }
 
 // ── Share history entry ──
@@ -0,0 +1,35 @@
+// ── App settings (persisted by the backend in settings.json) ──
+export interface AppSettings {
+  version: number; // settings.json schema version; settings:set ignores it
+  notificationsEnabled: boolean; // global switch for desktop notifications
+  // Pause folder sync on metered connections; a no-op where
+  // system:network-status reports meteredDetectionSupported: false.
+  pauseSyncOnMetered: boolean;
+  // Downloads (including folder sync) keep the object's LastModified as mtime.
+  downloadPreserveMtime: boolean;
+  // Unix only: apply an s3fs-style `mode` metadata value to downloads.
+  downloadRestoreMode: boolean;
+  // Keep running in the tray after the window closes and start enabled
+  // folder-sync rules at launch (needs the passphrase in the keychain).
+  runInBackground: boolean;
+  // Lock the vault after this many seconds without RPC calls (vault:status
+  // polling doesn't count); null never locks. See vault:set-auto-lock.
+  autoLockSecs?: number | null;
+}
+
+// ── settings:effective ──
+// "user": set in settings.json or changed at runtime; "env": an OBJECT0_*
+// environment variable overrides it.
+export type EffectiveSettingSource = "default" | "user" | "env";
+
+export interface EffectiveSetting {
+  value: unknown;
+  default: unknown;
+  source: EffectiveSettingSource;
+}
+
+export interface EffectiveSettings {
+  path: string; // settings.json
+  // settings.json keys as-is; others are namespaced, e.g. "jobs.concurrency"
+  entries: Record<string, EffectiveSetting>;
+}